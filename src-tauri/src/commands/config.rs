@@ -1,15 +1,25 @@
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
 
-use crate::logging::log;
+use crate::logging::{log, log_warn};
 
 use crate::config::{
-    get_available_markdown_themes, get_available_ui_themes, load_config_from_content, AppConfig,
-    EditorConfig, GeneralConfig, InterfaceConfig, PreferencesConfig, ShortcutsConfig,
+    apply_notes_dir_override, apply_shortcut_changes, emit_editor_config, generate_config_toml,
+    generate_default_config_toml, get_available_code_themes, get_available_editor_themes,
+    get_available_markdown_themes, get_available_ui_themes, get_config_notes_dir_from_config,
+    get_config_warnings, load_config_from_content, notify_editor_config, write_config_atomically,
+    AppConfig, AvailableThemes, ConfigWarning, EditorConfig, GeneralConfig, InterfaceConfig,
+    PreferencesConfig, ShortcutsConfig,
 };
 use crate::core::{AppError, AppResult};
-use crate::utilities::paths::get_config_path;
-use crate::utilities::validation::validate_config;
+use crate::database::{apply_cache_pragmas, refresh_database_connection, with_db, with_db_mut};
+use crate::services::database_service::{init_db, sync_notes_with_filesystem};
+use crate::services::note_service::invalidate_rendered_notes;
+use crate::utilities::directory_health::{check_directory_health, DirectoryHealth};
+use crate::utilities::note_renderer::{markdown_extensions, RenderOptions};
+use crate::utilities::paths::{get_backup_dir_for_notes_path, get_config_path, get_templates_dir};
+use crate::utilities::validation::{validate_config, validate_notes_directory};
 use std::fs;
+use std::path::Path;
 
 #[tauri::command]
 pub fn get_config_content() -> Result<String, String> {
@@ -38,12 +48,7 @@ pub fn save_config_content(content: &str) -> Result<(), String> {
 
     validate_config(&config).map_err(|e| format!("Configuration validation failed: {}", e))?;
 
-    if let Some(parent) = config_path.parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create config directory: {}", e))?;
-    }
-
-    std::fs::write(&config_path, content)
+    write_config_atomically(&config_path, content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     log(
@@ -54,6 +59,261 @@ pub fn save_config_content(content: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Writes the default config template when no config file exists yet, so
+/// there is something to edit. Returns whether the file was created.
+pub fn ensure_config_file(config_path: &std::path::Path) -> AppResult<bool> {
+    if config_path.exists() {
+        return Ok(false);
+    }
+
+    let template = generate_default_config_toml()?;
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            AppError::ConfigSave(format!("Failed to create config directory: {}", e))
+        })?;
+    }
+    fs::write(config_path, template)
+        .map_err(|e| AppError::ConfigSave(format!("Failed to write config template: {}", e)))?;
+
+    log(
+        "CONFIG",
+        "Config template written",
+        Some(&config_path.display().to_string()),
+    );
+    Ok(true)
+}
+
+/// Sets up a usable first launch around the notes directory the user picked.
+#[tauri::command]
+pub fn initialize_first_run(
+    notes_directory: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<AppConfig, String> {
+    initialize_first_run_setup(&app_state, &notes_directory).map_err(|e| e.to_string())
+}
+
+/// Writes the config template with `notes_directory` filled in and creates the
+/// notes, trash and templates folders. Once first run is over the config is left
+/// alone, so repeating the call only makes sure the folders exist.
+pub fn initialize_first_run_setup(
+    app_state: &crate::core::state::AppState,
+    notes_directory: &str,
+) -> AppResult<AppConfig> {
+    validate_notes_directory(notes_directory)?;
+
+    let first_run = app_state
+        .was_first_run()
+        .load(std::sync::atomic::Ordering::Relaxed);
+    let mut config = app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+
+    if first_run {
+        config = AppConfig {
+            notes_directory: notes_directory.to_string(),
+            ..AppConfig::default()
+        };
+        write_config_atomically(&get_config_path(), &generate_config_toml(&config)?)?;
+    } else if config.notes_directory != notes_directory {
+        return Err(AppError::ConfigSave(format!(
+            "Setup already completed with notes directory: {}",
+            config.notes_directory
+        )));
+    }
+
+    let notes_dir = get_config_notes_dir_from_config(&config);
+    for dir in [
+        notes_dir.clone(),
+        get_backup_dir_for_notes_path(&notes_dir)?,
+        get_templates_dir(),
+    ] {
+        fs::create_dir_all(&dir).map_err(|e| {
+            AppError::FileWrite(format!("Failed to create {}: {}", dir.display(), e))
+        })?;
+    }
+
+    if first_run {
+        *app_state.config.write().unwrap_or_else(|e| e.into_inner()) = config.clone();
+        refresh_database_connection(app_state)?;
+        app_state.set_first_run(false);
+        log("CONFIG", "First run setup completed", Some(notes_directory));
+    }
+    Ok(config)
+}
+
+#[tauri::command]
+pub fn open_config_in_editor(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        let config_path = get_config_path();
+        ensure_config_file(&config_path)?;
+
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        crate::commands::note_external::open_in_external_editor(
+            &config_path,
+            &config.preferences.external_editor_command,
+            None,
+        )
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn validate_config_content(content: String) -> Result<Vec<ConfigWarning>, String> {
+    get_config_warnings(&content).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn reload_config(
+    app: AppHandle,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        let config_path = get_config_path();
+        let new_config = read_config_for_reload(&config_path)?;
+        let old_config = app_state
+            .config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+
+        apply_shortcut_changes(&app, &old_config, &new_config)?;
+
+        *app_state.config.write().unwrap_or_else(|e| e.into_inner()) = new_config.clone();
+
+        if RenderOptions::from_config(&old_config) != RenderOptions::from_config(&new_config) {
+            invalidate_rendered_notes(&app_state)?;
+        }
+
+        apply_reloaded_database_settings(&app_state, &old_config, &new_config)?;
+
+        if old_config.preferences.search_stemming != new_config.preferences.search_stemming {
+            // The sync sees the tokenizer no longer matches and re-indexes every note
+            with_db_mut(&app_state, |conn| {
+                Ok(sync_notes_with_filesystem(&app_state, conn, false, None)?)
+            })?;
+        }
+
+        log(
+            "CONFIG",
+            "Config reloaded",
+            Some(&config_path.display().to_string()),
+        );
+
+        notify_config_reloaded(&app, &new_config);
+        if old_config.editor != new_config.editor {
+            notify_editor_config(&app, &new_config.editor);
+        }
+        Ok(())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Reads the config file for a reload. A file that doesn't parse or validate is
+/// an error, so the caller keeps the config it has.
+pub fn read_config_for_reload(config_path: &Path) -> AppResult<AppConfig> {
+    let content = fs::read_to_string(config_path)
+        .map_err(|e| AppError::ConfigLoad(format!("Failed to read config file: {}", e)))?;
+
+    let raw_config = toml::from_str::<AppConfig>(&content)
+        .map_err(|e| AppError::ConfigLoad(format!("TOML syntax error: {}", e)))?;
+    validate_config(&raw_config).map_err(|e| {
+        AppError::ConfigLoad(format!(
+            "Configuration validation failed, keeping previous config: {}",
+            e
+        ))
+    })?;
+
+    let mut config = load_config_from_content(&content);
+    apply_notes_dir_override(&mut config);
+    Ok(config)
+}
+
+/// Sends the reloaded config to the frontend as `config-reloaded`.
+pub fn notify_config_reloaded<R: Runtime>(app: &AppHandle<R>, config: &AppConfig) {
+    if let Err(e) = app.emit("config-reloaded", config) {
+        log_warn(
+            "CONFIG_EVENT",
+            "Failed to emit config-reloaded event",
+            Some(&e.to_string()),
+        );
+    }
+}
+
+/// Moves to the database `new_config` points at, when the notes directory or
+/// `cache_database_path` changed, opening it with the cache pragmas and filling
+/// it from the notes on disk. Otherwise changed pragmas apply in place.
+pub fn apply_reloaded_database_settings(
+    app_state: &crate::core::state::AppState,
+    old_config: &AppConfig,
+    new_config: &AppConfig,
+) -> AppResult<()> {
+    let database_moved = get_config_notes_dir_from_config(old_config)
+        != get_config_notes_dir_from_config(new_config)
+        || old_config.preferences.cache_database_path != new_config.preferences.cache_database_path;
+    let pragmas_changed = old_config.preferences.cache_journal_mode
+        != new_config.preferences.cache_journal_mode
+        || old_config.preferences.cache_synchronous != new_config.preferences.cache_synchronous;
+
+    if database_moved && refresh_database_connection(app_state)? {
+        with_db_mut(app_state, |conn| {
+            init_db(conn)?;
+            sync_notes_with_filesystem(app_state, conn, false, None)?;
+            Ok(())
+        })
+    } else if pragmas_changed {
+        with_db(app_state, |conn| {
+            apply_cache_pragmas(conn, &new_config.preferences);
+            Ok(())
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Pushes the running editor settings to the frontend so the open editor
+/// reconfigures without a restart. Returns the settings that were sent.
+#[tauri::command]
+pub fn apply_editor_config(
+    app: AppHandle,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<EditorConfig, String> {
+    let editor = app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .editor
+        .clone();
+    emit_editor_config(&app, &editor).map_err(|e| e.to_string())
+}
+
+/// Lists the themes accepted by config validation. Editor themes are re-read
+/// from the user themes folder whenever a file in it has changed.
+#[tauri::command]
+pub fn get_available_themes() -> Result<AvailableThemes, String> {
+    let to_strings = |themes: Vec<&str>| themes.into_iter().map(str::to_string).collect();
+
+    Ok(AvailableThemes {
+        ui_themes: to_strings(get_available_ui_themes()),
+        markdown_themes: to_strings(get_available_markdown_themes()),
+        editor_themes: get_available_editor_themes(),
+        code_themes: to_strings(get_available_code_themes()),
+    })
+}
+
+/// Markdown extensions rendered under the configured `markdown_flavor`, so the
+/// editor can highlight the same syntax.
+#[tauri::command]
+pub fn get_markdown_extensions(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Vec<String> {
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    markdown_extensions(&RenderOptions::from_config(&config))
+}
+
 #[tauri::command]
 pub async fn scan_available_themes(app: AppHandle) -> Result<serde_json::Value, String> {
     let mut ui_themes = Vec::new();
@@ -152,11 +412,27 @@ fn apply_theme_fallbacks(ui_themes: &mut Vec<String>, markdown_themes: &mut Vec<
     }
 }
 
+#[tauri::command]
+pub fn get_default_config_toml() -> Result<String, String> {
+    generate_default_config_toml().map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn get_default_config() -> AppConfig {
     AppConfig::default()
 }
 
+/// The config the backend is running with, after invalid values were replaced
+/// by their defaults. `get_config_content` returns the file as written.
+#[tauri::command]
+pub fn get_active_config(app_state: tauri::State<crate::core::state::AppState>) -> AppConfig {
+    app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
 #[tauri::command]
 pub fn get_general_config(app_state: tauri::State<crate::core::state::AppState>) -> GeneralConfig {
     let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
@@ -242,3 +518,27 @@ pub fn validate_theme_path(path: String) -> AppResult<bool> {
         )),
     }
 }
+
+/// Checks a candidate notes directory on disk, for the settings UI to call when
+/// the user picks a folder.
+#[tauri::command]
+pub fn check_notes_directory_health(
+    path: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<DirectoryHealth, String> {
+    validate_notes_directory(&path).map_err(|e| e.to_string())?;
+
+    let (note_extensions, max_depth) = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        (
+            config.preferences.note_extensions.clone(),
+            config.preferences.max_recursion_depth,
+        )
+    };
+
+    Ok(check_directory_health(
+        std::path::Path::new(&path),
+        &note_extensions,
+        max_depth,
+    ))
+}
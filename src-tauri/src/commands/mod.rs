@@ -1,7 +1,19 @@
 pub mod config;
+pub mod note_archive;
+pub mod note_attachments;
 pub mod note_crud;
+pub mod note_encryption;
+pub mod note_export;
 pub mod note_external;
+pub mod note_folders;
+pub mod note_ids;
+pub mod note_integrity;
+pub mod note_links;
+pub mod note_pins;
+pub mod note_recents;
+pub mod note_replace;
 pub mod note_search;
+pub mod note_spellcheck;
 pub mod note_versions;
 pub mod notes;
 pub mod system;
@@ -0,0 +1,247 @@
+use crate::{
+    core::{AppError, AppResult, BatchResults},
+    database::with_db,
+    logging::log,
+    services::{
+        created_service::delete_note_created,
+        database_service::handle_database_recovery,
+        hash_service::delete_note_hash,
+        note_id_service::delete_note_id,
+        note_service::{remove_notes_from_database, update_note_in_database},
+        pin_service::delete_note_pin,
+        recent_service::delete_note_recent,
+        tag_service::delete_note_tags,
+    },
+    utilities::validation::{has_note_extension, validate_note_name},
+};
+use rusqlite::params;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use walkdir::WalkDir;
+
+// Archived notes live in a hidden folder, which the indexer and watcher already skip
+const ARCHIVE_DIR_NAME: &str = ".archive";
+
+#[tauri::command]
+pub fn archive_note(
+    name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        archive_note_file(&app_state, &configured_notes_dir(&app_state), &name)?;
+
+        match with_db(&app_state, |conn| {
+            conn.execute("DELETE FROM notes WHERE filename = ?1", params![name])?;
+            delete_note_tags(conn, &name)?;
+            delete_note_hash(conn, &name)?;
+            delete_note_pin(conn, &name)?;
+            delete_note_recent(conn, &name)?;
+            delete_note_id(conn, &name)?;
+            delete_note_created(conn, &name)?;
+            Ok(())
+        }) {
+            Ok(_) => Ok(()),
+            Err(e) => handle_database_recovery(
+                &app_state,
+                &format!("archive '{}'", name),
+                &e,
+                "Note archived but database rebuild failed",
+                "Database rebuild failed. Note was archived but database may be inconsistent.",
+            ),
+        }
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn archive_notes(
+    names: Vec<String>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<BatchResults, String> {
+    let notes_dir = configured_notes_dir(&app_state);
+    let mut results = Vec::with_capacity(names.len());
+    let mut archived = Vec::new();
+
+    for name in names {
+        match archive_note_file(&app_state, &notes_dir, &name) {
+            Ok(()) => {
+                archived.push(name.clone());
+                results.push((name, Ok(())));
+            }
+            Err(e) => results.push((name, Err(e.to_string()))),
+        }
+    }
+
+    // Index entries are dropped in one transaction once all files have moved
+    match remove_notes_from_database(&app_state, &archived) {
+        Ok(()) => Ok(results),
+        Err(e) => handle_database_recovery(
+            &app_state,
+            &format!("batch archive of {} notes", archived.len()),
+            &e,
+            "Notes archived but database rebuild failed",
+            "Database rebuild failed. Notes were archived but database may be inconsistent.",
+        )
+        .map(|_| results)
+        .map_err(|e| e.to_string()),
+    }
+}
+
+#[tauri::command]
+pub fn unarchive_note(
+    name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        validate_note_name(&name)?;
+        let notes_dir = configured_notes_dir(&app_state);
+        let note_path = notes_dir.join(&name);
+        let archive_path = notes_dir.join(ARCHIVE_DIR_NAME).join(&name);
+
+        if !archive_path.is_file() {
+            return Err(AppError::FileNotFound(format!(
+                "Archived note not found: {}",
+                name
+            )));
+        }
+        if note_path.exists() {
+            return Err(AppError::InvalidNoteName(format!(
+                "Cannot unarchive '{}': a note with that name already exists. Rename or delete it first.",
+                name
+            )));
+        }
+
+        move_note_file(&app_state, &archive_path, &note_path)?;
+        log(
+            "FILE_OPERATION",
+            &format!("UNARCHIVE: {} | SUCCESS", name),
+            None,
+        );
+
+        let content = fs::read_to_string(&note_path)
+            .map_err(|e| AppError::FileRead(format!("Failed to read unarchived note: {}", e)))?;
+        let modified = modified_secs(&note_path);
+
+        match update_note_in_database(&app_state, &name, &content, modified) {
+            Ok(()) => Ok(()),
+            Err(e) => handle_database_recovery(
+                &app_state,
+                &format!("unarchive '{}'", name),
+                &e,
+                "Note unarchived but database rebuild failed",
+                "Database rebuild failed. Note was unarchived but may not be searchable.",
+            ),
+        }
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_archived_notes(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<String>, String> {
+    let mut archived = archived_notes(&app_state);
+    archived.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(archived.into_iter().map(|(name, _)| name).collect())
+}
+
+/// Every note in the archive, relative to it, with its modified time.
+pub fn archived_notes(app_state: &crate::core::state::AppState) -> Vec<(String, i64)> {
+    let (archive_dir, note_extensions) = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        (
+            PathBuf::from(&config.notes_directory).join(ARCHIVE_DIR_NAME),
+            config.preferences.note_extensions.clone(),
+        )
+    };
+
+    if !archive_dir.is_dir() {
+        return Vec::new();
+    }
+
+    WalkDir::new(&archive_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| has_note_extension(entry.path(), &note_extensions))
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(&archive_dir).ok()?;
+            Some((
+                relative.to_string_lossy().to_string(),
+                modified_secs(entry.path()),
+            ))
+        })
+        .collect()
+}
+
+fn archive_note_file(
+    app_state: &crate::core::state::AppState,
+    notes_dir: &Path,
+    name: &str,
+) -> AppResult<()> {
+    validate_note_name(name)?;
+    let note_path = notes_dir.join(name);
+    let archive_path = notes_dir.join(ARCHIVE_DIR_NAME).join(name);
+
+    if !note_path.is_file() {
+        return Err(AppError::FileNotFound(format!("Note not found: {}", name)));
+    }
+    if archive_path.exists() {
+        return Err(AppError::InvalidNoteName(format!(
+            "Cannot archive '{}': an archived note with that name already exists",
+            name
+        )));
+    }
+
+    move_note_file(app_state, &note_path, &archive_path)?;
+    log(
+        "FILE_OPERATION",
+        &format!("ARCHIVE: {} | SUCCESS", name),
+        None,
+    );
+    Ok(())
+}
+
+fn configured_notes_dir(app_state: &crate::core::state::AppState) -> PathBuf {
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    PathBuf::from(&config.notes_directory)
+}
+
+fn move_note_file(
+    app_state: &crate::core::state::AppState,
+    from: &Path,
+    to: &Path,
+) -> AppResult<()> {
+    let modified = fs::metadata(from)?.modified()?;
+
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    super::notes::with_programmatic_flag(app_state, || {
+        if fs::rename(from, to).is_err() {
+            // fs::rename fails across filesystems (EXDEV). Fall back to copy + delete,
+            // restoring the modified time that the copy would otherwise reset.
+            fs::copy(from, to)?;
+            fs::OpenOptions::new()
+                .write(true)
+                .open(to)?
+                .set_modified(modified)?;
+            fs::remove_file(from)?;
+        }
+        Ok(())
+    })
+}
+
+fn modified_secs(path: &Path) -> i64 {
+    path.metadata()
+        .and_then(|m| m.modified())
+        .map(|mtime| {
+            mtime
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0)
+        })
+        .unwrap_or(0)
+}
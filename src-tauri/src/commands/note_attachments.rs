@@ -0,0 +1,40 @@
+use crate::{
+    core::AppResult,
+    logging::log,
+    utilities::{
+        attachments::{attachment_link, write_attachment},
+        validation::validate_note_name,
+    },
+};
+use std::path::PathBuf;
+
+/// Saves a pasted image into the attachments folder and returns the markdown link to
+/// insert into the note. Attachments are not notes, so nothing is indexed.
+#[tauri::command]
+pub fn save_pasted_image(
+    note: String,
+    png_bytes: Vec<u8>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        validate_note_name(&note)?;
+        let (notes_dir, max_bytes) = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            (
+                PathBuf::from(&config.notes_directory),
+                config.preferences.max_pasted_image_mb * 1024 * 1024,
+            )
+        };
+
+        let file_name = super::notes::with_programmatic_flag(&app_state, || {
+            write_attachment(&notes_dir, &png_bytes, max_bytes)
+        })?;
+        log(
+            "ATTACHMENTS",
+            &format!("Saved pasted image for '{}'", note),
+            Some(&file_name),
+        );
+        Ok(attachment_link(&note, &file_name))
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -1,36 +1,56 @@
 use crate::{
-    core::{AppError, AppResult},
+    config::PreferencesConfig,
+    core::{AppError, AppResult, BatchResults},
     database::with_db,
-    logging::log,
-    services::{database_service::handle_database_recovery, note_service::update_note_in_database},
+    logging::{log, log_error, log_warn},
+    search::list_notes_in_sort_order,
+    services::{
+        created_service::{delete_note_created, record_note_created, rename_note_created},
+        database_service::handle_database_recovery,
+        hash_service::{content_hash, delete_note_hash, rename_note_hash, store_note_hash},
+        link_service::load_note_link_index,
+        note_id_service::{delete_note_id, rename_note_id, track_note_id},
+        note_service::{remove_notes_from_database, render_options, update_note_in_database},
+        pin_service::{delete_note_pin, note_pin_renamed},
+        recent_service::delete_note_recent,
+        render_cache_service::render_note_cached,
+        tag_service::{delete_note_tags, rename_note_tags, sync_note_tags},
+    },
     utilities::{
-        file_safety::{create_versioned_backup, safe_write_note, BackupType},
-        note_renderer::render_note,
-        validation::validate_note_name,
+        file_safety::{
+            create_versioned_backup, safe_write_note, safe_write_note_keeping_version, BackupType,
+        },
+        note_renderer::{note_toc, render_note, resolve_wiki_links, RenderOptions, TocEntry},
+        note_stats::{compute_note_stats, compute_writing_progress, NoteStats, WritingProgress},
+        render_theme::{note_render_theme, RenderTheme},
+        save_format::SaveFormat,
+        strings::duplicate_note_name,
+        templates::{load_template, render_template, TimestampFormats},
+        validation::{validate_note_extension, validate_note_folder, validate_note_name},
     },
 };
+use chrono::Local;
 use rusqlite::params;
 use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+// Appends read the note and write it back, so two at once could drop one
+static APPEND_LOCK: Mutex<()> = Mutex::new(());
+
+/// Largest chunk `read_note_chunk` returns, whatever length is asked for.
+pub const MAX_NOTE_CHUNK_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Every note in the configured `note_sort_order`. Pins don't lead here, only
+/// in the empty-query search view.
 #[tauri::command]
 pub fn list_all_notes(
     app_state: tauri::State<crate::core::state::AppState>,
 ) -> Result<Vec<String>, String> {
-    let result = with_db(&app_state, |conn| {
-        let mut stmt = conn.prepare("SELECT filename FROM notes ORDER BY modified DESC")?;
-        let rows = stmt.query_map([], |row| row.get(0))?;
-
-        let mut results = Vec::new();
-        for r in rows {
-            if let Ok(filename) = r {
-                results.push(filename);
-            }
-        }
-
-        Ok(results)
-    });
-    result.map_err(|e| e.to_string())
+    list_notes_in_sort_order(&app_state)
+        .map(|notes| notes.into_iter().map(|note| note.filename).collect())
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -53,12 +73,136 @@ pub fn get_note_content(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn get_note_stats(
+    name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<NoteStats, String> {
+    get_note_content(&name, app_state).map(|content| compute_note_stats(&content))
+}
+
+#[tauri::command]
+pub fn get_writing_progress(
+    name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<WritingProgress, String> {
+    get_note_content(&name, app_state).map(|content| compute_writing_progress(&name, &content))
+}
+
+/// Outline of the note's headings. Slugs match the heading anchors rendered
+/// when `markdown_heading_anchors` is on.
+#[tauri::command]
+pub fn get_note_toc(
+    name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<TocEntry>, String> {
+    let render_options = render_options(&app_state);
+    get_note_content(&name, app_state).map(|content| note_toc(&name, &content, &render_options))
+}
+
+/// A piece of a note read by `read_note_chunk`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct NoteChunk {
+    pub content: String,
+    /// Byte offset to read the next chunk from
+    pub next_offset: u64,
+    /// Whether the chunk reaches the end of the file
+    pub eof: bool,
+}
+
+/// Reads about `len` bytes of a note from disk starting at byte `offset`, for
+/// notes above `stream_note_threshold_kb` that the editor loads piece by piece.
+/// Chunks end on a character boundary, running up to three bytes past `len` to
+/// finish a character, so each one is valid text on its own. A `len` of zero is
+/// an error, since the read would never advance.
+#[tauri::command]
+pub fn read_note_chunk(
+    name: String,
+    offset: u64,
+    len: u64,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<NoteChunk, String> {
+    let result = || -> AppResult<NoteChunk> {
+        validate_note_name(&name)?;
+        if len == 0 {
+            return Err(AppError::FileRead(
+                "Chunk length must be at least one byte".to_string(),
+            ));
+        }
+        let note_path = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            std::path::PathBuf::from(&config.notes_directory).join(&name)
+        };
+        if !note_path.is_file() {
+            return Err(AppError::FileNotFound(format!("Note not found: {}", name)));
+        }
+
+        let mut file = fs::File::open(&note_path)?;
+        let size = file.metadata()?.len();
+        if offset > size {
+            return Err(AppError::FileRead(format!(
+                "Offset {} is past the end of '{}' ({} bytes)",
+                offset, name, size
+            )));
+        }
+
+        let len = len.min(MAX_NOTE_CHUNK_BYTES).min(size - offset);
+        let mut chunk = Vec::with_capacity(len as usize + 3);
+        file.seek(SeekFrom::Start(offset))?;
+        (&mut file).take(len).read_to_end(&mut chunk)?;
+
+        let content = loop {
+            match String::from_utf8(chunk) {
+                Ok(content) => break content,
+                // Cut off partway through a character, so read on to its end
+                Err(e)
+                    if e.utf8_error().error_len().is_none()
+                        && e.as_bytes().len() < len as usize + 3 =>
+                {
+                    chunk = e.into_bytes();
+                    let before = chunk.len();
+                    (&mut file).take(1).read_to_end(&mut chunk)?;
+                    if chunk.len() == before {
+                        return Err(AppError::FileRead(format!(
+                            "'{}' ends partway through a character",
+                            name
+                        )));
+                    }
+                }
+                Err(e) => {
+                    return Err(AppError::FileRead(format!(
+                        "'{}' is not valid UTF-8 text at byte {}",
+                        name,
+                        offset + e.utf8_error().valid_up_to() as u64
+                    )))
+                }
+            }
+        };
+
+        // A file that shrank since the size was read just gives a shorter chunk
+        let next_offset = offset + content.len() as u64;
+        Ok(NoteChunk {
+            content,
+            next_offset,
+            eof: next_offset >= size,
+        })
+    }();
+    result.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn get_note_html_content(
     note_name: &str,
     app_state: tauri::State<crate::core::state::AppState>,
 ) -> Result<String, String> {
     validate_note_name(note_name).map_err(|e| e.to_string())?;
+    let render_options = render_options(&app_state);
+    let render_cache_max_entries = app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .preferences
+        .render_cache_max_entries;
 
     with_db(&app_state, |conn| {
         let mut stmt =
@@ -74,88 +218,176 @@ pub fn get_note_html_content(
             })
             .map_err(|_| AppError::FileNotFound(format!("Note not found: {}", note_name)))?;
 
-        if is_indexed {
-            Ok(html_content)
+        let html_content = if is_indexed {
+            html_content
         } else {
-            let html_render = render_note(note_name, &content);
+            let html_render = render_note_cached(
+                conn,
+                note_name,
+                &content,
+                &render_options,
+                render_cache_max_entries,
+            )?;
 
             if let Err(e) = conn.execute(
                 "UPDATE notes SET html_render = ?2, is_indexed = ?3 WHERE filename = ?1",
                 params![note_name, html_render, true],
             ) {
-                log(
+                log_warn(
                     "NOTE_INDEXING",
                     &format!("Failed to update note indexing for '{}'", note_name),
                     Some(&e.to_string()),
                 );
             }
 
-            Ok(html_render)
+            html_render
+        };
+
+        if !html_content.contains(r#"class="wiki-link""#) {
+            return Ok(html_content);
         }
+        resolve_wiki_links(&html_content, &load_note_link_index(conn)?)
     })
     .map_err(|e| e.to_string())
 }
 
+/// The markdown theme the note's frontmatter picks with `render_theme`, or
+/// `None` when the configured `markdown_render_theme` applies.
+#[tauri::command]
+pub fn get_note_render_theme(
+    name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Option<RenderTheme>, String> {
+    get_note_content(&name, app_state).map(|content| note_render_theme(&name, &content))
+}
+
 #[tauri::command]
 pub fn create_new_note(
     note_name: &str,
     app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    create_note_with_content(note_name, "", &app_state).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn create_note_from_template(
+    name: String,
+    template: String,
+    app_state: tauri::State<crate::core::state::AppState>,
 ) -> Result<(), String> {
     let result = || -> AppResult<()> {
-        validate_note_name(note_name)?;
+        validate_note_name(&name)?;
+        let template_body = load_template(&template)?;
+        let formats = TimestampFormats::from_config(
+            &app_state.config.read().unwrap_or_else(|e| e.into_inner()),
+        );
+        let content = render_template(&template_body, &name, &Local::now(), &formats);
+        create_note_with_content(&name, &content, &app_state)
+    }();
+    result.map_err(|e| e.to_string())
+}
 
-        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
-        let note_path = std::path::PathBuf::from(&config.notes_directory).join(note_name);
+#[tauri::command]
+pub fn duplicate_note(
+    name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        validate_note_name(&name)?;
+        let notes_dir = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            std::path::PathBuf::from(&config.notes_directory)
+        };
 
-        if let Some(parent) = note_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
+        let content = fs::read_to_string(notes_dir.join(&name))
+            .map_err(|_| AppError::FileNotFound(format!("Note not found: {}", name)))?;
 
-        // Atomic file creation - this eliminates TOCTOU by using create_new flag
-        super::notes::with_programmatic_flag(&app_state, || -> AppResult<()> {
-            match std::fs::OpenOptions::new()
-                .write(true)
-                .create_new(true) // This will fail if file already exists
-                .open(&note_path)
-            {
-                Ok(mut file) => {
-                    // File was created successfully, write empty content
-                    use std::io::Write;
-                    file.write_all(b"")
-                        .map_err(|e| AppError::FileWrite(e.to_string()))?;
-                    Ok(())
-                }
-                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Err(
-                    AppError::InvalidNoteName(format!("Note '{}' already exists", note_name)),
-                ),
-                Err(e) => Err(AppError::FileWrite(format!("Failed to create note: {}", e))),
-            }
-        })?;
+        let new_name = (1..)
+            .map(|copy_number| duplicate_note_name(&name, copy_number))
+            .find(|candidate| !notes_dir.join(candidate).exists())
+            .ok_or_else(|| {
+                AppError::FileWrite(format!("No free name to duplicate '{}' as", name))
+            })?;
 
-        let modified = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|d| d.as_secs() as i64)
-            .unwrap_or(0);
+        create_note_with_content(&new_name, &content, &app_state)?;
+        log(
+            "FILE_OPERATION",
+            &format!("DUPLICATE: {} -> {} | SUCCESS", name, new_name),
+            None,
+        );
+        Ok(new_name)
+    }();
+    result.map_err(|e| e.to_string())
+}
 
-        match with_db(&app_state, |conn| {
-            let html_render = render_note(note_name, "");
-            conn.execute(
-                "INSERT OR REPLACE INTO notes (filename, content, html_render, modified, is_indexed) VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![note_name, "", html_render, modified, true],
-            )?;
-            Ok(())
-        }) {
-            Ok(_) => Ok(()),
-            Err(e) => handle_database_recovery(
-                &app_state,
-                &format!("'{}'", note_name),
-                &e,
-                "Note created but database rebuild failed",
-                "Database rebuild failed. Note was created but may not be searchable.",
+fn create_note_with_content(
+    note_name: &str,
+    content: &str,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<()> {
+    validate_note_name(note_name)?;
+
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    validate_note_extension(note_name, &config.preferences.note_extensions)?;
+    let render_options = RenderOptions::from_config(&config);
+    let note_path = std::path::PathBuf::from(&config.notes_directory).join(note_name);
+
+    if let Some(parent) = note_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // Atomic file creation - this eliminates TOCTOU by using create_new flag
+    super::notes::with_programmatic_flag(app_state, || -> AppResult<()> {
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true) // This will fail if file already exists
+            .open(&note_path)
+        {
+            Ok(mut file) => {
+                // File was created successfully, write initial content
+                use std::io::Write;
+                file.write_all(content.as_bytes())
+                    .map_err(|e| AppError::FileWrite(e.to_string()))?;
+                Ok(())
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Err(
+                AppError::InvalidNoteName(format!("Note '{}' already exists", note_name)),
             ),
+            Err(e) => Err(AppError::FileWrite(format!("Failed to create note: {}", e))),
         }
-    }();
-    result.map_err(|e| e.to_string())
+    })?;
+
+    let modified = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+
+    match with_db(app_state, |conn| {
+        let html_render = render_note(note_name, content, &render_options);
+        conn.execute(
+            "INSERT OR REPLACE INTO notes (filename, content, html_render, modified, is_indexed) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![note_name, content, html_render, modified, true],
+        )?;
+        sync_note_tags(conn, note_name, content)?;
+        store_note_hash(conn, note_name, content)?;
+        track_note_id(
+            conn,
+            note_name,
+            &note_path,
+            &content_hash(content.as_bytes()),
+        )?;
+        record_note_created(conn, note_name, &note_path, modified)?;
+        Ok(())
+    }) {
+        Ok(_) => Ok(()),
+        Err(e) => handle_database_recovery(
+            app_state,
+            &format!("'{}'", note_name),
+            &e,
+            "Note created but database rebuild failed",
+            "Database rebuild failed. Note was created but may not be searchable.",
+        ),
+    }
 }
 
 #[tauri::command]
@@ -164,63 +396,285 @@ pub fn save_note_with_content_check(
     content: &str,
     original_content: &str,
     app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), AppError> {
+    // Errors stay structured so the frontend can read the on-disk content of a SaveConflict
+    validate_note_name(note_name)?;
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    let note_path = std::path::PathBuf::from(&config.notes_directory).join(note_name);
+    validate_content_unchanged(
+        &note_path,
+        note_name,
+        original_content,
+        content,
+        &SaveFormat::from_config(&config),
+    )?;
+    perform_safe_write_and_update(&note_path, content, note_name, &app_state)?;
+    Ok(())
+}
+
+/// Overwrites the note after the user chose to keep their edits over a `SaveConflict`.
+#[tauri::command]
+pub fn force_save_note(
+    name: String,
+    content: String,
+    app_state: tauri::State<crate::core::state::AppState>,
 ) -> Result<(), String> {
     let result = || -> AppResult<()> {
-        validate_note_name(note_name)?;
+        validate_note_name(&name)?;
         let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
-        let note_path = std::path::PathBuf::from(&config.notes_directory).join(note_name);
-        validate_content_unchanged(&note_path, note_name, original_content, content)?;
-        perform_safe_write_and_update(&note_path, content, note_name, &app_state)?;
+        let note_path = std::path::PathBuf::from(&config.notes_directory).join(&name);
+        perform_safe_write_and_update(&note_path, &content, &name, &app_state)?;
+
+        log(
+            "SAVE_CONFLICT",
+            &format!("FORCE_SAVE: {} | SUCCESS", name),
+            None,
+        );
         Ok(())
     }();
     result.map_err(|e| e.to_string())
 }
 
+/// Saves editor content for the autosave timer. There is no original-content
+/// check like in `save_note_with_content_check`; instead the new modification
+/// time (seconds) is returned so the editor can detect external changes.
+#[tauri::command]
+pub fn autosave_note(
+    name: String,
+    content: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<i64, String> {
+    let result = || -> AppResult<i64> {
+        validate_note_name(&name)?;
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        let note_path = std::path::PathBuf::from(&config.notes_directory).join(&name);
+        if !note_path.exists() {
+            return Err(AppError::FileNotFound(format!("Note not found: {}", name)));
+        }
+
+        // Saves in one editing session share a version inside safe_write_note
+        perform_safe_write_and_update(&note_path, &content, &name, &app_state)?;
+
+        let modified = fs::metadata(&note_path)?
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        Ok(modified)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Appends `text` as a new line at the end of a note, creating the note if it
+/// is missing, for quick capture without opening it. With `add_timestamp` the
+/// line starts with the current date and time in the configured formats.
+/// Returns the note's size in bytes afterwards.
+#[tauri::command]
+pub fn append_to_note(
+    name: String,
+    text: String,
+    add_timestamp: bool,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<u64, String> {
+    let result = || -> AppResult<u64> {
+        validate_note_name(&name)?;
+        let (note_path, formats) = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            validate_note_extension(&name, &config.preferences.note_extensions)?;
+            (
+                std::path::PathBuf::from(&config.notes_directory).join(&name),
+                TimestampFormats::from_config(&config),
+            )
+        };
+        let mut line = String::new();
+        if add_timestamp {
+            line.push_str(&render_template(
+                "{{date}} {{time}} ",
+                &name,
+                &Local::now(),
+                &formats,
+            ));
+        }
+        line.push_str(text.trim_end_matches(['\r', '\n']));
+        line.push('\n');
+
+        // The note is rewritten like any other save, so it is tidied to the
+        // save format and versioned. Appends made through the app take turns,
+        // but a write from another program in between can still be lost
+        let _guard = APPEND_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut content = match fs::read_to_string(&note_path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(AppError::FileRead(format!("Failed to read note: {}", e))),
+        };
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(&line);
+        perform_safe_write_and_update(&note_path, &content, &name, &app_state)?;
+
+        log(
+            "FILE_OPERATION",
+            &format!("APPEND: {} | {} bytes", name, line.len()),
+            None,
+        );
+        Ok(fs::metadata(&note_path)?.len())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn rename_note(
     old_name: String,
     new_name: String,
     app_state: tauri::State<crate::core::state::AppState>,
 ) -> Result<(), String> {
-    let result = || -> AppResult<()> {
-        validate_note_name(&old_name)?;
-        validate_note_name(&new_name)?;
+    rename_note_files(&old_name, &new_name, &app_state).map_err(|e| e.to_string())
+}
 
-        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
-        let notes_dir = std::path::PathBuf::from(&config.notes_directory);
-        let old_path = notes_dir.join(&old_name);
-        let new_path = notes_dir.join(&new_name);
-
-        match create_rename_backup(&old_path)? {
-            Some(backup_path) => perform_atomic_rename_with_database(
-                &old_path,
-                &new_path,
-                &old_name,
-                &new_name,
-                backup_path,
-                &app_state,
-            ),
-            None => handle_database_only_rename(&old_name, &new_name, &new_path, &app_state),
+/// Moves a note into `target_dir`, relative to the notes directory and created
+/// if missing, keeping its file name. Returns the note's new name.
+#[tauri::command]
+pub fn move_note(
+    name: String,
+    target_dir: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        validate_note_name(&name)?;
+        let target_dir = target_dir.trim().trim_matches('/');
+        validate_note_folder(target_dir)?;
+
+        let file_name = std::path::Path::new(&name)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| AppError::InvalidNoteName(format!("Invalid note name '{}'", name)))?;
+        let new_name = if target_dir.is_empty() {
+            file_name.clone()
+        } else {
+            format!("{}/{}", target_dir, file_name)
+        };
+        if new_name == name {
+            return Ok(new_name);
+        }
+
+        let notes_dir = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            std::path::PathBuf::from(&config.notes_directory)
+        };
+        if notes_dir.join(&new_name).exists() {
+            let folder = if target_dir.is_empty() {
+                "the notes folder"
+            } else {
+                target_dir
+            };
+            return Err(AppError::InvalidNoteName(format!(
+                "Cannot move '{}': a note named '{}' already exists in {}",
+                name, file_name, folder
+            )));
         }
+
+        rename_note_files(&name, &new_name, &app_state)?;
+        Ok(new_name)
     }();
     result.map_err(|e| e.to_string())
 }
 
+pub(super) fn rename_note_files(
+    old_name: &str,
+    new_name: &str,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<()> {
+    validate_note_name(old_name)?;
+    validate_note_name(new_name)?;
+
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    validate_note_extension(new_name, &config.preferences.note_extensions)?;
+    let notes_dir = std::path::PathBuf::from(&config.notes_directory);
+    let old_path = notes_dir.join(old_name);
+    let new_path = notes_dir.join(new_name);
+
+    match create_rename_backup(&old_path)? {
+        Some(backup_path) => perform_atomic_rename_with_database(
+            &old_path,
+            &new_path,
+            old_name,
+            new_name,
+            backup_path,
+            app_state,
+        ),
+        None => handle_database_only_rename(old_name, new_name, &new_path, app_state),
+    }
+}
+
+/// Issues the token `delete_note` needs for `note_name` while
+/// `confirm_before_delete` is on. It is single-use and expires after a minute.
+#[tauri::command]
+pub fn request_delete_confirmation(
+    note_name: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        validate_note_name(note_name)?;
+        Ok(app_state
+            .delete_confirmations
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .issue(note_name))
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Issues the token `delete_notes` needs for exactly the notes in `names`
+/// while `confirm_before_delete` is on, like `request_delete_confirmation`.
+#[tauri::command]
+pub fn request_batch_delete_confirmation(
+    names: Vec<String>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    Ok(app_state
+        .delete_confirmations
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .issue_batch(&names))
+}
+
 #[tauri::command]
 pub fn delete_note(
     note_name: &str,
+    confirmation_token: Option<String>,
     app_state: tauri::State<crate::core::state::AppState>,
 ) -> Result<(), String> {
     let result = || -> AppResult<()> {
         validate_note_name(note_name)?;
         let config = app_state.config.read().unwrap_or_else(|e| {
-            log(
+            log_warn(
                 "DELETE_NOTE",
                 "Config lock was poisoned, recovering",
                 Some(&format!("note: {}", note_name)),
             );
             e.into_inner()
         });
+        if config.preferences.confirm_before_delete {
+            let confirmed = confirmation_token.as_deref().is_some_and(|token| {
+                app_state
+                    .delete_confirmations
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .consume(note_name, token)
+            });
+            if !confirmed {
+                log_warn(
+                    "DELETE_NOTE",
+                    "Delete rejected without a valid confirmation token",
+                    Some(&format!("note: {}", note_name)),
+                );
+                return Err(AppError::ConfirmationRequired(format!(
+                    "deleting '{}' needs a token from request_delete_confirmation",
+                    note_name
+                )));
+            }
+        }
         let note_path = std::path::PathBuf::from(&config.notes_directory).join(note_name);
 
         log(
@@ -240,6 +694,81 @@ pub fn delete_note(
     result.map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn delete_notes(
+    names: Vec<String>,
+    confirmation_token: Option<String>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<BatchResults, String> {
+    let (notes_dir, confirm_before_delete) = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        (
+            std::path::PathBuf::from(&config.notes_directory),
+            config.preferences.confirm_before_delete,
+        )
+    };
+    if confirm_before_delete {
+        let confirmed = confirmation_token.as_deref().is_some_and(|token| {
+            app_state
+                .delete_confirmations
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .consume_batch(&names, token)
+        });
+        if !confirmed {
+            log_warn(
+                "DELETE_NOTE",
+                "Batch delete rejected without a valid confirmation token",
+                Some(&format!("notes: {}", names.len())),
+            );
+            return Err(AppError::ConfirmationRequired(
+                "deleting these notes needs a token from request_batch_delete_confirmation"
+                    .to_string(),
+            )
+            .to_string());
+        }
+    }
+
+    log(
+        "DELETE_NOTE",
+        "Batch filesystem operation initiated",
+        Some(&format!(
+            "notes: {}, directory: {}",
+            names.len(),
+            notes_dir.display()
+        )),
+    );
+
+    let mut results = Vec::with_capacity(names.len());
+    let mut deleted = Vec::new();
+
+    for name in names {
+        let outcome = validate_note_name(&name)
+            .and_then(|_| perform_backup_and_delete(&notes_dir.join(&name), &name, &app_state));
+        match outcome {
+            Ok(_) => {
+                deleted.push(name.clone());
+                results.push((name, Ok(())));
+            }
+            Err(e) => results.push((name, Err(e.to_string()))),
+        }
+    }
+
+    // Index entries are dropped in one transaction once all files are gone
+    match remove_notes_from_database(&app_state, &deleted) {
+        Ok(()) => Ok(results),
+        Err(e) => handle_database_recovery(
+            &app_state,
+            &format!("batch delete of {} notes", deleted.len()),
+            &e,
+            "Notes deleted but database rebuild failed",
+            "Database rebuild failed. Notes were deleted but database may be inconsistent.",
+        )
+        .map(|_| results)
+        .map_err(|e| e.to_string()),
+    }
+}
+
 fn perform_backup_and_delete(
     note_path: &std::path::PathBuf,
     note_name: &str,
@@ -266,7 +795,7 @@ fn perform_backup_and_delete(
                 }
                 Err(e) => {
                     if let Err(e) = fs::remove_file(&backup_path) {
-                        log(
+                        log_warn(
                             "BACKUP_CLEANUP",
                             &format!("Failed to remove backup file: {:?}", backup_path),
                             Some(&e.to_string()),
@@ -286,6 +815,12 @@ fn handle_database_only_delete(
 ) -> AppResult<()> {
     match with_db(app_state, |conn| {
         conn.execute("DELETE FROM notes WHERE filename = ?1", params![note_name])?;
+        delete_note_tags(conn, note_name)?;
+        delete_note_hash(conn, note_name)?;
+        delete_note_pin(conn, note_name)?;
+        delete_note_recent(conn, note_name)?;
+        delete_note_id(conn, note_name)?;
+        delete_note_created(conn, note_name)?;
         Ok(())
     }) {
         Ok(_) => Ok(()),
@@ -308,6 +843,12 @@ fn handle_database_cleanup(
 ) -> AppResult<()> {
     match with_db(app_state, |conn| {
         conn.execute("DELETE FROM notes WHERE filename = ?1", params![note_name])?;
+        delete_note_tags(conn, note_name)?;
+        delete_note_hash(conn, note_name)?;
+        delete_note_pin(conn, note_name)?;
+        delete_note_recent(conn, note_name)?;
+        delete_note_id(conn, note_name)?;
+        delete_note_created(conn, note_name)?;
         Ok(())
     }) {
         Ok(_) => Ok(()),
@@ -326,6 +867,7 @@ fn validate_content_unchanged(
     note_name: &str,
     original_content: &str,
     content: &str,
+    save_format: &SaveFormat,
 ) -> AppResult<()> {
     let current_content = if note_path.exists() {
         fs::read_to_string(note_path)?
@@ -333,7 +875,9 @@ fn validate_content_unchanged(
         String::new()
     };
 
-    if current_content != original_content {
+    // The editor keeps its own copy, so a note tidied or saved as CRLF still
+    // matches what the editor last loaded or sent
+    if save_format.apply(&current_content) != save_format.apply(original_content) {
         match create_versioned_backup(note_path, BackupType::SaveFailure, Some(content)) {
             Ok(backup_path) => {
                 log(
@@ -343,7 +887,7 @@ fn validate_content_unchanged(
                 );
             }
             Err(e) => {
-                log(
+                log_warn(
                     "FILE_BACKUP",
                     &format!(
                         "Failed to create save failure backup for '{}'",
@@ -354,27 +898,98 @@ fn validate_content_unchanged(
             }
         }
 
-        return Err(AppError::InvalidPath(format!(
-            "Cannot save '{}': file has been modified since editing began. \
-            This safety check prevents accidental data loss.",
-            note_name
-        )));
+        return Err(AppError::SaveConflict {
+            note: note_name.to_string(),
+            disk_content: current_content,
+        });
     }
 
     Ok(())
 }
 
-fn perform_safe_write_and_update(
+pub(super) fn perform_safe_write_and_update(
+    note_path: &std::path::PathBuf,
+    content: &str,
+    note_name: &str,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<()> {
+    write_and_update(note_path, content, note_name, app_state, safe_write_note)
+}
+
+/// Like `perform_safe_write_and_update`, but writes `content` exactly as given
+/// instead of tidying it, to put back what was on disk before a bulk edit.
+pub(super) fn perform_verbatim_write_and_update(
+    note_path: &std::path::PathBuf,
+    content: &str,
+    note_name: &str,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<()> {
+    let preferences = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        config.preferences.clone()
+    };
+    write_unformatted_and_update(
+        note_path,
+        content,
+        note_name,
+        app_state,
+        &preferences,
+        safe_write_note,
+    )
+}
+
+/// Like `perform_safe_write_and_update`, but always keeps the previous content
+/// as a version, for bulk edits the user may want to undo note by note.
+pub(super) fn perform_versioned_write_and_update(
     note_path: &std::path::PathBuf,
     content: &str,
     note_name: &str,
     app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<()> {
+    write_and_update(
+        note_path,
+        content,
+        note_name,
+        app_state,
+        safe_write_note_keeping_version,
+    )
+}
+
+fn write_and_update(
+    note_path: &std::path::PathBuf,
+    content: &str,
+    note_name: &str,
+    app_state: &tauri::State<crate::core::state::AppState>,
+    write: fn(&std::path::PathBuf, &str, &PreferencesConfig) -> AppResult<()>,
+) -> AppResult<()> {
+    let (save_format, preferences) = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        (SaveFormat::from_config(&config), config.preferences.clone())
+    };
+    let content = save_format.apply(content);
+    write_unformatted_and_update(
+        note_path,
+        content.as_ref(),
+        note_name,
+        app_state,
+        &preferences,
+        write,
+    )
+}
+
+fn write_unformatted_and_update(
+    note_path: &std::path::PathBuf,
+    content: &str,
+    note_name: &str,
+    app_state: &tauri::State<crate::core::state::AppState>,
+    preferences: &PreferencesConfig,
+    write: fn(&std::path::PathBuf, &str, &PreferencesConfig) -> AppResult<()>,
 ) -> AppResult<()> {
     if let Some(parent) = note_path.parent() {
         fs::create_dir_all(parent)?;
     }
 
-    super::notes::with_programmatic_flag(app_state, || safe_write_note(note_path, content))?;
+    super::notes::with_programmatic_flag(app_state, || write(note_path, content, preferences))?;
 
     let modified = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -485,13 +1100,18 @@ fn update_database_filename(
             "UPDATE notes SET filename = ?1 WHERE filename = ?2",
             params![new_name, old_name],
         )?;
+        rename_note_tags(conn, old_name, new_name)?;
+        rename_note_hash(conn, old_name, new_name)?;
+        rename_note_id(conn, old_name, new_name)?;
+        note_pin_renamed(conn, new_name)?;
+        rename_note_created(conn, old_name, new_name)?;
         Ok(())
     })
 }
 
 fn cleanup_backup_file(backup_path: &std::path::PathBuf) {
     if let Err(e) = fs::remove_file(backup_path) {
-        log(
+        log_warn(
             "BACKUP_CLEANUP",
             &format!("Failed to remove backup file: {:?}", backup_path),
             Some(&e.to_string()),
@@ -509,7 +1129,7 @@ fn log_successful_rename(old_name: &str, new_name: &str) {
 
 fn attempt_backup_restore(backup_path: &std::path::PathBuf, old_path: &std::path::PathBuf) {
     if let Err(restore_err) = fs::rename(backup_path, old_path) {
-        log(
+        log_error(
             "FILE_OPERATION",
             "CRITICAL: Failed to restore backup after failed rename",
             Some(&restore_err.to_string()),
@@ -555,6 +1175,11 @@ fn handle_database_only_rename(
                 "UPDATE notes SET filename = ?1 WHERE filename = ?2",
                 params![new_name, old_name],
             )?;
+            rename_note_tags(conn, old_name, new_name)?;
+            rename_note_hash(conn, old_name, new_name)?;
+            rename_note_id(conn, old_name, new_name)?;
+            note_pin_renamed(conn, new_name)?;
+            rename_note_created(conn, old_name, new_name)?;
             Ok(())
         }) {
             Ok(_) => return Ok(()),
@@ -0,0 +1,80 @@
+use crate::{
+    core::{AppError, AppResult},
+    database::with_db,
+    services::{note_service::update_note_in_database, render_cache_service::clear_render_cache},
+    utilities::{
+        file_safety::write_note_discarding_versions,
+        note_crypto::{decrypt_content, encrypt_content, is_encrypted_note},
+        validation::validate_note_name,
+    },
+};
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Encrypts a note on disk with `passphrase`. Its earlier versions are removed
+/// and the index only ever holds the ciphertext, so the note is left out of
+/// search and rendered as locked.
+#[tauri::command]
+pub fn encrypt_note(
+    name: String,
+    passphrase: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        validate_note_name(&name)?;
+        let note_path = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            std::path::PathBuf::from(&config.notes_directory).join(&name)
+        };
+
+        let plaintext = fs::read_to_string(&note_path)
+            .map_err(|_| AppError::FileNotFound(format!("Note not found: {}", name)))?;
+        if is_encrypted_note(&plaintext) {
+            return Err(AppError::Encryption(format!(
+                "Note '{}' is already encrypted",
+                name
+            )));
+        }
+        let encrypted = encrypt_content(&plaintext, &passphrase)?;
+
+        super::notes::with_programmatic_flag(&app_state, || {
+            write_note_discarding_versions(&note_path, &encrypted)
+        })?;
+
+        // Cached renders are keyed by content hash, so renders of earlier
+        // edits of this note can't be told apart from others and all go
+        with_db(&app_state, |conn| {
+            clear_render_cache(conn)?;
+            Ok(())
+        })?;
+
+        let modified = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        update_note_in_database(&app_state, &name, &encrypted, modified)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// The plaintext of an encrypted note, for viewing in this session. The note
+/// stays encrypted on disk; saving the plaintext back over it unlocks it.
+#[tauri::command]
+pub fn decrypt_note(
+    name: String,
+    passphrase: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        validate_note_name(&name)?;
+        let note_path = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            std::path::PathBuf::from(&config.notes_directory).join(&name)
+        };
+
+        let content = fs::read_to_string(&note_path)
+            .map_err(|_| AppError::FileNotFound(format!("Note not found: {}", name)))?;
+        decrypt_content(&content, &passphrase)
+    }();
+    result.map_err(|e| e.to_string())
+}
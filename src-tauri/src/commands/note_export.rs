@@ -0,0 +1,276 @@
+use crate::{
+    core::{AppError, AppResult},
+    database::with_db,
+    logging::log,
+    services::{
+        index_service::{
+            collect_index_entries, collect_index_stats, compare_index, parse_index_snapshot,
+            IndexDrift, IndexSnapshot, IndexStats, INDEX_SCHEMA_VERSION,
+        },
+        note_service::render_options,
+    },
+    utilities::{
+        clipboard,
+        export::{build_export_html, code_theme_css, markdown_theme_css, write_note_bundle},
+        note_renderer::render_note,
+        paths::get_backup_dir_for_notes_path,
+        render_theme::note_render_theme,
+        validation::validate_note_name,
+    },
+};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Exports go to a hidden folder so the indexer and watcher never pick them up
+const EXPORT_DIR_NAME: &str = ".exports";
+
+#[tauri::command]
+pub fn export_note(
+    name: String,
+    format: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        validate_note_name(&name)?;
+        let extension = match format.as_str() {
+            "markdown" => "md",
+            "html" => "html",
+            "bundle" => "zip",
+            other => {
+                return Err(AppError::Export(format!(
+                    "Unsupported export format '{}'. Expected one of: markdown, html, bundle",
+                    other
+                )))
+            }
+        };
+
+        let content = super::note_crud::get_note_content(&name, app_state.clone())
+            .map_err(|_| AppError::FileNotFound(format!("Note not found: {}", name)))?;
+
+        let notes_dir = PathBuf::from(
+            &app_state
+                .config
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .notes_directory,
+        );
+
+        let output_path = notes_dir
+            .join(EXPORT_DIR_NAME)
+            .join(&name)
+            .with_extension(extension);
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| export_write_error(&notes_dir, e))?;
+        }
+
+        super::notes::with_programmatic_flag(&app_state, || match format.as_str() {
+            "html" => {
+                let html = themed_note_html(&app_state, &name, &content)?;
+                fs::write(&output_path, html).map_err(|e| export_write_error(&notes_dir, e))
+            }
+            "bundle" => {
+                let versions = load_note_versions(&app_state, &notes_dir, &name)?;
+                let note_file_name = Path::new(&name)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| name.clone());
+                write_note_bundle(&output_path, &note_file_name, &content, &versions).map_err(|e| {
+                    match e {
+                        AppError::FilePermission(msg) => not_writable_error(&notes_dir, &msg),
+                        other => other,
+                    }
+                })
+            }
+            _ => fs::write(&output_path, &content).map_err(|e| export_write_error(&notes_dir, e)),
+        })?;
+
+        log(
+            "FILE_OPERATION",
+            &format!("EXPORT: {} as {} | SUCCESS", name, format),
+            None,
+        );
+        Ok(output_path.to_string_lossy().to_string())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Puts the note on the clipboard rendered with the configured markdown and
+/// code themes, with the raw note as the plain text fallback.
+#[tauri::command]
+pub fn copy_note_as_html(
+    name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        validate_note_name(&name)?;
+        let content = super::note_crud::get_note_content(&name, app_state.clone())
+            .map_err(|_| AppError::FileNotFound(format!("Note not found: {}", name)))?;
+
+        clipboard::write_html(&themed_note_html(&app_state, &name, &content)?, &content)?;
+
+        log(
+            "FILE_OPERATION",
+            &format!("COPY_HTML: {} | SUCCESS", name),
+            None,
+        );
+        Ok(())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Renders the note as a standalone HTML document styled like the preview.
+fn themed_note_html(
+    app_state: &crate::core::state::AppState,
+    name: &str,
+    content: &str,
+) -> AppResult<String> {
+    let (markdown_theme, code_theme, custom_markdown_theme_path) = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        (
+            config.interface.markdown_render_theme.clone(),
+            config.interface.md_render_code_theme.clone(),
+            config.interface.custom_markdown_theme_path.clone(),
+        )
+    };
+
+    let markdown_css = match (
+        note_render_theme(name, content),
+        &custom_markdown_theme_path,
+    ) {
+        (Some(theme), _) => theme.stylesheet(),
+        (None, Some(path)) => fs::read_to_string(path).map_err(|e| {
+            AppError::FileRead(format!(
+                "Failed to read custom markdown theme '{}': {}",
+                path, e
+            ))
+        })?,
+        (None, None) => markdown_theme_css(&markdown_theme)
+            .unwrap_or_default()
+            .to_string(),
+    };
+
+    Ok(build_export_html(
+        name,
+        &render_note(name, content, &render_options(app_state)),
+        &markdown_css,
+        code_theme_css(&code_theme).unwrap_or_default(),
+    ))
+}
+
+/// Writes the metadata of every indexed note to `path` as versioned JSON.
+/// Returns how many notes were written.
+#[tauri::command]
+pub fn export_index(
+    path: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<usize, String> {
+    let result = || -> AppResult<usize> {
+        let output_path = index_snapshot_path(&path)?;
+        let snapshot = IndexSnapshot {
+            schema_version: INDEX_SCHEMA_VERSION,
+            exported_at: chrono::Utc::now().timestamp(),
+            notes: with_db(&app_state, |conn| Ok(collect_index_entries(conn)?))?,
+        };
+        let json = serde_json::to_string_pretty(&snapshot)
+            .map_err(|e| AppError::Export(format!("Failed to serialize index: {}", e)))?;
+        fs::write(output_path, json)
+            .map_err(|e| AppError::FileWrite(format!("Failed to write index export: {}", e)))?;
+
+        log(
+            "FILE_OPERATION",
+            &format!(
+                "EXPORT INDEX: {} notes to {} | SUCCESS",
+                snapshot.notes.len(),
+                path
+            ),
+            None,
+        );
+        Ok(snapshot.notes.len())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Overview numbers read from the index, cheap enough to poll. Archive and
+/// trash counts are as of the last sync from disk.
+#[tauri::command]
+pub fn get_index_stats(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<IndexStats, String> {
+    let refresh = *app_state
+        .last_refresh
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    with_db(&app_state, |conn| Ok(collect_index_stats(conn, refresh)?)).map_err(|e| e.to_string())
+}
+
+/// Reads a snapshot written by `export_index` and reports how the current index
+/// has drifted from it. Nothing is changed.
+#[tauri::command]
+pub fn import_index(
+    path: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<IndexDrift, String> {
+    let result = || -> AppResult<IndexDrift> {
+        let json = fs::read_to_string(index_snapshot_path(&path)?)
+            .map_err(|e| AppError::FileRead(format!("Failed to read index export: {}", e)))?;
+        let snapshot = parse_index_snapshot(&json)?;
+        let current = with_db(&app_state, |conn| Ok(collect_index_entries(conn)?))?;
+        Ok(compare_index(&snapshot.notes, &current))
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+fn index_snapshot_path(path: &str) -> AppResult<&Path> {
+    let path = Path::new(path);
+    if !path.is_absolute() {
+        return Err(AppError::InvalidPath(format!(
+            "Index export path must be absolute: {}",
+            path.display()
+        )));
+    }
+    Ok(path)
+}
+
+fn load_note_versions(
+    app_state: &tauri::State<crate::core::state::AppState>,
+    notes_dir: &Path,
+    name: &str,
+) -> AppResult<Vec<(String, String)>> {
+    let backup_dir = get_backup_dir_for_notes_path(notes_dir)?;
+    let versions = super::note_versions::get_note_versions(name, app_state.clone())
+        .map_err(AppError::FileRead)?;
+
+    versions
+        .into_iter()
+        .map(|version| {
+            let content = fs::read_to_string(backup_dir.join(&version.filename)).map_err(|e| {
+                AppError::FileRead(format!(
+                    "Failed to read version '{}': {}",
+                    version.filename, e
+                ))
+            })?;
+            let file_name = Path::new(&version.filename)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or(version.filename);
+            Ok((file_name, content))
+        })
+        .collect()
+}
+
+fn not_writable_error(notes_dir: &Path, reason: &str) -> AppError {
+    AppError::FilePermission(format!(
+        "Notes directory '{}' is not writable: {}",
+        notes_dir.display(),
+        reason
+    ))
+}
+
+fn export_write_error(notes_dir: &Path, e: io::Error) -> AppError {
+    if e.kind() == io::ErrorKind::PermissionDenied {
+        not_writable_error(notes_dir, &e.to_string())
+    } else {
+        AppError::FileWrite(format!("Failed to write export: {}", e))
+    }
+}
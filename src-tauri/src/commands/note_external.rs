@@ -1,11 +1,12 @@
 use crate::{
     core::{AppError, AppResult},
-    utilities::validation::validate_note_name,
+    utilities::{external_editor::build_editor_command, validation::validate_note_name},
 };
 
 #[tauri::command]
 pub fn open_note_in_editor(
     note_name: &str,
+    line: Option<u32>,
     app_state: tauri::State<crate::core::state::AppState>,
 ) -> Result<(), String> {
     validate_note_name(note_name)
@@ -19,27 +20,67 @@ pub fn open_note_in_editor(
                 )));
             }
 
-            #[cfg(target_os = "macos")]
-            std::process::Command::new("open")
-                .arg(&note_path)
-                .status()
-                .map_err(AppError::from)?;
+            open_in_external_editor(
+                &note_path,
+                &config.preferences.external_editor_command,
+                line,
+            )
+        })
+        .map_err(|e| e.to_string())
+}
 
-            #[cfg(target_os = "windows")]
-            std::process::Command::new("explorer")
-                .arg(&note_path)
-                .status()
-                .map_err(AppError::from)?;
+/// Opens a file with the configured external editor command, or with the
+/// OS default application when the command is empty.
+pub fn open_in_external_editor(
+    path: &std::path::Path,
+    editor_command: &str,
+    line: Option<u32>,
+) -> AppResult<()> {
+    if !editor_command.trim().is_empty() {
+        let path = path
+            .to_str()
+            .ok_or_else(|| AppError::InvalidPath("Invalid path encoding".to_string()))?;
+        return spawn_external_editor(editor_command, path, line);
+    }
 
-            #[cfg(target_os = "linux")]
-            std::process::Command::new("xdg-open")
-                .arg(&note_path)
-                .status()
-                .map_err(AppError::from)?;
+    #[cfg(target_os = "macos")]
+    std::process::Command::new("open")
+        .arg(path)
+        .status()
+        .map_err(AppError::from)?;
 
-            Ok(())
-        })
-        .map_err(|e| e.to_string())
+    #[cfg(target_os = "windows")]
+    std::process::Command::new("explorer")
+        .arg(path)
+        .status()
+        .map_err(AppError::from)?;
+
+    #[cfg(target_os = "linux")]
+    std::process::Command::new("xdg-open")
+        .arg(path)
+        .status()
+        .map_err(AppError::from)?;
+
+    Ok(())
+}
+
+fn spawn_external_editor(template: &str, path: &str, line: Option<u32>) -> AppResult<()> {
+    let (program, args) = build_editor_command(template, path, line)?;
+    let mut child = std::process::Command::new(&program)
+        .args(&args)
+        .spawn()
+        .map_err(|e| {
+            AppError::from(std::io::Error::new(
+                e.kind(),
+                format!("Failed to launch external editor '{}': {}", program, e),
+            ))
+        })?;
+
+    // Editors such as terminal vim stay open, so reap the process in the background
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
 }
 
 #[tauri::command]
@@ -0,0 +1,68 @@
+use crate::{
+    commands::notes::with_programmatic_flag,
+    core::{AppError, AppResult},
+    logging::log,
+    utilities::{
+        note_folders::{list_folder_tree, FolderNode},
+        validation::validate_new_folder,
+    },
+};
+use std::path::PathBuf;
+
+/// Creates a folder, relative to the notes directory, along with any missing
+/// parents. Returns its normalized path.
+#[tauri::command]
+pub fn create_folder(
+    relative_path: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        let folder = relative_path.trim().trim_matches('/').to_string();
+        validate_new_folder(&folder)?;
+
+        let notes_dir = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            PathBuf::from(&config.notes_directory)
+        };
+        let folder_path = notes_dir.join(&folder);
+        if folder_path.is_dir() {
+            return Err(AppError::InvalidPath(format!(
+                "Folder '{}' already exists",
+                folder
+            )));
+        }
+        if folder_path.exists() {
+            return Err(AppError::InvalidPath(format!(
+                "A file named '{}' already exists",
+                folder
+            )));
+        }
+
+        with_programmatic_flag(&app_state, || {
+            std::fs::create_dir_all(&folder_path)
+                .map_err(|e| AppError::FileWrite(format!("Failed to create folder: {}", e)))
+        })?;
+
+        log(
+            "NOTE_FOLDER",
+            &format!("CREATE: {} | SUCCESS", folder),
+            None,
+        );
+        Ok(folder)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_folders(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<FolderNode>, String> {
+    let (notes_dir, max_depth) = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        (
+            PathBuf::from(&config.notes_directory),
+            config.preferences.max_recursion_depth,
+        )
+    };
+    Ok(list_folder_tree(&notes_dir, max_depth))
+}
@@ -0,0 +1,41 @@
+use crate::{
+    core::{AppError, AppResult},
+    database::with_db,
+    services::note_id_service,
+    utilities::validation::validate_note_name,
+};
+use rusqlite::params;
+
+/// The stable ID of a note, which stays the same when the note is renamed.
+#[tauri::command]
+pub fn get_note_id(
+    name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        validate_note_name(&name)?;
+        with_db(&app_state, |conn| {
+            let exists = conn
+                .prepare("SELECT 1 FROM notes WHERE filename = ?1")?
+                .exists(params![name])?;
+            if !exists {
+                return Err(AppError::FileNotFound(format!("Note not found: {}", name)));
+            }
+            Ok(note_id_service::note_id(conn, &name)?)
+        })
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// The current name of the note with `id`.
+#[tauri::command]
+pub fn get_note_by_id(
+    id: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    with_db(&app_state, |conn| {
+        note_id_service::note_for_id(conn, &id)?
+            .ok_or_else(|| AppError::FileNotFound(format!("Note not found for ID: {}", id)))
+    })
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,63 @@
+use crate::{
+    core::AppResult, database::with_db, logging::log, services::hash_service::content_hash,
+    utilities::validation::validate_note_name,
+};
+use std::fs;
+use std::path::PathBuf;
+
+fn configured_notes_dir(app_state: &crate::core::state::AppState) -> PathBuf {
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    PathBuf::from(&config.notes_directory)
+}
+
+/// Returns the hex SHA-256 of the note's bytes as they are on disk.
+#[tauri::command]
+pub fn get_note_hash(
+    name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        validate_note_name(&name)?;
+        let bytes = fs::read(configured_notes_dir(&app_state).join(&name))?;
+        Ok(content_hash(&bytes))
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Re-hashes every indexed note and returns, sorted, the ones whose file no
+/// longer matches the cached hash, including files that can no longer be read.
+#[tauri::command]
+pub fn verify_notes_integrity(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<String>, String> {
+    let result = || -> AppResult<Vec<String>> {
+        let cached: Vec<(String, String)> = with_db(&app_state, |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT note_hashes.filename, note_hashes.hash FROM note_hashes
+                     JOIN notes ON notes.filename = note_hashes.filename",
+            )?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            Ok(rows.collect::<Result<Vec<_>, _>>()?)
+        })?;
+
+        let notes_dir = configured_notes_dir(&app_state);
+        let mut mismatched: Vec<String> = cached
+            .into_iter()
+            .filter(|(filename, hash)| {
+                fs::read(notes_dir.join(filename))
+                    .map(|bytes| content_hash(&bytes) != *hash)
+                    .unwrap_or(true)
+            })
+            .map(|(filename, _)| filename)
+            .collect();
+        mismatched.sort();
+
+        log(
+            "INTEGRITY_CHECK",
+            &format!("{} notes differ from the cache", mismatched.len()),
+            None,
+        );
+        Ok(mismatched)
+    }();
+    result.map_err(|e| e.to_string())
+}
@@ -0,0 +1,148 @@
+use super::note_crud::{perform_safe_write_and_update, rename_note_files};
+use crate::{
+    core::{AppError, AppResult},
+    database::with_db,
+    logging::{log, log_error, log_warn},
+    services::link_service::{self, RelinkedNote},
+    utilities::{note_links::NoteLink, validation::validate_note_name},
+};
+use rusqlite::params;
+
+#[tauri::command]
+pub fn get_backlinks(
+    name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<String>, String> {
+    let result = || -> AppResult<Vec<String>> {
+        validate_note_name(&name)?;
+        with_db(&app_state, |conn| {
+            let exists = conn
+                .prepare("SELECT 1 FROM notes WHERE filename = ?1")?
+                .exists(params![name])?;
+            if !exists {
+                return Err(AppError::FileNotFound(format!("Note not found: {}", name)));
+            }
+            Ok(link_service::find_backlinks(conn, &name)?)
+        })
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Backlinks of the note with `id`, as note IDs.
+#[tauri::command]
+pub fn get_backlinks_by_id(
+    id: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<String>, String> {
+    with_db(&app_state, |conn| {
+        link_service::find_backlinks_by_id(conn, &id)
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_note_links(
+    name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<NoteLink>, String> {
+    let result = || -> AppResult<Vec<NoteLink>> {
+        validate_note_name(&name)?;
+        with_db(&app_state, |conn| {
+            link_service::find_note_links(conn, &name)
+        })
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Renames a note and rewrites the `[[wiki]]` and markdown links that pointed
+/// at it, returning how many notes had links rewritten. If a rewrite fails the
+/// rewritten notes are restored and the rename is undone.
+#[tauri::command]
+pub fn rename_note_with_relink(
+    old_name: String,
+    new_name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<usize, String> {
+    let result = || -> AppResult<usize> {
+        validate_note_name(&old_name)?;
+        validate_note_name(&new_name)?;
+
+        // Every rewrite is worked out before anything on disk changes
+        let relinked = with_db(&app_state, |conn| {
+            Ok(link_service::plan_relink(conn, &old_name, &new_name)?)
+        })?;
+
+        rename_note_files(&old_name, &new_name, &app_state)?;
+        if let Err(e) = write_relinked_notes(&relinked, &app_state) {
+            if let Err(undo_error) = rename_note_files(&new_name, &old_name, &app_state) {
+                log_error(
+                    "RELINK",
+                    "Failed to undo rename after link rewrite failure",
+                    Some(&format!(
+                        "old: {}, new: {}, error: {}",
+                        old_name, new_name, undo_error
+                    )),
+                );
+            }
+            return Err(e);
+        }
+
+        log(
+            "RELINK",
+            "Renamed note and rewrote links",
+            Some(&format!(
+                "old: {}, new: {}, notes updated: {}",
+                old_name,
+                new_name,
+                relinked.len()
+            )),
+        );
+        Ok(relinked.len())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+fn write_relinked_notes(
+    relinked: &[RelinkedNote],
+    app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<()> {
+    let notes_dir = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        std::path::PathBuf::from(&config.notes_directory)
+    };
+
+    for (written, note) in relinked.iter().enumerate() {
+        let path = notes_dir.join(&note.filename);
+        if let Err(e) =
+            perform_safe_write_and_update(&path, &note.relinked, &note.filename, app_state)
+        {
+            log_warn(
+                "RELINK",
+                "Failed to rewrite links, restoring notes already rewritten",
+                Some(&format!("note: {}, error: {}", note.filename, e)),
+            );
+            restore_relinked_notes(&relinked[..written], &notes_dir, app_state);
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+fn restore_relinked_notes(
+    relinked: &[RelinkedNote],
+    notes_dir: &std::path::Path,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) {
+    for note in relinked {
+        let path = notes_dir.join(&note.filename);
+        if let Err(e) =
+            perform_safe_write_and_update(&path, &note.original, &note.filename, app_state)
+        {
+            log_error(
+                "RELINK",
+                "Failed to restore note",
+                Some(&format!("note: {}, error: {}", note.filename, e)),
+            );
+        }
+    }
+}
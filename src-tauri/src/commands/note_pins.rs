@@ -0,0 +1,60 @@
+use crate::{
+    core::{AppError, AppResult},
+    database::with_db,
+    logging::log,
+    services::pin_service,
+    utilities::validation::validate_note_name,
+};
+use rusqlite::params;
+
+#[tauri::command]
+pub fn pin_note(
+    name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        validate_note_name(&name)?;
+        with_db(&app_state, |conn| {
+            let exists = conn
+                .prepare("SELECT 1 FROM notes WHERE filename = ?1")?
+                .exists(params![name])?;
+            if !exists {
+                return Err(AppError::FileNotFound(format!("Note not found: {}", name)));
+            }
+            pin_service::pin_note(conn, &name)?;
+            Ok(())
+        })?;
+
+        log("NOTE_PIN", &format!("PIN: {} | SUCCESS", name), None);
+        Ok(())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn unpin_note(
+    name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        validate_note_name(&name)?;
+        let removed = with_db(&app_state, |conn| Ok(pin_service::unpin_note(conn, &name)?))?;
+        if !removed {
+            return Err(AppError::FileNotFound(format!(
+                "Note is not pinned: {}",
+                name
+            )));
+        }
+
+        log("NOTE_PIN", &format!("UNPIN: {} | SUCCESS", name), None);
+        Ok(())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_pinned_notes(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<String>, String> {
+    with_db(&app_state, |conn| Ok(pin_service::list_pinned_notes(conn)?)).map_err(|e| e.to_string())
+}
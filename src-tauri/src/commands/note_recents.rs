@@ -0,0 +1,45 @@
+use crate::{
+    core::{AppError, AppResult},
+    database::with_db,
+    services::recent_service,
+    utilities::validation::validate_note_name,
+};
+use rusqlite::params;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[tauri::command]
+pub fn record_note_opened(
+    name: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        validate_note_name(&name)?;
+        let last_opened = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+
+        with_db(&app_state, |conn| {
+            let exists = conn
+                .prepare("SELECT 1 FROM notes WHERE filename = ?1")?
+                .exists(params![name])?;
+            if !exists {
+                return Err(AppError::FileNotFound(format!("Note not found: {}", name)));
+            }
+            recent_service::record_note_opened(conn, &name, last_opened)?;
+            Ok(())
+        })
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_recent_notes(
+    limit: usize,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<String>, String> {
+    with_db(&app_state, |conn| {
+        Ok(recent_service::list_recent_notes(conn, limit)?)
+    })
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,105 @@
+use super::note_crud::{perform_verbatim_write_and_update, perform_versioned_write_and_update};
+use crate::{
+    core::AppResult,
+    database::with_db,
+    logging::{log, log_error, log_warn},
+    services::replace_service::{plan_replace, ReplaceResult, ReplacedNote, Replacement},
+};
+
+/// Replaces `find` in every note, reporting the number of replacements per
+/// changed note. A dry run writes nothing and previews the changed lines
+/// instead. Otherwise each note's previous content is kept as a version, and
+/// if a write fails the notes already changed are restored.
+#[tauri::command]
+pub fn replace_across_notes(
+    find: String,
+    replace: String,
+    regex: bool,
+    dry_run: bool,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<ReplaceResult>, String> {
+    let result = || -> AppResult<Vec<ReplaceResult>> {
+        let replacement = Replacement::new(&find, &replace, regex)?;
+        let notes_dir = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            std::path::PathBuf::from(&config.notes_directory)
+        };
+        let replaced = with_db(&app_state, |conn| {
+            Ok(plan_replace(conn, &notes_dir, &replacement)?)
+        })?;
+
+        if dry_run {
+            return Ok(replaced
+                .iter()
+                .map(|note| ReplaceResult {
+                    filename: note.filename.clone(),
+                    replacements: note.replacements,
+                    preview: Some(replacement.preview(&note.original)),
+                })
+                .collect());
+        }
+
+        write_replaced_notes(&replaced, &notes_dir, &app_state)?;
+        log(
+            "REPLACE",
+            "Replaced text across notes",
+            Some(&format!(
+                "find: {}, regex: {}, notes updated: {}",
+                find,
+                regex,
+                replaced.len()
+            )),
+        );
+        Ok(replaced
+            .into_iter()
+            .map(|note| ReplaceResult {
+                filename: note.filename,
+                replacements: note.replacements,
+                preview: None,
+            })
+            .collect())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+fn write_replaced_notes(
+    replaced: &[ReplacedNote],
+    notes_dir: &std::path::Path,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) -> AppResult<()> {
+    for (written, note) in replaced.iter().enumerate() {
+        let path = notes_dir.join(&note.filename);
+        if let Err(e) =
+            perform_versioned_write_and_update(&path, &note.replaced, &note.filename, app_state)
+        {
+            log_warn(
+                "REPLACE",
+                "Failed to replace text, restoring notes already changed",
+                Some(&format!("note: {}, error: {}", note.filename, e)),
+            );
+            restore_replaced_notes(&replaced[..written], notes_dir, app_state);
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+fn restore_replaced_notes(
+    replaced: &[ReplacedNote],
+    notes_dir: &std::path::Path,
+    app_state: &tauri::State<crate::core::state::AppState>,
+) {
+    // The original is put back byte for byte, not tidied like a normal save
+    for note in replaced {
+        let path = notes_dir.join(&note.filename);
+        if let Err(e) =
+            perform_verbatim_write_and_update(&path, &note.original, &note.filename, app_state)
+        {
+            log_error(
+                "REPLACE",
+                "Failed to restore note",
+                Some(&format!("note: {}, error: {}", note.filename, e)),
+            );
+        }
+    }
+}
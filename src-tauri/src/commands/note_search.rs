@@ -1,11 +1,126 @@
-use crate::search::{search_notes_hybrid, NoteMetadata};
+use super::note_crud::get_note_content;
+use crate::database::with_db;
+use crate::search::{
+    find_in_note, search_for_generation, search_note_versions as search_versions,
+    search_notes_by_tag as search_by_tag, search_notes_fuzzy as search_fuzzy, search_notes_hybrid,
+    search_notes_regex as search_regex, LineMatch, NoteMetadata, ScoredNoteMetadata, VersionMatch,
+    SEARCH_SUPERSEDED,
+};
+use crate::services::tag_service::list_tags;
 
+/// Passing an increasing `generation` with each query makes a search that has
+/// been overtaken by a newer one fail with exactly `"superseded"`, which the
+/// frontend should ignore rather than treat as an error.
 #[tauri::command]
 pub fn search_notes(
     query: &str,
+    whole_word: Option<bool>,
+    path_prefix: Option<String>,
+    generation: Option<u64>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<NoteMetadata>, String> {
+    let max_results = app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .preferences
+        .max_search_results;
+    search_for_generation(&app_state, generation, || {
+        search_notes_hybrid(
+            &app_state,
+            query,
+            max_results,
+            whole_word.unwrap_or(false),
+            path_prefix.as_deref().unwrap_or(""),
+        )
+    })
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| SEARCH_SUPERSEDED.to_string())
+}
+
+#[tauri::command]
+pub fn search_notes_regex(
+    query: &str,
+    max_results: Option<usize>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<NoteMetadata>, String> {
+    // Searching reads the config again, so release the lock first
+    let limit = app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .preferences
+        .max_search_results;
+    let max_results = max_results.map_or(limit, |n| n.min(limit));
+    search_regex(&app_state, query, max_results).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn search_notes_fuzzy(
+    query: &str,
+    max_results: Option<usize>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<ScoredNoteMetadata>, String> {
+    // Searching reads the config again, so release the lock first
+    let limit = app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .preferences
+        .max_search_results;
+    let max_results = max_results.map_or(limit, |n| n.min(limit));
+    search_fuzzy(&app_state, query, max_results).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn search_notes_by_tag(
+    tag: String,
     app_state: tauri::State<crate::core::state::AppState>,
 ) -> Result<Vec<NoteMetadata>, String> {
     let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
-    search_notes_hybrid(&app_state, query, config.preferences.max_search_results)
+    search_by_tag(&app_state, &tag, config.preferences.max_search_results)
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub fn search_note_versions(
+    query: &str,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<VersionMatch>, String> {
+    // Searching reads the config again, so release the lock first
+    let max_results = app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .preferences
+        .max_search_results;
+    search_versions(&app_state, query, max_results).map_err(|e| e.to_string())
+}
+
+/// Every occurrence of `query` in one note, for find within a long note.
+#[tauri::command]
+pub fn search_within_note(
+    name: String,
+    query: String,
+    whole_word: Option<bool>,
+    case_sensitive: Option<bool>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<LineMatch>, String> {
+    let content = get_note_content(&name, app_state)?;
+    find_in_note(
+        &content,
+        &query,
+        whole_word.unwrap_or(false),
+        case_sensitive.unwrap_or(false),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Every tag with the number of notes using it, for a tag browser. Notes with
+/// a tag are listed by `search_notes_by_tag`.
+#[tauri::command]
+pub fn list_all_tags(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<(String, usize)>, String> {
+    with_db(&app_state, |conn| Ok(list_tags(conn)?)).map_err(|e| e.to_string())
+}
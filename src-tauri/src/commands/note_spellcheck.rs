@@ -0,0 +1,33 @@
+use crate::utilities::spellcheck::{dictionary_dir, SpellIssue};
+use crate::utilities::validation::validate_spellcheck_lang;
+
+/// Flags misspelled words in `text` with suggestions, skipping code. An empty
+/// `lang` uses `editor.spellcheck_lang`.
+#[tauri::command]
+pub fn spellcheck_text(
+    text: String,
+    lang: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<SpellIssue>, String> {
+    let (lang, dir) = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        let lang = if lang.trim().is_empty() {
+            config.editor.spellcheck_lang.clone()
+        } else {
+            validate_spellcheck_lang(&lang, config.editor.spellcheck_dictionary_dir.as_deref())
+                .map_err(|e| e.to_string())?;
+            lang
+        };
+        (
+            lang,
+            dictionary_dir(config.editor.spellcheck_dictionary_dir.as_deref()),
+        )
+    };
+
+    app_state
+        .spell_checker
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .check(&text, &lang, &dir)
+        .map_err(|e| e.to_string())
+}
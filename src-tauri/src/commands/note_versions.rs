@@ -1,12 +1,20 @@
 use crate::{
     core::{AppError, AppResult},
-    services::note_service::update_note_in_database,
+    services::{
+        note_service::update_note_in_database,
+        trash_service::{
+            purge_trash, purge_trashed_note_versions, trash_dir, trash_size, TrashSize,
+        },
+        version_service::{list_note_versions, NoteVersion},
+    },
     utilities::{
         file_safety::safe_write_note,
+        note_diff::{diff_lines, DiffHunk},
         strings::{
             format_timestamp_for_humans, parse_backup_filename, parse_deleted_backup_filename,
+            restored_note_name,
         },
-        validation::validate_note_name,
+        validation::{validate_note_extension, validate_note_name},
     },
 };
 use std::fs;
@@ -28,15 +36,6 @@ fn validate_backup_path(backup_dir: &Path, relative_name: &str) -> AppResult<Pat
     Ok(canonical_path)
 }
 
-#[derive(serde::Serialize)]
-pub struct NoteVersion {
-    pub filename: String,
-    pub backup_type: String,
-    pub timestamp: u64,
-    pub size: u64,
-    pub formatted_time: String,
-}
-
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct DeletedFile {
     pub filename: String,
@@ -53,62 +52,14 @@ pub fn get_note_versions(
     let result = || -> AppResult<Vec<NoteVersion>> {
         validate_note_name(note_name)?;
 
-        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
-        let notes_dir = std::path::PathBuf::from(&config.notes_directory);
-        let backup_dir = crate::utilities::paths::get_backup_dir_for_notes_path(&notes_dir)?;
-        if !backup_dir.exists() {
-            return Ok(Vec::new());
-        }
-
-        let base_name = if let Some(stem) = std::path::Path::new(note_name).file_stem() {
-            stem.to_string_lossy()
-        } else {
-            std::borrow::Cow::from(note_name)
+        let (notes_dir, date_format) = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            (
+                std::path::PathBuf::from(&config.notes_directory),
+                config.preferences.date_format.clone(),
+            )
         };
-
-        let note_path = notes_dir.join(note_name);
-        let note_backup_dir = crate::utilities::file_safety::safe_backup_path(&note_path)
-            .ok()
-            .and_then(|p| p.parent().map(|p| p.to_path_buf()))
-            .unwrap_or_else(|| backup_dir.clone());
-
-        if !note_backup_dir.exists() {
-            return Ok(Vec::new());
-        }
-
-        let mut versions = Vec::new();
-
-        if let Ok(entries) = fs::read_dir(&note_backup_dir) {
-            for entry in entries.flatten() {
-                let filename = entry.file_name().to_string_lossy().to_string();
-
-                if let Some((backup_type, timestamp)) = parse_backup_filename(&filename, &base_name)
-                {
-                    if let Ok(metadata) = entry.metadata() {
-                        let size = metadata.len();
-                        let formatted_time = format_timestamp_for_humans(timestamp);
-                        let relative_filename = entry
-                            .path()
-                            .strip_prefix(&backup_dir)
-                            .map(|p| p.to_string_lossy().to_string())
-                            .unwrap_or(filename);
-
-                        versions.push(NoteVersion {
-                            filename: relative_filename,
-                            backup_type,
-                            timestamp,
-                            size,
-                            formatted_time,
-                        });
-                    }
-                }
-            }
-        }
-
-        // Sort by timestamp (newest first)
-        versions.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
-
-        Ok(versions)
+        list_note_versions(&notes_dir, note_name, &date_format)
     }();
     result.map_err(|e| e.to_string())
 }
@@ -130,6 +81,55 @@ pub fn get_version_content(
     result.map_err(|e| e.to_string())
 }
 
+fn read_note_version(backup_dir: &Path, note_name: &str, version: &str) -> AppResult<String> {
+    let version_path = validate_backup_path(backup_dir, version).map_err(|e| match e {
+        AppError::FileNotFound(_) => AppError::FileNotFound(format!(
+            "Version '{}' of '{}' no longer exists",
+            version, note_name
+        )),
+        other => other,
+    })?;
+
+    let base_name = Path::new(note_name)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_else(|| std::borrow::Cow::from(note_name));
+    let belongs_to_note = version_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| parse_backup_filename(name, &base_name))
+        .is_some();
+    if !belongs_to_note {
+        return Err(AppError::InvalidPath(format!(
+            "'{}' is not a version of '{}'",
+            version, note_name
+        )));
+    }
+
+    Ok(fs::read_to_string(&version_path)?)
+}
+
+#[tauri::command]
+pub fn diff_note_versions(
+    name: String,
+    version_a: String,
+    version_b: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<Vec<DiffHunk>, String> {
+    let result = || -> AppResult<Vec<DiffHunk>> {
+        validate_note_name(&name)?;
+
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        let notes_dir = PathBuf::from(&config.notes_directory);
+        let backup_dir = crate::utilities::paths::get_backup_dir_for_notes_path(&notes_dir)?;
+
+        let old_content = read_note_version(&backup_dir, &name, &version_a)?;
+        let new_content = read_note_version(&backup_dir, &name, &version_b)?;
+        Ok(diff_lines(&old_content, &new_content))
+    }();
+    result.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn recover_note_version(
     note_name: &str,
@@ -149,7 +149,7 @@ pub fn recover_note_version(
 
         // Use the same programmatic flag and safe write as normal saves
         super::notes::with_programmatic_flag(&app_state, || {
-            safe_write_note(&note_path, &version_content)
+            safe_write_note(&note_path, &version_content, &config.preferences)
         })?;
 
         let modified = SystemTime::now()
@@ -186,31 +186,20 @@ pub fn get_deleted_files(
         {
             let filename = entry.file_name().to_string_lossy().to_string();
 
-            if let Some((flat_original, timestamp)) = parse_deleted_backup_filename(&filename) {
-                let base_stem = flat_original
-                    .strip_suffix(".md")
-                    .unwrap_or(&flat_original)
-                    .to_string();
-
-                let subdir = entry
-                    .path()
-                    .parent()
-                    .and_then(|p| p.strip_prefix(&backup_dir).ok())
-                    .filter(|p| !p.as_os_str().is_empty())
-                    .map(|p| p.to_string_lossy().to_string());
-
-                let original_filename = match &subdir {
-                    Some(d) => format!("{}/{}.md", d, base_stem),
-                    None => format!("{}.md", base_stem),
-                };
-
+            if let Some((_, timestamp)) = parse_deleted_backup_filename(&filename) {
                 let backup_filename_rel = entry
                     .path()
                     .strip_prefix(&backup_dir)
                     .map(|p| p.to_string_lossy().to_string())
                     .unwrap_or(filename);
+                let Some(original_filename) =
+                    original_name_for_deleted_backup(&backup_filename_rel)
+                else {
+                    continue;
+                };
 
-                let formatted_time = format_timestamp_for_humans(timestamp);
+                let formatted_time =
+                    format_timestamp_for_humans(timestamp, &config.preferences.date_format);
 
                 deleted_files.push(DeletedFile {
                     filename: original_filename,
@@ -239,7 +228,7 @@ pub fn recover_deleted_file(
         validate_note_name(original_filename)?;
 
         let config = app_state.config.read().unwrap_or_else(|e| {
-            crate::logging::log(
+            crate::logging::log_warn(
                 "RECOVER_FILE",
                 "Config lock was poisoned, recovering",
                 Some(&format!(
@@ -276,7 +265,7 @@ pub fn recover_deleted_file(
 
         // Write to the original location
         super::notes::with_programmatic_flag(&app_state, || {
-            safe_write_note(&note_path, &backup_content)
+            safe_write_note(&note_path, &backup_content, &config.preferences)
         })?;
 
         let modified = SystemTime::now()
@@ -294,3 +283,159 @@ pub fn recover_deleted_file(
     }();
     result.map_err(|e| e.to_string())
 }
+
+/// Maps a deleted-note backup, relative to the backup directory, to the note it came from.
+fn original_name_for_deleted_backup(backup_filename: &str) -> Option<String> {
+    let path = Path::new(backup_filename);
+    let (flat_original, _) = parse_deleted_backup_filename(&path.file_name()?.to_string_lossy())?;
+    let base_stem = flat_original
+        .strip_suffix(".md")
+        .unwrap_or(&flat_original)
+        .to_string();
+
+    match path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        Some(dir) => Some(format!("{}/{}.md", dir.to_string_lossy(), base_stem)),
+        None => Some(format!("{}.md", base_stem)),
+    }
+}
+
+/// Restores a deleted note identified by its `backup_filename` from `get_deleted_files`.
+/// Uses `new_name` when given, otherwise the original name with a suffix if it is taken,
+/// and returns the name the note was restored as.
+#[tauri::command]
+pub fn restore_deleted_note(
+    id: String,
+    new_name: Option<String>,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<String, String> {
+    let result = || -> AppResult<String> {
+        let original_name = original_name_for_deleted_backup(&id)
+            .ok_or_else(|| AppError::InvalidPath(format!("'{}' is not a deleted note", id)))?;
+
+        let (notes_dir, preferences) = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            (
+                PathBuf::from(&config.notes_directory),
+                config.preferences.clone(),
+            )
+        };
+        let backup_dir = crate::utilities::paths::get_backup_dir_for_notes_path(&notes_dir)?;
+        let backup_path = validate_backup_path(&backup_dir, &id).map_err(|e| match e {
+            AppError::FileNotFound(_) => AppError::FileNotFound(format!(
+                "Deleted note '{}' no longer exists; it may have been purged from the trash",
+                original_name
+            )),
+            other => other,
+        })?;
+
+        let final_name = match new_name {
+            Some(name) => {
+                validate_note_name(&name)?;
+                validate_note_extension(&name, &preferences.note_extensions)?;
+                if notes_dir.join(&name).exists() {
+                    return Err(AppError::FileWrite(format!(
+                        "Cannot restore as '{}': file already exists",
+                        name
+                    )));
+                }
+                name
+            }
+            None => {
+                validate_note_name(&original_name)?;
+                std::iter::once(original_name.clone())
+                    .chain((1..).map(|attempt| restored_note_name(&original_name, attempt)))
+                    .find(|candidate| !notes_dir.join(candidate).exists())
+                    .ok_or_else(|| {
+                        AppError::FileWrite(format!(
+                            "No free name to restore '{}' as",
+                            original_name
+                        ))
+                    })?
+            }
+        };
+
+        let content = fs::read_to_string(&backup_path)?;
+        let note_path = notes_dir.join(&final_name);
+        super::notes::with_programmatic_flag(&app_state, || {
+            if let Some(parent) = note_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            safe_write_note(&note_path, &content, &preferences)
+        })?;
+
+        let modified = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        update_note_in_database(&app_state, &final_name, &content, modified)?;
+
+        let _ = fs::remove_file(&backup_path);
+        crate::logging::log(
+            "FILE_OPERATION",
+            &format!("RESTORE: {} -> {} | SUCCESS", id, final_name),
+            None,
+        );
+        Ok(final_name)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Permanently removes one deleted note, identified by its `backup_filename`
+/// from `get_deleted_files`, along with the versions of that note. Versions
+/// are kept while a note of that name exists again.
+#[tauri::command]
+pub fn purge_trash_item(
+    id: String,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        let original_name = original_name_for_deleted_backup(&id)
+            .ok_or_else(|| AppError::InvalidPath(format!("'{}' is not a deleted note", id)))?;
+
+        let notes_dir = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            PathBuf::from(&config.notes_directory)
+        };
+        let backup_dir = crate::utilities::paths::get_backup_dir_for_notes_path(&notes_dir)?;
+        let backup_path = validate_backup_path(&backup_dir, &id).map_err(|e| match e {
+            AppError::FileNotFound(_) => AppError::FileNotFound(format!(
+                "Deleted note '{}' is not in the trash",
+                original_name
+            )),
+            other => other,
+        })?;
+
+        fs::remove_file(&backup_path)?;
+        let versions = if notes_dir.join(&original_name).exists() {
+            0
+        } else {
+            purge_trashed_note_versions(&backup_path)?
+        };
+        crate::logging::log(
+            "TRASH",
+            &format!("PURGE: {} | {} versions removed", id, versions),
+            None,
+        );
+        Ok(())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn empty_trash(app_state: tauri::State<crate::core::state::AppState>) -> Result<usize, String> {
+    let result = || -> AppResult<usize> {
+        let purged = purge_trash(&trash_dir(&app_state)?, None)?;
+        crate::logging::log("TRASH", &format!("EMPTY: {} notes purged", purged), None);
+        Ok(purged)
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_trash_size(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<TrashSize, String> {
+    trash_dir(&app_state)
+        .map(|dir| trash_size(&dir))
+        .map_err(|e| e.to_string())
+}
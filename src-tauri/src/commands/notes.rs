@@ -27,7 +27,19 @@ where
 
 // Re-export all note-related commands from their respective modules
 // This maintains backward compatibility while organizing the code better
+pub use super::note_archive::*;
+pub use super::note_attachments::*;
 pub use super::note_crud::*;
+pub use super::note_encryption::*;
+pub use super::note_export::*;
 pub use super::note_external::*;
+pub use super::note_folders::*;
+pub use super::note_ids::*;
+pub use super::note_integrity::*;
+pub use super::note_links::*;
+pub use super::note_pins::*;
+pub use super::note_recents::*;
+pub use super::note_replace::*;
 pub use super::note_search::*;
+pub use super::note_spellcheck::*;
 pub use super::note_versions::*;
@@ -1,17 +1,23 @@
 use crate::{
     config::{reload_config, ConfigReloadResult},
-    database::{refresh_database_connection, with_db_mut},
-    logging::log,
-    services::database_service::{
-        init_db, load_all_notes_into_sqlite, load_all_notes_into_sqlite_with_progress,
-        recreate_database_with_progress,
+    database::{apply_cache_pragmas, refresh_database_connection, with_db, with_db_mut},
+    logging::{get_log_path, log, log_warn, recent_logs, LogLevel, LogRecord},
+    services::{
+        database_service::{
+            init_db, load_all_notes_into_sqlite_with_progress, recreate_database_with_progress,
+            sync_notes_with_filesystem,
+        },
+        index_service::{compact_database, CacheCompaction, IndexRefresh},
+        note_service::{invalidate_rendered_notes, render_options},
+        render_cache_service,
+        trash_service::{list_trash_entries, trash_dir},
     },
 };
 use tauri::{AppHandle, Emitter};
 
 fn emit_with_logging<T: serde::Serialize + Clone>(app: &AppHandle, event: &str, payload: T) {
     if let Err(e) = app.emit(event, payload) {
-        log(
+        log_warn(
             "UI_UPDATE",
             &format!("Failed to emit {}", event),
             Some(&e.to_string()),
@@ -31,9 +37,10 @@ pub async fn initialize_notes_with_progress(
 #[tauri::command]
 pub async fn refresh_cache(
     app: AppHandle,
+    force: bool,
     app_state: tauri::State<'_, crate::core::state::AppState>,
 ) -> Result<(), String> {
-    let result = perform_cache_refresh(&app, &app_state).await;
+    let result = perform_cache_refresh(&app, &app_state, force).await;
     result.map_err(|e: crate::core::AppError| e.to_string())
 }
 
@@ -54,23 +61,60 @@ async fn perform_notes_initialization(
 
     let result = execute_notes_loading_task(app, app_state).await?;
 
-    handle_initialization_result(app, result)
+    handle_initialization_result(app, result)?;
+    record_index_refresh(app_state);
+    Ok(())
 }
 
 async fn perform_cache_refresh(
     app: &AppHandle,
     app_state: &tauri::State<'_, crate::core::state::AppState>,
+    force: bool,
 ) -> Result<(), crate::core::AppError> {
     emit_with_logging(app, "db-loading-start", "Refreshing notes...");
     emit_with_logging(app, "db-loading-progress", "Loading settings...");
 
+    let previous_render_options = render_options(app_state);
     let reload_result = handle_config_reload(app, app_state)?;
     handle_database_connection_refresh(app, app_state, reload_result)?;
+    // Pragma changes take effect without reopening the connection
+    let preferences = app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .preferences
+        .clone();
+    with_db(app_state, |conn| {
+        apply_cache_pragmas(conn, &preferences);
+        Ok(())
+    })?;
+    if render_options(app_state) != previous_render_options {
+        invalidate_rendered_notes(app_state)?;
+    }
 
     emit_cache_refresh_progress(app);
 
-    let result = execute_cache_refresh_task(app_state).await?;
-    handle_cache_refresh_result(app, app_state, result).await
+    let result = execute_cache_refresh_task(app_state, force).await?;
+    handle_cache_refresh_result(app, app_state, result).await?;
+    record_index_refresh(app_state);
+    Ok(())
+}
+
+/// Counts the notes kept outside the index now, so `get_index_stats` does not
+/// have to walk the archive and trash when it is polled.
+pub fn record_index_refresh(app_state: &crate::core::state::AppState) {
+    let trashed_notes = trash_dir(app_state)
+        .map(|dir| list_trash_entries(&dir).len())
+        .unwrap_or(0);
+    let refresh = IndexRefresh {
+        refreshed_at: chrono::Utc::now().timestamp(),
+        archived_notes: super::note_archive::archived_notes(app_state).len(),
+        trashed_notes,
+    };
+    *app_state
+        .last_refresh
+        .lock()
+        .unwrap_or_else(|e| e.into_inner()) = Some(refresh);
 }
 
 fn emit_initialization_progress(app: &AppHandle) {
@@ -135,20 +179,20 @@ fn handle_database_connection_refresh(
     app_state: &tauri::State<'_, crate::core::state::AppState>,
     reload_result: ConfigReloadResult,
 ) -> Result<(), crate::core::AppError> {
-    if reload_result == ConfigReloadResult::NotesDirChanged {
+    if reload_result != ConfigReloadResult::Unchanged {
         match refresh_database_connection(app_state) {
             Ok(true) => {
                 emit_with_logging(
                     app,
                     "db-loading-progress",
-                    "Notes directory changed, database connection refreshed",
+                    "Database location changed, database connection refreshed",
                 );
             }
             Ok(false) => {
                 emit_with_logging(
                     app,
                     "db-loading-progress",
-                    "Notes directory unchanged, continuing with existing database",
+                    "Database location unchanged, continuing with existing database",
                 );
             }
             Err(e) => {
@@ -172,13 +216,20 @@ fn emit_cache_refresh_progress(app: &AppHandle) {
 
 async fn execute_cache_refresh_task(
     app_state: &tauri::State<'_, crate::core::state::AppState>,
+    force: bool,
 ) -> Result<Result<(), crate::core::AppError>, crate::core::AppError> {
     let app_state_clone = app_state.inner().clone();
 
     tokio::task::spawn_blocking(move || {
         with_db_mut(&app_state_clone, |conn| {
             init_db(conn)?;
-            load_all_notes_into_sqlite(&app_state_clone, conn).map_err(|e| e.into())
+            let files_read = sync_notes_with_filesystem(&app_state_clone, conn, force, None)?;
+            log(
+                "CACHE_REFRESH",
+                &format!("Re-read {} changed notes (force: {})", files_read, force),
+                None,
+            );
+            Ok(())
         })
     })
     .await
@@ -209,7 +260,7 @@ async fn handle_cache_refresh_failure(
         "db-loading-progress",
         "Database sync failed, attempting recovery...",
     );
-    log(
+    log_warn(
         "DATABASE_RECOVERY",
         "Failed to refresh notes cache. Attempting recovery...",
         Some(&original_error.to_string()),
@@ -235,3 +286,57 @@ async fn handle_cache_refresh_failure(
     }
     result
 }
+
+/// Returns recent log entries for the debug panel; `None` includes every level.
+#[tauri::command]
+pub fn get_recent_logs(level_filter: Option<LogLevel>) -> Vec<LogRecord> {
+    recent_logs(level_filter.unwrap_or(LogLevel::Debug))
+}
+
+/// Returns where the active log file is written; archives sit beside it as `.1`, `.2`, ...
+#[tauri::command]
+pub fn get_log_file_path() -> Result<String, String> {
+    get_log_path()
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Drops every cached render and returns how many were removed. Notes already
+/// rendered into the index keep their HTML until their content or the render
+/// settings change.
+#[tauri::command]
+pub fn clear_render_cache(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<usize, String> {
+    with_db(&app_state, |conn| {
+        Ok(render_cache_service::clear_render_cache(conn)?)
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Compacts the cache database and returns its size before and after. Holds the
+/// database lock throughout, so nothing else reads or writes while it runs.
+#[tauri::command]
+pub fn compact_cache(
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<CacheCompaction, String> {
+    let result = with_db(&app_state, |conn| Ok(compact_database(conn)?));
+    match &result {
+        Ok(compaction) => log(
+            "DATABASE_OPERATION",
+            &format!(
+                "COMPACT: {} -> {} bytes | Reclaimed: {} bytes | SUCCESS",
+                compaction.before_bytes,
+                compaction.after_bytes,
+                compaction.reclaimed_bytes()
+            ),
+            None,
+        ),
+        Err(e) => log_warn(
+            "DATABASE_OPERATION",
+            "Failed to compact cache database",
+            Some(&e.to_string()),
+        ),
+    }
+    result.map_err(|e| e.to_string())
+}
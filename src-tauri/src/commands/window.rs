@@ -1,5 +1,11 @@
 use crate::core::AppResult;
-use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+#[cfg(target_os = "macos")]
+use crate::utilities::window_focus::show_app;
+use crate::utilities::window_geometry::{
+    clear_window_geometry, restore_window_geometry, save_window_geometry, DEFAULT_WINDOW_HEIGHT,
+    DEFAULT_WINDOW_WIDTH,
+};
+use tauri::{AppHandle, LogicalSize, Manager, WebviewUrl, WebviewWindowBuilder};
 
 #[tauri::command]
 pub fn show_main_window(
@@ -22,7 +28,7 @@ pub fn show_main_window(
                 let mut window_builder =
                     WebviewWindowBuilder::new(&app, "main", WebviewUrl::default())
                         .title("Symiosis Notes")
-                        .inner_size(1200.0, 800.0)
+                        .inner_size(DEFAULT_WINDOW_WIDTH, DEFAULT_WINDOW_HEIGHT)
                         .center()
                         .visible(false)
                         .decorations(window_decorations);
@@ -31,7 +37,8 @@ pub fn show_main_window(
                     window_builder = window_builder.always_on_top(true);
                 }
 
-                let _window = window_builder.build()?;
+                let window = window_builder.build()?;
+                restore_window_geometry(&window);
             }
         }
         Ok(())
@@ -43,9 +50,69 @@ pub fn show_main_window(
 pub fn hide_main_window(app: AppHandle) -> Result<(), String> {
     let result = || -> AppResult<()> {
         if let Some(window) = app.get_webview_window("main") {
+            save_window_geometry(&window);
             window.hide()?;
         }
         Ok(())
     }();
     result.map_err(|e| e.to_string())
 }
+
+/// Forgets the saved window geometry and puts the window back at its default size, centered.
+#[tauri::command]
+pub fn reset_window_geometry(
+    app: AppHandle,
+    app_state: tauri::State<crate::core::state::AppState>,
+) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        clear_window_geometry()?;
+        if let Some(window) = app.get_webview_window("main") {
+            let window_decorations = app_state
+                .config
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .interface
+                .window_decorations;
+            window.unmaximize()?;
+            window.set_decorations(window_decorations)?;
+            window.set_size(LogicalSize::new(
+                DEFAULT_WINDOW_WIDTH,
+                DEFAULT_WINDOW_HEIGHT,
+            ))?;
+            window.center()?;
+        }
+        Ok(())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+/// Pins the main window above other windows, or releases it. This only lasts for the
+/// session; `interface.always_on_top` decides the state at startup.
+#[tauri::command]
+pub fn set_always_on_top(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let result = || -> AppResult<()> {
+        if let Some(window) = app.get_webview_window("main") {
+            window.set_always_on_top(enabled)?;
+
+            // Changing the window level can leave the app inactive on macOS, so
+            // reactivate it the same way show_app does when the window is showing
+            #[cfg(target_os = "macos")]
+            if window.is_visible()? {
+                show_app(window);
+            }
+        }
+        Ok(())
+    }();
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn is_always_on_top(app: AppHandle) -> Result<bool, String> {
+    let result = || -> AppResult<bool> {
+        match app.get_webview_window("main") {
+            Some(window) => Ok(window.is_always_on_top()?),
+            None => Ok(false),
+        }
+    }();
+    result.map_err(|e| e.to_string())
+}
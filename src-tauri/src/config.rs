@@ -1,22 +1,62 @@
 use crate::core::{AppError, AppResult};
-use crate::logging::log;
-use crate::utilities::config_helpers::{default_global_shortcut, default_window_decorations};
+use crate::logging::{log, log_warn};
+use crate::utilities::config_helpers::{
+    default_cache_database_path, default_cache_journal_mode, default_cache_synchronous,
+    default_confirm_before_delete, default_date_format, default_ensure_final_newline,
+    default_external_editor_command, default_follow_symlinks, default_global_shortcut,
+    default_index_threads, default_line_ending, default_markdown_flavor,
+    default_markdown_heading_anchors, default_markdown_render_math, default_max_pasted_image_mb,
+    default_max_recursion_depth, default_note_extensions, default_note_sort_order,
+    default_pinned_notes_first, default_render_cache_max_entries,
+    default_search_normalize_diacritics, default_search_stemming, default_search_title_boost,
+    default_stream_note_threshold_kb, default_time_format, default_trash_retention_days,
+    default_trim_trailing_whitespace, default_version_history_limit,
+    default_version_min_interval_seconds, default_watcher_debounce_ms, default_window_decorations,
+    sanitize_editor_config,
+};
 
 pub use crate::utilities::config_helpers::{
-    get_available_code_themes, get_available_editor_modes, get_available_editor_themes,
-    get_available_markdown_themes, get_available_ui_themes, load_config_from_content,
+    generate_config_toml, generate_default_config_toml, get_available_cache_journal_modes,
+    get_available_cache_synchronous_modes, get_available_code_themes, get_available_editor_modes,
+    get_available_editor_themes, get_available_line_endings, get_available_markdown_flavors,
+    get_available_markdown_themes, get_available_note_sort_orders, get_available_ui_themes,
+    get_available_vim_actions, get_config_warnings, load_config_from_content, normalize_shortcut,
     parse_shortcut,
 };
-use crate::utilities::paths::{get_config_path, get_default_notes_dir};
+use crate::utilities::paths::{get_config_path, get_default_notes_dir, NOTES_DIR_ENV};
+use crate::utilities::spellcheck::BUNDLED_DICTIONARY;
+use crate::utilities::validation::validate_notes_directory;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
-use tauri::{AppHandle, Emitter};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_global_shortcut::GlobalShortcut;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConfigReloadResult {
     Unchanged,
     NotesDirChanged,
+    CacheDatabaseMoved,
+}
+
+/// A config field that failed validation and was replaced with its default.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConfigWarning {
+    pub field: String,
+    pub value: String,
+    pub default: String,
+}
+
+/// Every theme name the config accepts, so settings dropdowns match validation.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct AvailableThemes {
+    pub ui_themes: Vec<String>,
+    pub markdown_themes: Vec<String>,
+    /// Built-in themes followed by any user themes in the editor themes folder.
+    pub editor_themes: Vec<String>,
+    pub code_themes: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -25,6 +65,8 @@ pub struct AppConfig {
     pub notes_directory: String,
     #[serde(default = "default_global_shortcut")]
     pub global_shortcut: String,
+    #[serde(default)]
+    pub global_shortcut_new_note: Option<String>,
 
     #[serde(default)]
     pub general: GeneralConfig,
@@ -40,6 +82,9 @@ pub struct AppConfig {
 
     #[serde(default)]
     pub preferences: PreferencesConfig,
+
+    #[serde(default)]
+    pub logging: LoggingConfig,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -71,6 +116,7 @@ pub struct InterfaceConfig {
 pub struct ShortcutsConfig {
     pub create_note: String,
     pub rename_note: String,
+    pub duplicate_note: String,
     pub delete_note: String,
     pub edit_note: String,
     pub save_and_exit: String,
@@ -91,6 +137,7 @@ pub struct ShortcutsConfig {
     pub open_settings: String,
     pub version_explorer: String,
     pub recently_deleted: String,
+    pub toggle_always_on_top: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -98,9 +145,75 @@ pub struct ShortcutsConfig {
 pub struct PreferencesConfig {
     #[serde(default = "default_max_results")]
     pub max_search_results: usize,
+    #[serde(default = "default_note_extensions")]
+    pub note_extensions: Vec<String>,
+    #[serde(default = "default_search_normalize_diacritics")]
+    pub search_normalize_diacritics: bool,
+    #[serde(default = "default_search_title_boost")]
+    pub search_title_boost: f64,
+    #[serde(default = "default_search_stemming")]
+    pub search_stemming: bool,
+    #[serde(default = "default_watcher_debounce_ms")]
+    pub watcher_debounce_ms: u64,
+    #[serde(default = "default_max_recursion_depth")]
+    pub max_recursion_depth: usize,
+    #[serde(default = "default_follow_symlinks")]
+    pub follow_symlinks: bool,
+    #[serde(default = "default_index_threads")]
+    pub index_threads: usize,
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    #[serde(default = "default_version_history_limit")]
+    pub version_history_limit: usize,
+    #[serde(default = "default_version_min_interval_seconds")]
+    pub version_min_interval_seconds: u64,
+    #[serde(default = "default_max_pasted_image_mb")]
+    pub max_pasted_image_mb: u64,
+    #[serde(default = "default_stream_note_threshold_kb")]
+    pub stream_note_threshold_kb: u64,
+    #[serde(default = "default_render_cache_max_entries")]
+    pub render_cache_max_entries: usize,
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+    #[serde(default = "default_time_format")]
+    pub time_format: String,
+    #[serde(default = "default_note_sort_order")]
+    pub note_sort_order: String,
+    #[serde(default = "default_pinned_notes_first")]
+    pub pinned_notes_first: bool,
+    #[serde(default = "default_confirm_before_delete")]
+    pub confirm_before_delete: bool,
+    /// SQLite journal mode of the search index database.
+    #[serde(default = "default_cache_journal_mode")]
+    pub cache_journal_mode: String,
+    /// SQLite `synchronous` level of the search index database.
+    #[serde(default = "default_cache_synchronous")]
+    pub cache_synchronous: String,
+    /// Line breaks notes are saved with: "lf", "crlf", or "preserve" to write them unchanged.
+    #[serde(default = "default_line_ending")]
+    pub line_ending: String,
+    /// Strip trailing spaces and tabs outside fenced code when saving.
+    #[serde(default = "default_trim_trailing_whitespace")]
+    pub trim_trailing_whitespace: bool,
+    /// End every saved note with exactly one line break.
+    #[serde(default = "default_ensure_final_newline")]
+    pub ensure_final_newline: bool,
+    #[serde(default = "default_external_editor_command")]
+    pub external_editor_command: String,
+    #[serde(default = "default_markdown_heading_anchors")]
+    pub markdown_heading_anchors: bool,
+    #[serde(default = "default_markdown_render_math")]
+    pub markdown_render_math: bool,
+    /// "gfm" for GitHub Flavored Markdown extensions, or plain "commonmark".
+    #[serde(default = "default_markdown_flavor")]
+    pub markdown_flavor: String,
+    /// File for the search index, e.g. on local storage when the notes are on a
+    /// network drive. Unset keeps it in the app data directory.
+    #[serde(default = "default_cache_database_path")]
+    pub cache_database_path: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(default)]
 pub struct EditorConfig {
     pub mode: String,
@@ -109,6 +222,29 @@ pub struct EditorConfig {
     pub tab_size: u16,
     pub expand_tabs: bool,
     pub show_line_numbers: bool,
+    pub autosave_interval_seconds: u64,
+    /// Dictionary used by spellcheck_text, bundled or from the dictionary directory.
+    pub spellcheck_lang: String,
+    /// Directory of Hunspell `.aff`/`.dic` pairs; `dictionaries` next to the config when unset.
+    pub spellcheck_dictionary_dir: Option<String>,
+    /// Only used when `mode` is "vim".
+    pub vim: VimConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct VimConfig {
+    /// Key that `<leader>` stands for in mapping sequences.
+    pub leader: String,
+    /// Normal mode key sequence to editor action, e.g. `"<leader>w" = "save"`.
+    pub mappings: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct LoggingConfig {
+    pub max_file_size_mb: u64,
+    pub max_archives: usize,
 }
 
 fn default_notes_directory() -> String {
@@ -128,11 +264,13 @@ impl Default for AppConfig {
         Self {
             notes_directory: get_default_notes_dir(),
             global_shortcut: default_global_shortcut(),
+            global_shortcut_new_note: None,
             general: GeneralConfig::default(),
             interface: InterfaceConfig::default(),
             editor: EditorConfig::default(),
             shortcuts: ShortcutsConfig::default(),
             preferences: PreferencesConfig::default(),
+            logging: LoggingConfig::default(),
         }
     }
 }
@@ -168,6 +306,7 @@ impl Default for ShortcutsConfig {
         Self {
             create_note: "Ctrl+Enter".to_string(),
             rename_note: "Ctrl+m".to_string(),
+            duplicate_note: "Ctrl+Alt+d".to_string(),
             delete_note: "Ctrl+x".to_string(),
             edit_note: "Enter".to_string(),
             save_and_exit: "Ctrl+s".to_string(),
@@ -188,6 +327,7 @@ impl Default for ShortcutsConfig {
             open_settings: "Meta+,".to_string(),
             version_explorer: "Ctrl+/".to_string(),
             recently_deleted: "Ctrl+.".to_string(),
+            toggle_always_on_top: "Ctrl+t".to_string(),
         }
     }
 }
@@ -196,6 +336,44 @@ impl Default for PreferencesConfig {
     fn default() -> Self {
         Self {
             max_search_results: default_max_results(),
+            note_extensions: default_note_extensions(),
+            search_normalize_diacritics: default_search_normalize_diacritics(),
+            search_title_boost: default_search_title_boost(),
+            search_stemming: default_search_stemming(),
+            watcher_debounce_ms: default_watcher_debounce_ms(),
+            max_recursion_depth: default_max_recursion_depth(),
+            follow_symlinks: default_follow_symlinks(),
+            index_threads: default_index_threads(),
+            trash_retention_days: default_trash_retention_days(),
+            version_history_limit: default_version_history_limit(),
+            version_min_interval_seconds: default_version_min_interval_seconds(),
+            max_pasted_image_mb: default_max_pasted_image_mb(),
+            stream_note_threshold_kb: default_stream_note_threshold_kb(),
+            render_cache_max_entries: default_render_cache_max_entries(),
+            date_format: default_date_format(),
+            time_format: default_time_format(),
+            note_sort_order: default_note_sort_order(),
+            pinned_notes_first: default_pinned_notes_first(),
+            confirm_before_delete: default_confirm_before_delete(),
+            cache_journal_mode: default_cache_journal_mode(),
+            cache_synchronous: default_cache_synchronous(),
+            line_ending: default_line_ending(),
+            trim_trailing_whitespace: default_trim_trailing_whitespace(),
+            ensure_final_newline: default_ensure_final_newline(),
+            external_editor_command: default_external_editor_command(),
+            markdown_heading_anchors: default_markdown_heading_anchors(),
+            markdown_render_math: default_markdown_render_math(),
+            markdown_flavor: default_markdown_flavor(),
+            cache_database_path: default_cache_database_path(),
+        }
+    }
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            max_file_size_mb: crate::logging::DEFAULT_MAX_LOG_FILE_SIZE_MB,
+            max_archives: crate::logging::DEFAULT_MAX_LOG_ARCHIVES,
         }
     }
 }
@@ -209,6 +387,19 @@ impl Default for EditorConfig {
             tab_size: 2,
             expand_tabs: true,
             show_line_numbers: true,
+            autosave_interval_seconds: 0,
+            spellcheck_lang: BUNDLED_DICTIONARY.to_string(),
+            spellcheck_dictionary_dir: None,
+            vim: VimConfig::default(),
+        }
+    }
+}
+
+impl Default for VimConfig {
+    fn default() -> Self {
+        Self {
+            leader: "\\".to_string(),
+            mappings: BTreeMap::new(),
         }
     }
 }
@@ -225,12 +416,12 @@ pub fn get_config_notes_dir_from_config(config: &AppConfig) -> PathBuf {
 pub fn load_config() -> AppConfig {
     let config_path = get_config_path();
 
-    match fs::read_to_string(&config_path) {
+    let mut config = match fs::read_to_string(&config_path) {
         Ok(content) => load_config_from_content(&content),
         Err(_) => {
             let default_config = AppConfig::default();
             if let Err(e) = save_config(&default_config) {
-                log(
+                log_warn(
                     "CONFIG_CREATION",
                     "Failed to create default config file",
                     Some(&e.to_string()),
@@ -238,19 +429,21 @@ pub fn load_config() -> AppConfig {
             }
             default_config
         }
-    }
+    };
+    apply_notes_dir_override(&mut config);
+    config
 }
 
 pub fn load_config_with_first_run_info() -> (AppConfig, bool) {
     let config_path = get_config_path();
     let was_first_run = !config_path.exists();
 
-    let config = match fs::read_to_string(&config_path) {
+    let mut config = match fs::read_to_string(&config_path) {
         Ok(content) => load_config_from_content(&content),
         Err(_) => {
             let default_config = AppConfig::default();
             if let Err(e) = save_config(&default_config) {
-                log(
+                log_warn(
                     "CONFIG_CREATION",
                     "Failed to create default config file",
                     Some(&e.to_string()),
@@ -259,35 +452,38 @@ pub fn load_config_with_first_run_info() -> (AppConfig, bool) {
             default_config
         }
     };
+    apply_notes_dir_override(&mut config);
 
     (config, was_first_run)
 }
 
-pub fn save_config(config: &AppConfig) -> AppResult<()> {
-    let config_path = get_config_path();
+/// Lets `SYMIOSIS_NOTES_DIR` take precedence over `notes_directory`. It is
+/// applied after loading, so the value is never written back to the config file.
+pub fn apply_notes_dir_override(config: &mut AppConfig) {
+    let Ok(notes_dir) = std::env::var(NOTES_DIR_ENV) else {
+        return;
+    };
 
-    if let Some(parent) = config_path.parent() {
-        fs::create_dir_all(parent)?;
+    match validate_notes_directory(&notes_dir) {
+        Ok(()) => config.notes_directory = notes_dir,
+        Err(e) => log(
+            "CONFIG_VALIDATION",
+            &format!("Ignoring {} '{}'", NOTES_DIR_ENV, notes_dir),
+            Some(&e.to_string()),
+        ),
     }
+}
+
+pub fn save_config(config: &AppConfig) -> AppResult<()> {
+    let config_path = get_config_path();
 
-    let mut toml_content = toml::to_string_pretty(config)
+    let toml_content = toml::to_string_pretty(config)
         .map_err(|e| AppError::ConfigSave(format!("Failed to serialize config: {}", e)))?;
 
-    // Add commented examples for None values
-    if config.interface.custom_ui_theme_path.is_none() {
-        toml_content = toml_content.replace(
-            "[interface]",
-            "[interface]\n# custom_ui_theme_path = \"path/to/custom/ui_theme.css\"",
-        );
-    }
-    if config.interface.custom_markdown_theme_path.is_none() {
-        toml_content = toml_content.replace(
-            "# custom_ui_theme_path = \"path/to/custom/ui_theme.css\"",
-            "# custom_ui_theme_path = \"path/to/custom/ui_theme.css\"\n# custom_markdown_theme_path = \"path/to/custom/markdown_theme.css\""
-        );
-    }
+    let toml_content =
+        crate::utilities::config_helpers::add_optional_field_examples(config, toml_content);
 
-    fs::write(&config_path, toml_content)?;
+    write_config_atomically(&config_path, &toml_content)?;
 
     log(
         "CONFIG",
@@ -297,24 +493,74 @@ pub fn save_config(config: &AppConfig) -> AppResult<()> {
     Ok(())
 }
 
-pub fn reload_config(
+fn sibling_with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+/// Where the previous config content is kept after each save.
+pub fn config_backup_path(config_path: &Path) -> PathBuf {
+    sibling_with_suffix(config_path, ".bak")
+}
+
+/// Replaces the config file so it is never left half-written. The content is
+/// synced to a temp file beside the config and renamed over it, after the
+/// previous content has been copied to the `.bak` file.
+pub fn write_config_atomically(config_path: &Path, content: &str) -> AppResult<()> {
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let temp_path = sibling_with_suffix(config_path, ".tmp");
+    let result = (|| -> AppResult<()> {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+
+        if config_path.exists() {
+            fs::copy(config_path, config_backup_path(config_path)).map_err(|e| {
+                AppError::ConfigSave(format!("Failed to back up previous config: {}", e))
+            })?;
+        }
+
+        fs::rename(&temp_path, config_path)
+            .map_err(|e| AppError::ConfigSave(format!("Failed to replace config file: {}", e)))
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+pub fn reload_config<R: Runtime>(
     app_config: &std::sync::RwLock<AppConfig>,
-    app_handle: Option<AppHandle>,
+    app_handle: Option<AppHandle<R>>,
 ) -> Result<ConfigReloadResult, String> {
     let new_config = load_config();
 
-    let result = {
+    let (result, editor_changed) = {
         let old_config = app_config
             .read()
             .map_err(|_| "Failed to acquire read lock on config".to_string())?;
 
-        if get_config_notes_dir_from_config(&old_config)
+        if let Some(app) = &app_handle {
+            apply_shortcut_changes(app, &old_config, &new_config).map_err(|e| e.to_string())?;
+        }
+
+        let result = if get_config_notes_dir_from_config(&old_config)
             != get_config_notes_dir_from_config(&new_config)
         {
             ConfigReloadResult::NotesDirChanged
+        } else if old_config.preferences.cache_database_path
+            != new_config.preferences.cache_database_path
+        {
+            ConfigReloadResult::CacheDatabaseMoved
         } else {
             ConfigReloadResult::Unchanged
-        }
+        };
+        (result, old_config.editor != new_config.editor)
     };
 
     let mut config = app_config
@@ -322,15 +568,166 @@ pub fn reload_config(
         .map_err(|_| "Failed to acquire write lock on config".to_string())?;
     *config = new_config.clone();
     drop(config);
+    crate::logging::configure_rotation(&new_config.logging);
 
     if let Some(app) = app_handle {
         if let Err(e) = app.emit("config-updated", &new_config) {
-            log(
+            log_warn(
                 "CONFIG_EVENT",
                 "Failed to emit config-updated event",
                 Some(&e.to_string()),
             );
         }
+        if editor_changed {
+            notify_editor_config(&app, &new_config.editor);
+        }
     }
     Ok(result)
 }
+
+/// Re-registers the global shortcuts that differ between `old` and `new`. When
+/// registering a new shortcut fails the old one is restored and an error is
+/// returned, so the caller can keep the previous config.
+pub fn apply_shortcut_changes<R: Runtime>(
+    app: &AppHandle<R>,
+    old: &AppConfig,
+    new: &AppConfig,
+) -> AppResult<()> {
+    let Some(global_shortcut) = app.try_state::<GlobalShortcut<R>>() else {
+        return Ok(());
+    };
+
+    if old.global_shortcut != new.global_shortcut {
+        update_global_shortcut(&global_shortcut, &old.global_shortcut, &new.global_shortcut)?;
+    }
+
+    if old.global_shortcut_new_note != new.global_shortcut_new_note {
+        update_new_note_shortcut(
+            &global_shortcut,
+            old.global_shortcut_new_note.as_deref(),
+            new.global_shortcut_new_note.as_deref(),
+        )?;
+    }
+    Ok(())
+}
+
+fn update_global_shortcut<R: Runtime>(
+    global_shortcut: &GlobalShortcut<R>,
+    old_shortcut: &str,
+    new_shortcut: &str,
+) -> AppResult<()> {
+    let new = parse_shortcut(new_shortcut).ok_or_else(|| {
+        AppError::ConfigLoad(format!(
+            "Invalid global shortcut format: '{}'",
+            new_shortcut
+        ))
+    })?;
+    let old = parse_shortcut(old_shortcut);
+
+    if let Some(old) = old {
+        if let Err(e) = global_shortcut.unregister(old) {
+            log_warn(
+                "GLOBAL_SHORTCUT",
+                &format!("Failed to unregister global shortcut '{}'", old_shortcut),
+                Some(&e.to_string()),
+            );
+        }
+    }
+
+    if let Err(e) = global_shortcut.register(new) {
+        if let Some(old) = old {
+            let _ = global_shortcut.register(old);
+        }
+        return Err(AppError::ConfigLoad(format!(
+            "Failed to register global shortcut '{}': {}",
+            new_shortcut, e
+        )));
+    }
+
+    log(
+        "GLOBAL_SHORTCUT",
+        &format!(
+            "Global shortcut changed from '{}' to '{}'",
+            old_shortcut, new_shortcut
+        ),
+        None,
+    );
+    Ok(())
+}
+
+fn update_new_note_shortcut<R: Runtime>(
+    global_shortcut: &GlobalShortcut<R>,
+    old_shortcut: Option<&str>,
+    new_shortcut: Option<&str>,
+) -> AppResult<()> {
+    let old = old_shortcut.and_then(parse_shortcut);
+
+    if let Some(old) = old {
+        if let Err(e) = global_shortcut.unregister(old) {
+            log_warn(
+                "GLOBAL_SHORTCUT",
+                &format!(
+                    "Failed to unregister new note shortcut '{}'",
+                    old_shortcut.unwrap_or_default()
+                ),
+                Some(&e.to_string()),
+            );
+        }
+    }
+
+    if let Some(new_shortcut) = new_shortcut {
+        let new = parse_shortcut(new_shortcut).ok_or_else(|| {
+            AppError::ConfigLoad(format!(
+                "Invalid new note shortcut format: '{}'",
+                new_shortcut
+            ))
+        })?;
+        if let Err(e) = global_shortcut.register(new) {
+            if let Some(old) = old {
+                let _ = global_shortcut.register(old);
+            }
+            return Err(AppError::ConfigLoad(format!(
+                "Failed to register new note shortcut '{}': {}",
+                new_shortcut, e
+            )));
+        }
+    }
+
+    log(
+        "GLOBAL_SHORTCUT",
+        &format!(
+            "New note shortcut changed from '{}' to '{}'",
+            old_shortcut.unwrap_or("none"),
+            new_shortcut.unwrap_or("none")
+        ),
+        None,
+    );
+    Ok(())
+}
+
+/// Event carrying the editor settings, so an open editor picks up tab size,
+/// mode and theme changes without being reopened.
+pub const EDITOR_CONFIG_EVENT: &str = "editor-config-changed";
+
+/// Sends `editor` to the frontend with invalid values replaced by their
+/// defaults, and returns what was sent.
+pub fn emit_editor_config<R: Runtime>(
+    app: &AppHandle<R>,
+    editor: &EditorConfig,
+) -> tauri::Result<EditorConfig> {
+    let mut editor = editor.clone();
+    sanitize_editor_config(&mut editor, &EditorConfig::default(), &mut Vec::new());
+    app.emit(EDITOR_CONFIG_EVENT, &editor)?;
+    Ok(editor)
+}
+
+/// Like `emit_editor_config`, but a failure is only logged.
+pub fn notify_editor_config<R: Runtime>(app: &AppHandle<R>, editor: &EditorConfig) {
+    if let Err(e) = emit_editor_config(app, editor) {
+        log_warn(
+            "CONFIG_EVENT",
+            "Failed to emit editor-config-changed event",
+            Some(&e.to_string()),
+        );
+    }
+}
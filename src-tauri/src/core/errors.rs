@@ -14,6 +14,8 @@ pub enum AppError {
     FilePermission(String),
     FileWrite(String),
     FileRead(String),
+    // The note changed on disk since the editor read it; carries what is on disk now
+    SaveConflict { note: String, disk_content: String },
 
     // Validation errors
     InvalidNoteName(String),
@@ -30,6 +32,23 @@ pub enum AppError {
 
     // UI/Window errors
     WindowOperation(String),
+
+    // Export errors
+    Export(String),
+
+    // Attachment errors
+    Attachment(String),
+
+    // Clipboard errors
+    Clipboard(String),
+
+    // A destructive action was attempted without the confirmation it needs
+    ConfirmationRequired(String),
+
+    // Encryption errors
+    Encryption(String),
+    // The passphrase did not unlock the note; kept apart so the UI can ask again
+    WrongPassphrase,
 }
 
 impl fmt::Display for AppError {
@@ -43,6 +62,12 @@ impl fmt::Display for AppError {
             AppError::FilePermission(msg) => write!(f, "File permission error: {}", msg),
             AppError::FileWrite(msg) => write!(f, "File write error: {}", msg),
             AppError::FileRead(msg) => write!(f, "File read error: {}", msg),
+            AppError::SaveConflict { note, .. } => write!(
+                f,
+                "Save conflict: cannot save '{}': file has been modified since editing began. \
+                This safety check prevents accidental data loss.",
+                note
+            ),
 
             AppError::InvalidNoteName(msg) => write!(f, "Invalid note name: {}", msg),
             AppError::PathTraversal => write!(f, "Path traversal not allowed"),
@@ -55,6 +80,17 @@ impl fmt::Display for AppError {
             AppError::SearchQuery(msg) => write!(f, "Search query error: {}", msg),
 
             AppError::WindowOperation(msg) => write!(f, "Window operation error: {}", msg),
+
+            AppError::Export(msg) => write!(f, "Export error: {}", msg),
+
+            AppError::Attachment(msg) => write!(f, "Attachment error: {}", msg),
+
+            AppError::Clipboard(msg) => write!(f, "Clipboard error: {}", msg),
+
+            AppError::ConfirmationRequired(msg) => write!(f, "Confirmation required: {}", msg),
+
+            AppError::Encryption(msg) => write!(f, "Encryption error: {}", msg),
+            AppError::WrongPassphrase => write!(f, "Wrong passphrase"),
         }
     }
 }
@@ -69,7 +105,12 @@ impl From<std::io::Error> for AppError {
             std::io::ErrorKind::PermissionDenied => AppError::FilePermission(err.to_string()),
             _ => AppError::FileWrite(err.to_string()),
         };
-        crate::logging::log("ERROR", &error.to_string(), Some("From std::io::Error"));
+        crate::logging::log_at(
+            crate::logging::LogLevel::Error,
+            "ERROR",
+            &error.to_string(),
+            Some("From std::io::Error"),
+        );
         error
     }
 }
@@ -77,7 +118,12 @@ impl From<std::io::Error> for AppError {
 impl From<rusqlite::Error> for AppError {
     fn from(err: rusqlite::Error) -> Self {
         let error = AppError::DatabaseQuery(err.to_string());
-        crate::logging::log("ERROR", &error.to_string(), Some("From rusqlite::Error"));
+        crate::logging::log_at(
+            crate::logging::LogLevel::Error,
+            "ERROR",
+            &error.to_string(),
+            Some("From rusqlite::Error"),
+        );
         error
     }
 }
@@ -93,7 +139,12 @@ impl From<String> for AppError {
         } else {
             AppError::FileWrite(err)
         };
-        crate::logging::log("ERROR", &error.to_string(), Some("From String"));
+        crate::logging::log_at(
+            crate::logging::LogLevel::Error,
+            "ERROR",
+            &error.to_string(),
+            Some("From String"),
+        );
         error
     }
 }
@@ -107,7 +158,12 @@ impl From<&str> for AppError {
 impl From<tauri::Error> for AppError {
     fn from(err: tauri::Error) -> Self {
         let error = AppError::WindowOperation(err.to_string());
-        crate::logging::log("ERROR", &error.to_string(), Some("From tauri::Error"));
+        crate::logging::log_at(
+            crate::logging::LogLevel::Error,
+            "ERROR",
+            &error.to_string(),
+            Some("From tauri::Error"),
+        );
         error
     }
 }
@@ -122,7 +178,12 @@ impl From<AppError> for String {
 impl AppError {
     pub fn validation_error(field: &str, message: &str) -> Self {
         let error = AppError::InvalidNoteName(format!("{}: {}", field, message));
-        crate::logging::log("ERROR", &error.to_string(), None);
+        crate::logging::log_at(
+            crate::logging::LogLevel::Error,
+            "ERROR",
+            &error.to_string(),
+            None,
+        );
         error
     }
 
@@ -135,9 +196,17 @@ impl AppError {
             "Operation '{}' failed ({}), and database rebuild also failed: {}",
             operation, original_error, rebuild_error
         ));
-        crate::logging::log("ERROR", &error.to_string(), None);
+        crate::logging::log_at(
+            crate::logging::LogLevel::Error,
+            "ERROR",
+            &error.to_string(),
+            None,
+        );
         error
     }
 }
 
 pub type AppResult<T> = Result<T, AppError>;
+
+/// Per-item outcomes of a batch command, so partial failures don't abort the batch
+pub type BatchResults = Vec<(String, Result<(), String>)>;
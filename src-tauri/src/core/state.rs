@@ -1,6 +1,14 @@
-use crate::{config::AppConfig, core::AppResult, database::DatabaseManager, logging::log};
+use crate::{
+    config::AppConfig,
+    core::AppResult,
+    database::DatabaseManager,
+    logging::{log, log_error, log_warn},
+    search::HybridSearcher,
+    services::{confirmation_service::DeleteConfirmations, index_service::IndexRefresh},
+    utilities::spellcheck::SpellChecker,
+};
 use std::sync::{
-    atomic::{AtomicBool, AtomicUsize},
+    atomic::{AtomicBool, AtomicU64, AtomicUsize},
     Arc, Mutex, RwLock,
 };
 
@@ -11,26 +19,54 @@ pub struct AppState {
     pub programmatic_operation_in_progress: Arc<AtomicUsize>,
     pub database_manager: Arc<Mutex<DatabaseManager>>,
     pub database_rebuild_lock: Arc<RwLock<()>>,
+    pub delete_confirmations: Arc<Mutex<DeleteConfirmations>>,
+    pub searcher: Arc<Mutex<HybridSearcher>>,
+    /// Highest search generation the frontend has sent so far.
+    pub search_generation: Arc<AtomicU64>,
+    pub spell_checker: Arc<Mutex<SpellChecker>>,
+    /// Set each time notes finish syncing from disk, at startup or by `refresh_cache`.
+    pub last_refresh: Arc<Mutex<Option<IndexRefresh>>>,
 }
 
 impl AppState {
     pub fn new(config: AppConfig) -> AppResult<Self> {
-        let database_manager = DatabaseManager::new()?;
+        let database_manager = DatabaseManager::new(&config.preferences)?;
+        let recovered_from_corruption = database_manager.quarantined().is_some();
 
-        Ok(Self {
+        let state = Self {
             config: Arc::new(RwLock::new(config)),
             was_first_run: Arc::new(AtomicBool::new(false)),
             programmatic_operation_in_progress: Arc::new(AtomicUsize::new(0)),
             database_manager: Arc::new(Mutex::new(database_manager)),
             database_rebuild_lock: Arc::new(RwLock::new(())),
-        })
+            delete_confirmations: Arc::new(Mutex::new(DeleteConfirmations::default())),
+            searcher: Arc::new(Mutex::new(HybridSearcher::default())),
+            search_generation: Arc::new(AtomicU64::new(0)),
+            spell_checker: Arc::new(Mutex::new(SpellChecker::default())),
+            last_refresh: Arc::new(Mutex::new(None)),
+        };
+
+        if recovered_from_corruption {
+            // The replacement database is empty, so rebuild it from the notes on disk
+            crate::services::database_service::recreate_database(&state)?;
+        }
+        Ok(state)
+    }
+
+    /// Where a corrupt database was moved aside, reported once so the user can
+    /// be warned that the search index was rebuilt.
+    pub fn take_quarantined_database(&self) -> Option<std::path::PathBuf> {
+        self.database_manager
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take_quarantined()
     }
 
     pub fn new_with_fallback(config: AppConfig) -> AppResult<Self> {
         match Self::new(config.clone()) {
             Ok(state) => Ok(state),
             Err(original_error) => {
-                log(
+                log_warn(
                     "DATABASE_INIT_FAILURE",
                     "Database initialization failed, attempting recovery",
                     Some(&original_error.to_string()),
@@ -47,7 +83,7 @@ impl AppState {
                         Ok(state)
                     }
                     Err(recovery_error) => {
-                        log(
+                        log_error(
                             "DATABASE_RECOVERY_FAILURE",
                             "Database recovery failed",
                             Some(&recovery_error.to_string()),
@@ -68,7 +104,7 @@ impl AppState {
         if let Ok(db_path) = crate::utilities::paths::get_database_path() {
             if db_path.exists() {
                 if let Err(e) = std::fs::remove_file(&db_path) {
-                    log(
+                    log_warn(
                         "DATABASE_FILE_DELETE_FAILED",
                         "Failed to delete corrupted database file",
                         Some(&e.to_string()),
@@ -78,13 +114,18 @@ impl AppState {
         }
 
         // Try to create fresh database connection
-        let database_manager = DatabaseManager::new()?;
+        let database_manager = DatabaseManager::new(&config.preferences)?;
         let state = Self {
             config: Arc::new(RwLock::new(config)),
             was_first_run: Arc::new(AtomicBool::new(false)),
             programmatic_operation_in_progress: Arc::new(AtomicUsize::new(0)),
             database_manager: Arc::new(Mutex::new(database_manager)),
             database_rebuild_lock: Arc::new(RwLock::new(())),
+            delete_confirmations: Arc::new(Mutex::new(DeleteConfirmations::default())),
+            searcher: Arc::new(Mutex::new(HybridSearcher::default())),
+            search_generation: Arc::new(AtomicU64::new(0)),
+            spell_checker: Arc::new(Mutex::new(SpellChecker::default())),
+            last_refresh: Arc::new(Mutex::new(None)),
         };
 
         // Recreate database from filesystem
@@ -1,49 +1,120 @@
+use crate::config::PreferencesConfig;
 use crate::core::{AppError, AppResult};
+use crate::logging::{log, log_warn};
 use crate::utilities::paths::get_database_path;
-use rusqlite::Connection;
-use std::path::PathBuf;
+use rusqlite::{functions::FunctionFlags, Connection, ErrorCode};
+use std::path::{Path, PathBuf};
+
+// Room for every statement on the search and listing paths, which are
+// prepared through `prepare_cached` and reused for the connection's lifetime
+const STATEMENT_CACHE_CAPACITY: usize = 32;
+
+// Files SQLite may keep next to the database, moved aside along with it
+const SIDECAR_SUFFIXES: [&str; 3] = ["-wal", "-shm", "-journal"];
 
 pub struct DatabaseManager {
     connection: Connection,
     current_db_path: PathBuf,
+    /// Where a corrupt database found on open was moved to. The fresh database
+    /// that replaced it is empty until notes are read from disk again.
+    quarantined: Option<PathBuf>,
+    connections_opened: usize,
 }
 
 impl DatabaseManager {
-    pub fn new() -> AppResult<Self> {
+    pub fn new(preferences: &PreferencesConfig) -> AppResult<Self> {
         let db_path = get_database_path()?;
-        let conn = Self::create_connection(&db_path)?;
+        let (conn, quarantined) = Self::create_connection(&db_path, preferences)?;
 
         Ok(Self {
             connection: conn,
             current_db_path: db_path,
+            quarantined,
+            connections_opened: 1,
         })
     }
 
-    fn create_connection(db_path: &PathBuf) -> AppResult<Connection> {
+    fn create_connection(
+        db_path: &Path,
+        preferences: &PreferencesConfig,
+    ) -> AppResult<(Connection, Option<PathBuf>)> {
         if let Some(parent) = db_path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| {
                 AppError::DatabaseConnection(format!("Failed to create database directory: {}", e))
             })?;
         }
 
-        Connection::open(db_path)
-            .map_err(|e| AppError::DatabaseConnection(format!("Failed to open database: {}", e)))
+        let conn = Self::open_connection(db_path)?;
+        // Opening is lazy, so read the schema to find out whether the file is usable
+        let probe = conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| {
+            row.get::<_, i64>(0)
+        });
+        match probe {
+            Err(e) if is_corruption_error(&e) => {
+                drop(conn);
+                let quarantined = quarantine_database(db_path, &e)?;
+                let conn = Self::open_connection(db_path)?;
+                apply_cache_pragmas(&conn, preferences);
+                Ok((conn, Some(quarantined)))
+            }
+            _ => {
+                apply_cache_pragmas(&conn, preferences);
+                Ok((conn, None))
+            }
+        }
     }
 
-    pub fn ensure_current_connection(&mut self) -> AppResult<bool> {
+    fn open_connection(db_path: &Path) -> AppResult<Connection> {
+        let conn = Connection::open(db_path)
+            .map_err(|e| AppError::DatabaseConnection(format!("Failed to open database: {}", e)))?;
+        conn.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
+        // SQLite's lower() only folds ASCII, which would misplace accented names
+        // when the note list is sorted by name
+        conn.create_scalar_function(
+            "unicode_lower",
+            1,
+            FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+            |ctx| Ok(ctx.get::<String>(0)?.to_lowercase()),
+        )
+        .map_err(|e| {
+            AppError::DatabaseConnection(format!("Failed to register SQL functions: {}", e))
+        })?;
+        Ok(conn)
+    }
+
+    pub fn ensure_current_connection(
+        &mut self,
+        preferences: &PreferencesConfig,
+    ) -> AppResult<bool> {
         let expected_db_path = get_database_path()?;
 
         if self.current_db_path != expected_db_path {
-            let new_conn = Self::create_connection(&expected_db_path)?;
+            let (new_conn, quarantined) = Self::create_connection(&expected_db_path, preferences)?;
             // Atomically replace both connection and path
             self.connection = new_conn;
             self.current_db_path = expected_db_path;
+            self.quarantined = quarantined.or(self.quarantined.take());
+            self.connections_opened += 1;
             Ok(true) // Connection was reinitialized
         } else {
             Ok(false) // No reinitialization needed
         }
     }
 
+    /// How many connections this manager has opened, counting the first one.
+    pub fn connections_opened(&self) -> usize {
+        self.connections_opened
+    }
+
+    pub fn quarantined(&self) -> Option<&Path> {
+        self.quarantined.as_deref()
+    }
+
+    /// Hands over the quarantined path once, so the user is warned only once.
+    pub fn take_quarantined(&mut self) -> Option<PathBuf> {
+        self.quarantined.take()
+    }
+
     pub fn with_connection<T, F>(&self, f: F) -> AppResult<T>
     where
         F: FnOnce(&Connection) -> AppResult<T>,
@@ -94,6 +165,13 @@ where
 }
 
 pub fn refresh_database_connection(app_state: &crate::core::state::AppState) -> AppResult<bool> {
+    let preferences = app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .preferences
+        .clone();
+
     // First acquire read lock on rebuild_lock to ensure no rebuilds are happening
     let _rebuild_guard = app_state.database_rebuild_lock.read().map_err(|e| {
         AppError::DatabaseConnection(format!("Database rebuild lock poisoned: {}", e))
@@ -104,7 +182,85 @@ pub fn refresh_database_connection(app_state: &crate::core::state::AppState) ->
         AppError::DatabaseConnection(format!("Database manager lock poisoned: {}", e))
     })?;
 
-    manager.ensure_current_connection()
+    manager.ensure_current_connection(&preferences)
+}
+
+/// Applies `cache_journal_mode` and `cache_synchronous`, which sanitizing has
+/// already limited to known values. A failure keeps SQLite's defaults, so the
+/// index stays usable either way.
+pub fn apply_cache_pragmas(conn: &Connection, preferences: &PreferencesConfig) {
+    let journal_mode = conn.pragma_update_and_check(
+        None,
+        "journal_mode",
+        &preferences.cache_journal_mode,
+        |row| row.get::<_, String>(0),
+    );
+    match journal_mode {
+        Ok(mode) if mode.eq_ignore_ascii_case(&preferences.cache_journal_mode) => {}
+        // Some filesystems cannot do WAL, and in-memory databases only do MEMORY
+        Ok(mode) => log(
+            "DATABASE_PRAGMA",
+            &format!(
+                "Journal mode {} not available, using {}",
+                preferences.cache_journal_mode, mode
+            ),
+            None,
+        ),
+        Err(e) => log_warn(
+            "DATABASE_PRAGMA",
+            "Failed to set journal mode",
+            Some(&e.to_string()),
+        ),
+    }
+
+    if let Err(e) = conn.pragma_update(None, "synchronous", &preferences.cache_synchronous) {
+        log_warn(
+            "DATABASE_PRAGMA",
+            "Failed to set synchronous mode",
+            Some(&e.to_string()),
+        );
+    }
+}
+
+/// Whether `error` means the database file itself is damaged or not a database.
+pub fn is_corruption_error(error: &rusqlite::Error) -> bool {
+    matches!(
+        error.sqlite_error_code(),
+        Some(ErrorCode::DatabaseCorrupt | ErrorCode::NotADatabase)
+    )
+}
+
+/// Moves a corrupt database and its journal files aside with a timestamp
+/// suffix, so a fresh one can be created in its place without losing the
+/// original. Returns where the database went.
+pub fn quarantine_database(db_path: &Path, error: &rusqlite::Error) -> AppResult<PathBuf> {
+    let suffix = format!(".corrupt-{}", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+    let with_suffix = |path: &Path, extra: &str| {
+        let mut name = path.as_os_str().to_os_string();
+        name.push(extra);
+        PathBuf::from(name)
+    };
+
+    let quarantined = with_suffix(db_path, &suffix);
+    std::fs::rename(db_path, &quarantined).map_err(|e| {
+        AppError::DatabaseConnection(format!("Failed to move corrupt database aside: {}", e))
+    })?;
+    for sidecar in SIDECAR_SUFFIXES {
+        let path = with_suffix(db_path, sidecar);
+        if path.exists() {
+            let _ = std::fs::rename(&path, with_suffix(&quarantined, sidecar));
+        }
+    }
+
+    log_warn(
+        "DATABASE_CORRUPT",
+        &format!(
+            "Database is corrupt, moved it to {} and starting fresh",
+            quarantined.display()
+        ),
+        Some(&error.to_string()),
+    );
+    Ok(quarantined)
 }
 
 // Platform-specific utility functions
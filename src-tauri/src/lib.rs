@@ -14,8 +14,8 @@ use commands::*;
 use config::{load_config_with_first_run_info, parse_shortcut};
 use core::errors::AppError;
 use core::state::AppState;
-use logging::log;
-use services::database_service;
+use logging::{log_error, log_warn};
+use services::{database_service, trash_service};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::sync::OnceLock;
@@ -48,7 +48,7 @@ pub fn run() {
 
 pub fn initialize_notes(app_state: &AppState) {
     if let Err(e) = database_service::initialize_application_database(app_state) {
-        log(
+        log_error(
             "DATABASE_INIT",
             "Application database initialization failed",
             Some(&e.to_string()),
@@ -58,15 +58,16 @@ pub fn initialize_notes(app_state: &AppState) {
 
 fn load_config_and_initialize_state() -> AppState {
     let (config, was_first_run) = load_config_with_first_run_info();
+    logging::configure_rotation(&config.logging);
     let app_state = match AppState::new_with_fallback(config) {
         Ok(state) => state,
         Err(e) => {
-            log(
+            log_error(
                 "FATAL_DATABASE_ERROR",
                 "Database initialization failed and could not be recovered",
                 Some(&e.to_string()),
             );
-            log(
+            log_error(
                 "SHUTDOWN",
                 "Application shutting down due to unrecoverable database error",
                 None,
@@ -80,13 +81,19 @@ fn load_config_and_initialize_state() -> AppState {
     }
 
     initialize_notes(&app_state);
+    if let Err(e) = trash_service::purge_expired_trash(&app_state) {
+        log_warn(
+            "TRASH",
+            "Failed to purge expired deleted notes",
+            Some(&e.to_string()),
+        );
+    }
     app_state
 }
 
 fn build_tauri_app_with_plugins(app_state: AppState) -> tauri::Builder<tauri::Wry> {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .plugin(tauri_plugin_window_state::Builder::default().build())
         .manage(app_state)
 }
 
@@ -96,6 +103,7 @@ fn setup_window_configuration(app: &tauri::App) -> Result<(), Box<dyn std::error
             let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
             let _ = window.set_always_on_top(config.interface.always_on_top);
         }
+        utilities::window_geometry::restore_window_geometry(&window);
     }
     Ok(())
 }
@@ -123,52 +131,108 @@ fn handle_first_run_detection(app: &tauri::App) -> Result<(), Box<dyn std::error
     Ok(())
 }
 
+fn handle_database_recovery_notice(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(quarantined) = app
+        .try_state::<AppState>()
+        .and_then(|app_state| app_state.take_quarantined_database())
+    else {
+        return Ok(());
+    };
+
+    let app_handle = app.handle().clone();
+    let message = format!(
+        "The search database was corrupt and has been rebuilt. The damaged copy was kept at {}",
+        quarantined.display()
+    );
+    std::thread::spawn(move || {
+        // Give the frontend time to register its listeners, as for first-run detection
+        std::thread::sleep(std::time::Duration::from_millis(1000));
+        if let Err(e) = app_handle.emit("database-recovered", message) {
+            log_warn(
+                "UI_UPDATE",
+                "Failed to emit database-recovered event",
+                Some(&e.to_string()),
+            );
+        }
+    });
+    Ok(())
+}
+
 fn handle_main_window_toggle(app_handle: tauri::AppHandle) {
     match app_handle.get_webview_window("main") {
         Some(window) => {
-            let is_visible = window.is_visible().unwrap_or(false);
-            let is_focused = window.is_focused().unwrap_or(false);
-
-            if is_visible && is_focused {
-                utilities::mac_focus::hide_app_and_restore_previous(window);
-            } else {
-                utilities::mac_focus::save_current_frontmost_app();
-                utilities::mac_focus::show_app(window);
+            utilities::window_focus::toggle_app_visibility(window);
+        }
+        None => {
+            utilities::window_focus::save_current_frontmost_app();
+            if let Some(app_state) = app_handle.try_state::<AppState>() {
+                let _ = show_main_window(app_handle.clone(), app_state);
             }
         }
+    }
+}
+
+fn configured_main_shortcut(app: &AppHandle) -> Shortcut {
+    let global_shortcut = match app.try_state::<AppState>() {
+        Some(app_state) => app_state
+            .config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .global_shortcut
+            .clone(),
+        None => crate::config::AppConfig::default().global_shortcut,
+    };
+    parse_shortcut(&global_shortcut)
+        .unwrap_or_else(|| Shortcut::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::KeyN))
+}
+
+fn configured_new_note_shortcut(app: &AppHandle) -> Option<Shortcut> {
+    let app_state = app.try_state::<AppState>()?;
+    let new_note_shortcut = app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .global_shortcut_new_note
+        .clone()?;
+    parse_shortcut(&new_note_shortcut)
+}
+
+fn handle_new_note_shortcut(app_handle: tauri::AppHandle) {
+    utilities::window_focus::save_current_frontmost_app();
+    match app_handle.get_webview_window("main") {
+        Some(window) => utilities::window_focus::show_app(window),
         None => {
-            utilities::mac_focus::save_current_frontmost_app();
             if let Some(app_state) = app_handle.try_state::<AppState>() {
                 let _ = show_main_window(app_handle.clone(), app_state);
             }
         }
     }
+
+    if let Err(e) = app_handle.emit("create-new-note", ()) {
+        log_warn(
+            "GLOBAL_SHORTCUT",
+            "Failed to emit create-new-note event",
+            Some(&e.to_string()),
+        );
+    }
 }
 
 fn setup_global_shortcuts(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     #[cfg(desktop)]
     {
-        let config = if let Some(app_state) = app.try_state::<AppState>() {
-            app_state
-                .config
-                .read()
-                .unwrap_or_else(|e| e.into_inner())
-                .clone()
-        } else {
-            crate::config::AppConfig::default()
-        };
-        let main_shortcut = parse_shortcut(&config.global_shortcut).unwrap_or_else(|| {
-            Shortcut::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::KeyN)
-        });
+        let main_shortcut = configured_main_shortcut(app.handle());
 
         app.handle()
             .plugin(
                 tauri_plugin_global_shortcut::Builder::new()
                     .with_handler(move |app, shortcut, event| {
                         if event.state() == ShortcutState::Pressed {
-                            if shortcut == &main_shortcut {
+                            // Read the shortcut from state so reloaded configs take effect
+                            if shortcut == &configured_main_shortcut(app) {
                                 let app_handle = app.clone();
                                 handle_main_window_toggle(app_handle);
+                            } else if configured_new_note_shortcut(app).as_ref() == Some(shortcut) {
+                                handle_new_note_shortcut(app.clone());
                             }
                         }
                     })
@@ -179,6 +243,17 @@ fn setup_global_shortcuts(app: &tauri::App) -> Result<(), Box<dyn std::error::Er
         app.global_shortcut()
             .register(main_shortcut)
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+
+        // The new-note shortcut is optional, so failing to register it must not block startup
+        if let Some(new_note_shortcut) = configured_new_note_shortcut(app.handle()) {
+            if let Err(e) = app.global_shortcut().register(new_note_shortcut) {
+                log_warn(
+                    "GLOBAL_SHORTCUT",
+                    "Failed to register new note shortcut",
+                    Some(&e.to_string()),
+                );
+            }
+        }
     }
     Ok(())
 }
@@ -188,15 +263,20 @@ fn setup_app_components(app: &mut tauri::App) -> Result<(), Box<dyn std::error::
     setup_window_configuration(app)?;
     setup_notes_watcher_for_app(app)?;
     handle_first_run_detection(app)?;
+    handle_database_recovery_notice(app)?;
     setup_global_shortcuts(app)?;
+    utilities::window_focus::log_focus_limitations();
     Ok(())
 }
 
 fn handle_window_events(window: &tauri::Window, event: &tauri::WindowEvent) {
     match event {
         tauri::WindowEvent::CloseRequested { api, .. } => {
+            if let Some(webview_window) = window.app_handle().get_webview_window(window.label()) {
+                utilities::window_geometry::save_window_geometry(&webview_window);
+            }
             if let Err(e) = window.hide() {
-                log(
+                log_warn(
                     "WINDOW_OPERATION",
                     "Failed to hide window. Continuing anyway.",
                     Some(&e.to_string()),
@@ -212,44 +292,117 @@ fn register_command_handlers(
 ) -> impl Fn(tauri::ipc::Invoke<tauri::Wry>) -> bool + Send + Sync + 'static {
     tauri::generate_handler![
         search_notes,
+        search_notes_regex,
+        search_notes_fuzzy,
+        search_notes_by_tag,
+        list_all_tags,
+        search_note_versions,
+        search_within_note,
+        replace_across_notes,
         get_note_content,
         get_note_html_content,
+        get_note_render_theme,
+        get_note_stats,
+        get_writing_progress,
+        get_note_toc,
+        read_note_chunk,
+        spellcheck_text,
+        get_note_hash,
+        verify_notes_integrity,
+        export_note,
+        copy_note_as_html,
+        export_index,
+        get_index_stats,
+        import_index,
         create_new_note,
+        create_note_from_template,
+        duplicate_note,
+        request_delete_confirmation,
+        request_batch_delete_confirmation,
         delete_note,
+        delete_notes,
         rename_note,
+        archive_note,
+        archive_notes,
+        pin_note,
+        unpin_note,
+        list_pinned_notes,
+        get_note_id,
+        get_note_by_id,
+        encrypt_note,
+        decrypt_note,
+        record_note_opened,
+        save_pasted_image,
+        list_recent_notes,
+        get_backlinks,
+        get_backlinks_by_id,
+        get_note_links,
+        rename_note_with_relink,
+        move_note,
+        create_folder,
+        list_folders,
+        unarchive_note,
+        list_archived_notes,
         save_note_with_content_check,
+        autosave_note,
+        append_to_note,
+        force_save_note,
         initialize_notes_with_progress,
         refresh_cache,
+        get_recent_logs,
+        get_log_file_path,
+        clear_render_cache,
+        compact_cache,
         open_note_in_editor,
         open_note_folder,
         list_all_notes,
         get_note_versions,
         get_version_content,
+        diff_note_versions,
         recover_note_version,
         get_deleted_files,
         recover_deleted_file,
+        restore_deleted_note,
+        empty_trash,
+        purge_trash_item,
+        get_trash_size,
         show_main_window,
         hide_main_window,
+        reset_window_geometry,
+        set_always_on_top,
+        is_always_on_top,
         get_config_content,
         save_config_content,
+        open_config_in_editor,
+        validate_config_content,
+        reload_config,
+        apply_editor_config,
         config_exists,
         get_default_config,
+        get_active_config,
+        initialize_first_run,
+        get_default_config_toml,
         get_general_config,
         get_interface_config,
         get_editor_config,
         get_shortcuts_config,
         get_preferences_config,
         scan_available_themes,
+        get_available_themes,
+        get_markdown_extensions,
         load_custom_theme_file,
         validate_theme_path,
-        utilities::mac_focus::save_current_frontmost_app,
-        utilities::mac_focus::show_app,
-        utilities::mac_focus::hide_app_and_restore_previous
+        check_notes_directory_health,
+        utilities::window_focus::save_current_frontmost_app,
+        utilities::window_focus::show_app,
+        utilities::window_focus::hide_app_and_restore_previous,
+        utilities::window_focus::toggle_app_visibility,
+        utilities::window_focus::check_macos_permissions
     ]
 }
 
 fn handle_app_build_error(e: tauri::Error) -> ! {
-    log(
+    log_error(
         "APPLICATION_STARTUP",
         "Failed to build Tauri application",
         Some(&e.to_string()),
@@ -269,7 +422,32 @@ fn run_app_with_platform_config(mut app: tauri::App) {
     });
 }
 
+fn handle_pinned_note_menu_click(app_handle: &tauri::AppHandle, note_name: &str) {
+    utilities::window_focus::save_current_frontmost_app();
+    match app_handle.get_webview_window("main") {
+        Some(window) => utilities::window_focus::show_app(window),
+        None => {
+            if let Some(app_state) = app_handle.try_state::<AppState>() {
+                let _ = show_main_window(app_handle.clone(), app_state);
+            }
+        }
+    }
+
+    if let Err(e) = app_handle.emit("open-pinned-note", note_name) {
+        log_warn(
+            "TRAY_MENU",
+            "Failed to emit open-pinned-note event",
+            Some(&e.to_string()),
+        );
+    }
+}
+
 fn handle_tray_menu_event(app: &tauri::AppHandle, event: &tauri::menu::MenuEvent) {
+    if let Some(note_name) = utilities::tray_pins::pinned_note_for_menu_id(event.id.as_ref()) {
+        handle_pinned_note_menu_click(app, note_name);
+        return;
+    }
+
     match event.id.as_ref() {
         "open" => {
             let app_handle = app.app_handle().clone();
@@ -280,7 +458,7 @@ fn handle_tray_menu_event(app: &tauri::AppHandle, event: &tauri::menu::MenuEvent
         "refresh" => {
             let app_handle = app.app_handle().clone();
             if let Some(app_state) = app_handle.try_state::<AppState>() {
-                let _ = refresh_cache(app_handle.clone(), app_state);
+                let _ = refresh_cache(app_handle.clone(), false, app_state);
             }
         }
         "settings" => {
@@ -316,18 +494,18 @@ fn handle_tray_menu_event(app: &tauri::AppHandle, event: &tauri::menu::MenuEvent
 
                         if let Some(dock_item) = DOCK_MENU_ITEM.get() {
                             if let Err(e) = dock_item.set_text(new_text) {
-                                log(
+                                log_warn(
                                     "DOCK_TOGGLE",
                                     "Failed to update menu item text",
                                     Some(&AppError::from(e).to_string()),
                                 );
                             }
                         } else {
-                            log("DOCK_TOGGLE", "Dock menu item reference not found", None);
+                            log_warn("DOCK_TOGGLE", "Dock menu item reference not found", None);
                         }
                     }
                     Err(e) => {
-                        log(
+                        log_warn(
                             "DOCK_TOGGLE",
                             "Failed to set activation policy",
                             Some(&AppError::from(e).to_string()),
@@ -337,6 +515,10 @@ fn handle_tray_menu_event(app: &tauri::AppHandle, event: &tauri::menu::MenuEvent
             }
         }
         "quit" => {
+            // Exiting directly skips window events, so save the geometry first
+            if let Some(window) = app.get_webview_window("main") {
+                utilities::window_geometry::save_window_geometry(&window);
+            }
             std::process::exit(0);
         }
         _ => {}
@@ -357,6 +539,7 @@ fn handle_tray_icon_event(tray: &tauri::tray::TrayIcon, event: &tauri::tray::Tra
             match app.get_webview_window("main") {
                 Some(window) => {
                     if window.is_visible().unwrap_or(false) {
+                        utilities::window_geometry::save_window_geometry(&window);
                         let _ = window.hide();
                     } else {
                         let _ = window.show();
@@ -378,6 +561,16 @@ fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
     let refresh_item =
         MenuItem::with_id(app, "refresh", "Refresh Notes Cache", true, None::<&str>)?;
     let settings_item = MenuItem::with_id(app, "settings", "Settings", true, None::<&str>)?;
+    let pinned = app
+        .try_state::<AppState>()
+        .and_then(|app_state| {
+            database::with_db(&app_state, |conn| {
+                Ok(services::pin_service::list_pinned_notes(conn)?)
+            })
+            .ok()
+        })
+        .unwrap_or_default();
+    let pinned_submenu = utilities::tray_pins::pinned_notes_submenu(app, &pinned)?;
     let dock_text = if DOCK_VISIBLE.load(Ordering::Relaxed) {
         "Hide from Dock"
     } else {
@@ -386,7 +579,7 @@ fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
     let dock_item = MenuItem::with_id(app, "toggle_dock", dock_text, true, None::<&str>)?;
 
     if let Err(_) = DOCK_MENU_ITEM.set(dock_item.clone()) {
-        log(
+        log_warn(
             "TRAY_SETUP",
             "Failed to store dock menu item reference",
             None,
@@ -399,6 +592,7 @@ fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
         app,
         &[
             &open_item,
+            &pinned_submenu,
             &separator,
             &refresh_item,
             &settings_item,
@@ -413,7 +607,7 @@ fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
     if let Some(icon) = app.default_window_icon() {
         tray_builder = tray_builder.icon(icon.clone());
     } else {
-        log(
+        log_warn(
             "TRAY_SETUP",
             "Warning: Could not load default window icon for tray. Tray will appear without icon.",
             None,
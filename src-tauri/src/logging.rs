@@ -1,15 +1,170 @@
+use crate::config::LoggingConfig;
 use crate::core::{AppError, AppResult};
 use crate::utilities::strings::get_log_timestamp;
-use std::fs::{File, OpenOptions};
-use std::io::{BufWriter, Write};
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Mutex, OnceLock};
 
 const LOGGING_ENABLED: bool = true;
 
-static LOGGER: OnceLock<Mutex<BufWriter<File>>> = OnceLock::new();
+// Enough history for a debug panel without holding on to a whole session
+pub const MAX_RECENT_LOGS: usize = 1_000;
 
-fn get_log_path() -> AppResult<PathBuf> {
+pub const DEFAULT_MAX_LOG_FILE_SIZE_MB: u64 = 5;
+pub const DEFAULT_MAX_LOG_ARCHIVES: usize = 3;
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+// Rotation limits live outside the config lock because loading the config logs
+static MAX_LOG_FILE_BYTES: AtomicU64 = AtomicU64::new(DEFAULT_MAX_LOG_FILE_SIZE_MB * BYTES_PER_MB);
+static MAX_LOG_ARCHIVES: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_LOG_ARCHIVES);
+
+static LOGGER: OnceLock<Mutex<LogFile>> = OnceLock::new();
+static RECENT_LOGS: Mutex<VecDeque<LogRecord>> = Mutex::new(VecDeque::new());
+
+/// Severity of a log entry, most severe first, so `level <= filter` keeps an entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LogLevel::Error => "ERROR",
+            LogLevel::Warn => "WARN",
+            LogLevel::Info => "INFO",
+            LogLevel::Debug => "DEBUG",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogRecord {
+    pub timestamp: String,
+    pub level: LogLevel,
+    pub operation: String,
+    pub message: String,
+    pub details: Option<String>,
+}
+
+/// The live log file. Lines are flushed as they are written, so rotating only
+/// has to swap files while the logger lock is held.
+struct LogFile {
+    path: PathBuf,
+    writer: Option<BufWriter<File>>,
+    size: u64,
+}
+
+impl LogFile {
+    fn open(path: PathBuf) -> io::Result<Self> {
+        let file = open_for_append(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            writer: Some(BufWriter::new(file)),
+            size,
+        })
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let line_bytes = line.len() as u64 + 1;
+        let max_bytes = MAX_LOG_FILE_BYTES.load(Ordering::Relaxed);
+        if self.size > 0 && self.size + line_bytes > max_bytes {
+            // A failed rotation keeps appending to the current file rather than dropping the line
+            let _ = self.rotate();
+        }
+
+        if self.writer.is_none() {
+            self.reopen()?;
+        }
+        if let Some(writer) = self.writer.as_mut() {
+            writeln!(writer, "{}", line)?;
+            writer.flush()?;
+            self.size += line_bytes;
+        }
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        // The handle is closed before renaming, which Windows requires
+        if let Some(mut writer) = self.writer.take() {
+            writer.flush()?;
+        }
+        let rotated = rotate_log_files(&self.path, MAX_LOG_ARCHIVES.load(Ordering::Relaxed));
+        self.reopen()?;
+        rotated
+    }
+
+    fn reopen(&mut self) -> io::Result<()> {
+        let file = open_for_append(&self.path)?;
+        self.size = file.metadata()?.len();
+        self.writer = Some(BufWriter::new(file));
+        Ok(())
+    }
+}
+
+fn open_for_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// Path of the `index`th archive of `log_path`, e.g. `symiosis.log.1`.
+pub fn log_archive_path(log_path: &Path, index: usize) -> PathBuf {
+    let mut name = log_path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// Moves `log_path` to `.1`, shifting older archives up and dropping any past
+/// `max_archives`. Every step is a rename or the removal of the oldest archive,
+/// so a crash part way through never loses lines from the newer files.
+pub fn rotate_log_files(log_path: &Path, max_archives: usize) -> io::Result<()> {
+    if max_archives == 0 {
+        return match fs::remove_file(log_path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+
+    // Includes archives left over from a larger retention setting
+    let mut index = max_archives;
+    while log_archive_path(log_path, index).exists() {
+        fs::remove_file(log_archive_path(log_path, index))?;
+        index += 1;
+    }
+
+    for index in (1..max_archives).rev() {
+        let archive = log_archive_path(log_path, index);
+        if archive.exists() {
+            fs::rename(&archive, log_archive_path(log_path, index + 1))?;
+        }
+    }
+
+    if log_path.exists() {
+        fs::rename(log_path, log_archive_path(log_path, 1))?;
+    }
+    Ok(())
+}
+
+/// Applies the `[logging]` size cap and retention to subsequent writes.
+pub fn configure_rotation(config: &LoggingConfig) {
+    MAX_LOG_FILE_BYTES.store(
+        config.max_file_size_mb.saturating_mul(BYTES_PER_MB),
+        Ordering::Relaxed,
+    );
+    MAX_LOG_ARCHIVES.store(config.max_archives, Ordering::Relaxed);
+}
+
+pub fn get_log_path() -> AppResult<PathBuf> {
     crate::utilities::paths::get_data_dir()
         .ok_or_else(|| AppError::ConfigLoad("Failed to get data directory".to_string()))
         .map(|path| path.join("symiosis").join("symiosis.log"))
@@ -26,23 +181,33 @@ fn init_logger() -> AppResult<()> {
         std::fs::create_dir_all(parent)?;
     }
 
-    let file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&log_path)?;
-
-    let writer = BufWriter::new(file);
+    let log_file = LogFile::open(log_path)?;
 
     LOGGER
-        .set(Mutex::new(writer))
+        .set(Mutex::new(log_file))
         .map_err(|_| AppError::ConfigLoad("Failed to initialize logger".to_string()))?;
 
     log("LOGGER", "Symiosis logger initialized", None);
     Ok(())
 }
 
-/// Main logging function - logs to APP_DIR/symiosis.log
+/// Main logging function - logs to APP_DIR/symiosis.log at `Info` level
 pub fn log(operation: &str, message: &str, details: Option<&str>) {
+    log_at(LogLevel::Info, operation, message, details);
+}
+
+/// Like `log`, at `Warn` level, for failures the app carries on from.
+pub fn log_warn(operation: &str, message: &str, details: Option<&str>) {
+    log_at(LogLevel::Warn, operation, message, details);
+}
+
+/// Like `log`, at `Error` level, for failures that lose data or functionality.
+pub fn log_error(operation: &str, message: &str, details: Option<&str>) {
+    log_at(LogLevel::Error, operation, message, details);
+}
+
+/// Logs to APP_DIR/symiosis.log and keeps the entry for `recent_logs`
+pub fn log_at(level: LogLevel, operation: &str, message: &str, details: Option<&str>) {
     if !LOGGING_ENABLED {
         return;
     }
@@ -53,22 +218,51 @@ pub fn log(operation: &str, message: &str, details: Option<&str>) {
 
     let timestamp = get_log_timestamp();
     let log_line = if let Some(details) = details {
-        format!("[{}] {}: {} | {}", timestamp, operation, message, details)
+        format!(
+            "[{}] {} {}: {} | {}",
+            timestamp, level, operation, message, details
+        )
     } else {
-        format!("[{}] {}: {}", timestamp, operation, message)
+        format!("[{}] {} {}: {}", timestamp, level, operation, message)
     };
 
-    // Print ERROR messages to stderr in development builds
+    // Print errors to stderr in development builds
     #[cfg(debug_assertions)]
-    if operation == "ERROR" {
+    if level == LogLevel::Error {
         eprintln!("{}", log_line);
     }
 
+    remember(LogRecord {
+        timestamp,
+        level,
+        operation: operation.to_string(),
+        message: message.to_string(),
+        details: details.map(str::to_string),
+    });
+
     // Always log to file
     if let Some(logger) = LOGGER.get() {
-        if let Ok(mut writer) = logger.lock() {
-            let _ = writer.write_all(format!("{}\n", log_line).as_bytes());
-            let _ = writer.flush();
+        if let Ok(mut log_file) = logger.lock() {
+            let _ = log_file.write_line(&log_line);
         }
     }
 }
+
+fn remember(record: LogRecord) {
+    let mut recent = RECENT_LOGS.lock().unwrap_or_else(|e| e.into_inner());
+    if recent.len() == MAX_RECENT_LOGS {
+        recent.pop_front();
+    }
+    recent.push_back(record);
+}
+
+/// Returns the buffered entries at `level_filter` or more severe, oldest first.
+pub fn recent_logs(level_filter: LogLevel) -> Vec<LogRecord> {
+    RECENT_LOGS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .filter(|record| record.level <= level_filter)
+        .cloned()
+        .collect()
+}
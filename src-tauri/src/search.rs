@@ -1,16 +1,82 @@
 use crate::core::{AppError, AppResult};
+use crate::services::version_service::list_note_versions;
+use crate::utilities::config_helpers::default_search_title_boost;
+use crate::utilities::frontmatter::normalize_tag;
+use crate::utilities::paths::get_backup_dir_for_notes_path;
 use crate::utilities::strings::{
-    extract_title_from_content, extract_title_from_filename, sanitize_fts_query,
+    extract_title_from_content, extract_title_from_filename, fold_diacritics, is_stop_word,
+    sanitize_fts_query,
 };
+use crate::utilities::validation::validate_note_folder;
 use nucleo_matcher::{Config, Matcher, Utf32Str};
+use regex::{Regex, RegexBuilder};
 use rusqlite::params;
 use serde::Serialize;
 use std::cmp::Ordering;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering as AtomicOrdering;
 
 #[derive(Serialize)]
 pub struct NoteMetadata {
     pub filename: String,
     pub modified: i64,
+    /// When Symiosis first saw the note, which unlike `modified` survives edits
+    pub created_at: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlights: Option<SearchHighlights>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SearchHighlights {
+    pub match_count: usize,
+    /// HTML-escaped excerpts around the first matches, with each hit wrapped in `<mark>`
+    pub snippets: Vec<String>,
+}
+
+const MAX_SNIPPETS_PER_NOTE: usize = 3;
+const SNIPPET_CONTEXT_CHARS: usize = 40;
+
+#[derive(Serialize)]
+pub struct VersionMatch {
+    pub filename: String,
+    /// Backup file to open with `get_version_content`; `None` for the current content
+    pub version_filename: Option<String>,
+    pub timestamp: i64,
+    pub highlights: SearchHighlights,
+}
+
+/// One occurrence of a query inside a single note.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct LineMatch {
+    /// 1-based line number
+    pub line: usize,
+    /// Start of the match within its line, in UTF-16 code units like JavaScript string indices
+    pub start: usize,
+    /// End of the match (exclusive) within its line, in UTF-16 code units
+    pub end: usize,
+}
+
+// Version bodies are read from disk on every search, so only the newest are scanned
+const MAX_VERSIONS_SCANNED: usize = 5_000;
+
+struct VersionSource {
+    filename: String,
+    timestamp: i64,
+    body: SourceBody,
+}
+
+// Current content is already in memory; versions are only read once they are reached
+enum SourceBody {
+    Current(String),
+    Version(String),
+}
+
+#[derive(Serialize)]
+pub struct ScoredNoteMetadata {
+    pub filename: String,
+    pub modified: i64,
+    pub score: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -36,16 +102,62 @@ struct SearchCandidate {
     title: String,
     content: String,
     modified: i64,
+    created_at: i64,
 }
 
+// Score of a note the index matched only through stemming, below any literal match
+const STEMMED_MATCH_SCORE: u32 = 40;
+
 pub struct HybridSearcher {
     matcher: Matcher,
+    normalize_diacritics: bool,
+    title_boost: f64,
+    stemming: bool,
+}
+
+impl Default for HybridSearcher {
+    fn default() -> Self {
+        Self {
+            matcher: Matcher::new(Config::DEFAULT),
+            normalize_diacritics: Config::DEFAULT.normalize,
+            title_boost: default_search_title_boost(),
+            stemming: false,
+        }
+    }
 }
 
 impl HybridSearcher {
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
-        let matcher = Matcher::new(Config::DEFAULT);
-        Ok(Self { matcher })
+        Ok(Self::default())
+    }
+
+    fn apply_preferences(&mut self, app_state: &crate::core::state::AppState) {
+        let (normalize, title_boost, stemming) = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            (
+                config.preferences.search_normalize_diacritics,
+                config.preferences.search_title_boost,
+                config.preferences.search_stemming,
+            )
+        };
+        self.title_boost = title_boost;
+        self.stemming = stemming;
+
+        if normalize != self.normalize_diacritics {
+            let mut config = Config::DEFAULT;
+            config.normalize = normalize;
+            self.matcher = Matcher::new(config);
+            self.normalize_diacritics = normalize;
+        }
+    }
+
+    fn normalize(&self, text: &str) -> String {
+        let lower = text.to_lowercase();
+        if self.normalize_diacritics {
+            fold_diacritics(&lower)
+        } else {
+            lower
+        }
     }
 
     pub fn search(
@@ -53,36 +165,212 @@ impl HybridSearcher {
         app_state: &crate::core::state::AppState,
         query: &str,
         max_results: usize,
+        whole_word: bool,
+        path_prefix: &str,
     ) -> AppResult<Vec<NoteMetadata>> {
+        let folder = folder_prefix(path_prefix)?;
         if query.trim().is_empty() {
-            return self.get_recent_notes(app_state, max_results);
+            return list_notes_in_display_order(app_state, &folder, Some(max_results));
         }
 
-        let candidates = self.get_candidates_from_sqlite(app_state, query)?;
+        self.apply_preferences(app_state);
+
+        let pattern = self.query_pattern(query, whole_word)?;
+        let candidates = self.get_candidates_from_sqlite(app_state, query, whole_word, &folder)?;
         let mut results = Vec::new();
 
         for candidate in candidates {
-            if let Some(result) = self.score_candidate(&candidate, query) {
-                results.push(result);
+            let result = if whole_word {
+                self.score_whole_word_candidate(&candidate, query, &pattern)
+            } else {
+                self.score_candidate(&candidate, query)
+            };
+            if let Some(result) = result {
+                results.push((result, candidate));
             }
         }
 
-        results.sort_by(|a, b| self.compare_results(a, b));
+        results.sort_by(|(a, _), (b, _)| self.compare_results(a, b));
         results.truncate(max_results);
 
         Ok(results
             .into_iter()
-            .map(|r| NoteMetadata {
+            .map(|(r, candidate)| NoteMetadata {
                 filename: r.filename,
                 modified: r.modified,
+                created_at: candidate.created_at,
+                highlights: Some(self.highlight_matches(&candidate.content, &pattern)),
             })
             .collect())
     }
 
+    /// Ranks every note by fuzzy match of the query against its title or filename.
+    pub fn search_fuzzy(
+        &mut self,
+        app_state: &crate::core::state::AppState,
+        query: &str,
+        max_results: usize,
+    ) -> AppResult<Vec<ScoredNoteMetadata>> {
+        if query.trim().is_empty() {
+            return Ok(
+                list_notes_in_display_order(app_state, "", Some(max_results))?
+                    .into_iter()
+                    .map(|note| ScoredNoteMetadata {
+                        filename: note.filename,
+                        modified: note.modified,
+                        score: 0,
+                    })
+                    .collect(),
+            );
+        }
+
+        self.apply_preferences(app_state);
+        let query_lower = self.normalize(query.trim());
+        let candidates = crate::database::with_db(app_state, |conn| {
+            let mut stmt = conn.prepare("SELECT filename, content, modified FROM notes")?;
+            let rows = stmt.query_map([], |row| {
+                let filename: String = row.get(0)?;
+                let content: String = row.get(1)?;
+                let modified: i64 = row.get(2)?;
+                let title = extract_title_from_content(&content)
+                    .unwrap_or_else(|| extract_title_from_filename(&filename));
+                Ok((filename, title, modified))
+            })?;
+            let candidates = rows.collect::<Result<Vec<_>, _>>()?;
+            Ok(candidates)
+        })?;
+
+        let mut results = Vec::new();
+        for (filename, title, modified) in candidates {
+            let title_score = self.fuzzy_match(&self.normalize(&title), &query_lower);
+            let filename_score = self.fuzzy_match(&self.normalize(&filename), &query_lower);
+
+            if let Some(score) = title_score.max(filename_score) {
+                results.push((
+                    title,
+                    ScoredNoteMetadata {
+                        filename,
+                        modified,
+                        score,
+                    },
+                ));
+            }
+        }
+
+        results.sort_by(|(a_title, a), (b_title, b)| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| b.modified.cmp(&a.modified))
+                .then_with(|| a_title.cmp(b_title))
+        });
+        results.truncate(max_results);
+
+        Ok(results.into_iter().map(|(_, note)| note).collect())
+    }
+
+    /// Finds the query as a phrase in current note content and stored versions, newest
+    /// first, so text that has since been edited away can still be found.
+    pub fn search_versions(
+        &mut self,
+        app_state: &crate::core::state::AppState,
+        query: &str,
+        max_results: usize,
+    ) -> AppResult<Vec<VersionMatch>> {
+        if query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.apply_preferences(app_state);
+        let phrase = self
+            .normalize(query)
+            .split_whitespace()
+            .map(regex::escape)
+            .collect::<Vec<_>>()
+            .join(r"\s+");
+        let pattern = Regex::new(&phrase)
+            .map_err(|e| AppError::SearchQuery(format!("Invalid search query: {}", e)))?;
+
+        let (notes_dir, date_format) = {
+            let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+            (
+                PathBuf::from(&config.notes_directory),
+                config.preferences.date_format.clone(),
+            )
+        };
+        let backup_dir = get_backup_dir_for_notes_path(&notes_dir)?;
+        let notes = crate::database::with_db(app_state, |conn| {
+            let mut stmt = conn.prepare("SELECT filename, content, modified FROM notes")?;
+            let rows = stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            })?;
+            Ok(rows.collect::<Result<Vec<_>, _>>()?)
+        })?;
+
+        let mut sources = Vec::new();
+        for (filename, content, modified) in notes {
+            for version in list_note_versions(&notes_dir, &filename, &date_format)? {
+                sources.push(VersionSource {
+                    filename: filename.clone(),
+                    timestamp: version.timestamp as i64,
+                    body: SourceBody::Version(version.filename),
+                });
+            }
+            sources.push(VersionSource {
+                filename,
+                timestamp: modified,
+                body: SourceBody::Current(content),
+            });
+        }
+        sources.sort_by(|a, b| {
+            b.timestamp
+                .cmp(&a.timestamp)
+                .then_with(|| a.filename.cmp(&b.filename))
+        });
+
+        let mut versions_scanned = 0;
+        let mut results = Vec::new();
+        for source in sources {
+            if results.len() >= max_results {
+                break;
+            }
+            let (content, version_filename) = match source.body {
+                SourceBody::Current(content) => (content, None),
+                SourceBody::Version(version) => {
+                    if versions_scanned == MAX_VERSIONS_SCANNED {
+                        continue;
+                    }
+                    versions_scanned += 1;
+                    match fs::read_to_string(backup_dir.join(&version)) {
+                        Ok(content) => (content, Some(version)),
+                        Err(_) => continue,
+                    }
+                }
+            };
+
+            let highlights = self.highlight_matches(&content, &pattern);
+            if highlights.match_count > 0 {
+                results.push(VersionMatch {
+                    filename: source.filename,
+                    version_filename,
+                    timestamp: source.timestamp,
+                    highlights,
+                });
+            }
+        }
+
+        Ok(results)
+    }
+
     fn get_candidates_from_sqlite(
         &self,
         app_state: &crate::core::state::AppState,
         query: &str,
+        whole_word: bool,
+        folder: &str,
     ) -> AppResult<Vec<SearchCandidate>> {
         let sanitized_query = sanitize_fts_query(query);
 
@@ -90,29 +378,45 @@ impl HybridSearcher {
             return Ok(Vec::new());
         }
 
-        let fts_pattern = if sanitized_query.contains(' ') {
-            sanitized_query
-                .split_whitespace()
-                .filter(|word| !word.trim().is_empty())
-                .map(|word| format!("{}*", word))
-                .collect::<Vec<_>>()
-                .join(" OR ")
-        } else {
-            format!("{}*", sanitized_query)
-        };
+        let mut words: Vec<&str> = sanitized_query.split_whitespace().collect();
+        if self.stemming {
+            let content_words: Vec<&str> = words
+                .iter()
+                .copied()
+                .filter(|word| !is_stop_word(word))
+                .collect();
+            // A query made only of stop words still searches for them
+            if !content_words.is_empty() {
+                words = content_words;
+            }
+        }
+
+        // Whole-word searches drop the prefix wildcard so FTS only returns complete tokens
+        let suffix = if whole_word { "" } else { "*" };
+        let fts_pattern = words
+            .iter()
+            .map(|word| format!("{}{}", word, suffix))
+            .collect::<Vec<_>>()
+            .join(" OR ");
 
         crate::database::with_db(app_state, |conn| {
-            let mut stmt = conn.prepare(
-                "SELECT filename, content, modified FROM notes
-                     WHERE notes MATCH ?
+            // Cached on the connection, since search-as-you-type runs this per keystroke
+            let mut stmt = conn.prepare_cached(
+                "SELECT notes.filename, notes.content, notes.modified,
+                        COALESCE(note_created_at.created_at, notes.modified)
+                     FROM notes
+                     LEFT JOIN note_created_at ON note_created_at.filename = notes.filename
+                     WHERE notes MATCH ?1
+                       AND substr(notes.filename, 1, length(?2)) = ?2
                      ORDER BY rank
                      LIMIT 500",
             )?;
 
-            let rows = stmt.query_map(params![fts_pattern], |row| {
+            let rows = stmt.query_map(params![fts_pattern, folder], |row| {
                 let filename: String = row.get(0)?;
                 let content: String = row.get(1)?;
                 let modified: i64 = row.get(2)?;
+                let created_at: i64 = row.get(3)?;
 
                 let title = extract_title_from_content(&content)
                     .unwrap_or_else(|| extract_title_from_filename(&filename));
@@ -122,6 +426,7 @@ impl HybridSearcher {
                     title,
                     content,
                     modified,
+                    created_at,
                 })
             })?;
 
@@ -135,9 +440,9 @@ impl HybridSearcher {
         candidate: &SearchCandidate,
         query: &str,
     ) -> Option<SearchResult> {
-        let query_lower = query.to_lowercase();
-        let title_lower = candidate.title.to_lowercase();
-        let filename_lower = candidate.filename.to_lowercase();
+        let query_lower = self.normalize(query);
+        let title_lower = self.normalize(&candidate.title);
+        let filename_lower = self.normalize(&candidate.filename);
 
         if let Some((score, match_type)) =
             self.score_title_match(&title_lower, &filename_lower, &query_lower)
@@ -145,7 +450,7 @@ impl HybridSearcher {
             Some(SearchResult {
                 filename: candidate.filename.clone(),
                 title: candidate.title.clone(),
-                score,
+                score: self.boost_title_score(score),
                 match_type,
                 modified: candidate.modified,
             })
@@ -157,11 +462,99 @@ impl HybridSearcher {
                 match_type: MatchType::Content,
                 modified: candidate.modified,
             })
+        } else if self.stemming {
+            // FTS matched another form of the word, e.g. "run" for "running"
+            Some(SearchResult {
+                filename: candidate.filename.clone(),
+                title: candidate.title.clone(),
+                score: STEMMED_MATCH_SCORE,
+                match_type: MatchType::Content,
+                modified: candidate.modified,
+            })
         } else {
             None
         }
     }
 
+    /// Builds a regex matching any query term in normalized text, optionally as a complete word.
+    fn query_pattern(&self, query: &str, whole_word: bool) -> AppResult<Regex> {
+        let terms = self
+            .normalize(query)
+            .split_whitespace()
+            .map(regex::escape)
+            .collect::<Vec<_>>()
+            .join("|");
+
+        let pattern = if whole_word {
+            format!(r"\b(?:{})\b", terms)
+        } else {
+            format!("(?:{})", terms)
+        };
+        Regex::new(&pattern)
+            .map_err(|e| AppError::SearchQuery(format!("Invalid search query: {}", e)))
+    }
+
+    /// Counts pattern matches in the note body and renders snippets around the first few.
+    fn highlight_matches(&self, content: &str, pattern: &Regex) -> SearchHighlights {
+        // Normalize per character so every normalized byte maps back to its source character
+        let mut normalized = String::with_capacity(content.len());
+        let mut origins = Vec::with_capacity(content.len());
+        for (start, c) in content.char_indices() {
+            let piece = self.normalize(c.encode_utf8(&mut [0; 4]));
+            normalized.push_str(&piece);
+            origins.extend(std::iter::repeat_n(
+                (start, start + c.len_utf8()),
+                piece.len(),
+            ));
+        }
+
+        let ranges: Vec<(usize, usize)> = pattern
+            .find_iter(&normalized)
+            .filter(|m| !m.is_empty())
+            .map(|m| (origins[m.start()].0, origins[m.end() - 1].1))
+            .collect();
+
+        SearchHighlights {
+            match_count: ranges.len(),
+            snippets: build_snippets(content, &ranges),
+        }
+    }
+
+    fn score_whole_word_candidate(
+        &self,
+        candidate: &SearchCandidate,
+        query: &str,
+        pattern: &Regex,
+    ) -> Option<SearchResult> {
+        let query_lower = self.normalize(query.trim());
+        let title_lower = self.normalize(&candidate.title);
+        let filename_lower = self.normalize(&candidate.filename);
+
+        let (score, match_type) = if title_lower == query_lower {
+            (self.boost_title_score(1100), MatchType::ExactTitle)
+        } else if pattern.is_match(&title_lower) {
+            (self.boost_title_score(800), MatchType::PrefixTitle)
+        } else if pattern.is_match(&filename_lower) {
+            (self.boost_title_score(750), MatchType::PrefixTitle)
+        } else {
+            let count = pattern
+                .find_iter(&self.normalize(&candidate.content))
+                .count() as u32;
+            if count == 0 {
+                return None;
+            }
+            (50 + count * 10, MatchType::Content)
+        };
+
+        Some(SearchResult {
+            filename: candidate.filename.clone(),
+            title: candidate.title.clone(),
+            score,
+            match_type,
+            modified: candidate.modified,
+        })
+    }
+
     fn score_title_match(
         &mut self,
         title_lower: &str,
@@ -195,8 +588,13 @@ impl HybridSearcher {
         None
     }
 
+    /// Weighs a title or filename match against body matches, which keep their raw score.
+    fn boost_title_score(&self, score: u32) -> u32 {
+        (score as f64 * self.title_boost).round() as u32
+    }
+
     fn score_content_match(&mut self, content: &str, query_lower: &str) -> Option<u32> {
-        let content_lower = content.to_lowercase();
+        let content_lower = self.normalize(content);
 
         if content_lower.contains(query_lower) {
             let count = content_lower.matches(query_lower).count() as u32;
@@ -217,41 +615,372 @@ impl HybridSearcher {
     }
 
     fn compare_results(&self, a: &SearchResult, b: &SearchResult) -> Ordering {
-        b.match_type
-            .cmp(&a.match_type)
-            .then_with(|| b.score.cmp(&a.score))
+        b.score
+            .cmp(&a.score)
+            .then_with(|| b.match_type.cmp(&a.match_type))
             .then_with(|| b.modified.cmp(&a.modified))
             .then_with(|| a.title.cmp(&b.title))
     }
+}
 
-    fn get_recent_notes(
-        &self,
-        app_state: &crate::core::state::AppState,
-        max_results: usize,
-    ) -> AppResult<Vec<NoteMetadata>> {
-        crate::database::with_db(app_state, |conn| {
-            let mut stmt = conn
-                .prepare("SELECT filename, modified FROM notes ORDER BY modified DESC LIMIT ?")?;
+/// Lists notes for the empty-query view in the configured `note_sort_order`,
+/// with pinned notes first in pin order when `pinned_notes_first` is set.
+/// `folder` is a prefix from `folder_prefix`, empty for every note.
+pub fn list_notes_in_display_order(
+    app_state: &crate::core::state::AppState,
+    folder: &str,
+    max_results: Option<usize>,
+) -> AppResult<Vec<NoteMetadata>> {
+    let pinned_first = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        config.preferences.pinned_notes_first
+    };
+    list_notes_sorted(app_state, folder, max_results, pinned_first)
+}
 
-            let rows = stmt.query_map([max_results], |row| {
-                Ok(NoteMetadata {
-                    filename: row.get(0)?,
-                    modified: row.get(1)?,
-                })
-            })?;
+/// Every note in the configured `note_sort_order`, ignoring pins.
+pub fn list_notes_in_sort_order(
+    app_state: &crate::core::state::AppState,
+) -> AppResult<Vec<NoteMetadata>> {
+    list_notes_sorted(app_state, "", None, false)
+}
 
-            let notes = rows.collect::<Result<Vec<_>, _>>()?;
-            Ok(notes)
-        })
+fn list_notes_sorted(
+    app_state: &crate::core::state::AppState,
+    folder: &str,
+    max_results: Option<usize>,
+    pinned_first: bool,
+) -> AppResult<Vec<NoteMetadata>> {
+    let sort_order = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        config.preferences.note_sort_order.clone()
+    };
+    let order_by = match sort_order.as_str() {
+        "modified_asc" => "notes.modified ASC",
+        "name_asc" => "unicode_lower(notes.filename) ASC, notes.filename ASC",
+        "name_desc" => "unicode_lower(notes.filename) DESC, notes.filename DESC",
+        "created_desc" => "created_at DESC",
+        _ => "notes.modified DESC",
+    };
+
+    crate::database::with_db(app_state, |conn| {
+        // Joining pins only when they lead leaves every position NULL otherwise,
+        // so the pin ordering terms fall away
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT notes.filename, notes.modified,
+                    COALESCE(note_created_at.created_at, notes.modified) AS created_at
+                 FROM notes
+                 LEFT JOIN note_created_at ON note_created_at.filename = notes.filename
+                 LEFT JOIN note_ids ON note_ids.filename = notes.filename
+                 LEFT JOIN pinned_notes ON pinned_notes.note_id = note_ids.id AND ?1
+                 WHERE substr(notes.filename, 1, length(?2)) = ?2
+                 ORDER BY pinned_notes.position IS NULL, pinned_notes.position,
+                          {}, notes.filename
+                 LIMIT ?3",
+            order_by
+        ))?;
+
+        // A negative LIMIT is no limit in SQLite
+        let limit = max_results.map_or(-1, |max| max as i64);
+        let rows = stmt.query_map(params![pinned_first, folder, limit], |row| {
+            Ok(NoteMetadata {
+                filename: row.get(0)?,
+                modified: row.get(1)?,
+                created_at: row.get(2)?,
+                highlights: None,
+            })
+        })?;
+
+        let notes = rows.collect::<Result<Vec<_>, _>>()?;
+        Ok(notes)
+    })
+}
+
+/// Turns a folder to search in into the prefix its notes' filenames start with,
+/// empty when searching everywhere.
+fn folder_prefix(path_prefix: &str) -> AppResult<String> {
+    let folder = path_prefix.trim().trim_matches('/');
+    validate_note_folder(folder)?;
+    if folder.is_empty() {
+        Ok(String::new())
+    } else {
+        Ok(format!("{}/", folder))
+    }
+}
+
+struct SnippetWindow {
+    start: usize,
+    end: usize,
+    marks: Vec<(usize, usize)>,
+}
+
+/// Groups match ranges into at most `MAX_SNIPPETS_PER_NOTE` excerpts; matches that
+/// fall inside an earlier excerpt extend it instead of starting a new one.
+fn build_snippets(content: &str, ranges: &[(usize, usize)]) -> Vec<String> {
+    let mut windows: Vec<SnippetWindow> = Vec::new();
+
+    for &(start, end) in ranges {
+        if let Some(window) = windows.last_mut() {
+            if start < window.end {
+                window.end = window.end.max(context_end(content, end));
+                window.marks.push((start, end));
+                continue;
+            }
+        }
+        if windows.len() == MAX_SNIPPETS_PER_NOTE {
+            break;
+        }
+        windows.push(SnippetWindow {
+            start: context_start(content, start),
+            end: context_end(content, end),
+            marks: vec![(start, end)],
+        });
     }
+
+    windows
+        .iter()
+        .map(|window| render_snippet(content, window))
+        .collect()
+}
+
+fn context_start(content: &str, offset: usize) -> usize {
+    content[..offset]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_CONTEXT_CHARS - 1)
+        .map_or(0, |(i, _)| i)
+}
+
+fn context_end(content: &str, offset: usize) -> usize {
+    content[offset..]
+        .char_indices()
+        .nth(SNIPPET_CONTEXT_CHARS)
+        .map_or(content.len(), |(i, _)| offset + i)
+}
+
+fn render_snippet(content: &str, window: &SnippetWindow) -> String {
+    let mut snippet = String::new();
+    let mut cursor = window.start;
+
+    for &(mark_start, mark_end) in &window.marks {
+        // Distinct matches can map back onto the same source character
+        if mark_start < cursor {
+            continue;
+        }
+        snippet.push_str(&html_escape::encode_text(&content[cursor..mark_start]));
+        snippet.push_str("<mark>");
+        snippet.push_str(&html_escape::encode_text(&content[mark_start..mark_end]));
+        snippet.push_str("</mark>");
+        cursor = mark_end;
+    }
+    snippet.push_str(&html_escape::encode_text(&content[cursor..window.end]));
+
+    let snippet = snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+    format!(
+        "{}{}{}",
+        if window.start > 0 { "…" } else { "" },
+        snippet,
+        if window.end < content.len() {
+            "…"
+        } else {
+            ""
+        }
+    )
 }
 
 pub fn search_notes_hybrid(
     app_state: &crate::core::state::AppState,
     query: &str,
     max_results: usize,
+    whole_word: bool,
+    path_prefix: &str,
+) -> AppResult<Vec<NoteMetadata>> {
+    // Shared so each keystroke reuses the matcher's buffers; searches already
+    // take turns on the database connection
+    let mut searcher = app_state.searcher.lock().unwrap_or_else(|e| e.into_inner());
+    searcher.search(app_state, query, max_results, whole_word, path_prefix)
+}
+
+/// What `search_notes` returns instead of results once a newer search exists.
+pub const SEARCH_SUPERSEDED: &str = "superseded";
+
+/// Runs `search` on behalf of search-as-you-type query number `generation`,
+/// which the frontend increments for every query it sends. Returns `None` when
+/// a higher generation arrived before or while it ran, so the caller can drop
+/// the stale results instead of showing them. Without a generation the search
+/// always runs and is never superseded.
+pub fn search_for_generation<T>(
+    app_state: &crate::core::state::AppState,
+    generation: Option<u64>,
+    search: impl FnOnce() -> AppResult<T>,
+) -> AppResult<Option<T>> {
+    let Some(generation) = generation else {
+        return search().map(Some);
+    };
+    let latest = &app_state.search_generation;
+    let superseded = || latest.load(AtomicOrdering::SeqCst) > generation;
+
+    latest.fetch_max(generation, AtomicOrdering::SeqCst);
+    if superseded() {
+        return Ok(None);
+    }
+    let results = search()?;
+    Ok((!superseded()).then_some(results))
+}
+
+// Upper bound on compiled regex size so pathological patterns fail fast instead of hanging
+pub const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+pub fn search_notes_regex(
+    app_state: &crate::core::state::AppState,
+    pattern: &str,
+    max_results: usize,
 ) -> AppResult<Vec<NoteMetadata>> {
+    if pattern.is_empty() {
+        return Err(AppError::SearchQuery(
+            "Regex pattern cannot be empty".to_string(),
+        ));
+    }
+
+    let regex = RegexBuilder::new(pattern)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| AppError::SearchQuery(format!("Invalid regex pattern: {}", e)))?;
+
+    crate::database::with_db(app_state, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT notes.filename, notes.content, notes.modified,
+                    COALESCE(note_created_at.created_at, notes.modified)
+                 FROM notes
+                 LEFT JOIN note_created_at ON note_created_at.filename = notes.filename
+                 ORDER BY notes.modified DESC",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            if results.len() >= max_results {
+                break;
+            }
+
+            let (filename, content, modified, created_at) = row?;
+            if regex.is_match(&filename) || regex.is_match(&content) {
+                results.push(NoteMetadata {
+                    filename,
+                    modified,
+                    created_at,
+                    highlights: None,
+                });
+            }
+        }
+
+        Ok(results)
+    })
+}
+
+pub fn search_note_versions(
+    app_state: &crate::core::state::AppState,
+    query: &str,
+    max_results: usize,
+) -> AppResult<Vec<VersionMatch>> {
     let mut searcher =
         HybridSearcher::new().map_err(|e| AppError::DatabaseConnection(e.to_string()))?;
-    searcher.search(app_state, query, max_results)
+    searcher.search_versions(app_state, query, max_results)
+}
+
+pub fn search_notes_fuzzy(
+    app_state: &crate::core::state::AppState,
+    query: &str,
+    max_results: usize,
+) -> AppResult<Vec<ScoredNoteMetadata>> {
+    let mut searcher =
+        HybridSearcher::new().map_err(|e| AppError::DatabaseConnection(e.to_string()))?;
+    searcher.search_fuzzy(app_state, query, max_results)
+}
+
+pub fn search_notes_by_tag(
+    app_state: &crate::core::state::AppState,
+    tag: &str,
+    max_results: usize,
+) -> AppResult<Vec<NoteMetadata>> {
+    let tag = normalize_tag(tag)
+        .ok_or_else(|| AppError::SearchQuery("Tag cannot be empty".to_string()))?;
+
+    crate::database::with_db(app_state, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT notes.filename, notes.modified,
+                    COALESCE(note_created_at.created_at, notes.modified)
+                 FROM note_tags
+                 JOIN notes ON notes.filename = note_tags.filename
+                 LEFT JOIN note_created_at ON note_created_at.filename = notes.filename
+                 WHERE note_tags.tag = ?1
+                 ORDER BY notes.modified DESC
+                 LIMIT ?2",
+        )?;
+
+        let rows = stmt.query_map(params![tag, max_results], |row| {
+            Ok(NoteMetadata {
+                filename: row.get(0)?,
+                modified: row.get(1)?,
+                created_at: row.get(2)?,
+                highlights: None,
+            })
+        })?;
+
+        let notes = rows.collect::<Result<Vec<_>, _>>()?;
+        Ok(notes)
+    })
+}
+
+/// Finds every occurrence of `query` in `content`, line by line. The query is
+/// matched literally as one phrase, ignoring case unless `case_sensitive`.
+pub fn find_in_note(
+    content: &str,
+    query: &str,
+    whole_word: bool,
+    case_sensitive: bool,
+) -> AppResult<Vec<LineMatch>> {
+    if query.is_empty() {
+        return Err(AppError::SearchQuery(
+            "Search query cannot be empty".to_string(),
+        ));
+    }
+
+    let escaped = regex::escape(query);
+    let pattern = if whole_word {
+        format!(r"\b{}\b", escaped)
+    } else {
+        escaped
+    };
+    let regex = RegexBuilder::new(&pattern)
+        .case_insensitive(!case_sensitive)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| AppError::SearchQuery(format!("Invalid search query: {}", e)))?;
+
+    let mut matches = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let mut utf16_cursor = (0, 0);
+        for found in regex.find_iter(line) {
+            let (byte, units) = utf16_cursor;
+            let start = units + line[byte..found.start()].encode_utf16().count();
+            let end = start + found.as_str().encode_utf16().count();
+            utf16_cursor = (found.end(), end);
+
+            matches.push(LineMatch {
+                line: index + 1,
+                start,
+                end,
+            });
+        }
+    }
+    Ok(matches)
 }
@@ -0,0 +1,74 @@
+use crate::services::hash_service::content_hash;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// How long a confirmation token stays valid after it is issued.
+pub const DELETE_CONFIRMATION_TTL: Duration = Duration::from_secs(60);
+
+static TOKEN_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Single-use tokens that a delete must present when `confirm_before_delete`
+/// is on, at most one outstanding per note.
+#[derive(Debug, Default)]
+pub struct DeleteConfirmations {
+    pending: HashMap<String, (String, Instant)>,
+}
+
+impl DeleteConfirmations {
+    /// Issues a token for `note_name`, replacing any earlier one.
+    pub fn issue(&mut self, note_name: &str) -> String {
+        let now = Instant::now();
+        self.pending
+            .retain(|_, (_, issued)| now.duration_since(*issued) < DELETE_CONFIRMATION_TTL);
+
+        let token = new_token(note_name);
+        self.pending
+            .insert(note_name.to_string(), (token.clone(), now));
+        token
+    }
+
+    /// Whether `token` was issued for `note_name` and has not expired. A
+    /// matching token is used up either way.
+    pub fn consume(&mut self, note_name: &str, token: &str) -> bool {
+        match self.pending.get(note_name) {
+            Some((pending, _)) if pending == token => {}
+            _ => return false,
+        }
+        self.pending
+            .remove(note_name)
+            .is_some_and(|(_, issued)| issued.elapsed() < DELETE_CONFIRMATION_TTL)
+    }
+
+    /// Issues one token for deleting exactly the notes in `names`, in any order.
+    pub fn issue_batch(&mut self, names: &[String]) -> String {
+        self.issue(&batch_key(names))
+    }
+
+    /// Like `consume`, for a token from `issue_batch` for the same set of notes.
+    pub fn consume_batch(&mut self, names: &[String], token: &str) -> bool {
+        self.consume(&batch_key(names), token)
+    }
+}
+
+// Note names can't contain a NUL, so a batch never shares a key with a note
+fn batch_key(names: &[String]) -> String {
+    let mut names: Vec<&str> = names.iter().map(String::as_str).collect();
+    names.sort_unstable();
+    names.dedup();
+    format!("\0batch\0{}", names.join("\0"))
+}
+
+// Guards against accidental deletes rather than an attacker, so a hash of the
+// note, the time and a counter is unpredictable enough
+fn new_token(note_name: &str) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let count = TOKEN_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let hash = content_hash(
+        format!("{}:{}:{}:{}", note_name, nanos, std::process::id(), count).as_bytes(),
+    );
+    hash[..32].to_string()
+}
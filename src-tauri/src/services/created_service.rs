@@ -0,0 +1,73 @@
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+pub fn init_created_table(conn: &Connection) -> rusqlite::Result<()> {
+    // Birth times are not preserved by every filesystem or sync client, so once
+    // recorded a creation time is kept through database rebuilds like pins are
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS note_created_at (
+            filename TEXT PRIMARY KEY,
+            created_at INTEGER NOT NULL
+        );",
+    )
+}
+
+/// Records when a note was first seen, from the file's birth time when the
+/// filesystem reports one and `fallback` otherwise. Notes that already have a
+/// creation time keep it.
+pub fn record_note_created(
+    conn: &Connection,
+    filename: &str,
+    path: &Path,
+    fallback: i64,
+) -> rusqlite::Result<()> {
+    let exists = conn
+        .prepare("SELECT 1 FROM note_created_at WHERE filename = ?1")?
+        .exists(params![filename])?;
+    if exists {
+        return Ok(());
+    }
+
+    conn.execute(
+        "INSERT INTO note_created_at (filename, created_at) VALUES (?1, ?2)",
+        params![filename, file_birth_time(path).unwrap_or(fallback)],
+    )?;
+    Ok(())
+}
+
+pub fn delete_note_created(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM note_created_at WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
+
+pub fn rename_note_created(
+    conn: &Connection,
+    old_name: &str,
+    new_name: &str,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "UPDATE note_created_at SET filename = ?1 WHERE filename = ?2",
+        params![new_name, old_name],
+    )?;
+    Ok(())
+}
+
+pub fn load_created_times(conn: &Connection) -> rusqlite::Result<HashMap<String, i64>> {
+    let mut stmt = conn.prepare("SELECT filename, created_at FROM note_created_at")?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect()
+}
+
+fn file_birth_time(path: &Path) -> Option<i64> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.created())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+}
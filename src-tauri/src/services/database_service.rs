@@ -2,13 +2,34 @@ use crate::{
     config::get_config_notes_dir,
     core::{state::AppState, AppError, AppResult},
     database::with_db,
-    logging::log,
+    logging::{log, log_error, log_warn},
+    services::{
+        created_service::{init_created_table, load_created_times, record_note_created},
+        hash_service::{
+            content_hash, delete_note_hash, init_hashes_table, load_note_hashes, store_content_hash,
+        },
+        note_id_service::{init_note_ids_table, track_note_id},
+        note_service::render_options,
+        pin_service::init_pins_table,
+        recent_service::init_recents_table,
+        render_cache_service::init_render_cache_table,
+        tag_service::{delete_note_tags, init_tags_table, store_note_tags},
+    },
+    utilities::{
+        frontmatter::parse_frontmatter,
+        note_ignore::NoteIgnore,
+        note_renderer::{render_note, RenderOptions},
+        symlinks::{resolve_symlink, SymlinkTarget},
+        validation::has_note_extension,
+    },
 };
-use rusqlite::{params, Connection};
+use rayon::{prelude::*, ThreadPoolBuilder};
+use rusqlite::{params, Connection, OptionalExtension};
 use std::{
     collections::{HashMap, HashSet},
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
     time::UNIX_EPOCH,
 };
 use tauri::{AppHandle, Emitter};
@@ -18,8 +39,25 @@ use walkdir::WalkDir;
 // Remaining notes get metadata-only and are processed on demand
 const IMMEDIATE_RENDER_COUNT: usize = 2000;
 
+const NOTES_TABLE_COLUMNS: &str =
+    "filename, content, html_render, modified UNINDEXED, is_indexed UNINDEXED";
+
 pub fn init_db(conn: &Connection) -> rusqlite::Result<()> {
-    conn.execute_batch("CREATE VIRTUAL TABLE IF NOT EXISTS notes USING fts5(filename, content, html_render, modified UNINDEXED, is_indexed UNINDEXED);")?;
+    conn.execute_batch(&format!(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS notes USING fts5({});",
+        NOTES_TABLE_COLUMNS
+    ))?;
+    // Last-seen file sizes, checked alongside notes.modified so same-second edits are caught
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS note_file_sizes (filename TEXT PRIMARY KEY, size INTEGER NOT NULL);",
+    )?;
+    init_tags_table(conn)?;
+    init_hashes_table(conn)?;
+    init_note_ids_table(conn)?;
+    init_pins_table(conn)?;
+    init_recents_table(conn)?;
+    init_render_cache_table(conn)?;
+    init_created_table(conn)?;
 
     let mut stmt = conn.prepare(
         "SELECT filename, COUNT(*) as count FROM notes GROUP BY filename HAVING count > 1",
@@ -51,11 +89,92 @@ pub fn load_all_notes_into_sqlite(
     load_all_notes_into_sqlite_with_progress(app_state, conn, None)
 }
 
+/// Makes the notes table tokenize for `search_stemming`, so notes are stemmed the
+/// same way as queries. Switching drops the index, and returns true so the caller
+/// re-reads every note.
+pub fn ensure_search_tokenizer(conn: &Connection, stemming: bool) -> rusqlite::Result<bool> {
+    let sql: Option<String> = conn
+        .query_row(
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'notes'",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if sql.is_some_and(|sql| sql.contains("porter") == stemming) {
+        return Ok(false);
+    }
+
+    log(
+        "NOTE_INDEXING",
+        "Search stemming changed, rebuilding the search index",
+        Some(&format!("search_stemming: {}", stemming)),
+    );
+    conn.execute("DROP TABLE IF EXISTS notes", [])?;
+    conn.execute("DROP TABLE IF EXISTS note_tags", [])?;
+    conn.execute("DROP TABLE IF EXISTS note_file_sizes", [])?;
+    conn.execute("DROP TABLE IF EXISTS note_hashes", [])?;
+    conn.execute("DROP TABLE IF EXISTS render_cache", [])?;
+    let tokenizer = if stemming {
+        ", tokenize = 'porter unicode61'"
+    } else {
+        ""
+    };
+    conn.execute_batch(&format!(
+        "CREATE VIRTUAL TABLE notes USING fts5({}{});",
+        NOTES_TABLE_COLUMNS, tokenizer
+    ))?;
+    init_db(conn)?;
+    Ok(true)
+}
+
+/// What the index last recorded about each note, compared against the filesystem
+/// during a sync.
+struct IndexedFiles {
+    notes: HashMap<String, (i64, bool)>,
+    sizes: HashMap<String, u64>,
+    hashes: HashMap<String, String>,
+    created: HashMap<String, i64>,
+}
+
+/// Brings the index in line with the notes directory, re-reading only files whose
+/// modified time or size changed unless `force` is set. Files whose content still
+/// hashes to what was indexed only get their modified time updated. Returns how many
+/// files were re-indexed.
+pub fn sync_notes_with_filesystem(
+    app_state: &AppState,
+    conn: &mut Connection,
+    force: bool,
+    app_handle: Option<&AppHandle>,
+) -> rusqlite::Result<usize> {
+    ensure_notes_directory_exists()?;
+    let force = ensure_search_tokenizer(conn, get_search_stemming(app_state))? || force;
+    let filesystem_files = scan_filesystem_for_notes(
+        &get_note_extensions(app_state),
+        get_max_recursion_depth(app_state),
+        get_follow_symlinks(app_state),
+    )?;
+    let indexed = IndexedFiles {
+        notes: load_existing_database_files(conn)?,
+        sizes: load_file_sizes(conn)?,
+        hashes: load_note_hashes(conn)?,
+        created: load_created_times(conn)?,
+    };
+    sync_database_with_filesystem(
+        conn,
+        &filesystem_files,
+        &indexed,
+        force,
+        &render_options(app_state),
+        get_index_threads(app_state),
+        app_handle,
+    )
+}
+
 fn ensure_notes_directory_exists() -> rusqlite::Result<()> {
     let notes_dir = get_config_notes_dir();
     if !notes_dir.exists() {
         if let Err(e) = fs::create_dir_all(&notes_dir) {
-            log(
+            log_warn(
                 "DIRECTORY_CREATION",
                 "Failed to create notes directory",
                 Some(&e.to_string()),
@@ -71,51 +190,217 @@ fn ensure_notes_directory_exists() -> rusqlite::Result<()> {
     Ok(())
 }
 
-fn is_note_file(path: &std::path::Path) -> bool {
-    path.extension()
-        .and_then(|ext| ext.to_str())
-        .map(|ext| matches!(ext, "md" | "txt" | "markdown"))
-        .unwrap_or(false)
+fn get_note_extensions(app_state: &AppState) -> Vec<String> {
+    app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .preferences
+        .note_extensions
+        .clone()
 }
 
-fn scan_filesystem_for_notes() -> rusqlite::Result<Vec<(String, PathBuf, i64)>> {
-    let notes_dir = get_config_notes_dir();
-    let mut filesystem_files = Vec::new();
+fn get_max_recursion_depth(app_state: &AppState) -> usize {
+    app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .preferences
+        .max_recursion_depth
+}
 
-    for entry in WalkDir::new(&notes_dir).into_iter().filter_map(|e| e.ok()) {
-        if entry.file_type().is_file() {
-            let path = entry.path();
-            let relative = path.strip_prefix(&notes_dir).unwrap_or(path);
-            let filename = relative.to_string_lossy().to_string();
+fn get_follow_symlinks(app_state: &AppState) -> bool {
+    app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .preferences
+        .follow_symlinks
+}
 
-            if filename.contains("/.") || filename.starts_with('.') {
-                continue;
-            }
+fn get_search_stemming(app_state: &AppState) -> bool {
+    app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .preferences
+        .search_stemming
+}
 
-            if !is_note_file(path) {
-                continue;
-            }
+fn get_index_threads(app_state: &AppState) -> usize {
+    app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .preferences
+        .index_threads
+}
 
-            let modified = entry
+/// Symlinked directories are never descended into, which rules out cycles
+/// through the tree; symlinked files are indexed under the link's path when
+/// `follow_symlinks` is set.
+fn scan_filesystem_for_notes(
+    note_extensions: &[String],
+    max_depth: usize,
+    follow_symlinks: bool,
+) -> rusqlite::Result<Vec<(String, PathBuf, i64, u64)>> {
+    let notes_dir = get_config_notes_dir();
+    let note_ignore = NoteIgnore::load(&notes_dir);
+    let mut filesystem_files = Vec::new();
+    let mut ignored_files = 0;
+    let mut ignored_folders = 0;
+
+    let entries = WalkDir::new(&notes_dir)
+        .max_depth(max_depth)
+        .into_iter()
+        .filter_entry(|entry| {
+            let relative = entry
                 .path()
-                .metadata()
-                .and_then(|m| m.modified())
-                .map(|mtime| {
-                    mtime
-                        .duration_since(UNIX_EPOCH)
-                        .map(|d| d.as_secs() as i64)
-                        .unwrap_or(0)
-                })
-                .unwrap_or(0);
+                .strip_prefix(&notes_dir)
+                .unwrap_or(entry.path());
+            let is_dir = entry.file_type().is_dir();
+            if !note_ignore.is_ignored(relative, is_dir) {
+                return true;
+            }
+            if is_dir {
+                ignored_folders += 1;
+            } else {
+                ignored_files += 1;
+            }
+            false
+        })
+        .filter_map(|e| e.ok());
 
-            filesystem_files.push((filename, path.to_path_buf(), modified));
+    let mut symlinks = Vec::new();
+    for entry in entries {
+        let file_type = entry.file_type();
+        if file_type.is_file() {
+            let path = entry.path();
+            let Some(filename) = note_filename(&notes_dir, path, note_extensions) else {
+                continue;
+            };
+            let metadata = entry.path().metadata().ok();
+            let (modified, size) = file_times(metadata.as_ref());
+            filesystem_files.push((filename, path.to_path_buf(), modified, size));
+        } else if file_type.is_symlink() && follow_symlinks {
+            symlinks.push(entry.into_path());
         }
     }
 
+    if !symlinks.is_empty() {
+        index_symlinked_notes(
+            &notes_dir,
+            &symlinks,
+            note_extensions,
+            &mut filesystem_files,
+        );
+    }
+
+    if ignored_files > 0 || ignored_folders > 0 {
+        log(
+            "NOTE_IGNORE",
+            &format!(
+                "Skipped {} ignored files and {} ignored folders",
+                ignored_files, ignored_folders
+            ),
+            Some(&notes_dir.display().to_string()),
+        );
+    }
+
     filesystem_files.sort_by(|a, b| b.2.cmp(&a.2));
     Ok(filesystem_files)
 }
 
+/// The note's name relative to the notes directory, or `None` for hidden
+/// files and files without a note extension.
+fn note_filename(notes_dir: &Path, path: &Path, note_extensions: &[String]) -> Option<String> {
+    let relative = path.strip_prefix(notes_dir).unwrap_or(path);
+    let filename = relative.to_string_lossy().to_string();
+
+    if filename.contains("/.") || filename.starts_with('.') {
+        return None;
+    }
+    if !has_note_extension(path, note_extensions) {
+        return None;
+    }
+    Some(filename)
+}
+
+fn file_times(metadata: Option<&fs::Metadata>) -> (i64, u64) {
+    let modified = metadata
+        .and_then(|m| m.modified().ok())
+        .map(|mtime| {
+            mtime
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0)
+        })
+        .unwrap_or(0);
+    (modified, metadata.map(|m| m.len()).unwrap_or(0))
+}
+
+/// Adds the notes behind symlinked files. Links whose target is already
+/// indexed, directly or through another link, are skipped so the same file
+/// never shows up as two notes.
+fn index_symlinked_notes(
+    notes_dir: &Path,
+    symlinks: &[PathBuf],
+    note_extensions: &[String],
+    filesystem_files: &mut Vec<(String, PathBuf, i64, u64)>,
+) {
+    let mut visited: HashSet<PathBuf> = filesystem_files
+        .iter()
+        .filter_map(|(_, path, _, _)| fs::canonicalize(path).ok())
+        .collect();
+    let mut skipped_directories = 0;
+
+    for link in symlinks {
+        let Some(filename) = note_filename(notes_dir, link, note_extensions) else {
+            continue;
+        };
+
+        match resolve_symlink(link) {
+            SymlinkTarget::File(metadata) => {
+                let Ok(target) = fs::canonicalize(link) else {
+                    continue;
+                };
+                if !visited.insert(target.clone()) {
+                    log(
+                        "NOTE_INDEXING",
+                        "Skipping symlink to a note that is already indexed",
+                        Some(&format!("link: {}, target: {}", filename, target.display())),
+                    );
+                    continue;
+                }
+                let (modified, size) = file_times(Some(&metadata));
+                filesystem_files.push((filename, link.clone(), modified, size));
+            }
+            SymlinkTarget::Directory => skipped_directories += 1,
+            SymlinkTarget::Loop => log(
+                "NOTE_INDEXING",
+                "Skipping symlink loop",
+                Some(&link.display().to_string()),
+            ),
+            SymlinkTarget::Broken => log(
+                "NOTE_INDEXING",
+                "Skipping broken symlink",
+                Some(&link.display().to_string()),
+            ),
+        }
+    }
+
+    if skipped_directories > 0 {
+        log(
+            "NOTE_INDEXING",
+            &format!(
+                "Skipped {} symlinked folders, which are never followed",
+                skipped_directories
+            ),
+            Some(&notes_dir.display().to_string()),
+        );
+    }
+}
+
 fn load_existing_database_files(
     conn: &Connection,
 ) -> rusqlite::Result<HashMap<String, (i64, bool)>> {
@@ -137,70 +422,282 @@ fn load_existing_database_files(
     Ok(database_files)
 }
 
+fn load_file_sizes(conn: &Connection) -> rusqlite::Result<HashMap<String, u64>> {
+    let mut stmt = conn.prepare("SELECT filename, size FROM note_file_sizes")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+    })?;
+    rows.collect()
+}
+
 fn sync_database_with_filesystem(
     conn: &mut Connection,
-    filesystem_files: &[(String, PathBuf, i64)],
-    database_files: &HashMap<String, (i64, bool)>,
+    filesystem_files: &[(String, PathBuf, i64, u64)],
+    indexed: &IndexedFiles,
+    force: bool,
+    render_options: &RenderOptions,
+    index_threads: usize,
     app_handle: Option<&AppHandle>,
-) -> rusqlite::Result<()> {
+) -> rusqlite::Result<usize> {
     let tx = conn.transaction()?;
 
-    remove_deleted_files_from_database(&tx, filesystem_files, database_files)?;
-    process_filesystem_files(&tx, filesystem_files, database_files, app_handle)?;
+    remove_deleted_files_from_database(&tx, filesystem_files, &indexed.notes)?;
+    let files_read = process_filesystem_files(
+        &tx,
+        filesystem_files,
+        indexed,
+        force,
+        render_options,
+        index_threads,
+        app_handle,
+    )?;
+
+    // Swept after the notes are back in, so note IDs, pins, recents and
+    // creation times survive a rebuild
+    for table in ["note_ids", "note_created_at"] {
+        tx.execute(
+            &format!(
+                "DELETE FROM {} WHERE filename NOT IN (SELECT filename FROM notes)",
+                table
+            ),
+            [],
+        )?;
+    }
+    for table in ["pinned_notes", "recent_notes"] {
+        tx.execute(
+            &format!(
+                "DELETE FROM {} WHERE note_id NOT IN (SELECT id FROM note_ids)",
+                table
+            ),
+            [],
+        )?;
+    }
 
-    tx.commit()
+    tx.commit()?;
+    Ok(files_read)
 }
 
 fn remove_deleted_files_from_database(
     tx: &rusqlite::Transaction,
-    filesystem_files: &[(String, PathBuf, i64)],
+    filesystem_files: &[(String, PathBuf, i64, u64)],
     database_files: &HashMap<String, (i64, bool)>,
 ) -> rusqlite::Result<()> {
-    let filesystem_filenames: HashSet<_> =
-        filesystem_files.iter().map(|(name, _, _)| name).collect();
+    let filesystem_filenames: HashSet<_> = filesystem_files
+        .iter()
+        .map(|(name, _, _, _)| name)
+        .collect();
 
     for filename in database_files.keys() {
         if !filesystem_filenames.contains(filename) {
             tx.execute("DELETE FROM notes WHERE filename = ?1", params![filename])?;
+            delete_note_tags(tx, filename)?;
+            delete_note_hash(tx, filename)?;
         }
     }
 
+    // Sizes can outlive notes removed through other paths (rename, delete, watcher)
+    tx.execute(
+        "DELETE FROM note_file_sizes WHERE filename NOT IN (SELECT filename FROM notes)",
+        [],
+    )?;
+    tx.execute(
+        "DELETE FROM note_hashes WHERE filename NOT IN (SELECT filename FROM notes)",
+        [],
+    )?;
+
     Ok(())
 }
 
 fn process_filesystem_files(
     tx: &rusqlite::Transaction,
-    filesystem_files: &[(String, PathBuf, i64)],
-    database_files: &HashMap<String, (i64, bool)>,
+    filesystem_files: &[(String, PathBuf, i64, u64)],
+    indexed: &IndexedFiles,
+    force: bool,
+    render_options: &RenderOptions,
+    index_threads: usize,
     app_handle: Option<&AppHandle>,
-) -> rusqlite::Result<()> {
-    let total_files = filesystem_files.len();
+) -> rusqlite::Result<usize> {
+    let prepared = read_filesystem_files(
+        filesystem_files,
+        indexed,
+        force,
+        render_options,
+        index_threads,
+        app_handle,
+    );
+
+    let mut files_read = 0;
 
-    for (index, (filename, path, fs_modified)) in filesystem_files.iter().enumerate() {
-        emit_progress_if_needed(app_handle, index, total_files)?;
+    for ((filename, path, fs_modified, fs_size), prepared) in filesystem_files.iter().zip(prepared)
+    {
+        match prepared.read {
+            FileRead::Reindexed(note) => {
+                if let Some(note) = note {
+                    store_read_note(tx, filename, &note, *fs_modified)?;
+                    track_note_id(tx, filename, path, &note.hash)?;
+                }
+                files_read += 1;
+            }
+            read => {
+                if prepared.touched {
+                    // Only the timestamp moved, e.g. a sync client touched the file
+                    tx.execute(
+                        "UPDATE notes SET modified = ?2 WHERE filename = ?1",
+                        params![filename, fs_modified],
+                    )?;
+                }
+                if let FileRead::Rendered(html_render) = read {
+                    if let Some(html_render) = html_render {
+                        tx.execute(
+                            "UPDATE notes SET html_render = ?2, is_indexed = ?3 WHERE filename = ?1",
+                            params![filename, html_render, true],
+                        )?;
+                    }
+                    files_read += 1;
+                }
+            }
+        }
 
-        let (db_modified, is_indexed) = database_files.get(filename).copied().unwrap_or((0, false));
+        if !indexed.created.contains_key(filename) {
+            record_note_created(tx, filename, path, *fs_modified)?;
+        }
 
-        if *fs_modified != db_modified {
-            process_modified_file(tx, filename, path, *fs_modified, index)?;
-        } else if !is_indexed && index < IMMEDIATE_RENDER_COUNT {
-            update_unindexed_file(tx, filename, path)?;
+        if indexed.sizes.get(filename) != Some(fs_size) {
+            tx.execute(
+                "INSERT OR REPLACE INTO note_file_sizes (filename, size) VALUES (?1, ?2)",
+                params![filename, *fs_size as i64],
+            )?;
         }
     }
 
-    Ok(())
+    Ok(files_read)
+}
+
+/// What reading a file produced, ready to be written to the index.
+struct PreparedFile {
+    read: FileRead,
+    /// The modified time changed but the content did not.
+    touched: bool,
+}
+
+enum FileRead {
+    /// Changed or forced; `None` when the file could not be read.
+    Reindexed(Option<ReadNote>),
+    /// Unchanged but still waiting for its first render.
+    Rendered(Option<String>),
+    Unchanged,
 }
 
-fn emit_progress_if_needed(
+struct ReadNote {
+    content: String,
+    html_render: Option<String>,
+    tags: Vec<String>,
+    tag_labels: Vec<String>,
+    hash: String,
+}
+
+/// Reads, parses and hashes the files on a pool of `index_threads` threads, 0
+/// meaning one per core. Results come back in the order of `filesystem_files`,
+/// so the writes that follow do not depend on how the reads were scheduled.
+fn read_filesystem_files(
+    filesystem_files: &[(String, PathBuf, i64, u64)],
+    indexed: &IndexedFiles,
+    force: bool,
+    render_options: &RenderOptions,
+    index_threads: usize,
     app_handle: Option<&AppHandle>,
+) -> Vec<PreparedFile> {
+    // Counts files as the workers finish them, so progress moves while reading
+    let files_done = AtomicUsize::new(0);
+    let read_all = || {
+        filesystem_files
+            .par_iter()
+            .enumerate()
+            .map(|(index, file)| {
+                let prepared = prepare_file(index, file, indexed, force, render_options);
+                let done = files_done.fetch_add(1, Ordering::Relaxed);
+                emit_progress_if_needed(app_handle, done, filesystem_files.len());
+                prepared
+            })
+            .collect()
+    };
+
+    match ThreadPoolBuilder::new().num_threads(index_threads).build() {
+        Ok(pool) => pool.install(read_all),
+        Err(e) => {
+            log_warn(
+                "NOTE_INDEXING",
+                "Failed to start indexing threads, using the shared pool",
+                Some(&e.to_string()),
+            );
+            read_all()
+        }
+    }
+}
+
+fn prepare_file(
     index: usize,
-    total_files: usize,
-) -> rusqlite::Result<()> {
+    (filename, path, fs_modified, fs_size): &(String, PathBuf, i64, u64),
+    indexed: &IndexedFiles,
+    force: bool,
+    render_options: &RenderOptions,
+) -> PreparedFile {
+    let (fs_modified, fs_size) = (*fs_modified, *fs_size);
+    let (db_modified, is_indexed) = indexed.notes.get(filename).copied().unwrap_or((0, false));
+    let stored_size = indexed.sizes.get(filename).copied();
+    // A missing size means the note predates size tracking, so the mtime alone decides
+    let size_changed = stored_size.is_some_and(|size| size != fs_size);
+    let metadata_changed = fs_modified != db_modified || size_changed;
+
+    if force || (metadata_changed && !matches_indexed_hash(path, indexed.hashes.get(filename))) {
+        let note = fs::read_to_string(path).ok().map(|content| {
+            let html_render = (index < IMMEDIATE_RENDER_COUNT)
+                .then(|| render_note(filename, &content, render_options));
+            let frontmatter = parse_frontmatter(filename, &content);
+            ReadNote {
+                tags: frontmatter.tags,
+                tag_labels: frontmatter.tag_labels,
+                hash: content_hash(content.as_bytes()),
+                html_render,
+                content,
+            }
+        });
+        return PreparedFile {
+            read: FileRead::Reindexed(note),
+            touched: false,
+        };
+    }
+
+    let read = if !is_indexed && index < IMMEDIATE_RENDER_COUNT {
+        FileRead::Rendered(
+            fs::read_to_string(path)
+                .ok()
+                .map(|content| render_note(filename, &content, render_options)),
+        )
+    } else {
+        FileRead::Unchanged
+    };
+    PreparedFile {
+        read,
+        touched: metadata_changed,
+    }
+}
+
+fn matches_indexed_hash(path: &Path, indexed_hash: Option<&String>) -> bool {
+    let Some(indexed_hash) = indexed_hash else {
+        return false;
+    };
+    fs::read(path)
+        .map(|bytes| content_hash(&bytes) == *indexed_hash)
+        .unwrap_or(false)
+}
+
+fn emit_progress_if_needed(app_handle: Option<&AppHandle>, index: usize, total_files: usize) {
     if let Some(app) = app_handle {
         if index == 0 || (index + 1) % 10 == 0 || index == total_files - 1 {
             let progress_msg = format!("Loading {} of {} notes...", index + 1, total_files);
             if let Err(e) = app.emit("db-loading-progress", progress_msg) {
-                log(
+                log_warn(
                     "UI_UPDATE",
                     "Failed to emit db-loading-progress event",
                     Some(&e.to_string()),
@@ -208,61 +705,37 @@ fn emit_progress_if_needed(
             }
         }
     }
-    Ok(())
 }
 
-fn process_modified_file(
+fn store_read_note(
     tx: &rusqlite::Transaction,
     filename: &str,
-    path: &PathBuf,
+    note: &ReadNote,
     fs_modified: i64,
-    index: usize,
-) -> rusqlite::Result<()> {
-    let Ok(content) = fs::read_to_string(path) else {
-        return Ok(());
-    };
-
-    if index < IMMEDIATE_RENDER_COUNT {
-        let html_render = crate::utilities::note_renderer::render_note(filename, &content);
-        tx.execute(
-            "INSERT OR REPLACE INTO notes (filename, content, html_render, modified, is_indexed) VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![filename, content, html_render, fs_modified, true],
-        )?;
-    } else {
-        tx.execute(
-            "INSERT OR REPLACE INTO notes (filename, content, html_render, modified, is_indexed) VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![filename, content, "", fs_modified, false],
-        )?;
-    }
-
-    Ok(())
-}
-
-fn update_unindexed_file(
-    tx: &rusqlite::Transaction,
-    filename: &str,
-    path: &PathBuf,
 ) -> rusqlite::Result<()> {
-    let Ok(content) = fs::read_to_string(path) else {
-        return Ok(());
-    };
-    let html_render = crate::utilities::note_renderer::render_note(filename, &content);
     tx.execute(
-        "UPDATE notes SET html_render = ?2, is_indexed = ?3 WHERE filename = ?1",
-        params![filename, html_render, true],
+        "INSERT OR REPLACE INTO notes (filename, content, html_render, modified, is_indexed) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            filename,
+            note.content,
+            note.html_render.as_deref().unwrap_or(""),
+            fs_modified,
+            note.html_render.is_some()
+        ],
     )?;
+
+    store_note_tags(tx, filename, &note.tags, &note.tag_labels)?;
+    store_content_hash(tx, filename, &note.hash)?;
+
     Ok(())
 }
 
 pub fn load_all_notes_into_sqlite_with_progress(
-    _app_state: &AppState,
+    app_state: &AppState,
     conn: &mut Connection,
     app_handle: Option<&AppHandle>,
 ) -> rusqlite::Result<()> {
-    ensure_notes_directory_exists()?;
-    let filesystem_files = scan_filesystem_for_notes()?;
-    let database_files = load_existing_database_files(conn)?;
-    sync_database_with_filesystem(conn, &filesystem_files, &database_files, app_handle)
+    sync_notes_with_filesystem(app_state, conn, false, app_handle).map(|_| ())
 }
 
 pub fn recreate_database(app_state: &AppState) -> AppResult<()> {
@@ -284,6 +757,10 @@ pub fn recreate_database(app_state: &AppState) -> AppResult<()> {
 
     manager.with_connection_mut(|conn| {
         conn.execute("DROP TABLE IF EXISTS notes", [])?;
+        conn.execute("DROP TABLE IF EXISTS note_tags", [])?;
+        conn.execute("DROP TABLE IF EXISTS note_file_sizes", [])?;
+        conn.execute("DROP TABLE IF EXISTS note_hashes", [])?;
+        conn.execute("DROP TABLE IF EXISTS render_cache", [])?;
 
         init_db(conn)?;
 
@@ -314,7 +791,7 @@ pub async fn recreate_database_with_progress(
     );
 
     if let Err(e) = app_handle.emit("db-loading-progress", "Rebuilding notes database...") {
-        log(
+        log_warn(
             "UI_UPDATE",
             "Failed to emit rebuild progress",
             Some(&e.to_string()),
@@ -330,11 +807,15 @@ pub async fn recreate_database_with_progress(
 
         manager.with_connection_mut(|conn| {
             conn.execute("DROP TABLE IF EXISTS notes", [])?;
+            conn.execute("DROP TABLE IF EXISTS note_tags", [])?;
+            conn.execute("DROP TABLE IF EXISTS note_file_sizes", [])?;
+            conn.execute("DROP TABLE IF EXISTS note_hashes", [])?;
+            conn.execute("DROP TABLE IF EXISTS render_cache", [])?;
 
             init_db(conn)?;
 
             if let Err(e) = app_handle.emit("db-loading-progress", "Rendering notes...") {
-                log(
+                log_warn(
                     "UI_UPDATE",
                     "Failed to emit rendering progress",
                     Some(&e.to_string()),
@@ -356,7 +837,7 @@ pub async fn recreate_database_with_progress(
             );
         }
         Err(ref e) => {
-            log(
+            log_error(
                 "DATABASE_REBUILD_FAILURE",
                 "Database rebuild failed - database operations resumed but may be inconsistent",
                 Some(&e.to_string()),
@@ -365,7 +846,7 @@ pub async fn recreate_database_with_progress(
     }
 
     if let Err(e) = app_handle.emit("db-loading-progress", "Notes database ready.") {
-        log(
+        log_warn(
             "UI_UPDATE",
             "Failed to emit completion progress",
             Some(&e.to_string()),
@@ -382,10 +863,22 @@ pub fn quick_filesystem_sync_check(app_state: &AppState) -> AppResult<bool> {
         return Ok(true);
     }
 
+    let note_extensions = get_note_extensions(app_state);
+    let max_depth = get_max_recursion_depth(app_state);
+    let note_ignore = NoteIgnore::load(&notes_dir);
+
     with_db(app_state, |conn| {
         let mut files: Vec<_> = WalkDir::new(&notes_dir)
             .follow_links(false)
+            .max_depth(max_depth)
             .into_iter()
+            .filter_entry(|entry| {
+                let relative = entry
+                    .path()
+                    .strip_prefix(&notes_dir)
+                    .unwrap_or(entry.path());
+                !note_ignore.is_ignored(relative, entry.file_type().is_dir())
+            })
             .filter_map(|e| e.ok())
             .filter(|e| e.file_type().is_file())
             .filter(|e| {
@@ -398,7 +891,7 @@ pub fn quick_filesystem_sync_check(app_state: &AppState) -> AppResult<bool> {
                     return false;
                 }
 
-                is_note_file(path)
+                has_note_extension(path, &note_extensions)
             })
             .collect();
 
@@ -419,7 +912,7 @@ pub fn quick_filesystem_sync_check(app_state: &AppState) -> AppResult<bool> {
             let file_content = match std::fs::read_to_string(file_path) {
                 Ok(content) => content,
                 Err(_) => {
-                    log(
+                    log_warn(
                         "FILE_SYNC_CHECK",
                         &format!(
                             "Warning: Could not read file {} during sync check",
@@ -465,7 +958,7 @@ pub fn quick_filesystem_sync_check(app_state: &AppState) -> AppResult<bool> {
 }
 
 fn log_fatal_database_error(category: &str, operation: &str, error: &AppError) {
-    log(
+    log_error(
         category,
         &format!(
             "💥 FATAL: {}. Application will continue with limited functionality.",
@@ -487,9 +980,19 @@ fn is_new_database() -> bool {
 fn cleanup_database_if_no_config(app_state: &AppState) -> () {
     if !crate::utilities::paths::get_config_path().exists() {
         if let Err(e) = with_db(app_state, |conn| {
-            conn.execute("DELETE FROM notes", []).map_err(|e| e.into())
+            conn.execute("DELETE FROM notes", [])?;
+            conn.execute("DELETE FROM note_tags", [])?;
+            conn.execute("DELETE FROM tag_labels", [])?;
+            conn.execute("DELETE FROM note_file_sizes", [])?;
+            conn.execute("DELETE FROM note_hashes", [])?;
+            conn.execute("DELETE FROM render_cache", [])?;
+            conn.execute("DELETE FROM pinned_notes", [])?;
+            conn.execute("DELETE FROM recent_notes", [])?;
+            conn.execute("DELETE FROM note_created_at", [])?;
+            conn.execute("DELETE FROM note_ids", [])?;
+            Ok(())
         }) {
-            log(
+            log_warn(
                 "DATABASE_CLEANUP",
                 "Failed to purge database. Continuing anyway.",
                 Some(&e.to_string()),
@@ -518,7 +1021,7 @@ fn validate_and_sync_filesystem(app_state: &AppState) -> AppResult<()> {
             }
         }
         Err(e) => {
-            log(
+            log_warn(
                 "DATABASE_SYNC",
                 "⚠️  Filesystem sync check failed. Continuing without rebuild.",
                 Some(&e.to_string()),
@@ -537,7 +1040,7 @@ fn handle_database_initialization_failure(
     if is_new_db {
         log("DATABASE_INIT", "🔧 Creating new database...", None);
     } else {
-        log(
+        log_error(
             "DATABASE_INIT",
             "❌ CRITICAL: Database initialization failed",
             Some(&e.to_string()),
@@ -575,14 +1078,20 @@ fn handle_database_initialization_failure(
 }
 
 fn initialize_database_schema(app_state: &AppState) -> AppResult<()> {
-    with_db(app_state, |conn| init_db(conn).map_err(|e| e.into()))
+    // A dropped index leaves the table empty, which the sync check then rebuilds
+    let stemming = get_search_stemming(app_state);
+    with_db(app_state, |conn| {
+        init_db(conn)?;
+        ensure_search_tokenizer(conn, stemming)?;
+        Ok(())
+    })
 }
 
 fn prepare_database_environment() -> () {
     if let Ok(db_path) = crate::utilities::paths::get_database_path() {
         if let Some(parent) = db_path.parent() {
             if let Err(e) = std::fs::create_dir_all(parent) {
-                log(
+                log_warn(
                     "INIT_ERROR",
                     &format!("Failed to create database directory: {:?}", parent),
                     Some(&e.to_string()),
@@ -592,7 +1101,7 @@ fn prepare_database_environment() -> () {
     }
 
     if let Err(e) = crate::utilities::file_safety::cleanup_temp_files() {
-        log(
+        log_warn(
             "INIT_CLEANUP",
             "Failed to clean up temp files during initialization",
             Some(&e.to_string()),
@@ -623,7 +1132,7 @@ pub fn handle_database_recovery(
     success_message: &str,
     failure_message: &str,
 ) -> AppResult<()> {
-    log(
+    log_warn(
         "DATABASE_RECOVERY",
         &format!(
             "Database operation failed for {}: {}. Rebuilding database...",
@@ -642,7 +1151,7 @@ pub fn handle_database_recovery(
             Ok(())
         }
         Err(rebuild_error) => {
-            log(
+            log_error(
                 "DATABASE_RECOVERY",
                 failure_message,
                 Some(&rebuild_error.to_string()),
@@ -0,0 +1,86 @@
+use rusqlite::{params, Connection};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Hex encoded SHA-256 of `bytes`.
+pub fn content_hash(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+pub fn init_hashes_table(conn: &Connection) -> rusqlite::Result<()> {
+    let exists: bool = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='note_hashes'",
+        [],
+        |row| row.get::<_, i64>(0).map(|count| count > 0),
+    )?;
+
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS note_hashes (
+            filename TEXT PRIMARY KEY,
+            hash TEXT NOT NULL
+        );",
+    )?;
+
+    // Notes indexed before hashes existed are not re-read from disk, so hash
+    // their stored content, which is what was read from the file
+    if !exists {
+        backfill_hashes(conn)?;
+    }
+
+    Ok(())
+}
+
+/// Records the hash of the content just written to the index for `filename`.
+pub fn store_note_hash(conn: &Connection, filename: &str, content: &str) -> rusqlite::Result<()> {
+    store_content_hash(conn, filename, &content_hash(content.as_bytes()))
+}
+
+/// Stores a hash computed ahead of time, e.g. while notes are read in parallel.
+pub fn store_content_hash(conn: &Connection, filename: &str, hash: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO note_hashes (filename, hash) VALUES (?1, ?2)",
+        params![filename, hash],
+    )?;
+    Ok(())
+}
+
+pub fn delete_note_hash(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM note_hashes WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
+
+pub fn rename_note_hash(conn: &Connection, old_name: &str, new_name: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "UPDATE note_hashes SET filename = ?1 WHERE filename = ?2",
+        params![new_name, old_name],
+    )?;
+    Ok(())
+}
+
+pub fn load_note_hashes(conn: &Connection) -> rusqlite::Result<HashMap<String, String>> {
+    let mut stmt = conn.prepare("SELECT filename, hash FROM note_hashes")?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect()
+}
+
+fn backfill_hashes(conn: &Connection) -> rusqlite::Result<()> {
+    let notes = {
+        let mut stmt = conn.prepare("SELECT filename, content FROM notes")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+
+    for (filename, content) in notes {
+        store_note_hash(conn, &filename, &content)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,220 @@
+use crate::{
+    core::{AppError, AppResult},
+    services::hash_service::content_hash,
+};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Bumped whenever a field of the snapshot changes meaning or is removed.
+pub const INDEX_SCHEMA_VERSION: u32 = 1;
+
+/// A portable copy of the note index, for backup tooling and debugging the cache.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexSnapshot {
+    pub schema_version: u32,
+    pub exported_at: i64,
+    pub notes: Vec<IndexEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    pub name: String,
+    /// Path relative to the notes directory, which is how notes are identified.
+    pub path: String,
+    pub size: u64,
+    pub modified: i64,
+    pub tags: Vec<String>,
+    pub hash: String,
+}
+
+/// How the current index differs from a snapshot, each list sorted by path.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct IndexDrift {
+    /// In the snapshot but no longer indexed.
+    pub missing: Vec<String>,
+    /// Indexed but not in the snapshot.
+    pub added: Vec<String>,
+    /// In both, with different content.
+    pub changed: Vec<String>,
+}
+
+/// What the last sync from disk found outside the index, kept so stats can be
+/// read without walking the filesystem again.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct IndexRefresh {
+    pub refreshed_at: i64,
+    pub archived_notes: usize,
+    pub trashed_notes: usize,
+}
+
+/// Overview numbers for the note index. Counts of archived and trashed notes and
+/// the refresh time are `None` until notes have been synced once this session.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IndexStats {
+    pub total_notes: usize,
+    /// Bytes of note content as last indexed, not the notes' current size on disk.
+    pub content_bytes: u64,
+    pub tag_count: usize,
+    pub archived_notes: Option<usize>,
+    pub trashed_notes: Option<usize>,
+    pub last_refreshed_at: Option<i64>,
+    pub schema_version: u32,
+}
+
+pub fn collect_index_stats(
+    conn: &Connection,
+    refresh: Option<IndexRefresh>,
+) -> rusqlite::Result<IndexStats> {
+    let (total_notes, content_bytes): (i64, i64) = conn.query_row(
+        "SELECT COUNT(*), COALESCE(SUM(LENGTH(CAST(content AS BLOB))), 0) FROM notes",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let tag_count: i64 =
+        conn.query_row("SELECT COUNT(DISTINCT tag) FROM note_tags", [], |row| {
+            row.get(0)
+        })?;
+
+    Ok(IndexStats {
+        total_notes: total_notes as usize,
+        content_bytes: content_bytes as u64,
+        tag_count: tag_count as usize,
+        archived_notes: refresh.map(|refresh| refresh.archived_notes),
+        trashed_notes: refresh.map(|refresh| refresh.trashed_notes),
+        last_refreshed_at: refresh.map(|refresh| refresh.refreshed_at),
+        schema_version: INDEX_SCHEMA_VERSION,
+    })
+}
+
+/// Size on disk of the cache database, counting its write-ahead log, around a
+/// `compact_database` run.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct CacheCompaction {
+    pub before_bytes: u64,
+    pub after_bytes: u64,
+}
+
+impl CacheCompaction {
+    pub fn reclaimed_bytes(&self) -> u64 {
+        self.before_bytes.saturating_sub(self.after_bytes)
+    }
+}
+
+/// Merges the full-text index and rewrites the database without the free pages
+/// that deleted notes leave behind. Must not run inside a transaction.
+pub fn compact_database(conn: &Connection) -> rusqlite::Result<CacheCompaction> {
+    let before_bytes = database_file_size(conn);
+    conn.execute("INSERT INTO notes(notes) VALUES('optimize')", [])?;
+    conn.execute_batch("VACUUM;")?;
+    // In WAL mode the rewritten pages sit in the log until it is checkpointed
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+    Ok(CacheCompaction {
+        before_bytes,
+        after_bytes: database_file_size(conn),
+    })
+}
+
+fn database_file_size(conn: &Connection) -> u64 {
+    let Some(path) = conn.path().filter(|path| !path.is_empty()) else {
+        return 0;
+    };
+    ["", "-wal"]
+        .iter()
+        .filter_map(|suffix| fs::metadata(format!("{}{}", path, suffix)).ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Reads every indexed note, sorted by path. Sizes and hashes describe the
+/// content as it was last indexed.
+pub fn collect_index_entries(conn: &Connection) -> rusqlite::Result<Vec<IndexEntry>> {
+    let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+    {
+        let mut stmt = conn.prepare("SELECT filename, tag FROM note_tags ORDER BY tag")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        for row in rows {
+            let (filename, tag) = row?;
+            tags.entry(filename).or_default().push(tag);
+        }
+    }
+
+    let mut hashes: HashMap<String, String> = {
+        let mut stmt = conn.prepare("SELECT filename, hash FROM note_hashes")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<_, _>>()?
+    };
+
+    let mut stmt =
+        conn.prepare("SELECT filename, content, modified FROM notes ORDER BY filename")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i64>(2)?,
+        ))
+    })?;
+
+    rows.map(|row| {
+        let (path, content, modified) = row?;
+        let name = Path::new(&path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.clone());
+        Ok(IndexEntry {
+            name,
+            size: content.len() as u64,
+            modified,
+            tags: tags.remove(&path).unwrap_or_default(),
+            hash: hashes
+                .remove(&path)
+                .unwrap_or_else(|| content_hash(content.as_bytes())),
+            path,
+        })
+    })
+    .collect()
+}
+
+pub fn parse_index_snapshot(json: &str) -> AppResult<IndexSnapshot> {
+    let snapshot: IndexSnapshot = serde_json::from_str(json)
+        .map_err(|e| AppError::Export(format!("Invalid index snapshot: {}", e)))?;
+
+    if snapshot.schema_version == 0 || snapshot.schema_version > INDEX_SCHEMA_VERSION {
+        return Err(AppError::Export(format!(
+            "Unsupported index snapshot schema version {} (expected at most {})",
+            snapshot.schema_version, INDEX_SCHEMA_VERSION
+        )));
+    }
+    Ok(snapshot)
+}
+
+/// Compares by path and content hash; modified times alone do not count as drift.
+pub fn compare_index(snapshot: &[IndexEntry], current: &[IndexEntry]) -> IndexDrift {
+    let snapshot: BTreeMap<&str, &str> = snapshot
+        .iter()
+        .map(|entry| (entry.path.as_str(), entry.hash.as_str()))
+        .collect();
+    let current: BTreeMap<&str, &str> = current
+        .iter()
+        .map(|entry| (entry.path.as_str(), entry.hash.as_str()))
+        .collect();
+
+    let mut drift = IndexDrift::default();
+    for (path, hash) in &snapshot {
+        match current.get(path) {
+            None => drift.missing.push(path.to_string()),
+            Some(current_hash) if current_hash != hash => drift.changed.push(path.to_string()),
+            Some(_) => {}
+        }
+    }
+    drift.added = current
+        .keys()
+        .filter(|path| !snapshot.contains_key(*path))
+        .map(|path| path.to_string())
+        .collect();
+    drift
+}
@@ -0,0 +1,142 @@
+use crate::core::{AppError, AppResult};
+use crate::services::note_id_service::{note_for_id, note_id};
+use crate::utilities::{
+    note_crypto::is_encrypted_note,
+    note_links::{LinkRename, NoteLink, NoteLinkIndex},
+    note_renderer::{extract_note_links, is_markdown_note, relink_note_content, wiki_link_targets},
+    strings::extract_title_from_content,
+};
+use rusqlite::{params, Connection};
+
+/// A note whose links to a renamed note have been rewritten.
+#[derive(Debug, Clone)]
+pub struct RelinkedNote {
+    /// Name of the note once the rename is done
+    pub filename: String,
+    pub original: String,
+    pub relinked: String,
+}
+
+fn load_note_filenames(conn: &Connection) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT filename FROM notes")?;
+    let filenames = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(filenames)
+}
+
+/// Every note by filename and by title. Only the start of each note is read,
+/// which is where its title line is.
+pub fn load_note_link_index(conn: &Connection) -> rusqlite::Result<NoteLinkIndex> {
+    let mut stmt = conn.prepare("SELECT filename, substr(content, 1, 1024) FROM notes")?;
+    let notes = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let titles: Vec<(String, String)> = notes
+        .iter()
+        .filter(|(_, start)| !is_encrypted_note(start))
+        .filter_map(|(filename, start)| {
+            extract_title_from_content(start).map(|title| (filename.clone(), title))
+        })
+        .collect();
+    Ok(NoteLinkIndex::new(notes.into_iter().map(|(filename, _)| filename)).with_titles(titles))
+}
+
+/// Notes whose `[[wiki links]]` resolve to `filename`, sorted by name.
+pub fn find_backlinks(conn: &Connection, filename: &str) -> rusqlite::Result<Vec<String>> {
+    let index = load_note_link_index(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT filename, content FROM notes WHERE filename != ?1 AND content LIKE '%[[%'",
+    )?;
+    let rows = stmt.query_map(params![filename], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let mut backlinks = Vec::new();
+    for row in rows {
+        let (source, content) = row?;
+        if !is_markdown_note(&source) {
+            continue;
+        }
+        let links_here = wiki_link_targets(&content)
+            .iter()
+            .any(|target| index.resolve(target) == Some(filename));
+        if links_here {
+            backlinks.push(source);
+        }
+    }
+
+    backlinks.sort();
+    Ok(backlinks)
+}
+
+/// IDs of the notes whose `[[wiki links]]` reach the note with `id`, so callers
+/// can hold on to both ends through renames. Links themselves name notes in
+/// their text, so they are resolved by name and mapped to IDs here.
+pub fn find_backlinks_by_id(conn: &Connection, id: &str) -> AppResult<Vec<String>> {
+    let filename = note_for_id(conn, id)?
+        .ok_or_else(|| AppError::FileNotFound(format!("Note not found for ID: {}", id)))?;
+    let ids = find_backlinks(conn, &filename)?
+        .iter()
+        .map(|source| note_id(conn, source))
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(ids)
+}
+
+/// Outgoing links of a note. Plain text notes are not parsed for links.
+pub fn find_note_links(conn: &Connection, filename: &str) -> AppResult<Vec<NoteLink>> {
+    let content = conn
+        .query_row(
+            "SELECT content FROM notes WHERE filename = ?1",
+            params![filename],
+            |row| row.get::<_, String>(0),
+        )
+        .map_err(|_| AppError::FileNotFound(format!("Note not found: {}", filename)))?;
+
+    if !is_markdown_note(filename) {
+        return Ok(Vec::new());
+    }
+    Ok(extract_note_links(&content, &load_note_link_index(conn)?))
+}
+
+/// Rewritten content for every markdown note that links to `old_filename`,
+/// including the note itself, as if it were renamed to `new_filename`. Nothing
+/// is written; sorted by name.
+pub fn plan_relink(
+    conn: &Connection,
+    old_filename: &str,
+    new_filename: &str,
+) -> rusqlite::Result<Vec<RelinkedNote>> {
+    let filenames = load_note_filenames(conn)?;
+    let rename = LinkRename::new(&filenames, old_filename, new_filename);
+
+    let mut stmt = conn.prepare(
+        "SELECT filename, content FROM notes WHERE content LIKE '%[%' ORDER BY filename",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let mut relinked_notes = Vec::new();
+    for row in rows {
+        let (filename, content) = row?;
+        if !is_markdown_note(&filename) {
+            continue;
+        }
+        if let Some(relinked) = relink_note_content(&content, &rename) {
+            relinked_notes.push(RelinkedNote {
+                filename: if filename == old_filename {
+                    new_filename.to_string()
+                } else {
+                    filename
+                },
+                original: content,
+                relinked,
+            });
+        }
+    }
+    Ok(relinked_notes)
+}
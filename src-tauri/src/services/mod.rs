@@ -1,2 +1,15 @@
+pub mod confirmation_service;
+pub mod created_service;
 pub mod database_service;
+pub mod hash_service;
+pub mod index_service;
+pub mod link_service;
+pub mod note_id_service;
 pub mod note_service;
+pub mod pin_service;
+pub mod recent_service;
+pub mod render_cache_service;
+pub mod replace_service;
+pub mod tag_service;
+pub mod trash_service;
+pub mod version_service;
@@ -0,0 +1,117 @@
+use crate::services::{hash_service::content_hash, pin_service::note_pin_renamed};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+
+pub fn init_note_ids_table(conn: &Connection) -> rusqlite::Result<()> {
+    // Pins and recents refer to notes by these IDs, so like them the table
+    // survives database rebuilds. `hash` is the content last indexed, used to
+    // recognise a note renamed outside the app.
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS note_ids (
+            filename TEXT PRIMARY KEY,
+            id TEXT NOT NULL UNIQUE,
+            hash TEXT
+        );",
+    )
+}
+
+/// The stable ID of `filename`, assigning one the first time it is asked for.
+/// Notes indexed before IDs existed get theirs this way, without being rewritten.
+pub fn note_id(conn: &Connection, filename: &str) -> rusqlite::Result<String> {
+    if let Some(id) = existing_note_id(conn, filename)? {
+        return Ok(id);
+    }
+
+    let id = new_note_id();
+    conn.execute(
+        "INSERT INTO note_ids (filename, id, hash)
+             SELECT ?1, ?2, (SELECT hash FROM note_hashes WHERE filename = ?1)",
+        params![filename, id],
+    )?;
+    Ok(id)
+}
+
+fn existing_note_id(conn: &Connection, filename: &str) -> rusqlite::Result<Option<String>> {
+    conn.query_row(
+        "SELECT id FROM note_ids WHERE filename = ?1",
+        params![filename],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// The indexed note that has `id`, if any.
+pub fn note_for_id(conn: &Connection, id: &str) -> rusqlite::Result<Option<String>> {
+    conn.query_row(
+        "SELECT note_ids.filename FROM note_ids
+             JOIN notes ON notes.filename = note_ids.filename
+             WHERE note_ids.id = ?1",
+        params![id],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// Records the content just indexed for the note at `path`. A note seen for the
+/// first time takes over the ID of a note with the same content that is gone
+/// from disk, so a rename outside the app keeps its pins and recents.
+pub fn track_note_id(
+    conn: &Connection,
+    filename: &str,
+    path: &Path,
+    hash: &str,
+) -> rusqlite::Result<()> {
+    let updated = conn.execute(
+        "UPDATE note_ids SET hash = ?2 WHERE filename = ?1",
+        params![filename, hash],
+    )?;
+    if updated > 0 {
+        return Ok(());
+    }
+
+    // Empty notes all look alike, so they cannot be told apart after a rename
+    if hash != content_hash(b"") {
+        let notes_dir = path
+            .ancestors()
+            .nth(Path::new(filename).components().count());
+        let mut stmt = conn.prepare("SELECT filename FROM note_ids WHERE hash = ?1")?;
+        let candidates = stmt
+            .query_map(params![hash], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        let renamed_from = notes_dir.and_then(|dir| {
+            candidates
+                .into_iter()
+                .find(|candidate| !dir.join(candidate).exists())
+        });
+        if let Some(old_name) = renamed_from {
+            rename_note_id(conn, &old_name, filename)?;
+            return note_pin_renamed(conn, filename);
+        }
+    }
+
+    conn.execute(
+        "INSERT INTO note_ids (filename, id, hash) VALUES (?1, ?2, ?3)",
+        params![filename, new_note_id(), hash],
+    )?;
+    Ok(())
+}
+
+pub fn delete_note_id(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM note_ids WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
+
+pub fn rename_note_id(conn: &Connection, old_name: &str, new_name: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "UPDATE note_ids SET filename = ?1 WHERE filename = ?2",
+        params![new_name, old_name],
+    )?;
+    Ok(())
+}
+
+fn new_note_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
@@ -1,19 +1,45 @@
 use crate::{
     core::{AppError, AppResult},
-    database::with_db,
-    logging::log,
-    utilities::note_renderer::render_note,
+    database::{with_db, with_db_mut},
+    logging::{log, log_error},
+    services::{
+        created_service::{delete_note_created, record_note_created},
+        hash_service::{content_hash, delete_note_hash, store_note_hash},
+        note_id_service::{delete_note_id, track_note_id},
+        pin_service::delete_note_pin,
+        recent_service::delete_note_recent,
+        tag_service::{delete_note_tags, sync_note_tags},
+    },
+    utilities::note_renderer::{render_note, RenderOptions},
 };
 use rusqlite::params;
 
+pub fn render_options(app_state: &crate::core::state::AppState) -> RenderOptions {
+    RenderOptions::from_config(&app_state.config.read().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Marks every cached render as stale so notes are re-rendered the next time
+/// they are opened, e.g. after a rendering preference changes.
+pub fn invalidate_rendered_notes(app_state: &crate::core::state::AppState) -> AppResult<()> {
+    with_db(app_state, |conn| {
+        conn.execute("UPDATE notes SET is_indexed = 0", [])?;
+        Ok(())
+    })
+}
+
 pub fn update_note_in_database(
     app_state: &crate::core::state::AppState,
     note_name: &str,
     content: &str,
     modified: i64,
 ) -> AppResult<()> {
+    let render_options = render_options(app_state);
+    let note_path = {
+        let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+        std::path::PathBuf::from(&config.notes_directory).join(note_name)
+    };
     with_db(app_state, |conn| {
-        let html_render = render_note(note_name, content);
+        let html_render = render_note(note_name, content, &render_options);
 
         // First try to update existing note
         let updated_rows = conn
@@ -30,6 +56,16 @@ pub fn update_note_in_database(
             )?;
         }
 
+        sync_note_tags(conn, note_name, content)?;
+        store_note_hash(conn, note_name, content)?;
+        track_note_id(
+            conn,
+            note_name,
+            &note_path,
+            &content_hash(content.as_bytes()),
+        )?;
+        record_note_created(conn, note_name, &note_path, modified)?;
+
         // Verify database was updated correctly
         let db_content = conn
             .query_row(
@@ -48,7 +84,7 @@ pub fn update_note_in_database(
                 content.len(),
                 db_content.len()
             );
-            log(
+            log_error(
                 "DATABASE_VERIFICATION",
                 "Database update verification failed",
                 Some(&error_msg),
@@ -70,3 +106,34 @@ pub fn update_note_in_database(
         Ok(())
     })
 }
+
+/// Removes several notes and their tags in a single transaction, for batch operations.
+pub fn remove_notes_from_database(
+    app_state: &crate::core::state::AppState,
+    note_names: &[String],
+) -> AppResult<()> {
+    if note_names.is_empty() {
+        return Ok(());
+    }
+
+    with_db_mut(app_state, |conn| {
+        let tx = conn.transaction()?;
+        for note_name in note_names {
+            tx.execute("DELETE FROM notes WHERE filename = ?1", params![note_name])?;
+            delete_note_tags(&tx, note_name)?;
+            delete_note_hash(&tx, note_name)?;
+            delete_note_pin(&tx, note_name)?;
+            delete_note_recent(&tx, note_name)?;
+            delete_note_id(&tx, note_name)?;
+            delete_note_created(&tx, note_name)?;
+        }
+        tx.commit()?;
+
+        log(
+            "DATABASE_OPERATION",
+            &format!("BATCH DELETE: {} notes | SUCCESS", note_names.len()),
+            None,
+        );
+        Ok(())
+    })
+}
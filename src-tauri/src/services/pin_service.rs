@@ -0,0 +1,120 @@
+use crate::services::note_id_service::note_id;
+use rusqlite::{params, Connection};
+use std::sync::OnceLock;
+
+// Set by whatever shows the pins, so far only the tray menu
+static PINS_CHANGED_LISTENER: OnceLock<fn(&Connection)> = OnceLock::new();
+
+/// Registers `listener` to be called with the connection after pins change.
+/// Only the first registration takes effect.
+pub fn on_pins_changed(listener: fn(&Connection)) -> bool {
+    PINS_CHANGED_LISTENER.set(listener).is_ok()
+}
+
+pub fn init_pins_table(conn: &Connection) -> rusqlite::Result<()> {
+    // Pins are user data that cannot be recovered from the notes directory,
+    // so unlike the other index tables this one survives database rebuilds.
+    // They refer to notes by ID, so a pin follows its note through renames.
+    if has_column(conn, "pinned_notes", "filename")? {
+        return migrate_pins_to_note_ids(conn);
+    }
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS pinned_notes (
+            note_id TEXT PRIMARY KEY,
+            position INTEGER NOT NULL
+        );",
+    )
+}
+
+/// Whether `table` exists with a column named `column`.
+pub fn has_column(conn: &Connection, table: &str, column: &str) -> rusqlite::Result<bool> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info(?1) WHERE name = ?2",
+        params![table, column],
+        |row| row.get::<_, i64>(0).map(|count| count > 0),
+    )
+}
+
+// Pins used to be keyed by filename; each pinned note gets its ID now
+fn migrate_pins_to_note_ids(conn: &Connection) -> rusqlite::Result<()> {
+    // All or nothing, so an interrupted migration leaves the old table to retry from
+    let tx = conn.unchecked_transaction()?;
+    tx.execute_batch(
+        "ALTER TABLE pinned_notes RENAME TO pinned_notes_by_filename;
+         CREATE TABLE pinned_notes (
+            note_id TEXT PRIMARY KEY,
+            position INTEGER NOT NULL
+         );",
+    )?;
+    let pins: Vec<(String, i64)> = tx
+        .prepare("SELECT filename, position FROM pinned_notes_by_filename")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    for (filename, position) in pins {
+        tx.execute(
+            "INSERT OR IGNORE INTO pinned_notes (note_id, position) VALUES (?1, ?2)",
+            params![note_id(&tx, &filename)?, position],
+        )?;
+    }
+    tx.execute_batch("DROP TABLE pinned_notes_by_filename;")?;
+    tx.commit()
+}
+
+/// Pins a note after every existing pin. Pinning an already pinned note keeps its position.
+pub fn pin_note(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    let added = conn.execute(
+        "INSERT OR IGNORE INTO pinned_notes (note_id, position)
+             SELECT ?1, COALESCE(MAX(position), 0) + 1 FROM pinned_notes",
+        params![note_id(conn, filename)?],
+    )?;
+    pins_changed(conn, added);
+    Ok(())
+}
+
+pub fn unpin_note(conn: &Connection, filename: &str) -> rusqlite::Result<bool> {
+    let removed = conn.execute(
+        "DELETE FROM pinned_notes
+             WHERE note_id = (SELECT id FROM note_ids WHERE filename = ?1)",
+        params![filename],
+    )?;
+    pins_changed(conn, removed);
+    Ok(removed > 0)
+}
+
+pub fn list_pinned_notes(conn: &Connection) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT note_ids.filename FROM pinned_notes
+             JOIN note_ids ON note_ids.id = pinned_notes.note_id
+             JOIN notes ON notes.filename = note_ids.filename
+             ORDER BY pinned_notes.position",
+    )?;
+    let rows = stmt.query_map([], |row| row.get(0))?;
+    rows.collect()
+}
+
+pub fn delete_note_pin(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    unpin_note(conn, filename).map(|_| ())
+}
+
+/// Refreshes the tray after `filename` got its new name, if it is pinned. The
+/// pin itself already follows the note's ID.
+pub fn note_pin_renamed(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    let pinned = conn
+        .prepare(
+            "SELECT 1 FROM pinned_notes
+                 JOIN note_ids ON note_ids.id = pinned_notes.note_id
+                 WHERE note_ids.filename = ?1",
+        )?
+        .exists(params![filename])?;
+    pins_changed(conn, usize::from(pinned));
+    Ok(())
+}
+
+// Keeps the listener in step; most deletes and renames touch no pin
+fn pins_changed(conn: &Connection, rows: usize) {
+    if rows > 0 {
+        if let Some(listener) = PINS_CHANGED_LISTENER.get() {
+            listener(conn);
+        }
+    }
+}
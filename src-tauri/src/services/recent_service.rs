@@ -0,0 +1,72 @@
+use crate::services::note_id_service::note_id;
+use crate::services::pin_service::has_column;
+use rusqlite::{params, Connection};
+
+pub fn init_recents_table(conn: &Connection) -> rusqlite::Result<()> {
+    // Like pins, open history cannot be recovered from the notes directory,
+    // so this table survives database rebuilds and refers to notes by ID
+    if has_column(conn, "recent_notes", "filename")? {
+        return migrate_recents_to_note_ids(conn);
+    }
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS recent_notes (
+            note_id TEXT PRIMARY KEY,
+            last_opened INTEGER NOT NULL
+        );",
+    )
+}
+
+fn migrate_recents_to_note_ids(conn: &Connection) -> rusqlite::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute_batch(
+        "ALTER TABLE recent_notes RENAME TO recent_notes_by_filename;
+         CREATE TABLE recent_notes (
+            note_id TEXT PRIMARY KEY,
+            last_opened INTEGER NOT NULL
+         );",
+    )?;
+    let recents: Vec<(String, i64)> = tx
+        .prepare("SELECT filename, last_opened FROM recent_notes_by_filename")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    for (filename, last_opened) in recents {
+        record_note_opened(&tx, &filename, last_opened)?;
+    }
+    tx.execute_batch("DROP TABLE recent_notes_by_filename;")?;
+    tx.commit()
+}
+
+/// `last_opened` is in milliseconds so notes opened in quick succession keep their order.
+pub fn record_note_opened(
+    conn: &Connection,
+    filename: &str,
+    last_opened: i64,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO recent_notes (note_id, last_opened) VALUES (?1, ?2)
+             ON CONFLICT(note_id) DO UPDATE SET last_opened = excluded.last_opened",
+        params![note_id(conn, filename)?, last_opened],
+    )?;
+    Ok(())
+}
+
+pub fn list_recent_notes(conn: &Connection, limit: usize) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT note_ids.filename FROM recent_notes
+             JOIN note_ids ON note_ids.id = recent_notes.note_id
+             JOIN notes ON notes.filename = note_ids.filename
+             ORDER BY recent_notes.last_opened DESC, note_ids.filename
+             LIMIT ?1",
+    )?;
+    let rows = stmt.query_map(params![limit as i64], |row| row.get(0))?;
+    rows.collect()
+}
+
+pub fn delete_note_recent(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM recent_notes
+             WHERE note_id = (SELECT id FROM note_ids WHERE filename = ?1)",
+        params![filename],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use crate::{
+    services::hash_service::content_hash,
+    utilities::note_renderer::{is_markdown_note, render_note, RenderOptions},
+};
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub fn init_render_cache_table(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS render_cache (
+            key TEXT PRIMARY KEY,
+            html TEXT NOT NULL,
+            last_used INTEGER NOT NULL
+        );",
+    )?;
+    Ok(())
+}
+
+/// Identifies a render by everything that affects its output: whether the note
+/// is rendered as markdown, the render settings and the content itself. Notes
+/// with identical content share an entry, which also survives renames.
+pub fn render_cache_key(filename: &str, content: &str, options: &RenderOptions) -> String {
+    let settings = format!(
+        "markdown={};anchors={};math={};code_theme={};commonmark={}\n",
+        is_markdown_note(filename),
+        options.heading_anchors,
+        options.math,
+        options.code_theme.as_deref().unwrap_or(""),
+        options.commonmark
+    );
+    content_hash(format!("{}{}", settings, content).as_bytes())
+}
+
+/// Renders `content`, reusing a previous render of the same content and
+/// settings when one is cached. Keeps the `max_entries` most recently used
+/// renders; zero bypasses the cache entirely.
+pub fn render_note_cached(
+    conn: &Connection,
+    filename: &str,
+    content: &str,
+    options: &RenderOptions,
+    max_entries: usize,
+) -> rusqlite::Result<String> {
+    if max_entries == 0 {
+        return Ok(render_note(filename, content, options));
+    }
+
+    let key = render_cache_key(filename, content, options);
+    let cached: Option<String> = conn
+        .query_row(
+            "SELECT html FROM render_cache WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    if let Some(html) = cached {
+        touch_entry(conn, &key)?;
+        return Ok(html);
+    }
+
+    let html = render_note(filename, content, options);
+    conn.execute(
+        "INSERT OR REPLACE INTO render_cache (key, html, last_used)
+         VALUES (?1, ?2, (SELECT COALESCE(MAX(last_used), 0) + 1 FROM render_cache))",
+        params![key, html],
+    )?;
+    conn.execute(
+        "DELETE FROM render_cache WHERE key NOT IN
+         (SELECT key FROM render_cache ORDER BY last_used DESC LIMIT ?1)",
+        params![max_entries as i64],
+    )?;
+    Ok(html)
+}
+
+pub fn clear_render_cache(conn: &Connection) -> rusqlite::Result<usize> {
+    conn.execute("DELETE FROM render_cache", [])
+}
+
+// A counter rather than a timestamp, so entries used within the same second
+// still evict in order
+fn touch_entry(conn: &Connection, key: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "UPDATE render_cache SET last_used = (SELECT MAX(last_used) + 1 FROM render_cache)
+         WHERE key = ?1",
+        params![key],
+    )?;
+    Ok(())
+}
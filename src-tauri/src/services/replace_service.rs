@@ -0,0 +1,143 @@
+use crate::core::{AppError, AppResult};
+use crate::search::REGEX_SIZE_LIMIT;
+use crate::utilities::note_crypto::is_encrypted_note;
+use regex::{NoExpand, Regex, RegexBuilder};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// Changed lines shown per note by a dry run.
+pub const MAX_REPLACE_PREVIEW_LINES: usize = 5;
+
+/// What a find and replace does, or did, to one note.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReplaceResult {
+    pub filename: String,
+    pub replacements: usize,
+    /// The first changed lines; only filled in by a dry run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview: Option<Vec<ReplacePreviewLine>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReplacePreviewLine {
+    /// 1-based line number
+    pub line: usize,
+    pub before: String,
+    pub after: String,
+}
+
+/// A note's content before and after a planned replace.
+#[derive(Debug, Clone)]
+pub struct ReplacedNote {
+    pub filename: String,
+    pub original: String,
+    pub replaced: String,
+    pub replacements: usize,
+}
+
+/// A compiled find and replace. Literal searches insert `replace` as-is,
+/// while regex searches expand `$1` and `${name}` in it.
+pub struct Replacement {
+    pattern: Regex,
+    replace: String,
+    expand: bool,
+}
+
+impl Replacement {
+    pub fn new(find: &str, replace: &str, regex: bool) -> AppResult<Self> {
+        if find.is_empty() {
+            return Err(AppError::SearchQuery(
+                "Text to find cannot be empty".to_string(),
+            ));
+        }
+
+        let source = if regex {
+            find.to_string()
+        } else {
+            regex::escape(find)
+        };
+        let pattern = RegexBuilder::new(&source)
+            .size_limit(REGEX_SIZE_LIMIT)
+            .build()
+            .map_err(|e| AppError::SearchQuery(format!("Invalid regex pattern: {}", e)))?;
+
+        // A pattern like `x*` would insert the replacement between every character
+        if pattern.is_match("") {
+            return Err(AppError::SearchQuery(
+                "Pattern must not match empty text".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            pattern,
+            replace: replace.to_string(),
+            expand: regex,
+        })
+    }
+
+    /// `text` with every match replaced, or `None` when nothing matched.
+    pub fn apply(&self, text: &str) -> Option<(String, usize)> {
+        let replacements = self.pattern.find_iter(text).count();
+        if replacements == 0 {
+            return None;
+        }
+
+        let replaced = if self.expand {
+            self.pattern.replace_all(text, self.replace.as_str())
+        } else {
+            self.pattern.replace_all(text, NoExpand(&self.replace))
+        };
+        Some((replaced.into_owned(), replacements))
+    }
+
+    /// The first changed lines of `content`, before and after the replace.
+    pub fn preview(&self, content: &str) -> Vec<ReplacePreviewLine> {
+        content
+            .lines()
+            .enumerate()
+            .filter_map(|(index, line)| {
+                self.apply(line).map(|(after, _)| ReplacePreviewLine {
+                    line: index + 1,
+                    before: line.to_string(),
+                    after,
+                })
+            })
+            .take(MAX_REPLACE_PREVIEW_LINES)
+            .collect()
+    }
+}
+
+/// Every note the replacement changes, sorted by name. Nothing is written.
+/// Notes are read from disk rather than the index, which may lag behind an
+/// edit made outside the app; unreadable and encrypted notes are left alone.
+pub fn plan_replace(
+    conn: &Connection,
+    notes_dir: &Path,
+    replacement: &Replacement,
+) -> rusqlite::Result<Vec<ReplacedNote>> {
+    let mut stmt = conn.prepare("SELECT filename FROM notes ORDER BY filename")?;
+    let filenames = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut replaced_notes = Vec::new();
+    for filename in filenames {
+        let Ok(content) = fs::read_to_string(notes_dir.join(&filename)) else {
+            continue;
+        };
+        if is_encrypted_note(&content) {
+            continue;
+        }
+        if let Some((replaced, replacements)) = replacement.apply(&content) {
+            replaced_notes.push(ReplacedNote {
+                filename,
+                original: content,
+                replaced,
+                replacements,
+            });
+        }
+    }
+    Ok(replaced_notes)
+}
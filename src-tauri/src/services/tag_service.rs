@@ -0,0 +1,117 @@
+use crate::utilities::frontmatter::parse_frontmatter;
+use rusqlite::{params, Connection};
+
+fn table_exists(conn: &Connection, name: &str) -> rusqlite::Result<bool> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name=?1",
+        params![name],
+        |row| row.get::<_, i64>(0).map(|count| count > 0),
+    )
+}
+
+pub fn init_tags_table(conn: &Connection) -> rusqlite::Result<()> {
+    let exists = table_exists(conn, "note_tags")? && table_exists(conn, "tag_labels")?;
+
+    // Labels outlive the notes using them, so a tag keeps the casing it was
+    // first seen with even across index rebuilds
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS note_tags (
+            filename TEXT NOT NULL,
+            tag TEXT NOT NULL,
+            PRIMARY KEY (filename, tag)
+        );
+        CREATE INDEX IF NOT EXISTS idx_note_tags_tag ON note_tags(tag);
+        CREATE TABLE IF NOT EXISTS tag_labels (
+            tag TEXT PRIMARY KEY,
+            label TEXT NOT NULL
+        );",
+    )?;
+
+    // Databases created before tags existed already hold unchanged notes that
+    // will not be re-read from disk, so index their tags from stored content
+    if !exists {
+        backfill_tags(conn)?;
+    }
+
+    Ok(())
+}
+
+pub fn sync_note_tags(conn: &Connection, filename: &str, content: &str) -> rusqlite::Result<()> {
+    let frontmatter = parse_frontmatter(filename, content);
+    store_note_tags(conn, filename, &frontmatter.tags, &frontmatter.tag_labels)
+}
+
+/// Replaces the indexed tags of a note with `tags`, already parsed from its
+/// frontmatter, and remembers `labels` for tags not seen before.
+pub fn store_note_tags(
+    conn: &Connection,
+    filename: &str,
+    tags: &[String],
+    labels: &[String],
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM note_tags WHERE filename = ?1",
+        params![filename],
+    )?;
+    for (tag, label) in tags.iter().zip(labels) {
+        conn.execute(
+            "INSERT OR IGNORE INTO note_tags (filename, tag) VALUES (?1, ?2)",
+            params![filename, tag],
+        )?;
+        conn.execute(
+            "INSERT OR IGNORE INTO tag_labels (tag, label) VALUES (?1, ?2)",
+            params![tag, label],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Every tag in use with the number of notes using it, most used first and
+/// then by name. Tags show the casing they were first seen with.
+pub fn list_tags(conn: &Connection) -> rusqlite::Result<Vec<(String, usize)>> {
+    let mut stmt = conn.prepare(
+        "SELECT COALESCE(tag_labels.label, note_tags.tag), COUNT(*) AS uses
+         FROM note_tags
+         JOIN notes ON notes.filename = note_tags.filename
+         LEFT JOIN tag_labels ON tag_labels.tag = note_tags.tag
+         GROUP BY note_tags.tag
+         ORDER BY uses DESC, note_tags.tag",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+    })?;
+    rows.collect()
+}
+
+pub fn delete_note_tags(conn: &Connection, filename: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM note_tags WHERE filename = ?1",
+        params![filename],
+    )?;
+    Ok(())
+}
+
+pub fn rename_note_tags(conn: &Connection, old_name: &str, new_name: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "UPDATE note_tags SET filename = ?1 WHERE filename = ?2",
+        params![new_name, old_name],
+    )?;
+    Ok(())
+}
+
+fn backfill_tags(conn: &Connection) -> rusqlite::Result<()> {
+    let notes = {
+        let mut stmt = conn.prepare("SELECT filename, content FROM notes")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+
+    for (filename, content) in notes {
+        sync_note_tags(conn, &filename, &content)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use crate::{
+    core::{state::AppState, AppResult},
+    logging::log,
+    utilities::{
+        paths::get_backup_dir_for_notes_path,
+        strings::{parse_backup_filename, parse_deleted_backup_filename},
+    },
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+pub struct TrashEntry {
+    pub path: PathBuf,
+    pub deleted_at: u64,
+    pub size: u64,
+}
+
+#[derive(serde::Serialize)]
+pub struct TrashSize {
+    pub count: usize,
+    pub total_bytes: u64,
+}
+
+pub fn trash_dir(app_state: &AppState) -> AppResult<PathBuf> {
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    get_backup_dir_for_notes_path(&PathBuf::from(&config.notes_directory))
+}
+
+/// Deleted notes are the `delete_backup` copies kept next to the other backups.
+pub fn list_trash_entries(backup_dir: &Path) -> Vec<TrashEntry> {
+    if !backup_dir.exists() {
+        return Vec::new();
+    }
+
+    WalkDir::new(backup_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|entry| {
+            let filename = entry.file_name().to_string_lossy();
+            let (_, deleted_at) = parse_deleted_backup_filename(&filename)?;
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            Some(TrashEntry {
+                path: entry.into_path(),
+                deleted_at,
+                size,
+            })
+        })
+        .collect()
+}
+
+pub fn trash_size(backup_dir: &Path) -> TrashSize {
+    let entries = list_trash_entries(backup_dir);
+    TrashSize {
+        count: entries.len(),
+        total_bytes: entries.iter().map(|entry| entry.size).sum(),
+    }
+}
+
+/// Removes trashed notes deleted before `cutoff`, or all of them when no cutoff is given.
+pub fn purge_trash(backup_dir: &Path, cutoff: Option<u64>) -> AppResult<usize> {
+    let mut purged = 0;
+    for entry in list_trash_entries(backup_dir) {
+        if cutoff.is_some_and(|cutoff| entry.deleted_at >= cutoff) {
+            continue;
+        }
+
+        fs::remove_file(&entry.path)?;
+        purged += 1;
+        log(
+            "TRASH",
+            &format!(
+                "Purged '{}'",
+                entry
+                    .path
+                    .strip_prefix(backup_dir)
+                    .unwrap_or(&entry.path)
+                    .display()
+            ),
+            Some(&format!("deleted_at: {}", entry.deleted_at)),
+        );
+    }
+    Ok(purged)
+}
+
+/// Removes the versions kept next to the trashed note at `trashed_path`, unless
+/// another deleted copy of the same note still relies on them. Returns how many
+/// were removed.
+pub fn purge_trashed_note_versions(trashed_path: &Path) -> AppResult<usize> {
+    let Some(parent) = trashed_path.parent() else {
+        return Ok(0);
+    };
+    let Some((original_filename, _)) = trashed_path
+        .file_name()
+        .and_then(|name| parse_deleted_backup_filename(&name.to_string_lossy()))
+    else {
+        return Ok(0);
+    };
+    let base_name = original_filename
+        .strip_suffix(".md")
+        .unwrap_or(&original_filename);
+
+    let mut versions = Vec::new();
+    for entry in fs::read_dir(parent)?.flatten() {
+        let filename = entry.file_name().to_string_lossy().to_string();
+        match parse_backup_filename(&filename, base_name) {
+            Some((backup_type, _)) if backup_type == "delete_backup" => return Ok(0),
+            Some(_) => versions.push(entry.path()),
+            None => {}
+        }
+    }
+
+    for version in &versions {
+        fs::remove_file(version)?;
+    }
+    Ok(versions.len())
+}
+
+/// Applies `trash_retention_days`, where 0 keeps deleted notes forever.
+pub fn purge_expired_trash(app_state: &AppState) -> AppResult<usize> {
+    let retention_days = app_state
+        .config
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .preferences
+        .trash_retention_days;
+    if retention_days == 0 {
+        return Ok(0);
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let cutoff = now.saturating_sub(u64::from(retention_days) * SECONDS_PER_DAY);
+    purge_trash(&trash_dir(app_state)?, Some(cutoff))
+}
@@ -0,0 +1,101 @@
+use crate::{
+    core::AppResult,
+    utilities::{
+        file_safety::safe_backup_path,
+        paths::get_backup_dir_for_notes_path,
+        strings::{format_timestamp_for_humans, parse_backup_filename},
+    },
+};
+use std::borrow::Cow;
+use std::fs;
+use std::path::Path;
+
+#[derive(serde::Serialize)]
+pub struct NoteVersion {
+    pub filename: String,
+    pub backup_type: String,
+    pub timestamp: u64,
+    pub size: u64,
+    pub formatted_time: String,
+}
+
+/// Lists the stored versions of a note, newest first. Version filenames are relative
+/// to the backup directory, and older versions show their date in `date_format`.
+/// A version with the same content as the one before it is left out.
+pub fn list_note_versions(
+    notes_dir: &Path,
+    note_name: &str,
+    date_format: &str,
+) -> AppResult<Vec<NoteVersion>> {
+    let backup_dir = get_backup_dir_for_notes_path(notes_dir)?;
+    if !backup_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let base_name = if let Some(stem) = Path::new(note_name).file_stem() {
+        stem.to_string_lossy()
+    } else {
+        Cow::from(note_name)
+    };
+
+    let note_path = notes_dir.join(note_name);
+    let note_backup_dir = safe_backup_path(&note_path)
+        .ok()
+        .and_then(|p| p.parent().map(|p| p.to_path_buf()))
+        .unwrap_or_else(|| backup_dir.clone());
+
+    if !note_backup_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut versions = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(&note_backup_dir) {
+        for entry in entries.flatten() {
+            let filename = entry.file_name().to_string_lossy().to_string();
+
+            if let Some((backup_type, timestamp)) = parse_backup_filename(&filename, &base_name) {
+                if let Ok(metadata) = entry.metadata() {
+                    let size = metadata.len();
+                    let formatted_time = format_timestamp_for_humans(timestamp, date_format);
+                    let relative_filename = entry
+                        .path()
+                        .strip_prefix(&backup_dir)
+                        .map(|p| p.to_string_lossy().to_string())
+                        .unwrap_or(filename);
+
+                    versions.push((
+                        entry.path(),
+                        NoteVersion {
+                            filename: relative_filename,
+                            backup_type,
+                            timestamp,
+                            size,
+                            formatted_time,
+                        },
+                    ));
+                }
+            }
+        }
+    }
+
+    // Sort by timestamp (newest first)
+    versions.sort_by(|(_, a), (_, b)| b.timestamp.cmp(&a.timestamp));
+
+    let mut distinct = Vec::with_capacity(versions.len());
+    let mut versions = versions.into_iter().peekable();
+    while let Some((path, version)) = versions.next() {
+        let repeats_older = versions.peek().is_some_and(|(older_path, older)| {
+            older.size == version.size && same_content(&path, older_path)
+        });
+        if !repeats_older {
+            distinct.push(version);
+        }
+    }
+
+    Ok(distinct)
+}
+
+fn same_content(a: &Path, b: &Path) -> bool {
+    matches!((fs::read(a), fs::read(b)), (Ok(a), Ok(b)) if a == b)
+}
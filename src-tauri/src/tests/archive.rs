@@ -0,0 +1,116 @@
+//! Archive Unit Tests
+//!
+//! Tests moving notes in and out of the archive folder.
+
+use crate::tests::test_utils::{
+    test_archive_note, test_archive_notes, test_create_new_note, test_list_all_notes,
+    test_list_archived_notes, test_unarchive_note, TestConfigOverride,
+};
+use serial_test::serial;
+use std::fs;
+use std::time::{Duration, SystemTime};
+
+fn set_mtime(path: &std::path::Path, mtime: SystemTime) {
+    fs::OpenOptions::new()
+        .write(true)
+        .open(path)
+        .expect("Failed to open note")
+        .set_modified(mtime)
+        .expect("Failed to set modified time");
+}
+
+#[test]
+#[serial]
+fn test_archive_and_unarchive_round_trip() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+
+    test_create_new_note("project/old-idea.md").expect("Failed to create note");
+    let original_mtime = SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+    set_mtime(&notes_dir.join("project/old-idea.md"), original_mtime);
+
+    test_archive_note("project/old-idea.md").expect("Failed to archive note");
+
+    let archived_path = notes_dir.join(".archive/project/old-idea.md");
+    assert!(archived_path.exists(), "Note should move into the archive");
+    assert!(!notes_dir.join("project/old-idea.md").exists());
+    assert_eq!(
+        fs::metadata(&archived_path).unwrap().modified().unwrap(),
+        original_mtime,
+        "Archiving must preserve the modified timestamp"
+    );
+
+    let notes = test_list_all_notes().expect("Failed to list notes");
+    assert!(!notes.contains(&"project/old-idea.md".to_string()));
+    assert_eq!(
+        test_list_archived_notes().expect("Failed to list archive"),
+        vec!["project/old-idea.md".to_string()]
+    );
+
+    test_unarchive_note("project/old-idea.md").expect("Failed to unarchive note");
+
+    assert!(!archived_path.exists());
+    assert_eq!(
+        fs::metadata(notes_dir.join("project/old-idea.md"))
+            .unwrap()
+            .modified()
+            .unwrap(),
+        original_mtime
+    );
+    let notes = test_list_all_notes().expect("Failed to list notes");
+    assert!(notes.contains(&"project/old-idea.md".to_string()));
+    assert!(test_list_archived_notes().unwrap().is_empty());
+}
+
+#[test]
+#[serial]
+fn test_unarchive_refuses_to_overwrite_existing_note() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+
+    test_create_new_note("clash.md").expect("Failed to create note");
+    test_archive_note("clash.md").expect("Failed to archive note");
+    test_create_new_note("clash.md").expect("Failed to recreate note");
+
+    let err = test_unarchive_note("clash.md").expect_err("Collision should be rejected");
+    assert!(err.contains("already exists"), "Unexpected error: {}", err);
+    assert!(notes_dir.join(".archive/clash.md").exists());
+
+    let err = test_archive_note("clash.md").expect_err("Archive collision should be rejected");
+    assert!(err.contains("already exists"), "Unexpected error: {}", err);
+    assert!(notes_dir.join("clash.md").exists());
+}
+
+#[test]
+#[serial]
+fn test_archive_missing_note_fails() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    assert!(test_archive_note("missing.md").is_err());
+    assert!(test_unarchive_note("missing.md").is_err());
+    assert!(test_list_archived_notes().unwrap().is_empty());
+}
+
+#[test]
+#[serial]
+fn test_archive_notes_reports_partial_failures() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+
+    test_create_new_note("first.md").expect("Failed to create note");
+    test_create_new_note("second.md").expect("Failed to create note");
+
+    let results = test_archive_notes(&["first.md", "missing.md", "second.md"])
+        .expect("Batch archive should complete");
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0], ("first.md".to_string(), Ok(())));
+    assert!(results[1].1.as_ref().unwrap_err().contains("not found"));
+    assert_eq!(results[2], ("second.md".to_string(), Ok(())));
+
+    assert!(notes_dir.join(".archive/first.md").exists());
+    assert!(notes_dir.join(".archive/second.md").exists());
+    let notes = test_list_all_notes().expect("Failed to list notes");
+    assert!(!notes.contains(&"first.md".to_string()));
+    assert!(!notes.contains(&"second.md".to_string()));
+}
@@ -291,7 +291,8 @@ fn test_atomic_write_rollback_protection() {
 
     // Test normal write operation (should succeed)
     let new_content = "New content after successful write";
-    let result = safe_write_note(&note_path, new_content);
+    let preferences = crate::config::AppConfig::default().preferences;
+    let result = safe_write_note(&note_path, new_content, &preferences);
 
     match result {
         Ok(()) => {
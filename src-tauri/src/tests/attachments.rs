@@ -0,0 +1,89 @@
+//! Attachment Unit Tests
+//!
+//! Tests saving pasted images, content-hash deduplication and payload checks.
+
+use crate::tests::test_utils::{test_create_new_note, test_save_pasted_image, TestConfigOverride};
+use crate::utilities::attachments::{attachment_link, detect_image_extension};
+use serial_test::serial;
+use std::fs;
+
+const PNG_HEADER: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+fn png_bytes(payload: &[u8]) -> Vec<u8> {
+    [PNG_HEADER, payload].concat()
+}
+
+#[test]
+fn test_detect_image_extension() {
+    assert_eq!(detect_image_extension(&png_bytes(b"data")), Some("png"));
+    assert_eq!(detect_image_extension(b"\xff\xd8\xff\xe0data"), Some("jpg"));
+    assert_eq!(detect_image_extension(b"GIF89adata"), Some("gif"));
+    assert_eq!(
+        detect_image_extension(b"RIFF\0\0\0\0WEBPVP8 "),
+        Some("webp")
+    );
+    assert_eq!(detect_image_extension(b"RIFF\0\0\0\0WAVEfmt "), None);
+    assert_eq!(detect_image_extension(b"# Just a note"), None);
+    assert_eq!(detect_image_extension(b""), None);
+}
+
+#[test]
+fn test_attachment_link_is_relative_to_note() {
+    assert_eq!(
+        attachment_link("note.md", "abc.png"),
+        "![](attachments/abc.png)"
+    );
+    assert_eq!(
+        attachment_link("work/projects/note.md", "abc.png"),
+        "![](../../attachments/abc.png)"
+    );
+}
+
+#[test]
+#[serial]
+fn test_pasted_image_saved_once_per_content() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_create_new_note("note.md").expect("Failed to create note");
+    fs::create_dir_all(test_config.notes_dir().join("work")).expect("Failed to create folder");
+    test_create_new_note("work/other.md").expect("Failed to create note");
+
+    let first = test_save_pasted_image("note.md", png_bytes(b"one")).expect("Paste should work");
+    let again = test_save_pasted_image("note.md", png_bytes(b"one")).expect("Paste should work");
+    let nested =
+        test_save_pasted_image("work/other.md", png_bytes(b"one")).expect("Paste should work");
+    let second = test_save_pasted_image("note.md", png_bytes(b"two")).expect("Paste should work");
+
+    assert!(first.starts_with("![](attachments/") && first.ends_with(".png)"));
+    assert_eq!(first, again);
+    assert_eq!(nested, first.replace("](", "](../"));
+    assert_ne!(first, second);
+
+    let attachments_dir = test_config.notes_dir().join("attachments");
+    assert_eq!(fs::read_dir(&attachments_dir).unwrap().count(), 2);
+    let file_name = first
+        .trim_start_matches("![](attachments/")
+        .trim_end_matches(')');
+    assert_eq!(
+        fs::read(attachments_dir.join(file_name)).unwrap(),
+        png_bytes(b"one")
+    );
+}
+
+#[test]
+#[serial]
+fn test_pasted_image_rejects_bad_payloads() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_config.update_config(|config| config.preferences.max_pasted_image_mb = 1);
+    test_create_new_note("note.md").expect("Failed to create note");
+
+    let err = test_save_pasted_image("note.md", b"not an image".to_vec())
+        .expect_err("Text should be rejected");
+    assert!(err.contains("not a PNG"), "{}", err);
+
+    let err = test_save_pasted_image("note.md", png_bytes(&vec![0; 1024 * 1024]))
+        .expect_err("Images over the cap should be rejected");
+    assert!(err.contains("byte limit"), "{}", err);
+
+    assert!(test_save_pasted_image("../note.md", png_bytes(b"one")).is_err());
+    assert!(!test_config.notes_dir().join("attachments").exists());
+}
@@ -3,7 +3,7 @@
 //! Tests for concurrent access patterns and multi-user scenarios.
 
 use crate::config::*;
-use crate::utilities::note_renderer::render_note;
+use crate::utilities::note_renderer::{render_note, RenderOptions};
 use crate::utilities::paths::get_database_path;
 use crate::utilities::paths::{get_config_path, get_default_notes_dir};
 use crate::utilities::validation::{validate_config, validate_note_name, validate_shortcut_format};
@@ -219,7 +219,7 @@ fn test_concurrent_render_operations() {
         .into_iter()
         .map(|(filename, content)| {
             thread::spawn(move || {
-                let rendered = render_note(filename, content);
+                let rendered = render_note(filename, content, &RenderOptions::default());
 
                 // Verify basic rendering properties
                 assert!(!rendered.is_empty());
@@ -334,7 +334,7 @@ fn test_stress_concurrent_operations() {
                         3 => {
                             // Rendering
                             let content = format!("# Thread {} Operation {}", thread_id, op_id);
-                            let _ = render_note("test.md", &content);
+                            let _ = render_note("test.md", &content, &RenderOptions::default());
                         }
                         _ => unreachable!(),
                     }
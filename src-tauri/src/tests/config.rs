@@ -3,8 +3,26 @@
 //! Tests config loading, parsing, and validation functionality.
 //! These tests access internal/private functions and test the actual production behavior.
 
-use crate::config::{load_config, load_config_from_content, parse_shortcut, AppConfig};
-use crate::utilities::paths::{get_config_path, get_default_notes_dir};
+use crate::commands::config::{
+    ensure_config_file, get_available_themes, initialize_first_run_setup, save_config_content,
+};
+use crate::config::{
+    config_backup_path, generate_default_config_toml, get_available_editor_themes,
+    get_available_ui_themes, get_config_warnings, load_config, load_config_from_content,
+    normalize_shortcut, parse_shortcut, write_config_atomically, AppConfig, EditorConfig,
+};
+use crate::tests::test_utils::{test_get_active_config, TestConfigOverride};
+use crate::utilities::config_helpers::{
+    get_user_editor_themes, sanitize_editor_config, MAX_SEARCH_RESULTS_CEILING,
+};
+use crate::utilities::note_renderer::RenderOptions;
+use crate::utilities::paths::{
+    get_config_path, get_database_path, get_default_notes_dir, get_editor_themes_dir,
+    parse_config_path_override, resolve_config_path, NOTES_DIR_ENV,
+};
+use crate::utilities::validation::{validate_config, validate_preferences_config};
+use serial_test::serial;
+use std::path::PathBuf;
 
 #[test]
 fn test_default_config_values() {
@@ -117,6 +135,105 @@ fn test_shortcut_parsing() {
     assert!(parse_shortcut("Not+A+Real+Shortcut").is_none());
 }
 
+#[test]
+fn test_shortcut_normalization() {
+    assert_eq!(normalize_shortcut("ctrl+shift+n"), "Ctrl+Shift+N");
+    assert_eq!(normalize_shortcut("Shift+Ctrl+N"), "Ctrl+Shift+N");
+    assert_eq!(normalize_shortcut(" control + SHIFT + n "), "Ctrl+Shift+N");
+    assert_eq!(normalize_shortcut("cmd+alt+arrowup"), "Alt+Meta+ArrowUp");
+    assert_eq!(normalize_shortcut("Ctrl+enter"), "Ctrl+Enter");
+    assert_eq!(normalize_shortcut("Meta+,"), "Meta+,");
+
+    // Letters are spelled the way a key press reports them: shifted only with Shift
+    assert_eq!(normalize_shortcut("Ctrl+M"), "Ctrl+m");
+    assert_eq!(normalize_shortcut("Alt+Ctrl+D"), "Ctrl+Alt+d");
+    let cmd_or_ctrl = if cfg!(target_os = "macos") {
+        "Meta+m"
+    } else {
+        "Ctrl+m"
+    };
+    assert_eq!(normalize_shortcut("CmdOrCtrl+M"), cmd_or_ctrl);
+    assert_eq!(normalize_shortcut("cmdorcontrol+m"), cmd_or_ctrl);
+
+    assert_eq!(
+        parse_shortcut("shift+ctrl+n"),
+        parse_shortcut("Ctrl+Shift+N")
+    );
+    assert!(parse_shortcut("Meta+F1").is_some());
+}
+
+#[test]
+fn test_shortcut_conflicts_detected_after_normalization() {
+    let config = load_config_from_content(
+        r#"
+global_shortcut = "ctrl+shift+n"
+
+[shortcuts]
+create_note = "Shift+Ctrl+N"
+"#,
+    );
+
+    assert_eq!(config.global_shortcut, "Ctrl+Shift+N");
+    assert_eq!(config.shortcuts.create_note, "Ctrl+Enter"); // default
+}
+
+#[test]
+fn test_shortcuts_stored_normalized() {
+    let config = load_config_from_content(
+        r#"
+global_shortcut = "shift+alt+space"
+global_shortcut_new_note = "control+alt+N"
+
+[shortcuts]
+create_note = "ctrl+ENTER"
+rename_note = "Ctrl+M"
+copy_current_section = "shift+ctrl+y"
+"#,
+    );
+
+    assert_eq!(config.global_shortcut, "Alt+Shift+Space");
+    assert_eq!(
+        config.global_shortcut_new_note.as_deref(),
+        Some("Ctrl+Alt+n")
+    );
+    assert_eq!(config.shortcuts.create_note, "Ctrl+Enter");
+    assert_eq!(config.shortcuts.rename_note, "Ctrl+m");
+    assert_eq!(config.shortcuts.copy_current_section, "Ctrl+Shift+Y");
+}
+
+#[test]
+fn test_shortcut_conflicts_ignore_letter_case() {
+    let toml = r#"
+[shortcuts]
+create_note = "Ctrl+M"
+"#;
+
+    let config = load_config_from_content(toml);
+    assert_eq!(config.shortcuts.create_note, "Ctrl+m");
+
+    // rename_note's default "Ctrl+m" is now taken
+    let warnings = get_config_warnings(toml).expect("Valid TOML should produce warnings");
+    assert!(warnings.iter().any(|w| w.field == "shortcuts.rename_note"));
+}
+
+#[test]
+#[cfg(not(target_os = "macos"))]
+fn test_cmd_or_ctrl_conflicts_with_ctrl() {
+    let toml = r#"
+[shortcuts]
+create_note = "CmdOrCtrl+j"
+"#;
+
+    let config = load_config_from_content(toml);
+    assert_eq!(config.shortcuts.create_note, "Ctrl+j");
+
+    let warnings = get_config_warnings(toml).expect("Valid TOML should produce warnings");
+    assert!(
+        warnings.iter().any(|w| w.field == "shortcuts.down"),
+        "down's default Ctrl+j is taken"
+    );
+}
+
 #[test]
 fn test_load_config_behavior() {
     // load_config() reads from platform-appropriate config path
@@ -271,6 +388,7 @@ global_shortcut = "InvalidShortcut"
 create_note = "Ctrl+Enter"
 rename_note = "++Invalid++"
 delete_note = ""
+duplicate_note = "++Invalid++"
 "#;
 
     let config = load_config_from_content(invalid_shortcuts_toml);
@@ -282,6 +400,7 @@ delete_note = ""
     assert_eq!(config.global_shortcut, "Ctrl+Shift+N"); // default
     assert_eq!(config.shortcuts.rename_note, "Ctrl+m"); // default
     assert_eq!(config.shortcuts.delete_note, "Ctrl+x"); // default
+    assert_eq!(config.shortcuts.duplicate_note, "Ctrl+Alt+d"); // default
 }
 
 #[test]
@@ -420,6 +539,7 @@ navigate_next = "Ctrl+n"
 open_settings = "Meta+,"
 version_explorer = "Ctrl+/"
 recently_deleted = "Ctrl+."
+toggle_always_on_top = "Ctrl+Alt+t"
 
 [preferences]
 max_search_results = 250
@@ -441,5 +561,1132 @@ max_search_results = 250
     assert_eq!(config.shortcuts.create_note, "Ctrl+Enter");
     assert_eq!(config.shortcuts.rename_note, "Ctrl+r");
     assert_eq!(config.shortcuts.refresh_cache, "F5");
+    assert_eq!(config.shortcuts.toggle_always_on_top, "Ctrl+Alt+t");
     assert_eq!(config.preferences.max_search_results, 250);
 }
+
+// ============================================================================
+// USER EDITOR THEMES
+// ============================================================================
+
+#[test]
+#[serial]
+fn test_user_editor_themes_are_accepted() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let themes_dir = get_editor_themes_dir();
+    std::fs::create_dir_all(&themes_dir).expect("Failed to create themes directory");
+    std::fs::write(
+        themes_dir.join("team-dark.json"),
+        r##"{"background": "#101010", "foreground": "#eeeeee"}"##,
+    )
+    .expect("Failed to write JSON theme");
+    std::fs::write(
+        themes_dir.join("team-light.css"),
+        ".cm-editor { background: #ffffff; }",
+    )
+    .expect("Failed to write CSS theme");
+
+    let themes = get_available_editor_themes();
+    assert!(themes.contains(&"team-dark".to_string()));
+    assert!(themes.contains(&"team-light".to_string()));
+    assert!(themes.contains(&"gruvbox-dark".to_string()));
+
+    let config = load_config_from_content(
+        r#"
+[editor]
+theme = "team-dark"
+"#,
+    );
+    assert_eq!(config.editor.theme, "team-dark");
+}
+
+#[test]
+#[serial]
+fn test_available_themes_match_validation() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let themes_dir = get_editor_themes_dir();
+    std::fs::create_dir_all(&themes_dir).expect("Failed to create themes directory");
+    std::fs::write(
+        themes_dir.join("team-dark.json"),
+        r##"{"background": "#101010"}"##,
+    )
+    .expect("Failed to write theme");
+
+    let themes = get_available_themes().expect("Failed to list themes");
+    assert_eq!(
+        themes.ui_themes,
+        get_available_ui_themes()
+            .into_iter()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(themes.editor_themes, get_available_editor_themes());
+    assert!(themes.editor_themes.contains(&"team-dark".to_string()));
+    assert!(themes.markdown_themes.contains(&"article".to_string()));
+    assert!(themes.code_themes.contains(&"nord".to_string()));
+
+    // Every listed theme must survive validation unchanged
+    for theme in &themes.code_themes {
+        let content = format!(
+            "notes_directory = \"/tmp/symiosis-notes\"\n[interface]\nmd_render_code_theme = \"{}\"",
+            theme
+        );
+        assert!(
+            get_config_warnings(&content).unwrap().is_empty(),
+            "{}",
+            theme
+        );
+    }
+    for theme in &themes.editor_themes {
+        let content = format!(
+            "notes_directory = \"/tmp/symiosis-notes\"\n[editor]\ntheme = \"{}\"",
+            theme
+        );
+        assert!(
+            get_config_warnings(&content).unwrap().is_empty(),
+            "{}",
+            theme
+        );
+    }
+}
+
+#[test]
+#[serial]
+fn test_invalid_user_editor_theme_is_skipped() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let themes_dir = get_editor_themes_dir();
+    std::fs::create_dir_all(&themes_dir).expect("Failed to create themes directory");
+    std::fs::write(themes_dir.join("broken.json"), "{ not json").expect("Failed to write theme");
+    std::fs::write(themes_dir.join("valid.json"), r##"{"background": "#000"}"##)
+        .expect("Failed to write theme");
+    std::fs::write(themes_dir.join("notes.txt"), "not a theme").expect("Failed to write file");
+
+    let user_themes = get_user_editor_themes();
+    assert_eq!(user_themes, vec!["valid".to_string()]);
+
+    let config = load_config_from_content(
+        r#"
+[editor]
+theme = "broken"
+"#,
+    );
+    assert_eq!(config.editor.theme, "gruvbox-dark"); // default
+}
+
+#[test]
+#[serial]
+fn test_user_editor_themes_rescanned_when_files_change() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let themes_dir = get_editor_themes_dir();
+    std::fs::create_dir_all(&themes_dir).expect("Failed to create themes directory");
+    std::fs::write(themes_dir.join("first.json"), r##"{"background": "#000"}"##)
+        .expect("Failed to write theme");
+    assert_eq!(get_user_editor_themes(), vec!["first".to_string()]);
+    assert_eq!(get_user_editor_themes(), vec!["first".to_string()]);
+
+    std::fs::write(themes_dir.join("second.css"), ".cm-editor { color: red; }")
+        .expect("Failed to write theme");
+    assert_eq!(
+        get_user_editor_themes(),
+        vec!["first".to_string(), "second".to_string()]
+    );
+
+    // Editing a file in place leaves the directory untouched; the new size
+    // is enough to notice it whatever the mtime granularity
+    std::fs::write(themes_dir.join("second.css"), "").expect("Failed to write theme");
+    assert_eq!(get_user_editor_themes(), vec!["first".to_string()]);
+
+    std::fs::remove_file(themes_dir.join("first.json")).expect("Failed to remove theme");
+    assert!(get_user_editor_themes().is_empty());
+}
+
+#[test]
+fn test_config_warnings_report_rejected_fields() {
+    let toml = r#"
+[interface]
+ui_theme = "not-a-theme"
+
+[editor]
+tab_size = 0
+
+[shortcuts]
+create_note = ""
+"#;
+
+    let warnings = get_config_warnings(toml).expect("Valid TOML should produce warnings");
+    let defaults = AppConfig::default();
+
+    let ui_theme = warnings
+        .iter()
+        .find(|w| w.field == "interface.ui_theme")
+        .expect("ui_theme should be reported");
+    assert_eq!(ui_theme.value, "not-a-theme");
+    assert_eq!(ui_theme.default, defaults.interface.ui_theme);
+
+    let tab_size = warnings
+        .iter()
+        .find(|w| w.field == "editor.tab_size")
+        .expect("tab_size should be reported");
+    assert_eq!(tab_size.value, "0");
+    assert_eq!(tab_size.default, defaults.editor.tab_size.to_string());
+
+    assert!(warnings.iter().any(|w| w.field == "shortcuts.create_note"));
+}
+
+#[test]
+fn test_sanitize_editor_config_before_live_apply() {
+    let defaults = EditorConfig::default();
+    let mut editor = EditorConfig {
+        mode: "vim".to_string(),
+        tab_size: 4,
+        ..EditorConfig::default()
+    };
+    let mut warnings = Vec::new();
+    sanitize_editor_config(&mut editor, &defaults, &mut warnings);
+    assert!(warnings.is_empty());
+    assert_eq!((editor.mode.as_str(), editor.tab_size), ("vim", 4));
+
+    let mut editor = EditorConfig {
+        mode: "nano".to_string(),
+        tab_size: 64,
+        ..EditorConfig::default()
+    };
+    sanitize_editor_config(&mut editor, &defaults, &mut warnings);
+    assert_eq!(editor, defaults);
+    let fields: Vec<&str> = warnings.iter().map(|w| w.field.as_str()).collect();
+    assert_eq!(fields, vec!["editor.mode", "editor.tab_size"]);
+}
+
+#[test]
+fn test_conflicting_shortcuts_keep_first_binding() {
+    let toml = r#"
+[shortcuts]
+create_note = "Ctrl+X"
+rename_note = "Ctrl+X"
+"#;
+
+    let config = load_config_from_content(toml);
+    assert_eq!(config.shortcuts.create_note, "Ctrl+x");
+    assert_eq!(config.shortcuts.rename_note, "Ctrl+m"); // default
+
+    let warnings = get_config_warnings(toml).expect("Valid TOML should produce warnings");
+    let conflict = warnings
+        .iter()
+        .find(|w| w.field == "shortcuts.rename_note")
+        .expect("Conflict should be reported");
+    assert_eq!(conflict.value, "Ctrl+x");
+    assert_eq!(conflict.default, "Ctrl+m");
+}
+
+#[test]
+fn test_conflicting_shortcut_reset_to_default_even_when_taken() {
+    // delete_note claims scroll_down's default, which scroll_down still goes back to
+    let config = load_config_from_content(
+        r#"
+[shortcuts]
+delete_note = "Ctrl+d"
+"#,
+    );
+
+    assert_eq!(config.shortcuts.delete_note, "Ctrl+d");
+    assert_eq!(config.shortcuts.scroll_down, "Ctrl+d");
+}
+
+#[test]
+fn test_config_warnings_empty_for_valid_config() {
+    let warnings = get_config_warnings("notes_directory = \"/tmp/symiosis-notes\"")
+        .expect("Minimal config should parse");
+    assert!(warnings.is_empty(), "Unexpected warnings: {:?}", warnings);
+}
+
+#[test]
+fn test_max_search_results_ceiling() {
+    let config_with = |max: usize| {
+        format!(
+            "notes_directory = \"/tmp/symiosis-notes\"\n[preferences]\nmax_search_results = {}",
+            max
+        )
+    };
+
+    let warnings = get_config_warnings(&config_with(MAX_SEARCH_RESULTS_CEILING))
+        .expect("Config at the ceiling should parse");
+    assert!(warnings.is_empty(), "Unexpected warnings: {:?}", warnings);
+
+    let warnings = get_config_warnings(&config_with(MAX_SEARCH_RESULTS_CEILING + 1))
+        .expect("Config over the ceiling should parse");
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "preferences.max_search_results"));
+
+    let mut preferences = AppConfig::default().preferences;
+    preferences.max_search_results = MAX_SEARCH_RESULTS_CEILING - 1;
+    assert!(validate_preferences_config(&preferences).is_ok());
+
+    preferences.max_search_results = MAX_SEARCH_RESULTS_CEILING + 1;
+    let err = validate_preferences_config(&preferences).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&MAX_SEARCH_RESULTS_CEILING.to_string()));
+}
+
+#[test]
+fn test_config_warnings_syntax_error() {
+    let result = get_config_warnings("[interface\nui_theme = ");
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_default_config_toml_round_trips() {
+    let toml = generate_default_config_toml().expect("Failed to generate default config TOML");
+    let parsed: AppConfig = toml::from_str(&toml).expect("Generated TOML should parse");
+    let defaults = AppConfig::default();
+
+    assert_eq!(parsed.global_shortcut, defaults.global_shortcut);
+    assert_eq!(parsed.interface.ui_theme, defaults.interface.ui_theme);
+    assert_eq!(parsed.editor.tab_size, defaults.editor.tab_size);
+    assert_eq!(parsed.shortcuts.create_note, defaults.shortcuts.create_note);
+    assert_eq!(
+        parsed.preferences.max_search_results,
+        defaults.preferences.max_search_results
+    );
+}
+
+#[test]
+fn test_default_config_toml_documents_options() {
+    let toml = generate_default_config_toml().expect("Failed to generate default config TOML");
+    let ui_theme_options = format!("# Options: {}", get_available_ui_themes().join(", "));
+
+    let ui_theme_line = toml
+        .lines()
+        .position(|line| line.starts_with("ui_theme = "))
+        .expect("ui_theme should be present");
+    assert_eq!(
+        toml.lines().nth(ui_theme_line - 1),
+        Some(ui_theme_options.as_str())
+    );
+    assert!(toml.contains("# custom_ui_theme_path = "));
+    assert!(toml.contains("[shortcuts]\n# Format: "));
+    assert!(toml.contains("global_shortcut = \"Ctrl+Shift+N\"\n# global_shortcut_new_note = "));
+}
+
+#[test]
+fn test_new_note_shortcut_validation() {
+    let config = load_config_from_content(
+        r#"
+global_shortcut_new_note = "Ctrl+Alt+N"
+"#,
+    );
+    assert_eq!(
+        config.global_shortcut_new_note.as_deref(),
+        Some("Ctrl+Alt+N")
+    );
+    assert_eq!(load_config_from_content("").global_shortcut_new_note, None);
+
+    let invalid = load_config_from_content(
+        r#"
+global_shortcut_new_note = "++Invalid++"
+"#,
+    );
+    assert_eq!(invalid.global_shortcut_new_note, None);
+}
+
+#[test]
+fn test_new_note_shortcut_must_differ_from_main() {
+    let toml = r#"
+global_shortcut = "Ctrl+Shift+N"
+global_shortcut_new_note = "shift+ctrl+n"
+"#;
+
+    let config = load_config_from_content(toml);
+    assert_eq!(config.global_shortcut, "Ctrl+Shift+N");
+    assert_eq!(config.global_shortcut_new_note, None);
+
+    let raw: AppConfig = toml::from_str(toml).expect("Valid TOML");
+    assert!(validate_config(&raw).is_err());
+
+    let warnings = get_config_warnings(toml).expect("Valid TOML should produce warnings");
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "global_shortcut_new_note"));
+}
+
+#[test]
+fn test_note_extensions_default_and_sanitizing() {
+    let config = load_config_from_content("");
+    assert_eq!(
+        config.preferences.note_extensions,
+        vec!["md", "txt", "markdown"]
+    );
+
+    let toml = r#"
+notes_directory = "/tmp/symiosis-notes"
+
+[preferences]
+note_extensions = ["md", ".txt"]
+"#;
+    let warnings = get_config_warnings(toml).expect("Valid TOML should produce warnings");
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "preferences.note_extensions"));
+
+    let config = load_config_from_content(toml);
+    assert_eq!(
+        config.preferences.note_extensions,
+        AppConfig::default().preferences.note_extensions
+    );
+}
+
+#[test]
+fn test_external_editor_command_requires_path_placeholder() {
+    let config_with = |command: &str| {
+        format!(
+            "notes_directory = \"/tmp/symiosis-notes\"\n[preferences]\nexternal_editor_command = '{}'",
+            command
+        )
+    };
+
+    let config = load_config_from_content(&config_with("code -g {path}:{line}"));
+    assert_eq!(
+        config.preferences.external_editor_command,
+        "code -g {path}:{line}"
+    );
+
+    let warnings =
+        get_config_warnings(&config_with("nvim")).expect("Valid TOML should produce warnings");
+    let warning = warnings
+        .iter()
+        .find(|w| w.field == "preferences.external_editor_command")
+        .expect("Command without {path} should be reported");
+    assert_eq!(warning.value, "nvim");
+
+    let config = load_config_from_content(&config_with("nvim"));
+    assert_eq!(config.preferences.external_editor_command, "");
+}
+
+#[test]
+#[serial]
+fn test_cache_database_path_moves_the_index() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let default_path = get_database_path().expect("Should resolve database path");
+    let local = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let cache_path = local.path().join("cache").join("notes.sqlite");
+
+    let path_setting = cache_path.to_string_lossy().to_string();
+    test_config.update_config(|config| {
+        config.preferences.cache_database_path = Some(path_setting.clone())
+    });
+    assert_eq!(get_database_path().unwrap(), cache_path);
+    assert!(
+        cache_path.parent().unwrap().is_dir(),
+        "The folder is created when validated"
+    );
+    assert_eq!(
+        crate::config::get_config_notes_dir(),
+        test_config.notes_dir(),
+        "Notes stay where they are"
+    );
+
+    test_config.update_config(|config| {
+        config.preferences.cache_database_path = Some("relative/notes.sqlite".into())
+    });
+    assert_eq!(get_database_path().unwrap(), default_path);
+}
+
+#[test]
+fn test_invalid_cache_database_path_falls_back_to_default() {
+    let dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let config_with = |path: &str| {
+        format!(
+            "notes_directory = \"/tmp/symiosis-notes\"\n[preferences]\ncache_database_path = '{}'",
+            path
+        )
+    };
+
+    for invalid in ["notes.sqlite", &dir.path().to_string_lossy()] {
+        let warnings =
+            get_config_warnings(&config_with(invalid)).expect("Valid TOML should produce warnings");
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.field == "preferences.cache_database_path" && w.value == invalid),
+            "{} should be rejected",
+            invalid
+        );
+        assert_eq!(
+            load_config_from_content(&config_with(invalid))
+                .preferences
+                .cache_database_path,
+            None
+        );
+    }
+}
+
+#[test]
+fn test_cache_pragmas_are_validated() {
+    let config = load_config_from_content(
+        "[preferences]\ncache_journal_mode = \"DELETE\"\ncache_synchronous = \"OFF\"",
+    );
+    assert_eq!(config.preferences.cache_journal_mode, "DELETE");
+    assert_eq!(config.preferences.cache_synchronous, "OFF");
+
+    let invalid = "[preferences]\ncache_journal_mode = \"wal2\"\ncache_synchronous = \"EXTRA\"";
+    let warnings = get_config_warnings(invalid).expect("Valid TOML should produce warnings");
+    for field in [
+        "preferences.cache_journal_mode",
+        "preferences.cache_synchronous",
+    ] {
+        assert!(warnings.iter().any(|w| w.field == field), "{}", field);
+    }
+    let config = load_config_from_content(invalid);
+    assert_eq!(config.preferences.cache_journal_mode, "WAL");
+    assert_eq!(config.preferences.cache_synchronous, "NORMAL");
+
+    let mut preferences = AppConfig::default().preferences;
+    preferences.cache_synchronous = "normal".to_string();
+    assert!(validate_preferences_config(&preferences).is_err());
+}
+
+#[test]
+fn test_line_ending_is_validated() {
+    assert_eq!(AppConfig::default().preferences.line_ending, "preserve");
+    let config = load_config_from_content("[preferences]\nline_ending = \"crlf\"");
+    assert_eq!(config.preferences.line_ending, "crlf");
+
+    let invalid = "[preferences]\nline_ending = \"CRLF\"";
+    let warnings = get_config_warnings(invalid).expect("Valid TOML should produce warnings");
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "preferences.line_ending"));
+    assert_eq!(
+        load_config_from_content(invalid).preferences.line_ending,
+        "preserve"
+    );
+
+    let mut preferences = AppConfig::default().preferences;
+    preferences.line_ending = "cr".to_string();
+    assert!(validate_preferences_config(&preferences).is_err());
+}
+
+#[test]
+fn test_markdown_flavor_is_validated() {
+    assert_eq!(AppConfig::default().preferences.markdown_flavor, "gfm");
+    let config = load_config_from_content("[preferences]\nmarkdown_flavor = \"commonmark\"");
+    assert_eq!(config.preferences.markdown_flavor, "commonmark");
+    assert!(RenderOptions::from_config(&config).commonmark);
+
+    let invalid = "[preferences]\nmarkdown_flavor = \"multimarkdown\"";
+    let warnings = get_config_warnings(invalid).expect("Valid TOML should produce warnings");
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "preferences.markdown_flavor"));
+    assert_eq!(
+        load_config_from_content(invalid)
+            .preferences
+            .markdown_flavor,
+        "gfm"
+    );
+
+    let mut preferences = AppConfig::default().preferences;
+    preferences.markdown_flavor = "GFM".to_string();
+    assert!(validate_preferences_config(&preferences).is_err());
+}
+
+#[test]
+fn test_note_sort_order_falls_back_to_modified_desc() {
+    let config_with = |order: &str| {
+        format!(
+            "notes_directory = \"/tmp/symiosis-notes\"\n[preferences]\nnote_sort_order = \"{}\"",
+            order
+        )
+    };
+
+    for order in [
+        "modified_desc",
+        "modified_asc",
+        "name_asc",
+        "name_desc",
+        "created_desc",
+    ] {
+        assert_eq!(
+            load_config_from_content(&config_with(order))
+                .preferences
+                .note_sort_order,
+            order
+        );
+    }
+
+    let config = load_config_from_content(&config_with("random"));
+    assert_eq!(config.preferences.note_sort_order, "modified_desc");
+    let warnings = get_config_warnings(&config_with("random")).expect("Valid TOML");
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "preferences.note_sort_order" && w.value == "random"));
+}
+
+#[test]
+fn test_version_history_preferences_are_bounded() {
+    let config_with = |limit: usize, interval: u64| {
+        format!(
+            "notes_directory = \"/tmp/symiosis-notes\"\n[preferences]\nversion_history_limit = {}\nversion_min_interval_seconds = {}",
+            limit, interval
+        )
+    };
+
+    let config = load_config_from_content(&config_with(5, 60));
+    assert_eq!(config.preferences.version_history_limit, 5);
+    assert_eq!(config.preferences.version_min_interval_seconds, 60);
+
+    let content = config_with(0, 1_000_000);
+    let config = load_config_from_content(&content);
+    assert_eq!(config.preferences.version_history_limit, 20);
+    assert_eq!(config.preferences.version_min_interval_seconds, 0);
+
+    let warnings = get_config_warnings(&content).expect("Valid TOML");
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "preferences.version_history_limit" && w.value == "0"));
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "preferences.version_min_interval_seconds" && w.value == "1000000"));
+}
+
+#[test]
+fn test_max_recursion_depth_must_be_positive() {
+    let content =
+        "notes_directory = \"/tmp/symiosis-notes\"\n[preferences]\nmax_recursion_depth = 0";
+    let config = load_config_from_content(content);
+    assert_eq!(config.preferences.max_recursion_depth, 64);
+
+    let warnings = get_config_warnings(content).expect("Valid TOML");
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "preferences.max_recursion_depth" && w.value == "0"));
+
+    let config = load_config_from_content(
+        "notes_directory = \"/tmp/symiosis-notes\"\n[preferences]\nmax_recursion_depth = 3",
+    );
+    assert_eq!(config.preferences.max_recursion_depth, 3);
+}
+
+#[test]
+fn test_logging_rotation_settings_are_bounded() {
+    let config_with = |size_mb: u64, archives: usize| {
+        format!(
+            "notes_directory = \"/tmp/symiosis-notes\"\n[logging]\nmax_file_size_mb = {}\nmax_archives = {}",
+            size_mb, archives
+        )
+    };
+
+    let config = load_config_from_content(&config_with(20, 0));
+    assert_eq!(config.logging.max_file_size_mb, 20);
+    assert_eq!(config.logging.max_archives, 0);
+
+    let content = config_with(0, 50);
+    let config = load_config_from_content(&content);
+    assert_eq!(config.logging.max_file_size_mb, 5);
+    assert_eq!(config.logging.max_archives, 3);
+
+    let warnings = get_config_warnings(&content).expect("Valid TOML");
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "logging.max_file_size_mb" && w.value == "0"));
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "logging.max_archives" && w.value == "50"));
+}
+
+#[test]
+fn test_vim_mappings_are_parsed() {
+    let config = load_config_from_content(
+        r#"
+notes_directory = "/tmp/symiosis-notes"
+
+[editor]
+mode = "vim"
+
+[editor.vim]
+leader = " "
+
+[editor.vim.mappings]
+"<leader>w" = "save"
+"zz" = "foldToggle"
+"#,
+    );
+
+    assert_eq!(config.editor.vim.leader, " ");
+    assert_eq!(config.editor.vim.mappings.len(), 2);
+    assert_eq!(config.editor.vim.mappings["<leader>w"], "save");
+    assert_eq!(config.editor.vim.mappings["zz"], "foldToggle");
+    assert!(validate_config(&config).is_ok());
+
+    let defaults = load_config_from_content("notes_directory = \"/tmp/symiosis-notes\"");
+    assert_eq!(defaults.editor.vim.leader, "\\");
+    assert!(defaults.editor.vim.mappings.is_empty());
+}
+
+#[test]
+fn test_invalid_vim_mappings_are_rejected() {
+    let content = r#"
+notes_directory = "/tmp/symiosis-notes"
+
+[editor.vim]
+leader = "ab"
+
+[editor.vim.mappings]
+"<leader>w" = "save"
+"<leader>x" = "launchMissiles"
+" " = "exit"
+"#;
+
+    let config = load_config_from_content(content);
+    assert_eq!(config.editor.vim.leader, "\\");
+    assert_eq!(
+        config.editor.vim.mappings.keys().collect::<Vec<_>>(),
+        vec!["<leader>w"]
+    );
+
+    let warnings = get_config_warnings(content).expect("Valid TOML");
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "editor.vim.leader" && w.value == "ab"));
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "editor.vim.mappings.<leader>x" && w.value == "launchMissiles"));
+    assert!(warnings.iter().any(|w| w.value == "exit"));
+    assert_eq!(warnings.len(), 3);
+}
+
+#[test]
+fn test_date_and_time_formats_are_validated() {
+    let config_with = |date_format: &str, time_format: &str| {
+        format!(
+            "notes_directory = \"/tmp/symiosis-notes\"\n[preferences]\ndate_format = \"{}\"\ntime_format = \"{}\"",
+            date_format, time_format
+        )
+    };
+
+    let config = load_config_from_content(&config_with("%d.%m.%Y", "%I:%M %p"));
+    assert_eq!(config.preferences.date_format, "%d.%m.%Y");
+    assert_eq!(config.preferences.time_format, "%I:%M %p");
+
+    let defaults = AppConfig::default();
+    assert_eq!(defaults.preferences.date_format, "%Y-%m-%d");
+    assert_eq!(defaults.preferences.time_format, "%H:%M");
+
+    let content = config_with("%Q", "");
+    let config = load_config_from_content(&content);
+    assert_eq!(config.preferences.date_format, "%Y-%m-%d");
+    assert_eq!(config.preferences.time_format, "%H:%M");
+
+    let warnings = get_config_warnings(&content).expect("Valid TOML");
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "preferences.date_format" && w.value == "%Q"));
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "preferences.time_format" && w.value.is_empty()));
+}
+
+#[test]
+fn test_render_cache_max_entries_is_bounded() {
+    let config_with = |entries: usize| {
+        format!(
+            "notes_directory = \"/tmp/symiosis-notes\"\n[preferences]\nrender_cache_max_entries = {}",
+            entries
+        )
+    };
+
+    assert_eq!(
+        load_config_from_content(&config_with(0))
+            .preferences
+            .render_cache_max_entries,
+        0
+    );
+
+    let content = config_with(1_000_000);
+    assert_eq!(
+        load_config_from_content(&content)
+            .preferences
+            .render_cache_max_entries,
+        500
+    );
+    let warnings = get_config_warnings(&content).expect("Valid TOML");
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "preferences.render_cache_max_entries" && w.value == "1000000"));
+}
+
+#[test]
+fn test_autosave_interval_is_capped() {
+    let content =
+        "notes_directory = \"/tmp/symiosis-notes\"\n[editor]\nautosave_interval_seconds = 7200";
+    let config = load_config_from_content(content);
+    assert_eq!(config.editor.autosave_interval_seconds, 0);
+
+    let warnings = get_config_warnings(content).expect("Valid TOML");
+    assert!(warnings
+        .iter()
+        .any(|w| w.field == "editor.autosave_interval_seconds" && w.value == "7200"));
+
+    let config = load_config_from_content(
+        "notes_directory = \"/tmp/symiosis-notes\"\n[editor]\nautosave_interval_seconds = 30",
+    );
+    assert_eq!(config.editor.autosave_interval_seconds, 30);
+}
+
+#[test]
+fn test_ensure_config_file_writes_template_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let config_path = dir.path().join("nested").join("config.toml");
+
+    assert!(ensure_config_file(&config_path).expect("Failed to write template"));
+    let template = std::fs::read_to_string(&config_path).unwrap();
+    assert_eq!(template, generate_default_config_toml().unwrap());
+
+    std::fs::write(&config_path, "notes_directory = \"/tmp/mine\"").unwrap();
+    assert!(!ensure_config_file(&config_path).expect("Existing config should be kept"));
+    assert_eq!(
+        std::fs::read_to_string(&config_path).unwrap(),
+        "notes_directory = \"/tmp/mine\""
+    );
+}
+
+#[test]
+fn test_write_config_atomically_keeps_backup() {
+    let dir = tempfile::tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+
+    write_config_atomically(&config_path, "first").expect("First write failed");
+    assert_eq!(std::fs::read_to_string(&config_path).unwrap(), "first");
+    assert!(!config_backup_path(&config_path).exists());
+
+    write_config_atomically(&config_path, "second").expect("Second write failed");
+    assert_eq!(std::fs::read_to_string(&config_path).unwrap(), "second");
+    assert_eq!(
+        std::fs::read_to_string(config_backup_path(&config_path)).unwrap(),
+        "first"
+    );
+
+    let leftovers: Vec<_> = std::fs::read_dir(dir.path())
+        .unwrap()
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.ends_with(".tmp"))
+        .collect();
+    assert!(
+        leftovers.is_empty(),
+        "Temp file left behind: {:?}",
+        leftovers
+    );
+}
+
+#[test]
+#[serial]
+fn test_save_config_content_rejects_invalid_toml_without_writing() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let config_path = get_config_path();
+    let original = std::fs::read_to_string(&config_path).unwrap();
+
+    assert!(save_config_content("notes_directory = [unclosed").is_err());
+    assert!(save_config_content("[preferences]\nmax_search_results = \"many\"").is_err());
+    assert_eq!(std::fs::read_to_string(&config_path).unwrap(), original);
+    assert!(!config_backup_path(&config_path).exists());
+
+    let valid = format!(
+        "notes_directory = \"{}\"\n",
+        test_config.notes_dir().display()
+    );
+    save_config_content(&valid).expect("Valid config should save");
+    assert_eq!(std::fs::read_to_string(&config_path).unwrap(), valid);
+    assert_eq!(
+        std::fs::read_to_string(config_backup_path(&config_path)).unwrap(),
+        original
+    );
+}
+
+#[test]
+fn test_parse_config_path_override() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let portable = temp_dir.path().join("portable").join("config.toml");
+
+    assert_eq!(
+        parse_config_path_override(&portable.to_string_lossy()),
+        Some(portable.clone())
+    );
+    assert_eq!(parse_config_path_override(""), None);
+    assert_eq!(parse_config_path_override("   "), None);
+    assert_eq!(parse_config_path_override("relative/config.toml"), None);
+    // A directory is a likely mistake for the file inside it
+    assert_eq!(
+        parse_config_path_override(&temp_dir.path().to_string_lossy()),
+        None
+    );
+}
+
+#[test]
+fn test_config_path_env_precedence() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let portable = temp_dir.path().join("config.toml");
+    let portable_str = portable.to_str().unwrap();
+    let test_path = temp_dir.path().join("test").join("config.toml");
+    let default = || PathBuf::from("/default/config.toml");
+
+    // The test override stays ahead of the environment so tests stay isolated
+    assert_eq!(
+        resolve_config_path(Some(test_path.clone()), Some(portable_str), default),
+        test_path
+    );
+    assert_eq!(
+        resolve_config_path(None, Some(portable_str), default),
+        portable
+    );
+    assert_eq!(
+        resolve_config_path(None, Some("relative/config.toml"), default),
+        default()
+    );
+    assert_eq!(
+        resolve_config_path(None, Some(temp_dir.path().to_str().unwrap()), default),
+        default(),
+        "A directory is not a config file"
+    );
+    assert_eq!(resolve_config_path(None, None, default), default());
+}
+
+#[test]
+#[serial]
+fn test_notes_dir_env_takes_precedence_over_config() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let configured = test_config.notes_dir();
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let portable_notes = temp_dir.path().join("notes").to_string_lossy().to_string();
+
+    std::env::set_var(NOTES_DIR_ENV, &portable_notes);
+    let overridden = load_config();
+    let config_file = std::fs::read_to_string(get_config_path()).unwrap();
+
+    std::env::set_var(NOTES_DIR_ENV, "/etc/notes");
+    let invalid = load_config();
+
+    std::env::remove_var(NOTES_DIR_ENV);
+    let restored = load_config();
+
+    assert_eq!(overridden.notes_directory, portable_notes);
+    // The override is never persisted to the config file
+    assert!(!config_file.contains(&portable_notes));
+    assert_eq!(PathBuf::from(&invalid.notes_directory), configured);
+    assert_eq!(PathBuf::from(&restored.notes_directory), configured);
+}
+
+#[test]
+#[serial]
+fn test_active_config_is_sanitized() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_config.update_config(|config| config.preferences.max_search_results = 0);
+
+    let config_file = std::fs::read_to_string(get_config_path()).unwrap();
+    assert!(config_file.contains("max_search_results = 0"));
+
+    let active = test_get_active_config();
+    assert_eq!(
+        active.preferences.max_search_results,
+        AppConfig::default().preferences.max_search_results
+    );
+    assert_eq!(
+        PathBuf::from(&active.notes_directory),
+        test_config.notes_dir()
+    );
+}
+
+#[test]
+#[serial]
+fn test_initialize_first_run_is_idempotent() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let chosen = test_config.notes_dir().with_file_name("chosen_notes");
+    let chosen_str = chosen.to_string_lossy().to_string();
+
+    let app_state = crate::core::state::AppState::new_with_fallback(load_config())
+        .expect("Test database setup failed");
+    app_state.set_first_run(true);
+
+    let config = initialize_first_run_setup(&app_state, &chosen_str).expect("Setup should succeed");
+    assert_eq!(config.notes_directory, chosen_str);
+    assert!(chosen.is_dir());
+    assert!(crate::utilities::paths::get_templates_dir().is_dir());
+    assert!(!app_state
+        .was_first_run()
+        .load(std::sync::atomic::Ordering::Relaxed));
+
+    let written = std::fs::read_to_string(get_config_path()).unwrap();
+    assert!(written.contains("# Directory where notes are stored"));
+    assert_eq!(load_config().notes_directory, chosen_str);
+
+    let again = initialize_first_run_setup(&app_state, &chosen_str).expect("Repeat should succeed");
+    assert_eq!(again.notes_directory, config.notes_directory);
+    assert_eq!(std::fs::read_to_string(get_config_path()).unwrap(), written);
+
+    assert!(
+        initialize_first_run_setup(&app_state, &test_config.notes_dir().to_string_lossy()).is_err()
+    );
+    assert!(initialize_first_run_setup(&app_state, "/etc/notes").is_err());
+    assert_eq!(std::fs::read_to_string(get_config_path()).unwrap(), written);
+}
+
+#[test]
+#[serial]
+fn test_reload_opens_moved_cache_database() {
+    use crate::commands::config::apply_reloaded_database_settings;
+    use crate::core::state::AppState;
+    use crate::database::with_db;
+    use crate::tests::test_utils::test_create_new_note;
+
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_create_new_note("kept.md").expect("Failed to create note");
+    let old_config = load_config();
+    let app_state = AppState::new_with_fallback(old_config.clone()).unwrap();
+    let moved = test_config
+        .notes_dir()
+        .parent()
+        .unwrap()
+        .join("moved-cache")
+        .join("moved.sqlite");
+
+    test_config.update_config(|config| {
+        config.preferences.cache_database_path = Some(moved.display().to_string());
+        config.preferences.cache_journal_mode = "DELETE".to_string();
+    });
+    let new_config = load_config();
+    apply_reloaded_database_settings(&app_state, &old_config, &new_config)
+        .expect("Reload should succeed");
+
+    let (db_file, journal_mode, notes): (String, String, Vec<String>) =
+        with_db(&app_state, |conn| {
+            let db_file = conn.query_row("PRAGMA database_list", [], |row| row.get(2))?;
+            let journal_mode = conn.query_row("PRAGMA journal_mode", [], |row| row.get(0))?;
+            let mut stmt = conn.prepare("SELECT filename FROM notes")?;
+            let notes = stmt
+                .query_map([], |row| row.get(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok((db_file, journal_mode, notes))
+        })
+        .unwrap();
+    assert_eq!(PathBuf::from(db_file), moved);
+    assert!(
+        journal_mode.eq_ignore_ascii_case("delete"),
+        "{}",
+        journal_mode
+    );
+    assert_eq!(
+        notes,
+        vec!["kept.md"],
+        "The new database is filled from disk"
+    );
+}
+
+#[test]
+#[serial]
+fn test_reload_rejects_invalid_config() {
+    use crate::commands::config::read_config_for_reload;
+
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let config_path = get_config_path();
+    let valid = read_config_for_reload(&config_path).expect("The test config should reload");
+    assert_eq!(
+        PathBuf::from(&valid.notes_directory),
+        test_config.notes_dir()
+    );
+
+    std::fs::write(&config_path, "notes_directory = [unclosed").unwrap();
+    let err = read_config_for_reload(&config_path).unwrap_err();
+    assert!(err.to_string().contains("TOML syntax error"), "{}", err);
+
+    std::fs::write(&config_path, "[editor]\ntab_size = 99\n").unwrap();
+    let err = read_config_for_reload(&config_path).unwrap_err();
+    assert!(
+        err.to_string().contains("keeping previous config"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_reload_emits_config_reloaded() {
+    use crate::commands::config::notify_config_reloaded;
+    use std::sync::mpsc;
+    use tauri::Listener;
+
+    let app = tauri::test::mock_app();
+    let (tx, rx) = mpsc::channel();
+    app.listen("config-reloaded", move |event| {
+        tx.send(event.payload().to_string()).unwrap();
+    });
+
+    let config = AppConfig {
+        global_shortcut: "Alt+Space".to_string(),
+        ..AppConfig::default()
+    };
+    notify_config_reloaded(app.handle(), &config);
+
+    let payload = rx
+        .recv_timeout(std::time::Duration::from_secs(5))
+        .expect("config-reloaded should be emitted");
+    let received: AppConfig = serde_json::from_str(&payload).unwrap();
+    assert_eq!(received.global_shortcut, "Alt+Space");
+}
+
+#[test]
+#[serial]
+fn test_refresh_reload_applies_changed_shortcuts() {
+    use std::sync::mpsc;
+    use std::sync::RwLock;
+    use tauri::Listener;
+
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let app_config = RwLock::new(load_config());
+    test_config.update_config(|c| {
+        c.global_shortcut = "Alt+Space".to_string();
+        c.global_shortcut_new_note = Some("Ctrl+Alt+N".to_string());
+    });
+
+    let app = tauri::test::mock_app();
+    let (tx, rx) = mpsc::channel();
+    app.listen("config-updated", move |event| {
+        tx.send(event.payload().to_string()).unwrap();
+    });
+
+    // The same reload `refresh_cache` runs after Settings are saved
+    crate::config::reload_config(&app_config, Some(app.handle().clone()))
+        .expect("Reload with changed shortcuts should succeed");
+
+    let config = app_config.read().unwrap();
+    assert_eq!(config.global_shortcut, "Alt+Space");
+    assert_eq!(
+        config.global_shortcut_new_note.as_deref(),
+        Some("Ctrl+Alt+N")
+    );
+    let payload = rx
+        .recv_timeout(std::time::Duration::from_secs(5))
+        .expect("config-updated should be emitted");
+    let received: AppConfig = serde_json::from_str(&payload).unwrap();
+    assert_eq!(received.global_shortcut, "Alt+Space");
+}
+
+#[test]
+#[serial]
+fn test_reload_keeps_notes_dir_env_override() {
+    use crate::commands::config::read_config_for_reload;
+
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let portable_notes = temp_dir.path().join("notes").to_string_lossy().to_string();
+
+    std::env::set_var(NOTES_DIR_ENV, &portable_notes);
+    let reloaded = read_config_for_reload(&get_config_path());
+    std::env::remove_var(NOTES_DIR_ENV);
+
+    let reloaded = reloaded.expect("The test config should reload");
+    assert_eq!(reloaded.notes_directory, portable_notes);
+}
@@ -1,5 +1,7 @@
+use crate::core::AppError;
 use crate::tests::test_utils::test_save_note_with_content_check;
 use crate::tests::test_utils::TestConfigOverride;
+use crate::tests::test_utils::{test_force_save_note, test_save_note_detecting_conflict};
 use serial_test::serial;
 use std::fs;
 
@@ -190,4 +192,30 @@ mod serial_tests {
         // Cleanup
         let _ = fs::remove_file(&file_path);
     }
+
+    #[test]
+    fn test_save_conflict_returns_disk_content_and_force_save_overwrites() {
+        let test_config = TestConfigOverride::new().expect("Should create test config");
+        let note_name = "shared.md";
+        let note_path = test_config.notes_dir().join(note_name);
+        fs::write(&note_path, "Read by the editor").unwrap();
+
+        // Another editor saves first
+        fs::write(&note_path, "Saved in vim").unwrap();
+
+        let conflict =
+            test_save_note_detecting_conflict(note_name, "Edited here", "Read by the editor")
+                .expect_err("Save over an external change must conflict");
+        match conflict {
+            AppError::SaveConflict { note, disk_content } => {
+                assert_eq!(note, note_name);
+                assert_eq!(disk_content, "Saved in vim");
+            }
+            other => panic!("Expected SaveConflict, got {:?}", other),
+        }
+        assert_eq!(fs::read_to_string(&note_path).unwrap(), "Saved in vim");
+
+        test_force_save_note(note_name, "Edited here").expect("Force save should succeed");
+        assert_eq!(fs::read_to_string(&note_path).unwrap(), "Edited here");
+    }
 }
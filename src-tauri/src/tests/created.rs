@@ -0,0 +1,132 @@
+//! Note Creation Time Unit Tests
+//!
+//! Tests recording when notes are first seen and sorting by it.
+
+use crate::core::state::AppState;
+use crate::database::{with_db, with_db_mut};
+use crate::services::database_service::{recreate_database, sync_notes_with_filesystem};
+use crate::tests::test_utils::{
+    test_create_new_note, test_delete_note, test_list_all_notes, test_rename_note,
+    test_save_note_with_content_check, TestConfigOverride,
+};
+use rusqlite::{params, OptionalExtension};
+use serial_test::serial;
+use std::fs;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn app_state() -> AppState {
+    AppState::new_with_fallback(crate::config::load_config()).expect("Test database setup failed")
+}
+
+fn created_at(name: &str) -> Option<i64> {
+    with_db(&app_state(), |conn| {
+        Ok(conn
+            .query_row(
+                "SELECT created_at FROM note_created_at WHERE filename = ?1",
+                params![name],
+                |row| row.get(0),
+            )
+            .optional()?)
+    })
+    .expect("Failed to read creation time")
+}
+
+fn set_created_at(name: &str, created_at: i64) {
+    with_db(&app_state(), |conn| {
+        conn.execute(
+            "UPDATE note_created_at SET created_at = ?2 WHERE filename = ?1",
+            params![name, created_at],
+        )?;
+        Ok(())
+    })
+    .expect("Failed to set creation time");
+}
+
+fn sync() {
+    let app_state = app_state();
+    with_db_mut(&app_state, |conn| {
+        sync_notes_with_filesystem(&app_state, conn, false, None).map_err(|e| e.into())
+    })
+    .expect("Sync should succeed");
+}
+
+#[test]
+#[serial]
+fn test_created_at_survives_edits_and_renames() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let before = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    test_create_new_note("draft.md").expect("Failed to create note");
+    let recorded = created_at("draft.md").expect("Creation time should be recorded");
+    assert!(
+        recorded >= before - 1,
+        "{} should not predate {}",
+        recorded,
+        before
+    );
+
+    set_created_at("draft.md", 1_000);
+    test_save_note_with_content_check("draft.md", "# Draft\n\nedited", "")
+        .expect("Failed to save note");
+    sync();
+    assert_eq!(created_at("draft.md"), Some(1_000));
+
+    test_rename_note("draft.md".to_string(), "final.md".to_string())
+        .expect("Failed to rename note");
+    assert_eq!(created_at("final.md"), Some(1_000));
+    assert_eq!(created_at("draft.md"), None);
+
+    recreate_database(&app_state()).expect("Failed to rebuild database");
+    assert_eq!(created_at("final.md"), Some(1_000));
+
+    test_delete_note("final.md").expect("Failed to delete note");
+    assert_eq!(created_at("final.md"), None);
+}
+
+#[test]
+#[serial]
+fn test_created_at_initialized_when_first_indexed() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+
+    let path = notes_dir.join("existing.md");
+    fs::write(&path, "# Existing").expect("Failed to write note");
+    let old_mtime = UNIX_EPOCH + Duration::from_secs(1_500_000_000);
+    fs::OpenOptions::new()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(old_mtime)
+        .unwrap();
+    sync();
+
+    let expected = fs::metadata(&path)
+        .and_then(|metadata| metadata.created())
+        .map(|time| time.duration_since(UNIX_EPOCH).unwrap().as_secs() as i64)
+        .unwrap_or(1_500_000_000);
+    assert_eq!(created_at("existing.md"), Some(expected));
+}
+
+#[test]
+#[serial]
+fn test_created_desc_ignores_later_edits() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    for name in ["first.md", "second.md", "third.md"] {
+        test_create_new_note(name).expect("Failed to create note");
+    }
+    set_created_at("first.md", 1_000);
+    set_created_at("second.md", 2_000);
+    set_created_at("third.md", 3_000);
+    test_save_note_with_content_check("first.md", "# First\n\nedited last", "")
+        .expect("Failed to save note");
+
+    test_config.update_config(|config| config.preferences.note_sort_order = "created_desc".into());
+    assert_eq!(
+        test_list_all_notes().expect("Failed to list notes"),
+        vec!["third.md", "second.md", "first.md"]
+    );
+}
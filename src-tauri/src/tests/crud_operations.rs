@@ -5,12 +5,25 @@
 
 // Test wrappers imported from test_utils
 use crate::tests::test_utils::{
-    test_create_new_note, test_delete_note, test_get_note_content, test_get_note_html_content,
-    test_list_all_notes, test_rename_note, test_save_note_with_content_check, TestConfigOverride,
+    test_append_to_note, test_create_new_note, test_delete_note, test_delete_notes,
+    test_duplicate_note, test_get_note_content, test_get_note_html_content, test_list_all_notes,
+    test_move_note, test_rename_note, test_save_note_with_content_check, TestConfigOverride,
 };
+use crate::utilities::strings::duplicate_note_name;
 use serial_test::serial;
 use std::fs;
 
+#[test]
+fn test_duplicate_note_name_keeps_folder_and_extension() {
+    assert_eq!(duplicate_note_name("plan.md", 1), "plan copy.md");
+    assert_eq!(duplicate_note_name("plan.md", 2), "plan copy 2.md");
+    assert_eq!(
+        duplicate_note_name("work/v1.2/plan.txt", 3),
+        "work/v1.2/plan copy 3.txt"
+    );
+    assert_eq!(duplicate_note_name(".hidden", 1), ".hidden copy");
+}
+
 #[cfg(test)]
 #[serial]
 mod serial_tests {
@@ -340,6 +353,107 @@ mod serial_tests {
         );
     }
 
+    #[test]
+    fn test_duplicate_note_increments_copy_number() {
+        let _test_config = TestConfigOverride::new().expect("Should create test config");
+
+        test_create_new_note("folder/template.md").expect("Should create note");
+        test_save_note_with_content_check("folder/template.md", "Template body", "")
+            .expect("Should save content");
+
+        let first = test_duplicate_note("folder/template.md").expect("Should duplicate note");
+        assert_eq!(first, "folder/template copy.md");
+        let second = test_duplicate_note("folder/template.md").expect("Should duplicate again");
+        assert_eq!(second, "folder/template copy 2.md");
+
+        assert_eq!(
+            test_get_note_content(&second).expect("Copy should be indexed"),
+            "Template body"
+        );
+        let notes = test_list_all_notes().expect("Should list notes");
+        assert!(notes.contains(&first));
+        assert!(notes.contains(&second));
+
+        assert!(test_duplicate_note("folder/missing.md").is_err());
+    }
+
+    #[test]
+    fn test_save_normalizes_line_endings() {
+        let test_config = TestConfigOverride::new().expect("Should create test config");
+        let note_path = test_config.notes_dir().join("synced.md");
+        test_create_new_note("synced.md").expect("Should create note");
+
+        let mixed = "one\r\ntwo\nthree\r\n";
+        test_save_note_with_content_check("synced.md", mixed, "").expect("Should save");
+        assert_eq!(
+            fs::read_to_string(&note_path).unwrap(),
+            mixed,
+            "preserve writes line breaks unchanged"
+        );
+
+        test_config.update_config(|config| config.preferences.line_ending = "lf".to_string());
+        test_save_note_with_content_check("synced.md", "one\r\ntwo\r\n", mixed)
+            .expect("Should save");
+        assert_eq!(fs::read_to_string(&note_path).unwrap(), "one\ntwo\n");
+        assert_eq!(test_get_note_content("synced.md").unwrap(), "one\ntwo\n");
+
+        // The editor's LF copy of a CRLF note is not a conflict
+        test_config.update_config(|config| config.preferences.line_ending = "crlf".to_string());
+        test_save_note_with_content_check("synced.md", "one\ntwo\nthree", "one\ntwo\n")
+            .expect("Should save");
+        test_save_note_with_content_check("synced.md", "one\ntwo", "one\ntwo\nthree")
+            .expect("Saving over a CRLF note should not conflict");
+        assert_eq!(fs::read_to_string(&note_path).unwrap(), "one\r\ntwo");
+    }
+
+    #[test]
+    fn test_append_to_note_creates_and_appends_lines() {
+        let test_config = TestConfigOverride::new().expect("Should create test config");
+        test_config.update_config(|config| {
+            config.preferences.date_format = "[day]".to_string();
+            config.preferences.time_format = "now".to_string();
+        });
+
+        let size = test_append_to_note("inbox/capture.md", "First idea\n", false)
+            .expect("Should create the note");
+        assert_eq!(size, "First idea\n".len() as u64);
+
+        // A note without a trailing newline gets one before the new line
+        let note_path = test_config.notes_dir().join("inbox/capture.md");
+        fs::write(&note_path, "First idea").expect("Should edit note on disk");
+        let size = test_append_to_note("inbox/capture.md", "Call back", true)
+            .expect("Should append to the note");
+
+        let expected = "First idea\n[day] now Call back\n";
+        assert_eq!(size, expected.len() as u64);
+        assert_eq!(fs::read_to_string(&note_path).unwrap(), expected);
+        assert_eq!(
+            test_get_note_content("inbox/capture.md").expect("Append should be indexed"),
+            expected
+        );
+
+        assert!(test_append_to_note("../outside.md", "text", false).is_err());
+    }
+
+    #[test]
+    fn test_append_to_note_applies_the_save_format() {
+        let test_config = TestConfigOverride::new().expect("Should create test config");
+        test_config.update_config(|config| {
+            config.preferences.line_ending = "crlf".to_string();
+            config.preferences.trim_trailing_whitespace = true;
+        });
+        let note_path = test_config.notes_dir().join("capture.md");
+        fs::write(&note_path, "First idea \r\n").expect("Should write note");
+
+        test_append_to_note("capture.md", "Call back  ", false).expect("Should append");
+
+        assert_eq!(
+            fs::read_to_string(&note_path).unwrap(),
+            "First idea\r\nCall back  \r\n",
+            "Appends are tidied like saves, keeping a markdown hard break"
+        );
+    }
+
     #[test]
     fn test_rename_note_with_subdirectories() {
         let _test_config = TestConfigOverride::new().expect("Should create test config");
@@ -369,6 +483,63 @@ mod serial_tests {
         assert_eq!(new_content, content, "Content should be preserved");
     }
 
+    #[test]
+    fn test_move_note_into_folder() {
+        let test_config = TestConfigOverride::new().expect("Should create test config");
+        test_create_new_note("plan.md").expect("Should create note");
+        test_save_note_with_content_check("plan.md", "# Plan", "").expect("Should save content");
+
+        let moved = test_move_note("plan.md", "projects/2024/").expect("Should move note");
+        assert_eq!(moved, "projects/2024/plan.md");
+        assert!(!test_config.notes_dir().join("plan.md").exists());
+        assert_eq!(
+            fs::read_to_string(test_config.notes_dir().join(&moved)).unwrap(),
+            "# Plan"
+        );
+        assert_eq!(test_get_note_content(&moved).unwrap(), "# Plan");
+
+        let notes = test_list_all_notes().expect("Should list notes");
+        assert!(notes.contains(&moved));
+        assert!(!notes.contains(&"plan.md".to_string()));
+
+        // Moving to where it already is leaves it alone
+        assert_eq!(
+            test_move_note(&moved, "projects/2024").unwrap(),
+            "projects/2024/plan.md"
+        );
+
+        let back = test_move_note(&moved, "").expect("Should move note back to the root");
+        assert_eq!(back, "plan.md");
+        assert!(test_config.notes_dir().join("plan.md").exists());
+    }
+
+    #[test]
+    fn test_move_note_rejects_collisions_and_traversal() {
+        let test_config = TestConfigOverride::new().expect("Should create test config");
+        test_create_new_note("todo.md").expect("Should create note");
+        test_create_new_note("work/todo.md").expect("Should create note");
+
+        let err = test_move_note("todo.md", "work").expect_err("Collision should fail");
+        assert!(err.contains("already exists in work"), "{}", err);
+        assert!(test_config.notes_dir().join("todo.md").exists());
+
+        for target in [
+            "../outside",
+            "work/../../outside",
+            "/tmp",
+            ".hidden",
+            "a\\b",
+        ] {
+            assert!(
+                test_move_note("todo.md", target).is_err(),
+                "Target '{}' should be rejected",
+                target
+            );
+        }
+        assert!(test_move_note("missing.md", "work").is_err());
+        assert!(test_config.notes_dir().join("todo.md").exists());
+    }
+
     #[test]
     fn test_delete_note_success() {
         let _test_config = TestConfigOverride::new().expect("Should create test config");
@@ -420,6 +591,30 @@ mod serial_tests {
         );
     }
 
+    #[test]
+    fn test_delete_notes_reports_per_note_results() {
+        let _test_config = TestConfigOverride::new().expect("Should create test config");
+
+        test_create_new_note("batch_one.md").expect("Should create note");
+        test_create_new_note("batch_two.md").expect("Should create note");
+
+        let results = test_delete_notes(&["batch_one.md", "../escape.md", "batch_two.md"])
+            .expect("Batch delete should complete");
+
+        assert_eq!(results.len(), 3, "Should report a result for every note");
+        assert_eq!(results[0], ("batch_one.md".to_string(), Ok(())));
+        assert!(
+            results[1].1.is_err(),
+            "Invalid name should fail without aborting the batch"
+        );
+        assert_eq!(results[2], ("batch_two.md".to_string(), Ok(())));
+
+        let notes = test_list_all_notes().expect("Should list notes");
+        assert!(!notes.contains(&"batch_one.md".to_string()));
+        assert!(!notes.contains(&"batch_two.md".to_string()));
+        assert!(!_test_config.notes_dir().join("batch_one.md").exists());
+    }
+
     #[test]
     fn test_delete_note_with_backup_verification() {
         let _test_config = TestConfigOverride::new().expect("Should create test config");
@@ -3,10 +3,16 @@
 //! Tests for database integration functionality and backup systems.
 
 use crate::config::get_config_notes_dir;
+use crate::config::PreferencesConfig;
+use crate::core::state::AppState;
+use crate::database::{apply_cache_pragmas, with_db};
+use crate::tests::test_utils::{test_save_note_with_content_check, TestConfigOverride};
 use crate::utilities::paths::encode_path_for_backup;
 use crate::utilities::paths::{
-    get_backup_dir_for_notes_path, get_database_path_for_notes_dir, get_temp_dir,
+    get_backup_dir_for_notes_path, get_database_path, get_database_path_for_notes_dir, get_temp_dir,
 };
+use serial_test::serial;
+use std::fs;
 use std::path::PathBuf;
 
 #[test]
@@ -287,3 +293,64 @@ fn test_path_encoding_with_friendly_names_and_uniqueness() {
         );
     }
 }
+
+#[test]
+#[serial]
+fn test_corrupt_database_is_moved_aside_and_rebuilt() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_save_note_with_content_check("kept.md", "# Kept", "").expect("Failed to save note");
+
+    let db_path = get_database_path().expect("Should resolve database path");
+    let garbage = vec![0xA5u8; 4096];
+    fs::write(&db_path, &garbage).expect("Failed to corrupt database");
+
+    let app_state = AppState::new(crate::config::load_config())
+        .expect("A corrupt database should be recovered, not fail startup");
+
+    let indexed: Vec<String> = with_db(&app_state, |conn| {
+        let mut stmt = conn.prepare("SELECT filename FROM notes")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    })
+    .expect("Fresh database should be usable");
+    assert_eq!(indexed, vec!["kept.md"], "Notes are re-read from disk");
+
+    let quarantined = app_state
+        .take_quarantined_database()
+        .expect("Recovery should be reported");
+    assert!(quarantined
+        .to_string_lossy()
+        .starts_with(&format!("{}.corrupt-", db_path.display())));
+    assert_eq!(fs::read(&quarantined).unwrap(), garbage);
+    assert_eq!(app_state.take_quarantined_database(), None, "Reported once");
+}
+
+#[test]
+fn test_cache_pragmas_are_applied() {
+    let dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let conn = rusqlite::Connection::open(dir.path().join("notes.sqlite")).unwrap();
+    let pragma = |name: &str| -> String {
+        conn.query_row(&format!("PRAGMA {}", name), [], |row| {
+            row.get::<_, rusqlite::types::Value>(0)
+        })
+        .map(|value| match value {
+            rusqlite::types::Value::Text(text) => text,
+            rusqlite::types::Value::Integer(level) => level.to_string(),
+            other => format!("{:?}", other),
+        })
+        .unwrap()
+    };
+
+    apply_cache_pragmas(&conn, &PreferencesConfig::default());
+    assert_eq!(pragma("journal_mode"), "wal", "WAL is the default");
+    assert_eq!(pragma("synchronous"), "1", "NORMAL is the default");
+
+    let preferences = PreferencesConfig {
+        cache_journal_mode: "DELETE".to_string(),
+        cache_synchronous: "FULL".to_string(),
+        ..PreferencesConfig::default()
+    };
+    apply_cache_pragmas(&conn, &preferences);
+    assert_eq!(pragma("journal_mode"), "delete");
+    assert_eq!(pragma("synchronous"), "2");
+}
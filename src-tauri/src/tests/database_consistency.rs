@@ -757,3 +757,312 @@ mod real_database_function_tests {
         }
     }
 }
+
+#[test]
+#[serial]
+fn test_noop_refresh_reads_no_files() {
+    use super::test_utils::TestConfigOverride;
+    use crate::core::state::AppState;
+    use crate::database::with_db_mut;
+    use crate::services::database_service::sync_notes_with_filesystem;
+    use std::fs;
+
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+    const NOTE_COUNT: usize = 500;
+
+    for i in 0..NOTE_COUNT {
+        fs::write(
+            notes_dir.join(format!("note_{}.md", i)),
+            format!("# Note {}\n\nbody", i),
+        )
+        .expect("Failed to write note");
+    }
+
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Test database setup failed");
+    let sync = |force: bool| {
+        with_db_mut(&app_state, |conn| {
+            sync_notes_with_filesystem(&app_state, conn, force, None).map_err(|e| e.into())
+        })
+        .expect("Sync should succeed")
+    };
+
+    sync(false);
+
+    let files_read = sync(false);
+    assert_eq!(files_read, 0, "Unchanged notes must not be re-read");
+
+    // Same modified time but different size must still be detected
+    let edited = notes_dir.join("note_7.md");
+    let mtime = fs::metadata(&edited).unwrap().modified().unwrap();
+    fs::write(&edited, "# Note 7\n\nlonger body after edit").expect("Failed to edit note");
+    fs::OpenOptions::new()
+        .write(true)
+        .open(&edited)
+        .unwrap()
+        .set_modified(mtime)
+        .unwrap();
+    assert_eq!(sync(false), 1);
+
+    assert_eq!(sync(true), NOTE_COUNT, "Force should re-read every note");
+}
+
+#[test]
+#[serial]
+fn test_refresh_respects_max_recursion_depth() {
+    use super::test_utils::TestConfigOverride;
+    use crate::core::state::AppState;
+    use crate::database::with_db_mut;
+    use crate::services::database_service::sync_notes_with_filesystem;
+    use std::fs;
+
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+
+    for dir in ["", "work", "work/deep"] {
+        fs::create_dir_all(notes_dir.join(dir)).expect("Failed to create folder");
+        fs::write(notes_dir.join(dir).join("todo.md"), "# Todo").expect("Failed to write note");
+    }
+    test_config.update_config(|config| config.preferences.max_recursion_depth = 2);
+
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Test database setup failed");
+    let indexed = with_db_mut(&app_state, |conn| {
+        sync_notes_with_filesystem(&app_state, conn, true, None)?;
+        let mut stmt = conn.prepare("SELECT filename FROM notes ORDER BY filename")?;
+        let filenames = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(filenames)
+    })
+    .expect("Sync should succeed");
+
+    // Same-named notes keep their relative paths, and work/deep is past the limit
+    assert_eq!(indexed, vec!["todo.md", "work/todo.md"]);
+}
+
+#[test]
+#[serial]
+fn test_refresh_skips_ignored_paths() {
+    use super::test_utils::TestConfigOverride;
+    use crate::core::state::AppState;
+    use crate::database::with_db_mut;
+    use crate::services::database_service::sync_notes_with_filesystem;
+    use std::fs;
+
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+
+    for dir in ["", "drafts", ".git", ".archive"] {
+        fs::create_dir_all(notes_dir.join(dir)).expect("Failed to create folder");
+        fs::write(notes_dir.join(dir).join("todo.md"), "# Todo").expect("Failed to write note");
+    }
+    fs::write(notes_dir.join("scratch.md"), "# Scratch").expect("Failed to write note");
+    fs::write(notes_dir.join(".symiosisignore"), "drafts/\nscratch.md\n")
+        .expect("Failed to write ignore file");
+
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Test database setup failed");
+    let indexed = with_db_mut(&app_state, |conn| {
+        sync_notes_with_filesystem(&app_state, conn, true, None)?;
+        let mut stmt = conn.prepare("SELECT filename FROM notes ORDER BY filename")?;
+        let filenames = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(filenames)
+    })
+    .expect("Sync should succeed");
+
+    assert_eq!(indexed, vec!["todo.md"]);
+}
+
+#[cfg(unix)]
+fn indexed_after_sync() -> Vec<(String, String)> {
+    use crate::core::state::AppState;
+    use crate::database::with_db_mut;
+    use crate::services::database_service::sync_notes_with_filesystem;
+
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Test database setup failed");
+    with_db_mut(&app_state, |conn| {
+        sync_notes_with_filesystem(&app_state, conn, true, None)?;
+        let mut stmt = conn.prepare("SELECT filename, content FROM notes ORDER BY filename")?;
+        let notes = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(notes)
+    })
+    .expect("Sync should succeed")
+}
+
+#[cfg(unix)]
+#[test]
+#[serial]
+fn test_refresh_follows_file_symlinks_only() {
+    use super::test_utils::TestConfigOverride;
+    use std::fs;
+    use std::os::unix::fs::symlink;
+
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+    let elsewhere = tempfile::TempDir::new().expect("Failed to create temp dir");
+
+    fs::write(notes_dir.join("local.md"), "# Local").expect("Failed to write note");
+    fs::write(elsewhere.path().join("shared.md"), "# Shared").expect("Failed to write note");
+    fs::create_dir_all(elsewhere.path().join("folder")).expect("Failed to create folder");
+    fs::write(elsewhere.path().join("folder/inside.md"), "# Inside").expect("Failed to write note");
+
+    symlink(
+        elsewhere.path().join("shared.md"),
+        notes_dir.join("shared.md"),
+    )
+    .expect("Failed to link note");
+    symlink(
+        elsewhere.path().join("folder"),
+        notes_dir.join("linked-folder"),
+    )
+    .expect("Failed to link folder");
+    // A second name for a note that is already indexed
+    symlink(notes_dir.join("local.md"), notes_dir.join("alias.md")).expect("Failed to link note");
+    // Two links pointing at each other
+    symlink(notes_dir.join("loop-b.md"), notes_dir.join("loop-a.md")).expect("Failed to link");
+    symlink(notes_dir.join("loop-a.md"), notes_dir.join("loop-b.md")).expect("Failed to link");
+    symlink(notes_dir.join("missing.md"), notes_dir.join("broken.md")).expect("Failed to link");
+
+    assert_eq!(
+        indexed_after_sync(),
+        vec![
+            ("local.md".to_string(), "# Local".to_string()),
+            ("shared.md".to_string(), "# Shared".to_string()),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+#[serial]
+fn test_refresh_ignores_symlinks_when_disabled() {
+    use super::test_utils::TestConfigOverride;
+    use std::fs;
+    use std::os::unix::fs::symlink;
+
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_config.update_config(|config| config.preferences.follow_symlinks = false);
+    let notes_dir = test_config.notes_dir();
+    let elsewhere = tempfile::TempDir::new().expect("Failed to create temp dir");
+
+    fs::write(notes_dir.join("local.md"), "# Local").expect("Failed to write note");
+    fs::write(elsewhere.path().join("shared.md"), "# Shared").expect("Failed to write note");
+    symlink(
+        elsewhere.path().join("shared.md"),
+        notes_dir.join("shared.md"),
+    )
+    .expect("Failed to link note");
+
+    let indexed: Vec<String> = indexed_after_sync()
+        .into_iter()
+        .map(|(filename, _)| filename)
+        .collect();
+    assert_eq!(indexed, vec!["local.md"]);
+}
+
+#[cfg(unix)]
+#[test]
+#[serial]
+fn test_save_through_symlinked_note_keeps_link() {
+    use super::test_utils::{test_save_note_with_content_check, TestConfigOverride};
+    use std::fs;
+    use std::os::unix::fs::symlink;
+
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+    let elsewhere = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let target = elsewhere.path().join("shared.md");
+    let link = notes_dir.join("shared.md");
+
+    fs::write(&target, "# Shared").expect("Failed to write note");
+    symlink(&target, &link).expect("Failed to link note");
+    indexed_after_sync();
+
+    test_save_note_with_content_check("shared.md", "# Shared\n\nedited", "# Shared")
+        .expect("Saving through the link should succeed");
+
+    assert!(
+        fs::symlink_metadata(&link)
+            .expect("Link should still exist")
+            .file_type()
+            .is_symlink(),
+        "The note should still be a symlink"
+    );
+    assert_eq!(fs::read_to_string(&target).unwrap(), "# Shared\n\nedited");
+    let leftovers: Vec<_> = fs::read_dir(elsewhere.path())
+        .unwrap()
+        .flatten()
+        .map(|entry| entry.file_name())
+        .collect();
+    assert_eq!(
+        leftovers,
+        vec!["shared.md"],
+        "No temp file should be left behind"
+    );
+}
+
+#[test]
+#[serial]
+fn test_parallel_refresh_matches_single_thread() {
+    use super::test_utils::TestConfigOverride;
+    use crate::core::state::AppState;
+    use crate::database::with_db_mut;
+    use crate::services::database_service::sync_notes_with_filesystem;
+    use std::fs;
+
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+    const NOTE_COUNT: usize = 300;
+
+    for i in 0..NOTE_COUNT {
+        fs::write(
+            notes_dir.join(format!("note_{}.md", i)),
+            format!("---\ntags: [group{}]\n---\n# Note {}\n\nbody", i % 7, i),
+        )
+        .expect("Failed to write note");
+    }
+
+    let mut snapshots = Vec::new();
+    for threads in [1, 4, 0] {
+        test_config.update_config(|config| config.preferences.index_threads = threads);
+        let app_state = AppState::new_with_fallback(crate::config::load_config())
+            .expect("Test database setup failed");
+        let snapshot = with_db_mut(&app_state, |conn| {
+            let files_read = sync_notes_with_filesystem(&app_state, conn, true, None)?;
+            let mut stmt = conn.prepare(
+                "SELECT n.filename, n.content, n.is_indexed, h.hash, t.tag
+                 FROM notes n
+                 JOIN note_hashes h ON h.filename = n.filename
+                 JOIN note_tags t ON t.filename = n.filename
+                 ORDER BY n.filename",
+            )?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, bool>(2)?,
+                        row.get::<_, String>(3)?,
+                        row.get::<_, String>(4)?,
+                    ))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok((files_read, rows))
+        })
+        .expect("Sync should succeed");
+
+        assert_eq!(snapshot.0, NOTE_COUNT, "{} threads", threads);
+        assert_eq!(snapshot.1.len(), NOTE_COUNT, "{} threads", threads);
+        snapshots.push(snapshot.1);
+    }
+
+    assert_eq!(snapshots[0], snapshots[1]);
+    assert_eq!(snapshots[0], snapshots[2]);
+}
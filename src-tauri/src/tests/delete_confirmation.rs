@@ -0,0 +1,99 @@
+//! Delete Confirmation Unit Tests
+//!
+//! Tests confirmation tokens and the confirm_before_delete preference.
+
+use crate::services::confirmation_service::DeleteConfirmations;
+use crate::tests::test_utils::{
+    test_create_new_note, test_delete_note, test_delete_note_with_token,
+    test_delete_notes_with_token, test_request_batch_delete_confirmation, TestConfigOverride,
+};
+use serial_test::serial;
+
+#[test]
+fn test_tokens_are_single_use_and_per_note() {
+    let mut confirmations = DeleteConfirmations::default();
+
+    let token = confirmations.issue("draft.md");
+    assert!(!confirmations.consume("other.md", &token));
+    assert!(!confirmations.consume("draft.md", "not-the-token"));
+    assert!(confirmations.consume("draft.md", &token));
+    assert!(
+        !confirmations.consume("draft.md", &token),
+        "A token cannot be used twice"
+    );
+
+    let first = confirmations.issue("draft.md");
+    let second = confirmations.issue("draft.md");
+    assert_ne!(first, second);
+    assert!(
+        !confirmations.consume("draft.md", &first),
+        "Issuing again replaces the earlier token"
+    );
+    assert!(confirmations.consume("draft.md", &second));
+}
+
+#[test]
+#[serial]
+fn test_delete_requires_confirmation_by_default() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let note_path = test_config.notes_dir().join("draft.md");
+    test_create_new_note("draft.md").expect("Failed to create note");
+
+    for token in [None, Some("forged-token")] {
+        let err = test_delete_note_with_token("draft.md", token).unwrap_err();
+        assert!(err.contains("Confirmation required"), "{}", err);
+        assert!(note_path.exists(), "Unconfirmed delete must keep the note");
+    }
+
+    test_delete_note("draft.md").expect("Confirmed delete should succeed");
+    assert!(!note_path.exists());
+}
+
+#[test]
+#[serial]
+fn test_delete_without_confirmation_when_disabled() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_config.update_config(|config| config.preferences.confirm_before_delete = false);
+    let note_path = test_config.notes_dir().join("draft.md");
+    test_create_new_note("draft.md").expect("Failed to create note");
+
+    test_delete_note_with_token("draft.md", None).expect("Delete should not need a token");
+    assert!(!note_path.exists());
+}
+
+#[test]
+fn test_batch_tokens_are_bound_to_the_exact_set() {
+    let mut confirmations = DeleteConfirmations::default();
+    let names = vec!["a.md".to_string(), "b.md".to_string()];
+
+    let token = confirmations.issue_batch(&names);
+    assert!(!confirmations.consume_batch(&["a.md".to_string()], &token));
+    assert!(!confirmations.consume(&names[0], &token));
+    assert!(confirmations.consume_batch(&["b.md".to_string(), "a.md".to_string()], &token));
+    assert!(
+        !confirmations.consume_batch(&names, &token),
+        "Tokens are single-use"
+    );
+}
+
+#[test]
+#[serial]
+fn test_batch_delete_requires_confirmation() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+    test_create_new_note("one.md").expect("Failed to create note");
+    test_create_new_note("two.md").expect("Failed to create note");
+
+    let partial = test_request_batch_delete_confirmation(&["one.md"]).unwrap();
+    for token in [None, Some("forged-token"), Some(partial.as_str())] {
+        let err = test_delete_notes_with_token(&["one.md", "two.md"], token).unwrap_err();
+        assert!(err.contains("Confirmation required"), "{}", err);
+    }
+    assert!(notes_dir.join("one.md").exists() && notes_dir.join("two.md").exists());
+
+    let token = test_request_batch_delete_confirmation(&["one.md", "two.md"]).unwrap();
+    let results = test_delete_notes_with_token(&["one.md", "two.md"], Some(&token))
+        .expect("Confirmed batch delete should succeed");
+    assert!(results.iter().all(|(_, outcome)| outcome.is_ok()));
+    assert!(!notes_dir.join("one.md").exists() && !notes_dir.join("two.md").exists());
+}
@@ -0,0 +1,96 @@
+//! Directory Health Unit Tests
+//!
+//! Tests the on-disk checks run when a notes directory is picked.
+
+use crate::utilities::directory_health::{check_directory_health, detect_cloud_sync_provider};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn note_extensions() -> Vec<String> {
+    vec!["md".to_string(), "txt".to_string()]
+}
+
+#[test]
+fn test_health_of_writable_directory_counts_notes() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("work/deep")).unwrap();
+    fs::create_dir_all(root.join(".git")).unwrap();
+    fs::write(root.join("one.md"), "# One").unwrap();
+    fs::write(root.join("work/two.txt"), "two").unwrap();
+    fs::write(root.join("work/deep/three.md"), "three").unwrap();
+    fs::write(root.join("image.png"), "not a note").unwrap();
+    fs::write(root.join(".hidden.md"), "hidden").unwrap();
+    fs::write(root.join(".git/HEAD.md"), "ignored").unwrap();
+
+    let health = check_directory_health(root, &note_extensions(), 64);
+    assert!(health.exists);
+    assert!(health.is_directory);
+    assert!(health.writable, "write error: {:?}", health.write_error);
+    assert_eq!(health.note_count, 3);
+
+    let shallow = check_directory_health(root, &note_extensions(), 2);
+    assert_eq!(shallow.note_count, 2);
+
+    // The write probe must not leave anything behind
+    let leftovers: Vec<_> = fs::read_dir(root)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .starts_with(".symiosis-write-check")
+        })
+        .collect();
+    assert!(leftovers.is_empty());
+}
+
+#[test]
+fn test_health_of_missing_directory_or_file() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let missing = check_directory_health(&temp_dir.path().join("missing"), &note_extensions(), 64);
+    assert!(!missing.exists);
+    assert!(!missing.is_directory);
+    assert!(!missing.writable);
+    assert!(missing.write_error.is_some());
+    assert_eq!(missing.note_count, 0);
+
+    let file_path = temp_dir.path().join("note.md");
+    fs::write(&file_path, "not a directory").unwrap();
+    let file = check_directory_health(&file_path, &note_extensions(), 64);
+    assert!(file.exists);
+    assert!(!file.is_directory);
+    assert!(!file.writable);
+}
+
+#[test]
+fn test_detect_cloud_sync_provider() {
+    let cases = [
+        (
+            "/Users/me/Library/Mobile Documents/com~apple~CloudDocs/Notes",
+            Some("iCloud Drive"),
+        ),
+        ("/Users/me/Dropbox/Notes", Some("Dropbox")),
+        ("/Users/me/Dropbox (Personal)/Notes", Some("Dropbox")),
+        (
+            "/Users/me/Library/CloudStorage/OneDrive-Contoso/Notes",
+            Some("OneDrive"),
+        ),
+        (
+            "/Users/me/Library/CloudStorage/GoogleDrive-me@example.com/My Drive",
+            Some("Google Drive"),
+        ),
+        ("/home/me/Documents/Notes", None),
+    ];
+
+    for (path, expected) in cases {
+        assert_eq!(
+            detect_cloud_sync_provider(Path::new(path)),
+            expected,
+            "{}",
+            path
+        );
+    }
+}
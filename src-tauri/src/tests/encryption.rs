@@ -0,0 +1,137 @@
+//! Note Encryption Unit Tests
+//!
+//! Tests encrypting notes with a passphrase, unlocking them again and keeping
+//! their plaintext out of the index, the render cache and the version history.
+
+use crate::core::state::AppState;
+use crate::database::with_db;
+use crate::services::version_service::list_note_versions;
+use crate::tests::test_utils::{
+    test_create_new_note, test_decrypt_note, test_encrypt_note, test_get_note_content,
+    test_get_note_html_content, test_save_note_with_content_check, test_search_notes_hybrid,
+    TestConfigOverride,
+};
+use crate::utilities::note_crypto::{decrypt_content, encrypt_content, is_encrypted_note};
+use crate::utilities::note_renderer::LOCKED_NOTE_HTML;
+use serial_test::serial;
+use std::fs;
+
+const SECRET: &str = "# Bank\n\nthe vault code is marmalade";
+
+#[test]
+fn test_encrypt_content_round_trip() {
+    let encrypted = encrypt_content(SECRET, "correct horse").unwrap();
+
+    assert!(is_encrypted_note(&encrypted));
+    assert!(!encrypted.contains("marmalade"));
+    assert_ne!(
+        encrypted,
+        encrypt_content(SECRET, "correct horse").unwrap(),
+        "Each encryption uses a fresh salt and nonce"
+    );
+    assert_eq!(
+        decrypt_content(&encrypted, "correct horse").unwrap(),
+        SECRET
+    );
+}
+
+#[test]
+fn test_decrypt_content_rejects_wrong_passphrase_and_tampering() {
+    let encrypted = encrypt_content(SECRET, "correct horse").unwrap();
+
+    let err = decrypt_content(&encrypted, "battery staple").unwrap_err();
+    assert!(
+        matches!(err, crate::core::AppError::WrongPassphrase),
+        "{}",
+        err
+    );
+
+    // Flip a character in the ciphertext, which the AEAD tag must catch
+    let lines: Vec<&str> = encrypted.lines().collect();
+    let body = lines[1];
+    let flipped = if body.ends_with('A') { 'B' } else { 'A' };
+    let tampered_line = format!("{}{}", &body[..body.len() - 1], flipped);
+    let tampered = encrypted.replacen(body, &tampered_line, 1);
+    assert!(decrypt_content(&tampered, "correct horse").is_err());
+
+    assert!(
+        encrypt_content(SECRET, "").is_err(),
+        "Empty passphrases are refused"
+    );
+}
+
+#[test]
+#[serial]
+fn test_encrypt_note_locks_it_on_disk_and_in_session() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+    test_create_new_note("bank.md").expect("Failed to create note");
+    test_save_note_with_content_check("bank.md", SECRET, "").expect("Failed to save note");
+
+    test_encrypt_note("bank.md", "correct horse").expect("Failed to encrypt note");
+
+    let on_disk = fs::read_to_string(notes_dir.join("bank.md")).unwrap();
+    assert!(is_encrypted_note(&on_disk));
+    assert!(!on_disk.contains("marmalade"));
+    assert_eq!(
+        test_get_note_html_content("bank.md").unwrap(),
+        LOCKED_NOTE_HTML
+    );
+    assert!(test_encrypt_note("bank.md", "correct horse").is_err());
+
+    let err = test_decrypt_note("bank.md", "battery staple").unwrap_err();
+    assert_eq!(err, "Wrong passphrase");
+    assert_eq!(
+        test_decrypt_note("bank.md", "correct horse").unwrap(),
+        SECRET
+    );
+    assert_eq!(
+        fs::read_to_string(notes_dir.join("bank.md")).unwrap(),
+        on_disk,
+        "Unlocking for the session leaves the note encrypted on disk"
+    );
+}
+
+#[test]
+#[serial]
+fn test_encrypted_note_plaintext_is_not_indexed_or_kept() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+    test_create_new_note("bank.md").expect("Failed to create note");
+    test_save_note_with_content_check("bank.md", SECRET, "").expect("Failed to save note");
+    test_save_note_with_content_check("bank.md", &format!("{}\n\nmore", SECRET), SECRET)
+        .expect("Failed to save note");
+    assert_eq!(
+        test_search_notes_hybrid("marmalade", 10).unwrap(),
+        vec!["bank.md"]
+    );
+
+    test_encrypt_note("bank.md", "correct horse").expect("Failed to encrypt note");
+
+    assert!(test_search_notes_hybrid("marmalade", 10)
+        .unwrap()
+        .is_empty());
+    assert!(!test_get_note_content("bank.md")
+        .unwrap()
+        .contains("marmalade"));
+
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Test database setup failed");
+    let leaks: i64 = with_db(&app_state, |conn| {
+        Ok(conn.query_row(
+            "SELECT (SELECT COUNT(*) FROM notes
+                     WHERE content LIKE '%marmalade%' OR html_render LIKE '%marmalade%')
+                  + (SELECT COUNT(*) FROM render_cache WHERE html LIKE '%marmalade%')",
+            [],
+            |row| row.get(0),
+        )?)
+    })
+    .unwrap();
+    assert_eq!(
+        leaks, 0,
+        "No plaintext should be left in the notes or render cache"
+    );
+
+    let versions = list_note_versions(&notes_dir, "bank.md", "%Y-%m-%d").unwrap();
+    assert!(versions.is_empty(), "Plaintext versions should be removed");
+}
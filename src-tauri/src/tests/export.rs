@@ -0,0 +1,155 @@
+//! Export Unit Tests
+//!
+//! Tests exporting notes as markdown, standalone HTML and zip bundles.
+
+use crate::tests::test_utils::{
+    test_copy_note_as_html, test_create_new_note, test_export_note, test_list_all_notes,
+    test_save_note_with_content_check, TestConfigOverride,
+};
+use crate::utilities::export::{
+    build_export_html, code_theme_css, markdown_theme_css, write_note_bundle,
+};
+use serial_test::serial;
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+
+#[test]
+fn test_export_html_inlines_stylesheets() {
+    let html = build_export_html(
+        "notes/a & b.md",
+        "<p>Hello</p>\n<pre><code class=\"language-rust\">fn main() {}</code></pre>",
+        ".markdown-body { color: red; }",
+        ".hljs { background: black; }",
+    );
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>notes/a &amp; b.md</title>"));
+    assert!(html.contains(".markdown-body { color: red; }"));
+    assert!(html.contains(".hljs { background: black; }"));
+    assert!(html.contains("<div class=\"markdown-body\">\n<p>Hello</p>"));
+    assert!(html.contains("<pre><code class=\"hljs language-rust\">"));
+}
+
+#[test]
+fn test_every_listed_theme_has_embedded_css() {
+    for theme in crate::utilities::config_helpers::get_available_markdown_themes() {
+        assert!(
+            markdown_theme_css(theme).is_some(),
+            "Missing markdown theme CSS for {}",
+            theme
+        );
+    }
+    assert!(code_theme_css("gruvbox-dark-medium").is_some());
+    assert!(code_theme_css("not-a-theme").is_none());
+}
+
+#[test]
+fn test_bundle_contains_note_and_versions() {
+    let temp_dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let bundle_path = temp_dir.path().join("note.zip");
+
+    write_note_bundle(
+        &bundle_path,
+        "note.md",
+        "current",
+        &[(
+            "note.md.rollback.1700000000.md".to_string(),
+            "older".to_string(),
+        )],
+    )
+    .expect("Failed to write bundle");
+
+    let mut archive =
+        zip::ZipArchive::new(fs::File::open(&bundle_path).unwrap()).expect("Invalid zip");
+    let mut read_entry = |name: &str| {
+        let mut content = String::new();
+        archive
+            .by_name(name)
+            .expect("Missing zip entry")
+            .read_to_string(&mut content)
+            .unwrap();
+        content
+    };
+
+    assert_eq!(read_entry("note.md"), "current");
+    assert_eq!(
+        read_entry("versions/note.md.rollback.1700000000.md"),
+        "older"
+    );
+}
+
+#[test]
+#[serial]
+fn test_export_note_formats() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    test_create_new_note("project/report.md").expect("Failed to create note");
+    test_save_note_with_content_check("project/report.md", "# Report\n\nAll good", "")
+        .expect("Failed to save note");
+
+    let markdown_path = PathBuf::from(
+        test_export_note("project/report.md", "markdown").expect("Failed to export markdown"),
+    );
+    assert!(markdown_path.ends_with(".exports/project/report.md"));
+    assert_eq!(
+        fs::read_to_string(&markdown_path).unwrap(),
+        "# Report\n\nAll good"
+    );
+
+    let html_path =
+        PathBuf::from(test_export_note("project/report.md", "html").expect("Failed to export"));
+    let html = fs::read_to_string(&html_path).unwrap();
+    assert!(html_path.ends_with(".exports/project/report.html"));
+    assert!(html.contains("Report</h1>"));
+    assert!(html.contains("<style>"));
+
+    let bundle_path =
+        PathBuf::from(test_export_note("project/report.md", "bundle").expect("Failed to export"));
+    let archive = zip::ZipArchive::new(fs::File::open(&bundle_path).unwrap()).expect("Invalid zip");
+    assert!(archive.file_names().any(|name| name == "report.md"));
+
+    let notes = test_list_all_notes().expect("Failed to list notes");
+    assert!(
+        notes.iter().all(|note| !note.contains(".exports")),
+        "Exports must not be indexed as notes"
+    );
+}
+
+#[test]
+#[serial]
+fn test_export_note_rejects_bad_input() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    test_create_new_note("simple.md").expect("Failed to create note");
+
+    let err = test_export_note("simple.md", "pdf").expect_err("Unknown format should fail");
+    assert!(err.contains("Unsupported export format"), "{}", err);
+
+    let err = test_export_note("missing.md", "markdown").expect_err("Missing note should fail");
+    assert!(err.contains("not found"), "{}", err);
+}
+
+#[test]
+#[serial]
+fn test_copy_note_as_html_requires_an_existing_note() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let err = test_copy_note_as_html("missing.md").expect_err("Missing note should fail");
+    assert!(err.contains("Note not found"), "{}", err);
+    assert!(test_copy_note_as_html("../outside.md").is_err());
+}
+
+#[test]
+#[serial]
+fn test_copy_note_as_html_uses_the_system_clipboard() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_create_new_note("copied.md").expect("Failed to create note");
+    test_save_note_with_content_check("copied.md", "# Copied", "").expect("Failed to save note");
+
+    // A headless machine may have no clipboard to open, which is the only way this fails
+    if let Err(err) = test_copy_note_as_html("copied.md") {
+        assert!(err.contains("Clipboard error"), "{}", err);
+        assert!(!err.contains("only supported on macOS"), "{}", err);
+    }
+}
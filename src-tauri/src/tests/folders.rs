@@ -0,0 +1,141 @@
+//! Note Folder Unit Tests
+//!
+//! Tests creating folders, listing the folder tree and indexing around empty folders.
+
+use crate::tests::test_utils::{
+    test_create_folder, test_create_new_note, test_list_folders, TestConfigOverride,
+};
+use crate::utilities::note_folders::FolderNode;
+use serial_test::serial;
+use std::fs;
+
+fn folder_paths(nodes: &[FolderNode]) -> Vec<String> {
+    nodes
+        .iter()
+        .flat_map(|node| std::iter::once(node.path.clone()).chain(folder_paths(&node.children)))
+        .collect()
+}
+
+#[test]
+#[serial]
+fn test_create_folder_creates_nested_folders() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+
+    assert_eq!(
+        test_create_folder("/projects/2024/").expect("Failed to create folder"),
+        "projects/2024"
+    );
+    assert!(notes_dir.join("projects/2024").is_dir());
+
+    let err = test_create_folder("projects/2024").unwrap_err();
+    assert!(err.contains("already exists"), "{}", err);
+
+    fs::write(notes_dir.join("todo.md"), "# Todo").expect("Failed to write note");
+    let err = test_create_folder("todo.md").unwrap_err();
+    assert!(err.contains("file named"), "{}", err);
+}
+
+#[test]
+#[serial]
+fn test_create_folder_rejects_invalid_paths() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+
+    for path in [
+        "",
+        "  ",
+        "../outside",
+        "projects/../../outside",
+        "/tmp/absolute",
+        "projects\\windows",
+        ".hidden",
+        "projects/.git",
+        "attachments",
+        "CON",
+        "projects/nul.txt",
+        "trailing.",
+    ] {
+        assert!(
+            test_create_folder(path).is_err(),
+            "'{}' should be rejected",
+            path
+        );
+    }
+
+    // The name is only reserved at the root, where pasted images are stored
+    test_create_folder("projects/attachments").expect("Nested attachments folder is allowed");
+    assert!(!notes_dir.join("attachments").exists());
+    assert!(!notes_dir.parent().unwrap().join("outside").exists());
+}
+
+#[test]
+#[serial]
+fn test_list_folders_returns_sorted_tree() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+
+    for folder in ["work/clients", "Personal", "archive-2023", "work/admin"] {
+        test_create_folder(folder).expect("Failed to create folder");
+    }
+    for hidden in [".git/objects", ".archive", "drafts"] {
+        fs::create_dir_all(notes_dir.join(hidden)).expect("Failed to create folder");
+    }
+    fs::write(notes_dir.join(".symiosisignore"), "drafts/\n").expect("Failed to write ignore file");
+    fs::write(notes_dir.join("todo.md"), "# Todo").expect("Failed to write note");
+
+    let tree = test_list_folders().expect("Failed to list folders");
+    let names: Vec<&str> = tree.iter().map(|node| node.name.as_str()).collect();
+    assert_eq!(names, vec!["archive-2023", "Personal", "work"]);
+    assert_eq!(
+        folder_paths(&tree),
+        vec![
+            "archive-2023",
+            "Personal",
+            "work",
+            "work/admin",
+            "work/clients"
+        ]
+    );
+}
+
+#[test]
+#[serial]
+fn test_list_folders_respects_max_recursion_depth() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_config.update_config(|config| config.preferences.max_recursion_depth = 2);
+
+    test_create_folder("work/deep").expect("Failed to create folder");
+
+    // Notes in work/deep are past the limit, so the folder would always look empty
+    let tree = test_list_folders().expect("Failed to list folders");
+    assert_eq!(folder_paths(&tree), vec!["work"]);
+}
+
+#[test]
+#[serial]
+fn test_refresh_with_empty_folders() {
+    use crate::core::state::AppState;
+    use crate::database::with_db_mut;
+    use crate::services::database_service::sync_notes_with_filesystem;
+
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    test_create_folder("empty").expect("Failed to create folder");
+    test_create_folder("projects/empty").expect("Failed to create folder");
+    test_create_new_note("projects/plan.md").expect("Failed to create note");
+
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Test database setup failed");
+    let indexed = with_db_mut(&app_state, |conn| {
+        sync_notes_with_filesystem(&app_state, conn, true, None)?;
+        let mut stmt = conn.prepare("SELECT filename FROM notes ORDER BY filename")?;
+        let filenames = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(filenames)
+    })
+    .expect("Sync should succeed");
+
+    assert_eq!(indexed, vec!["projects/plan.md"]);
+}
@@ -0,0 +1,143 @@
+//! Frontmatter Unit Tests
+//!
+//! Tests YAML frontmatter parsing, tag-based note filtering and render theme overrides.
+
+use crate::tests::test_utils::{
+    test_create_new_note, test_list_all_tags, test_rename_note, test_save_note_with_content_check,
+    test_search_notes_by_tag, TestConfigOverride,
+};
+use crate::utilities::frontmatter::{extract_frontmatter_block, parse_frontmatter};
+use crate::utilities::paths::get_markdown_themes_dir;
+use crate::utilities::render_theme::{note_render_theme, RenderTheme};
+use serial_test::serial;
+use std::fs;
+
+#[test]
+fn test_parse_frontmatter_tag_list() {
+    let content = "---\ntags: [Rust, notes, rust]\ncreated: 2024-01-15\n---\n# Title\n";
+    let frontmatter = parse_frontmatter("note.md", content);
+
+    assert_eq!(frontmatter.tags, vec!["rust", "notes"]);
+    assert_eq!(frontmatter.tag_labels, vec!["Rust", "notes"]);
+    assert_eq!(frontmatter.created.as_deref(), Some("2024-01-15"));
+}
+
+#[test]
+fn test_parse_frontmatter_tag_string() {
+    let content = "---\ntags: \"#work, ideas\"\n---\nBody\n";
+    let frontmatter = parse_frontmatter("note.md", content);
+
+    assert_eq!(frontmatter.tags, vec!["work", "ideas"]);
+}
+
+#[test]
+fn test_parse_frontmatter_missing_or_malformed() {
+    let no_frontmatter = parse_frontmatter("note.md", "# Just a note\n---\ntags: [a]\n---\n");
+    assert!(no_frontmatter.tags.is_empty());
+
+    let unterminated = parse_frontmatter("note.md", "---\ntags: [a]\nno closing fence\n");
+    assert!(unterminated.tags.is_empty());
+
+    let malformed = parse_frontmatter("note.md", "---\ntags: [unclosed\n---\nBody\n");
+    assert!(malformed.tags.is_empty());
+    assert!(malformed.created.is_none());
+}
+
+#[test]
+fn test_extract_frontmatter_block() {
+    assert_eq!(
+        extract_frontmatter_block("---\ntags: [a]\n---\nBody"),
+        Some("tags: [a]\n")
+    );
+    assert_eq!(extract_frontmatter_block("Body\n---\n"), None);
+}
+
+#[test]
+#[serial]
+fn test_tag_search_filters_notes() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    for name in ["tagged.md", "other.md", "plain.md"] {
+        test_create_new_note(name).expect("Failed to create note");
+    }
+    test_save_note_with_content_check("tagged.md", "---\ntags: [rust, work]\n---\nBody", "")
+        .expect("Failed to save note");
+    test_save_note_with_content_check("other.md", "---\ntags: [work]\n---\nBody", "")
+        .expect("Failed to save note");
+    test_save_note_with_content_check("plain.md", "No frontmatter here", "")
+        .expect("Failed to save note");
+
+    let rust = test_search_notes_by_tag("Rust", 10).expect("Tag search failed");
+    assert_eq!(rust, vec!["tagged.md".to_string()]);
+
+    let work = test_search_notes_by_tag("#work", 10).expect("Tag search failed");
+    assert_eq!(work.len(), 2);
+
+    test_save_note_with_content_check(
+        "tagged.md",
+        "Frontmatter removed",
+        "---\ntags: [rust, work]\n---\nBody",
+    )
+    .expect("Failed to save note");
+    let rust = test_search_notes_by_tag("rust", 10).expect("Tag search failed");
+    assert!(rust.is_empty());
+
+    test_rename_note("other.md".to_string(), "renamed.md".to_string())
+        .expect("Failed to rename note");
+    let work = test_search_notes_by_tag("work", 10).expect("Tag search failed");
+    assert_eq!(work, vec!["renamed.md".to_string()]);
+}
+
+#[test]
+#[serial]
+fn test_list_all_tags_counts_notes_case_insensitively() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    for (name, content) in [
+        ("a.md", "---\ntags: [Rust, work]\n---\nBody"),
+        ("b.md", "---\ntags: [rust]\n---\nBody"),
+        ("c.md", "---\ntags: \"#Ideas, RUST\"\n---\nBody"),
+        ("d.md", "---\ntags: [work]\n---\nBody"),
+    ] {
+        test_create_new_note(name).expect("Failed to create note");
+        test_save_note_with_content_check(name, content, "").expect("Failed to save note");
+    }
+
+    let tags = test_list_all_tags().expect("Listing tags failed");
+    assert_eq!(
+        tags,
+        vec![
+            ("Rust".to_string(), 3),
+            ("work".to_string(), 2),
+            ("Ideas".to_string(), 1),
+        ]
+    );
+}
+
+#[test]
+#[serial]
+fn test_render_theme_override_falls_back_when_unknown() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let themes_dir = get_markdown_themes_dir();
+    fs::create_dir_all(&themes_dir).expect("Failed to create themes dir");
+    fs::write(themes_dir.join("resume.css"), "h1 { color: navy; }").expect("Failed to write theme");
+
+    assert_eq!(
+        note_render_theme("cv.md", "---\nrender_theme: article\n---\n# CV\n"),
+        Some(RenderTheme {
+            name: "article".to_string(),
+            css: None,
+        })
+    );
+    assert_eq!(
+        note_render_theme("cv.md", "---\nrender_theme: resume\n---\n# CV\n"),
+        Some(RenderTheme {
+            name: "resume".to_string(),
+            css: Some("h1 { color: navy; }".to_string()),
+        })
+    );
+    assert_eq!(
+        note_render_theme("cv.md", "---\nrender_theme: neon\n---\n# CV\n"),
+        None
+    );
+    assert_eq!(note_render_theme("cv.md", "# CV\n"), None);
+}
@@ -0,0 +1,171 @@
+//! Index Export Unit Tests
+//!
+//! Tests the versioned JSON snapshot of the note index and drift detection
+//! against it.
+
+use crate::commands::system::record_index_refresh;
+use crate::core::state::AppState;
+use crate::database::with_db;
+use crate::services::hash_service::content_hash;
+use crate::services::index_service::{
+    collect_index_stats, compare_index, parse_index_snapshot, IndexDrift, IndexEntry,
+    INDEX_SCHEMA_VERSION,
+};
+use crate::tests::test_utils::{
+    test_archive_note, test_compact_cache, test_create_new_note, test_delete_note,
+    test_export_index, test_get_index_stats, test_import_index, test_save_note_with_content_check,
+    TestConfigOverride,
+};
+use serial_test::serial;
+use std::fs;
+use tempfile::TempDir;
+
+fn entry(path: &str, content: &str) -> IndexEntry {
+    IndexEntry {
+        name: path.rsplit('/').next().unwrap().to_string(),
+        path: path.to_string(),
+        size: content.len() as u64,
+        modified: 0,
+        tags: Vec::new(),
+        hash: content_hash(content.as_bytes()),
+    }
+}
+
+#[test]
+fn test_compare_index_reports_drift_by_path_and_hash() {
+    let snapshot = vec![entry("a.md", "a"), entry("b.md", "b"), entry("c.md", "c")];
+    let mut touched = entry("a.md", "a");
+    touched.modified = 1_700_000_000;
+    let current = vec![touched, entry("b.md", "b edited"), entry("work/d.md", "d")];
+
+    assert_eq!(
+        compare_index(&snapshot, &current),
+        IndexDrift {
+            missing: vec!["c.md".to_string()],
+            added: vec!["work/d.md".to_string()],
+            changed: vec!["b.md".to_string()],
+        }
+    );
+    assert_eq!(compare_index(&snapshot, &snapshot), IndexDrift::default());
+}
+
+#[test]
+#[serial]
+fn test_index_stats_count_notes_tags_and_refresh() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    for (name, content) in [
+        ("one.md", "---\ntags: [rust, notes]\n---\nOne"),
+        ("two.md", "---\ntags: [rust]\n---\nTwo"),
+        ("old.md", "Old"),
+        ("gone.md", "Gone"),
+    ] {
+        test_save_note_with_content_check(name, content, "").expect("Failed to save note");
+    }
+    test_archive_note("old.md").expect("Failed to archive note");
+    test_delete_note("gone.md").expect("Failed to delete note");
+
+    let stats = test_get_index_stats().expect("Failed to get index stats");
+    assert_eq!(stats.total_notes, 2);
+    assert!(stats.content_bytes > 0);
+    assert_eq!(stats.tag_count, 2);
+    assert_eq!(stats.schema_version, INDEX_SCHEMA_VERSION);
+    assert_eq!(stats.last_refreshed_at, None, "Nothing has synced yet");
+
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Test database setup failed");
+    record_index_refresh(&app_state);
+    let refresh = *app_state.last_refresh.lock().unwrap();
+    let stats = with_db(&app_state, |conn| Ok(collect_index_stats(conn, refresh)?))
+        .expect("Failed to read index stats");
+    assert_eq!(stats.archived_notes, Some(1));
+    assert_eq!(stats.trashed_notes, Some(1));
+    assert!(stats.last_refreshed_at.is_some());
+}
+
+#[test]
+fn test_parse_index_snapshot_checks_schema_version() {
+    let snapshot = |version: u32| {
+        format!(
+            "{{\"schema_version\": {}, \"exported_at\": 0, \"notes\": []}}",
+            version
+        )
+    };
+
+    assert!(parse_index_snapshot(&snapshot(INDEX_SCHEMA_VERSION)).is_ok());
+    assert!(parse_index_snapshot(&snapshot(0)).is_err());
+    assert!(parse_index_snapshot(&snapshot(INDEX_SCHEMA_VERSION + 1)).is_err());
+    assert!(parse_index_snapshot("{\"notes\": []}").is_err());
+}
+
+#[test]
+#[serial]
+fn test_export_and_import_index_round_trip() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let export_dir = TempDir::new().unwrap();
+    let export_path = export_dir.path().join("index.json");
+    let export_path = export_path.to_string_lossy();
+
+    for name in ["plain.md", "tagged.md"] {
+        test_create_new_note(name).expect("Failed to create note");
+    }
+    test_save_note_with_content_check("tagged.md", "---\ntags: [work, ideas]\n---\n# Tagged", "")
+        .expect("Failed to save note");
+
+    assert_eq!(test_export_index(&export_path).unwrap(), 2);
+
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(export_dir.path().join("index.json")).unwrap())
+            .unwrap();
+    assert_eq!(json["schema_version"], INDEX_SCHEMA_VERSION);
+    let tagged = &json["notes"][1];
+    assert_eq!(tagged["path"], "tagged.md");
+    assert_eq!(tagged["tags"], serde_json::json!(["ideas", "work"]));
+    assert_eq!(
+        tagged["hash"],
+        content_hash(b"---\ntags: [work, ideas]\n---\n# Tagged")
+    );
+
+    assert_eq!(
+        test_import_index(&export_path).unwrap(),
+        IndexDrift::default()
+    );
+
+    test_save_note_with_content_check("plain.md", "# Changed", "").expect("Failed to save note");
+    test_create_new_note("later.md").expect("Failed to create note");
+    let drift = test_import_index(&export_path).unwrap();
+    assert_eq!(drift.changed, vec!["plain.md".to_string()]);
+    assert_eq!(drift.added, vec!["later.md".to_string()]);
+    assert!(drift.missing.is_empty());
+
+    assert!(test_export_index("relative/index.json").is_err());
+}
+
+#[test]
+#[serial]
+fn test_compact_cache_reclaims_deleted_notes() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let body = "lorem ipsum dolor sit amet ".repeat(800);
+    for i in 0..20 {
+        let name = format!("bulk-{}.md", i);
+        test_create_new_note(&name).expect("Failed to create note");
+        test_save_note_with_content_check(&name, &format!("# Bulk {}\n\n{}", i, body), "")
+            .expect("Failed to save note");
+    }
+    test_create_new_note("keep.md").expect("Failed to create note");
+    for i in 0..20 {
+        test_delete_note(&format!("bulk-{}.md", i)).expect("Failed to delete note");
+    }
+
+    let compaction = test_compact_cache().expect("Compaction should succeed");
+    assert!(
+        compaction.after_bytes < compaction.before_bytes,
+        "{:?}",
+        compaction
+    );
+    assert_eq!(
+        compaction.reclaimed_bytes(),
+        compaction.before_bytes - compaction.after_bytes
+    );
+    assert_eq!(test_get_index_stats().unwrap().total_notes, 1);
+}
@@ -0,0 +1,109 @@
+//! Integrity Unit Tests
+//!
+//! Tests note content hashes, hash-based skipping during refresh and the
+//! integrity check against the cached hashes.
+
+use crate::core::state::AppState;
+use crate::database::with_db_mut;
+use crate::services::database_service::sync_notes_with_filesystem;
+use crate::services::hash_service::content_hash;
+use crate::tests::test_utils::{
+    test_create_new_note, test_get_note_hash, test_save_note_with_content_check,
+    test_verify_notes_integrity, TestConfigOverride,
+};
+use serial_test::serial;
+use std::fs;
+use std::time::{Duration, SystemTime};
+
+#[test]
+fn test_content_hash_is_sha256_hex() {
+    assert_eq!(
+        content_hash(b"hello"),
+        "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+    );
+}
+
+#[test]
+#[serial]
+fn test_get_note_hash_reads_bytes_on_disk() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_create_new_note("hashed.md").expect("Failed to create note");
+    test_save_note_with_content_check("hashed.md", "# Hashed", "").expect("Failed to save note");
+
+    assert_eq!(
+        test_get_note_hash("hashed.md").unwrap(),
+        content_hash(b"# Hashed")
+    );
+
+    fs::write(
+        test_config.notes_dir().join("hashed.md"),
+        "# Edited elsewhere",
+    )
+    .unwrap();
+    assert_eq!(
+        test_get_note_hash("hashed.md").unwrap(),
+        content_hash(b"# Edited elsewhere")
+    );
+
+    assert!(test_get_note_hash("missing.md").is_err());
+    assert!(test_get_note_hash("../outside.md").is_err());
+}
+
+#[test]
+#[serial]
+fn test_verify_notes_integrity_reports_changed_files() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+    for name in ["kept.md", "synced.md", "gone.md"] {
+        test_create_new_note(name).expect("Failed to create note");
+        test_save_note_with_content_check(name, &format!("# {}", name), "")
+            .expect("Failed to save note");
+    }
+    assert!(test_verify_notes_integrity().unwrap().is_empty());
+
+    // Rewriting identical bytes is not a difference
+    fs::write(notes_dir.join("kept.md"), "# kept.md").unwrap();
+    fs::write(notes_dir.join("synced.md"), "# conflicted copy").unwrap();
+    fs::remove_file(notes_dir.join("gone.md")).unwrap();
+
+    assert_eq!(
+        test_verify_notes_integrity().unwrap(),
+        vec!["gone.md".to_string(), "synced.md".to_string()]
+    );
+}
+
+#[test]
+#[serial]
+fn test_refresh_skips_touched_files_with_unchanged_hash() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+    fs::write(notes_dir.join("touched.md"), "# Touched").unwrap();
+    fs::write(notes_dir.join("edited.md"), "# Edited").unwrap();
+
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Test database setup failed");
+    let sync = || {
+        with_db_mut(&app_state, |conn| {
+            sync_notes_with_filesystem(&app_state, conn, false, None).map_err(|e| e.into())
+        })
+        .expect("Sync should succeed")
+    };
+    sync();
+
+    let later = SystemTime::now() + Duration::from_secs(120);
+    for (name, content) in [("touched.md", "# Touched"), ("edited.md", "# Edited!")] {
+        let path = notes_dir.join(name);
+        fs::write(&path, content).unwrap();
+        fs::OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+    }
+
+    assert_eq!(sync(), 1, "Only the edited note should be re-indexed");
+    // The touched note's new modified time was recorded, so nothing is left to re-check
+    assert_eq!(sync(), 0);
+    assert!(test_verify_notes_integrity().unwrap().is_empty());
+}
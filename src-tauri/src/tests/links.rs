@@ -0,0 +1,278 @@
+//! Note Link Unit Tests
+//!
+//! Tests wiki link rendering, resolution against existing notes and backlinks.
+
+use crate::tests::test_utils::{
+    test_create_new_note, test_get_backlinks, test_get_note_content, test_get_note_html_content,
+    test_get_note_links, test_list_all_notes, test_rename_note_with_relink,
+    test_save_note_with_content_check, TestConfigOverride,
+};
+use crate::utilities::note_links::{is_external_target, LinkKind, LinkRename, NoteLinkIndex};
+use crate::utilities::note_renderer::{
+    extract_note_links, relink_note_content, render_note, resolve_wiki_links, wiki_link_targets,
+    RenderOptions,
+};
+use serial_test::serial;
+
+fn create_note_with_content(name: &str, content: &str) {
+    test_create_new_note(name).expect("Failed to create note");
+    test_save_note_with_content_check(name, content, "").expect("Failed to save note");
+}
+
+#[test]
+fn test_note_link_index_resolves_case_insensitively() {
+    let index = NoteLinkIndex::new(["Other Note.md", "project-plan.md", "work/ideas.md"]);
+
+    assert_eq!(index.resolve("other note"), Some("Other Note.md"));
+    assert_eq!(index.resolve("OTHER NOTE.md"), Some("Other Note.md"));
+    assert_eq!(index.resolve("Project Plan"), Some("project-plan.md"));
+    assert_eq!(index.resolve("project-plan"), Some("project-plan.md"));
+    assert_eq!(index.resolve("Ideas"), Some("work/ideas.md"));
+    assert_eq!(index.resolve("missing"), None);
+}
+
+#[test]
+fn test_note_link_index_prefers_filenames_over_titles() {
+    let index = NoteLinkIndex::new(["meeting-notes.md", "beta.md", "alpha.md"]).with_titles([
+        (
+            "meeting-notes.md".to_string(),
+            "Quarterly Review".to_string(),
+        ),
+        ("alpha.md".to_string(), "Beta".to_string()),
+    ]);
+
+    assert_eq!(index.resolve("quarterly review"), Some("meeting-notes.md"));
+    assert_eq!(index.resolve("Meeting Notes"), Some("meeting-notes.md"));
+    assert_eq!(index.resolve("Beta"), Some("beta.md"));
+}
+
+#[test]
+fn test_wiki_link_targets_skip_code() {
+    let content = "[[Alpha]] and [[Beta|the beta]]\n\n`[[inline]]`\n\n```\n[[fenced]]\n```";
+    assert_eq!(wiki_link_targets(content), vec!["Alpha", "Beta"]);
+}
+
+#[test]
+fn test_wiki_links_resolve_or_render_broken() {
+    let html = render_note(
+        "test.md",
+        "See [[Other Note]] and [[Missing|gone]]",
+        &RenderOptions::default(),
+    );
+    let index = NoteLinkIndex::new(["Other Note.md"]);
+    let resolved = resolve_wiki_links(&html, &index).unwrap();
+
+    assert!(resolved.contains(
+        r##"<a class="wiki-link" href="#" data-note="Other Note.md" data-target="Other Note""##
+    ));
+    assert!(resolved.contains(r#"<a class="wiki-link wiki-link-broken" data-target="Missing""#));
+    assert!(resolved.contains(">gone</a>"));
+}
+
+#[test]
+fn test_wiki_links_in_text_notes_stay_plain() {
+    let html = render_note("test.txt", "[[Other Note]]", &RenderOptions::default());
+    assert!(!html.contains("wiki-link"));
+}
+
+#[test]
+#[serial]
+fn test_note_html_resolves_wiki_links_on_read() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    create_note_with_content("source.md", "Link to [[Target]]");
+    let html = test_get_note_html_content("source.md").expect("Failed to get HTML");
+    assert!(html.contains("wiki-link-broken"));
+
+    // The cached render picks up the new note without being re-rendered
+    test_create_new_note("target.md").expect("Failed to create note");
+    let html = test_get_note_html_content("source.md").expect("Failed to get HTML");
+    assert!(html.contains(r#"data-note="target.md""#));
+    assert!(!html.contains("wiki-link-broken"));
+}
+
+#[test]
+#[serial]
+fn test_backlinks_list_linking_markdown_notes() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    create_note_with_content("hub.md", "# Hub");
+    create_note_with_content("b.md", "Back to [[HUB]]");
+    create_note_with_content("a.md", "See [[hub|the hub]] twice: [[Hub]]");
+    create_note_with_content("code.md", "`[[hub]]` is not a link");
+    create_note_with_content("plain.txt", "[[hub]]");
+    create_note_with_content("self.md", "[[self]]");
+
+    assert_eq!(
+        test_get_backlinks("hub.md").expect("Failed to get backlinks"),
+        vec!["a.md", "b.md"]
+    );
+    assert!(test_get_backlinks("self.md").unwrap().is_empty());
+    assert!(test_get_backlinks("missing.md").is_err());
+}
+
+#[test]
+#[serial]
+fn test_wiki_links_resolve_by_note_title() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    create_note_with_content("2024-q3.md", "# Quarterly Review\n\nNumbers");
+    create_note_with_content("source.md", "See [[Quarterly Review]]");
+
+    let html = test_get_note_html_content("source.md").expect("Failed to get HTML");
+    assert!(html.contains(r#"data-note="2024-q3.md""#), "{}", html);
+    assert!(!html.contains("wiki-link-broken"));
+    assert_eq!(
+        test_get_backlinks("2024-q3.md").expect("Failed to get backlinks"),
+        vec!["source.md"]
+    );
+}
+
+#[test]
+fn test_is_external_target() {
+    assert!(is_external_target("https://example.com"));
+    assert!(is_external_target("mailto:me@example.com"));
+    assert!(!is_external_target("other-note.md"));
+    assert!(!is_external_target("#heading"));
+    assert!(!is_external_target("C:\\notes\\other.md"));
+}
+
+#[test]
+fn test_extract_note_links_resolves_internal_targets() {
+    let index = NoteLinkIndex::new(["Other Note.md", "project-plan.md"]);
+    let content = "[Plan](project-plan.md#goals) [[Other Note|other]] [site](https://example.com)\n\n\
+                   [spaced](Other%20Note.md) [top](#top) [gone](missing.md) ![img](project-plan.md)";
+    let links = extract_note_links(content, &index);
+
+    let summary: Vec<_> = links
+        .iter()
+        .map(|link| (link.text.as_str(), link.note.as_deref(), link.external))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Plan", Some("project-plan.md"), false),
+            ("other", Some("Other Note.md"), false),
+            ("site", None, true),
+            ("spaced", Some("Other Note.md"), false),
+            ("top", None, false),
+            ("gone", None, false),
+        ]
+    );
+    assert_eq!(links[0].target, "project-plan.md#goals");
+    assert_eq!(links[1].kind, LinkKind::Wiki);
+    assert_eq!(links[2].kind, LinkKind::Markdown);
+}
+
+#[test]
+fn test_extract_note_links_flags_email_autolinks() {
+    let links = extract_note_links("<me@example.com>", &NoteLinkIndex::default());
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].target, "mailto:me@example.com");
+    assert!(links[0].external);
+}
+
+#[test]
+#[serial]
+fn test_note_links_command_lists_outgoing_links() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    create_note_with_content("target.md", "# Target");
+    create_note_with_content(
+        "source.md",
+        "[[Target]] and [docs](https://example.com/docs)",
+    );
+    create_note_with_content("plain.txt", "[[Target]]");
+
+    let links = test_get_note_links("source.md").expect("Failed to get links");
+    assert_eq!(links.len(), 2);
+    assert_eq!(links[0].note.as_deref(), Some("target.md"));
+    assert!(links[1].external);
+
+    assert!(test_get_note_links("plain.txt").unwrap().is_empty());
+    assert!(test_get_note_links("missing.md").is_err());
+}
+
+fn filenames(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn test_relink_rewrites_wiki_and_markdown_links() {
+    let notes = filenames(&["Old Note.md", "other.md"]);
+    let rename = LinkRename::new(&notes, "Old Note.md", "fresh-name.md");
+    let content = "[[Old Note|label]] [[old note]] [[Old Note.md]] [[other]]\n\n\
+                   [a](Old%20Note.md#intro \"title\") [b](<Old Note.md>) [c](./Old%20Note.md)";
+
+    assert_eq!(
+        relink_note_content(content, &rename).unwrap(),
+        "[[fresh-name|label]] [[fresh-name]] [[fresh-name.md]] [[other]]\n\n\
+         [a](fresh-name.md#intro \"title\") [b](<fresh-name.md>) [c](./fresh-name.md)"
+    );
+}
+
+#[test]
+fn test_relink_leaves_code_images_and_external_links() {
+    let notes = filenames(&["old.md"]);
+    let rename = LinkRename::new(&notes, "old.md", "new.md");
+    let content = "`[[old]]` ![img](old.md) [site](https://example.com/old.md) [r][ref]\n\n\
+                   [ref]: old.md";
+
+    assert_eq!(relink_note_content(content, &rename), None);
+}
+
+#[test]
+fn test_relink_uses_full_name_when_stem_is_taken() {
+    // "new" keeps naming a/new.md, which sorts first
+    let notes = filenames(&["a/new.md", "old.md"]);
+    let rename = LinkRename::new(&notes, "old.md", "b/new.md");
+    assert_eq!(
+        relink_note_content("[[old]]", &rename).unwrap(),
+        "[[b/new.md]]"
+    );
+}
+
+#[test]
+#[serial]
+fn test_rename_with_relink_updates_linking_notes() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    create_note_with_content("target.md", "# Target\n\nSelf: [[target]]");
+    create_note_with_content("a.md", "[[Target]] and [md](target.md#top)");
+    create_note_with_content("b.md", "No links to it: [[a]]");
+    create_note_with_content("plain.txt", "[[target]]");
+
+    let changed = test_rename_note_with_relink("target.md", "renamed.md").expect("Rename failed");
+    assert_eq!(changed, 2);
+
+    let notes = test_list_all_notes().expect("Failed to list notes");
+    assert!(notes.contains(&"renamed.md".to_string()));
+    assert!(!notes.contains(&"target.md".to_string()));
+
+    assert_eq!(
+        test_get_note_content("a.md").unwrap(),
+        "[[renamed]] and [md](renamed.md#top)"
+    );
+    assert_eq!(
+        test_get_note_content("renamed.md").unwrap(),
+        "# Target\n\nSelf: [[renamed]]"
+    );
+    assert_eq!(
+        test_get_note_content("b.md").unwrap(),
+        "No links to it: [[a]]"
+    );
+    assert_eq!(test_get_note_content("plain.txt").unwrap(), "[[target]]");
+    assert_eq!(test_get_backlinks("renamed.md").unwrap(), vec!["a.md"]);
+}
+
+#[test]
+#[serial]
+fn test_rename_with_relink_rejects_existing_target() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    create_note_with_content("one.md", "# One");
+    create_note_with_content("two.md", "[[one]]");
+
+    assert!(test_rename_note_with_relink("one.md", "two.md").is_err());
+    assert_eq!(test_get_note_content("two.md").unwrap(), "[[one]]");
+}
@@ -0,0 +1,105 @@
+//! Logging Unit Tests
+//!
+//! Tests log levels, level filtering, the in-memory buffer of recent entries
+//! and log file rotation.
+
+use crate::logging::{
+    log, log_archive_path, log_at, log_error, log_warn, recent_logs, rotate_log_files, LogLevel,
+    MAX_RECENT_LOGS,
+};
+use serial_test::serial;
+use std::fs;
+use tempfile::TempDir;
+
+fn operations_at(level_filter: LogLevel, prefix: &str) -> Vec<(LogLevel, String)> {
+    recent_logs(level_filter)
+        .into_iter()
+        .filter(|record| record.operation.starts_with(prefix))
+        .map(|record| (record.level, record.operation))
+        .collect()
+}
+
+#[test]
+#[serial]
+fn test_recent_logs_filter_by_level() {
+    log_error("LEVELS_ERROR", "failed", Some("details"));
+    log_warn("LEVELS_WARN", "careful", None);
+    log("LEVELS_INFO", "plain log calls are info", None);
+    log_at(LogLevel::Debug, "LEVELS_DEBUG", "noise", None);
+
+    assert_eq!(
+        operations_at(LogLevel::Warn, "LEVELS_"),
+        vec![
+            (LogLevel::Error, "LEVELS_ERROR".to_string()),
+            (LogLevel::Warn, "LEVELS_WARN".to_string()),
+        ]
+    );
+    assert_eq!(operations_at(LogLevel::Info, "LEVELS_").len(), 3);
+    assert_eq!(operations_at(LogLevel::Debug, "LEVELS_").len(), 4);
+
+    let error = recent_logs(LogLevel::Error)
+        .into_iter()
+        .find(|record| record.operation == "LEVELS_ERROR")
+        .expect("Error entry should be kept");
+    assert_eq!(error.message, "failed");
+    assert_eq!(error.details.as_deref(), Some("details"));
+}
+
+#[test]
+#[serial]
+fn test_recent_logs_keep_only_newest_entries() {
+    log("RING_FIRST", "oldest entry", None);
+    for _ in 0..MAX_RECENT_LOGS {
+        log("RING_FILLER", "newer entry", None);
+    }
+
+    let recent = recent_logs(LogLevel::Debug);
+    assert_eq!(recent.len(), MAX_RECENT_LOGS);
+    assert!(!recent.iter().any(|record| record.operation == "RING_FIRST"));
+}
+
+#[test]
+fn test_rotate_log_files_shifts_archives_and_drops_oldest() {
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("symiosis.log");
+
+    for generation in 1..=4 {
+        fs::write(&log_path, format!("generation {}", generation)).unwrap();
+        rotate_log_files(&log_path, 3).unwrap();
+    }
+
+    assert!(!log_path.exists());
+    assert_eq!(
+        fs::read_to_string(log_archive_path(&log_path, 1)).unwrap(),
+        "generation 4"
+    );
+    assert_eq!(
+        fs::read_to_string(log_archive_path(&log_path, 3)).unwrap(),
+        "generation 2"
+    );
+    assert!(!log_archive_path(&log_path, 4).exists());
+}
+
+#[test]
+fn test_rotate_log_files_respects_lowered_retention() {
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("symiosis.log");
+    for index in 1..=3 {
+        fs::write(log_archive_path(&log_path, index), "old").unwrap();
+    }
+    fs::write(&log_path, "current").unwrap();
+
+    rotate_log_files(&log_path, 1).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(log_archive_path(&log_path, 1)).unwrap(),
+        "current"
+    );
+    assert!(!log_archive_path(&log_path, 2).exists());
+    assert!(!log_archive_path(&log_path, 3).exists());
+
+    fs::write(&log_path, "discarded").unwrap();
+    rotate_log_files(&log_path, 0).unwrap();
+    assert!(!log_path.exists());
+    assert!(log_archive_path(&log_path, 1).exists());
+}
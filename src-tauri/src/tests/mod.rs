@@ -3,19 +3,47 @@
 //! This module contains unit tests for the symiosis library.
 //! These tests can access private functions and are part of the same crate.
 
+pub mod archive;
 pub mod atomic_operations;
+pub mod attachments;
 pub mod cleanup_test;
 pub mod concurrency;
 pub mod config;
 pub mod content_synchronization;
+pub mod created;
 pub mod crud_operations;
 pub mod database;
 pub mod database_consistency;
+pub mod delete_confirmation;
+pub mod directory_health;
 pub mod directory_paths;
+pub mod encryption;
 pub mod error_handling;
+pub mod export;
+pub mod folders;
+pub mod frontmatter;
+pub mod index_export;
+pub mod integrity;
+pub mod links;
+pub mod logging;
+pub mod note_chunks;
+pub mod note_ids;
+pub mod note_ignore;
 pub mod note_rendering;
+pub mod note_stats;
+pub mod pins;
+pub mod recents;
+pub mod render_cache;
+pub mod replace;
+pub mod save_format;
 pub mod search;
 pub mod security;
+pub mod spellcheck;
+pub mod templates;
 pub mod test_utils;
+pub mod trash;
 pub mod validation;
+pub mod versions;
 pub mod watcher;
+pub mod window_focus;
+pub mod window_geometry;
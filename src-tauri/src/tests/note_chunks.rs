@@ -0,0 +1,96 @@
+//! Note Chunk Unit Tests
+//!
+//! Tests reading large notes piece by piece with read_note_chunk.
+
+use crate::commands::notes::{NoteChunk, MAX_NOTE_CHUNK_BYTES};
+use crate::tests::test_utils::{test_read_note_chunk, TestConfigOverride};
+use serial_test::serial;
+use std::fs;
+
+#[test]
+#[serial]
+fn test_chunks_reassemble_the_note() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let content = "# Large\n\nnaïve café ".repeat(500);
+    fs::write(test_config.notes_dir().join("large.md"), &content).expect("Failed to write note");
+
+    // Chunks of 1000 bytes split some of the two-byte characters
+    let mut reassembled = String::new();
+    let mut offset = 0;
+    loop {
+        let chunk = test_read_note_chunk("large.md", offset, 1000).expect("Chunk read failed");
+        assert!(chunk.content.len() <= 1003);
+        assert_eq!(chunk.next_offset, offset + chunk.content.len() as u64);
+        offset = chunk.next_offset;
+        reassembled.push_str(&chunk.content);
+        if chunk.eof {
+            break;
+        }
+    }
+    assert_eq!(reassembled, content);
+
+    let chunk = test_read_note_chunk("large.md", 0, u64::MAX).expect("Chunk read failed");
+    assert!(chunk.eof);
+    assert!(chunk.content.len() as u64 <= MAX_NOTE_CHUNK_BYTES);
+}
+
+#[test]
+#[serial]
+fn test_chunks_end_on_character_boundaries() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    fs::write(test_config.notes_dir().join("cafe.md"), "café au lait").unwrap();
+
+    // "é" is bytes 3 and 4, so a four byte read would split it
+    let first = test_read_note_chunk("cafe.md", 0, 4).unwrap();
+    assert_eq!(first.content, "café");
+    assert_eq!(first.next_offset, 5);
+    assert!(!first.eof);
+    let rest = test_read_note_chunk("cafe.md", first.next_offset, 100).unwrap();
+    assert_eq!(rest.content, " au lait");
+    assert!(rest.eof);
+
+    let err = test_read_note_chunk("cafe.md", 4, 10).unwrap_err();
+    assert!(err.contains("not valid UTF-8"), "{}", err);
+}
+
+#[test]
+#[serial]
+fn test_chunk_offsets_past_eof_are_errors() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    fs::write(test_config.notes_dir().join("small.md"), "0123456789")
+        .expect("Failed to write note");
+
+    assert_eq!(
+        test_read_note_chunk("small.md", 10, 100).expect("Reading at EOF is allowed"),
+        NoteChunk {
+            content: String::new(),
+            next_offset: 10,
+            eof: true
+        }
+    );
+    assert_eq!(
+        test_read_note_chunk("small.md", 4, 3).unwrap(),
+        NoteChunk {
+            content: "456".to_string(),
+            next_offset: 7,
+            eof: false
+        }
+    );
+
+    let err = test_read_note_chunk("small.md", 11, 1).unwrap_err();
+    assert!(err.contains("past the end"), "{}", err);
+    assert!(test_read_note_chunk("small.md", u64::MAX, u64::MAX).is_err());
+    assert!(test_read_note_chunk("missing.md", 0, 10).is_err());
+}
+
+#[test]
+#[serial]
+fn test_zero_length_chunks_are_errors() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    fs::write(test_config.notes_dir().join("small.md"), "0123456789")
+        .expect("Failed to write note");
+
+    let err = test_read_note_chunk("small.md", 0, 0).unwrap_err();
+    assert!(err.contains("at least one byte"), "{}", err);
+    assert!(test_read_note_chunk("small.md", 10, 0).is_err());
+}
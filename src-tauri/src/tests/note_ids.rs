@@ -0,0 +1,94 @@
+//! Note ID Unit Tests
+//!
+//! Tests that notes keep their ID, pins and recents when renamed in or outside the app.
+
+use crate::core::state::AppState;
+use crate::database::with_db_mut;
+use crate::services::database_service::sync_notes_with_filesystem;
+use crate::tests::test_utils::{
+    test_create_new_note, test_get_backlinks_by_id, test_get_note_by_id, test_get_note_id,
+    test_list_pinned_notes, test_list_recent_notes, test_pin_note, test_record_note_opened,
+    test_rename_note, test_rename_note_with_relink, test_save_note_with_content_check,
+    TestConfigOverride,
+};
+use serial_test::serial;
+use std::fs;
+
+fn sync() {
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Test database setup failed");
+    with_db_mut(&app_state, |conn| {
+        sync_notes_with_filesystem(&app_state, conn, false, None).map_err(|e| e.into())
+    })
+    .expect("Sync should succeed");
+}
+
+#[test]
+#[serial]
+fn test_note_id_survives_rename() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_create_new_note("draft.md").expect("Failed to create note");
+
+    let id = test_get_note_id("draft.md").expect("Failed to get note ID");
+    assert_eq!(test_get_note_id("draft.md").unwrap(), id, "IDs are stable");
+
+    test_rename_note("draft.md".to_string(), "final.md".to_string())
+        .expect("Failed to rename note");
+    assert_eq!(test_get_note_id("final.md").unwrap(), id);
+    assert_eq!(test_get_note_by_id(&id).unwrap(), "final.md");
+    assert!(test_get_note_id("draft.md").is_err());
+
+    let err = test_get_note_by_id("no-such-id").unwrap_err();
+    assert!(err.contains("Note not found"), "{}", err);
+}
+
+#[test]
+#[serial]
+fn test_external_rename_keeps_id_pin_and_recent() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+    test_create_new_note("draft.md").expect("Failed to create note");
+    test_save_note_with_content_check("draft.md", "# Draft\n\nsome words", "")
+        .expect("Failed to save note");
+    test_pin_note("draft.md").expect("Failed to pin note");
+    test_record_note_opened("draft.md").expect("Failed to record open");
+    let id = test_get_note_id("draft.md").unwrap();
+
+    fs::rename(notes_dir.join("draft.md"), notes_dir.join("moved.md"))
+        .expect("Failed to rename note file");
+    sync();
+
+    assert_eq!(test_get_note_id("moved.md").unwrap(), id);
+    assert_eq!(test_list_pinned_notes().unwrap(), vec!["moved.md"]);
+    assert_eq!(test_list_recent_notes(10).unwrap(), vec!["moved.md"]);
+
+    // A copy has the same content, but its original is still there
+    fs::copy(notes_dir.join("moved.md"), notes_dir.join("copy.md"))
+        .expect("Failed to copy note file");
+    sync();
+    assert_ne!(test_get_note_id("copy.md").unwrap(), id);
+    assert_eq!(test_get_note_id("moved.md").unwrap(), id);
+}
+
+#[test]
+#[serial]
+fn test_backlinks_by_id_follow_renames() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_create_new_note("hub.md").expect("Failed to create note");
+    test_create_new_note("source.md").expect("Failed to create note");
+    test_save_note_with_content_check("source.md", "See [[hub]]", "").expect("Failed to save note");
+    let hub_id = test_get_note_id("hub.md").unwrap();
+    let source_id = test_get_note_id("source.md").unwrap();
+
+    assert_eq!(
+        test_get_backlinks_by_id(&hub_id).unwrap(),
+        vec![source_id.clone()]
+    );
+
+    test_rename_note_with_relink("hub.md", "center.md").expect("Failed to rename note");
+    test_rename_note("source.md".to_string(), "origin.md".to_string())
+        .expect("Failed to rename note");
+    assert_eq!(test_get_backlinks_by_id(&hub_id).unwrap(), vec![source_id]);
+
+    assert!(test_get_backlinks_by_id("no-such-id").is_err());
+}
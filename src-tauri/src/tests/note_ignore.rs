@@ -0,0 +1,43 @@
+//! Unit tests for `.symiosisignore` matching
+
+use crate::utilities::note_ignore::{NoteIgnore, IGNORE_FILE_NAME};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn note_ignore_with(patterns: &str) -> (TempDir, NoteIgnore) {
+    let temp_dir = TempDir::new().expect("Should create temp directory");
+    fs::write(temp_dir.path().join(IGNORE_FILE_NAME), patterns).expect("Should write ignore file");
+    let note_ignore = NoteIgnore::load(temp_dir.path());
+    (temp_dir, note_ignore)
+}
+
+#[test]
+fn test_patterns_from_ignore_file() {
+    let (_temp_dir, note_ignore) = note_ignore_with("drafts/\n*.tmp.md\n!keep.tmp.md\n");
+
+    assert!(note_ignore.is_ignored(Path::new("drafts"), true));
+    assert!(note_ignore.is_ignored(Path::new("drafts/idea.md"), false));
+    assert!(note_ignore.is_ignored(Path::new("work/scratch.tmp.md"), false));
+    assert!(!note_ignore.is_ignored(Path::new("keep.tmp.md"), false));
+    assert!(!note_ignore.is_ignored(Path::new("work/todo.md"), false));
+}
+
+#[test]
+fn test_builtin_folders_always_ignored() {
+    let (_temp_dir, note_ignore) = note_ignore_with("");
+
+    assert!(note_ignore.is_ignored(Path::new(".git"), true));
+    assert!(note_ignore.is_ignored(Path::new("work/.git/HEAD"), false));
+    assert!(note_ignore.is_ignored(Path::new(".archive/old.md"), false));
+    assert!(!note_ignore.is_ignored(Path::new("archive.md"), false));
+}
+
+#[test]
+fn test_missing_ignore_file() {
+    let temp_dir = TempDir::new().expect("Should create temp directory");
+    let note_ignore = NoteIgnore::load(temp_dir.path());
+
+    assert!(!note_ignore.is_ignored(Path::new(""), true));
+    assert!(!note_ignore.is_ignored(Path::new("drafts/idea.md"), false));
+}
@@ -2,7 +2,16 @@
 //!
 //! Tests for note content rendering functionality.
 
-use crate::utilities::note_renderer::render_note;
+use crate::tests::test_utils::{
+    test_get_note_toc, test_save_note_with_content_check, TestConfigOverride,
+};
+use crate::utilities::code_highlight::syntect_theme_name;
+use crate::utilities::config_helpers::get_available_code_themes;
+use crate::utilities::note_renderer::{
+    heading_slug, markdown_extensions, note_toc, render_note, RenderOptions, TocEntry,
+    GFM_EXTENSIONS,
+};
+use serial_test::serial;
 
 // Import the private function for testing
 use crate::utilities::note_renderer::linkify_urls_in_html;
@@ -10,7 +19,7 @@ use crate::utilities::note_renderer::linkify_urls_in_html;
 #[test]
 fn test_render_markdown_note() {
     let markdown_content = "# Hello World\n\nThis is **bold** text.";
-    let result = render_note("test.md", markdown_content);
+    let result = render_note("test.md", markdown_content, &RenderOptions::default());
 
     assert!(result.contains("<h1>"));
     assert!(result.contains("Hello World"));
@@ -21,7 +30,7 @@ fn test_render_markdown_note() {
 #[test]
 fn test_render_plain_text_note() {
     let text_content = "This is plain text with <script>alert('xss')</script>";
-    let result = render_note("test.txt", text_content);
+    let result = render_note("test.txt", text_content, &RenderOptions::default());
 
     assert!(result.starts_with("<pre>"));
     assert!(result.ends_with("</pre>"));
@@ -33,12 +42,12 @@ fn test_render_plain_text_note() {
 fn test_render_note_file_extension_detection() {
     let content = "# Test";
 
-    assert!(render_note("test.md", content).contains("<h1>"));
-    assert!(render_note("test.markdown", content).contains("<h1>"));
+    assert!(render_note("test.md", content, &RenderOptions::default()).contains("<h1>"));
+    assert!(render_note("test.markdown", content, &RenderOptions::default()).contains("<h1>"));
 
-    assert!(render_note("test.txt", content).starts_with("<pre>"));
-    assert!(render_note("test.rs", content).starts_with("<pre>"));
-    assert!(render_note("no-extension", content).starts_with("<pre>"));
+    assert!(render_note("test.txt", content, &RenderOptions::default()).starts_with("<pre>"));
+    assert!(render_note("test.rs", content, &RenderOptions::default()).starts_with("<pre>"));
+    assert!(render_note("no-extension", content, &RenderOptions::default()).starts_with("<pre>"));
 }
 
 // URL Linkification Tests
@@ -73,7 +82,7 @@ fn test_linkify_avoids_urls_inside_existing_links() {
 #[test]
 fn test_render_markdown_with_urls() {
     let content = "# Test\n\nVisit https://example.com for more info.";
-    let result = render_note("test.md", content);
+    let result = render_note("test.md", content, &RenderOptions::default());
     assert!(result.contains("<h1>Test</h1>"));
     assert!(result.contains(r#"<a href="https://example.com" target="_blank" rel="noopener noreferrer">https://example.com</a>"#));
 }
@@ -81,8 +90,266 @@ fn test_render_markdown_with_urls() {
 #[test]
 fn test_render_plain_text_with_urls() {
     let content = "Visit https://example.com for more info.";
-    let result = render_note("test.txt", content);
+    let result = render_note("test.txt", content, &RenderOptions::default());
     assert!(result.starts_with("<pre>"));
     assert!(result.contains(r#"<a href="https://example.com" target="_blank" rel="noopener noreferrer">https://example.com</a>"#));
     assert!(result.ends_with("</pre>"));
 }
+
+#[test]
+fn test_render_markdown_links_keep_href() {
+    let result = render_note(
+        "test.md",
+        "[Docs](https://example.com/docs) and [intro](#intro)",
+        &RenderOptions::default(),
+    );
+    assert!(result.contains(r#"href="https://example.com/docs""#));
+    assert!(result.contains(r##"href="#intro""##));
+}
+
+// Heading Anchor Tests
+
+fn with_heading_anchors() -> RenderOptions {
+    RenderOptions {
+        heading_anchors: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_heading_slug_is_github_style() {
+    assert_eq!(heading_slug("Hello World"), "hello-world");
+    assert_eq!(heading_slug("  What's New?  "), "whats-new");
+    assert_eq!(
+        heading_slug("snake_case & kebab-case"),
+        "snake_case--kebab-case"
+    );
+    assert_eq!(heading_slug("Café Menu"), "café-menu");
+    assert_eq!(heading_slug("!!!"), "");
+}
+
+#[test]
+fn test_heading_anchors_disabled_by_default() {
+    let result = render_note("test.md", "# Intro", &RenderOptions::default());
+    assert!(result.contains("<h1>Intro</h1>"));
+    assert!(!result.contains("heading-anchor"));
+}
+
+#[test]
+fn test_heading_anchors_add_ids_and_links() {
+    let result = render_note(
+        "test.md",
+        "# Getting Started\n\n## Use `cargo`",
+        &with_heading_anchors(),
+    );
+
+    assert!(result.contains(r##"<h1 id="getting-started"><a class="heading-anchor" href="#getting-started" rel="noopener noreferrer"></a>Getting Started</h1>"##));
+    assert!(result.contains(r#"<h2 id="use-cargo">"#));
+    assert!(result.contains("<code>cargo</code>"));
+}
+
+#[test]
+fn test_heading_anchors_dedupe_slugs() {
+    let result = render_note(
+        "test.md",
+        "# Notes\n\n## Notes\n\n### Notes\n\n# Notes 1",
+        &with_heading_anchors(),
+    );
+
+    assert!(result.contains(r#"<h1 id="notes">"#));
+    assert!(result.contains(r#"<h2 id="notes-1">"#));
+    assert!(result.contains(r#"<h3 id="notes-2">"#));
+    assert!(result.contains(r#"<h1 id="notes-1-1">"#));
+}
+
+#[test]
+fn test_heading_anchors_skip_text_notes_and_empty_slugs() {
+    let text = render_note("test.txt", "# Not a heading", &with_heading_anchors());
+    assert!(!text.contains("heading-anchor"));
+
+    let markdown = render_note("test.md", "# ???", &with_heading_anchors());
+    assert!(markdown.contains("<h1>???</h1>"));
+}
+
+fn toc_entry(level: u8, text: &str, slug: Option<&str>) -> TocEntry {
+    TocEntry {
+        level,
+        text: text.to_string(),
+        slug: slug.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_note_toc_matches_heading_anchors() {
+    let content =
+        "# Notes\n\n```\n# Not a heading\n```\n\n## Notes\n\n## Use `cargo`\n\n### ???\n\n# Notes";
+    let toc = note_toc("test.md", content, &RenderOptions::default());
+
+    assert_eq!(
+        toc,
+        vec![
+            toc_entry(1, "Notes", Some("notes")),
+            toc_entry(2, "Notes", Some("notes-1")),
+            toc_entry(2, "Use cargo", Some("use-cargo")),
+            toc_entry(3, "???", None),
+            toc_entry(1, "Notes", Some("notes-2")),
+        ]
+    );
+
+    let html = render_note("test.md", content, &with_heading_anchors());
+    for slug in toc.iter().filter_map(|entry| entry.slug.as_deref()) {
+        assert!(html.contains(&format!(r#"id="{}""#, slug)), "{}", slug);
+    }
+}
+
+#[test]
+fn test_note_toc_empty_for_text_notes() {
+    assert!(note_toc("test.txt", "# Title", &RenderOptions::default()).is_empty());
+}
+
+#[test]
+#[serial]
+fn test_note_toc_command() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_save_note_with_content_check("outline.md", "# Intro\n\n## Details", "")
+        .expect("Failed to save note");
+
+    let toc = test_get_note_toc("outline.md").expect("Failed to get table of contents");
+    assert_eq!(
+        toc,
+        vec![
+            toc_entry(1, "Intro", Some("intro")),
+            toc_entry(2, "Details", Some("details")),
+        ]
+    );
+    assert!(test_get_note_toc("missing.md").is_err());
+}
+
+// Math Tests
+
+fn with_math() -> RenderOptions {
+    RenderOptions {
+        math: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_math_wrapped_in_spans_with_delimiters() {
+    let result = render_note("test.md", "Energy $E=mc^2$\n\n$$\na<b\n$$", &with_math());
+    assert!(result.contains(r#"<span class="math math-inline">\(E=mc^2\)</span>"#));
+    assert!(result.contains("<span class=\"math math-display\">\\[\na&lt;b\n\\]</span>"));
+}
+
+#[test]
+fn test_math_ignores_dollars_in_code_and_prices() {
+    let content = "`$x$` costs $5 and $10\n\n```\n$$y$$\n```";
+    let result = render_note("test.md", content, &with_math());
+    assert!(!result.contains("math"));
+    assert!(result.contains("<code>$x$</code>"));
+    assert!(result.contains("$$y$$"));
+}
+
+#[test]
+fn test_math_left_as_text_when_disabled() {
+    let result = render_note("test.md", "$E=mc^2$", &RenderOptions::default());
+    assert!(result.contains("<p>$E=mc^2$</p>"));
+}
+
+// Server-side Code Highlighting Tests
+
+fn with_code_theme(theme: &str) -> RenderOptions {
+    RenderOptions {
+        code_theme: Some(theme.to_string()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_code_blocks_highlighted_with_theme_styles() {
+    let content = "```rust\nfn main() { let s = \"a<b\"; }\n```";
+    let result = render_note("test.md", content, &with_code_theme("gruvbox-dark-medium"));
+    assert!(result.contains(r#"<code class="hljs language-rust" style="background-color:"#));
+    assert!(result.contains("<span style=\"color:"));
+    assert!(result.contains("a&lt;b"));
+}
+
+#[test]
+fn test_code_theme_selects_syntect_theme() {
+    assert_eq!(syntect_theme_name("github"), "InspiredGitHub");
+    assert_eq!(syntect_theme_name("gruvbox-dark-hard"), "base16-mocha.dark");
+    for theme in get_available_code_themes() {
+        let content = "```python\ndef f(): pass\n```";
+        let result = render_note("test.md", content, &with_code_theme(theme));
+        assert!(result.contains("hljs language-python"), "{}", theme);
+    }
+}
+
+#[test]
+fn test_unknown_languages_stay_plain() {
+    let content = "```not-a-language\na < b\n```\n\n```\nplain\n```";
+    let result = render_note("test.md", content, &with_code_theme("github"));
+    assert!(result.contains("<pre><code class=\"language-not-a-language\">a &lt; b\n</code></pre>"));
+    assert!(result.contains("<pre><code>plain\n</code></pre>"));
+}
+
+#[test]
+fn test_code_blocks_not_highlighted_without_theme() {
+    let result = render_note(
+        "test.md",
+        "```rust\nfn main() {}\n```",
+        &RenderOptions::default(),
+    );
+    assert!(result.contains("<pre><code class=\"language-rust\">fn main() {}"));
+}
+
+// Markdown Flavor Tests
+
+fn with_commonmark() -> RenderOptions {
+    RenderOptions {
+        commonmark: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_gfm_renders_task_list() {
+    let result = render_note(
+        "test.md",
+        "- [x] done\n- [ ] todo\n- ~~dropped~~",
+        &RenderOptions::default(),
+    );
+    assert_eq!(
+        result.matches(r#"type="checkbox""#).count(),
+        2,
+        "{}",
+        result
+    );
+    assert_eq!(result.matches("checked").count(), 1, "{}", result);
+    assert!(result.contains("<del>dropped</del>"), "{}", result);
+}
+
+#[test]
+fn test_gfm_renders_table() {
+    let content = "| Name | Qty |\n| --- | --- |\n| Apples | 3 |";
+    let result = render_note("test.md", content, &RenderOptions::default());
+    assert!(result.contains("<table>"), "{}", result);
+    assert!(result.contains("<th>Name</th>"), "{}", result);
+    assert!(result.contains("<td>Apples</td>"), "{}", result);
+}
+
+#[test]
+fn test_commonmark_leaves_gfm_syntax_as_text() {
+    let content = "| Name |\n| --- |\n| Apples |\n\n- [x] done ~~old~~ https://example.com";
+    let result = render_note("test.md", content, &with_commonmark());
+    assert!(!result.contains("<table>"), "{}", result);
+    assert!(!result.contains("checkbox"), "{}", result);
+    assert!(result.contains("~~old~~"), "{}", result);
+    assert!(!result.contains("<a "), "{}", result);
+
+    assert_eq!(
+        markdown_extensions(&RenderOptions::default()),
+        GFM_EXTENSIONS
+    );
+    assert!(markdown_extensions(&with_commonmark()).is_empty());
+}
@@ -0,0 +1,100 @@
+//! Note Stats Unit Tests
+//!
+//! Tests word, character and reading time counts for notes, and word goal progress.
+
+use crate::tests::test_utils::{
+    test_create_new_note, test_get_note_stats, test_get_writing_progress,
+    test_save_note_with_content_check, TestConfigOverride,
+};
+use crate::utilities::note_stats::{compute_note_stats, compute_writing_progress};
+use serial_test::serial;
+
+#[test]
+fn test_stats_ignore_markdown_syntax() {
+    let stats =
+        compute_note_stats("# My Title\n\nSee [the docs](https://example.com/docs) **now**.");
+
+    assert_eq!(stats.word_count, 6);
+    assert_eq!(stats.character_count, "My Title\nSee the docs now.".len());
+    assert_eq!(stats.character_count_no_whitespace, 21);
+    assert_eq!(stats.reading_time_minutes, 1);
+}
+
+#[test]
+fn test_code_blocks_excluded_from_reading_time() {
+    let code = "let x = 1;\n".repeat(300);
+    let content = format!("Short intro.\n\n```rust\n{}```\n", code);
+
+    let stats = compute_note_stats(&content);
+
+    assert_eq!(stats.word_count, 2 + 300 * 4);
+    assert_eq!(stats.reading_time_minutes, 1);
+}
+
+#[test]
+fn test_reading_time_rounds_up_at_200_wpm() {
+    assert_eq!(compute_note_stats("").reading_time_minutes, 0);
+    assert_eq!(
+        compute_note_stats(&"word ".repeat(200)).reading_time_minutes,
+        1
+    );
+    assert_eq!(
+        compute_note_stats(&"word ".repeat(201)).reading_time_minutes,
+        2
+    );
+}
+
+#[test]
+fn test_frontmatter_not_counted() {
+    let stats = compute_note_stats("---\ntags: [rust, notes]\n---\nHello world");
+
+    assert_eq!(stats.word_count, 2);
+}
+
+#[test]
+#[serial]
+fn test_note_stats_command() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    test_create_new_note("stats.md").expect("Failed to create note");
+    test_save_note_with_content_check("stats.md", "## Heading\n\nOne two three", "")
+        .expect("Failed to save note");
+
+    let stats = test_get_note_stats("stats.md").expect("Failed to get stats");
+    assert_eq!(stats.word_count, 4);
+
+    assert!(test_get_note_stats("missing.md").is_err());
+}
+
+#[test]
+fn test_writing_progress_towards_goal() {
+    let progress = compute_writing_progress("draft.md", "---\ngoal: 8\n---\nOne two three four");
+    assert_eq!(progress.words, 4);
+    assert_eq!(progress.goal, Some(8));
+    assert_eq!(progress.percentage, Some(50.0));
+
+    let without_goal = compute_writing_progress("draft.md", "One two");
+    assert_eq!(without_goal.goal, None);
+    assert_eq!(without_goal.percentage, None);
+
+    for goal in ["0", "many", "-5"] {
+        let content = format!("---\ngoal: {}\n---\nBody", goal);
+        assert_eq!(compute_writing_progress("draft.md", &content).goal, None);
+    }
+}
+
+#[test]
+#[serial]
+fn test_writing_progress_command() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    test_create_new_note("sprint.md").expect("Failed to create note");
+    test_save_note_with_content_check("sprint.md", "---\ngoal: 2\n---\nOne two three", "")
+        .expect("Failed to save note");
+
+    let progress = test_get_writing_progress("sprint.md").expect("Failed to get progress");
+    assert_eq!(progress.words, 3);
+    assert_eq!(progress.percentage, Some(150.0));
+
+    assert!(test_get_writing_progress("missing.md").is_err());
+}
@@ -0,0 +1,145 @@
+//! Pinned Notes Unit Tests
+//!
+//! Tests pinning notes, pin ordering and pinned notes in the default note list.
+
+use crate::services::pin_service::on_pins_changed;
+use crate::tests::test_utils::{
+    test_create_new_note, test_delete_note, test_list_all_notes, test_list_pinned_notes,
+    test_pin_note, test_rename_note, test_search_notes_hybrid, test_unpin_note, TestConfigOverride,
+};
+use crate::utilities::tray_pins::{pinned_note_for_menu_id, PINNED_NOTE_MENU_PREFIX};
+use rusqlite::Connection;
+use serial_test::serial;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static PIN_CHANGES: AtomicUsize = AtomicUsize::new(0);
+
+fn count_pin_change(_conn: &Connection) {
+    PIN_CHANGES.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+#[serial]
+fn test_pins_keep_pin_order() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    for name in ["daily.md", "inbox.md", "ideas.md"] {
+        test_create_new_note(name).expect("Failed to create note");
+    }
+
+    test_pin_note("inbox.md").expect("Failed to pin note");
+    test_pin_note("daily.md").expect("Failed to pin note");
+    test_pin_note("inbox.md").expect("Pinning twice should be a no-op");
+    assert_eq!(
+        test_list_pinned_notes().expect("Failed to list pins"),
+        vec!["inbox.md", "daily.md"]
+    );
+
+    test_unpin_note("inbox.md").expect("Failed to unpin note");
+    assert_eq!(
+        test_list_pinned_notes().expect("Failed to list pins"),
+        vec!["daily.md"]
+    );
+    assert!(test_unpin_note("inbox.md").is_err());
+}
+
+#[test]
+#[serial]
+fn test_pins_changed_listener_runs_only_on_changes() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    assert!(on_pins_changed(count_pin_change));
+    assert!(
+        !on_pins_changed(count_pin_change),
+        "Only one listener is kept"
+    );
+    test_create_new_note("daily.md").expect("Failed to create note");
+    test_create_new_note("other.md").expect("Failed to create note");
+
+    let before = PIN_CHANGES.load(Ordering::SeqCst);
+    test_pin_note("daily.md").expect("Failed to pin note");
+    test_pin_note("daily.md").expect("Pinning twice should be a no-op");
+    test_delete_note("other.md").expect("Failed to delete note");
+    assert_eq!(PIN_CHANGES.load(Ordering::SeqCst), before + 1);
+
+    test_unpin_note("daily.md").expect("Failed to unpin note");
+    assert_eq!(PIN_CHANGES.load(Ordering::SeqCst), before + 2);
+}
+
+#[test]
+#[serial]
+fn test_pin_missing_note_fails() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let err = test_pin_note("missing.md").expect_err("Pinning a missing note should fail");
+    assert!(err.contains("Note not found"), "{}", err);
+    assert!(test_list_pinned_notes().unwrap().is_empty());
+}
+
+#[test]
+#[serial]
+fn test_pins_follow_rename_and_delete() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    test_create_new_note("draft.md").expect("Failed to create note");
+    test_create_new_note("scratch.md").expect("Failed to create note");
+    test_pin_note("draft.md").expect("Failed to pin note");
+    test_pin_note("scratch.md").expect("Failed to pin note");
+
+    test_rename_note("draft.md".to_string(), "final.md".to_string())
+        .expect("Failed to rename note");
+    test_delete_note("scratch.md").expect("Failed to delete note");
+
+    assert_eq!(
+        test_list_pinned_notes().expect("Failed to list pins"),
+        vec!["final.md"]
+    );
+}
+
+#[test]
+#[serial]
+fn test_pinned_notes_lead_default_list() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    for name in ["old.md", "older.md", "newest.md"] {
+        test_create_new_note(name).expect("Failed to create note");
+    }
+    test_pin_note("older.md").expect("Failed to pin note");
+    test_pin_note("old.md").expect("Failed to pin note");
+
+    let notes = test_search_notes_hybrid("", 10).expect("Failed to list notes");
+    assert_eq!(&notes[..2], ["older.md", "old.md"]);
+    assert!(notes.contains(&"newest.md".to_string()));
+}
+
+#[test]
+fn test_tray_menu_ids_map_back_to_pinned_notes() {
+    let id = format!("{}work/plan.md", PINNED_NOTE_MENU_PREFIX);
+    assert_eq!(pinned_note_for_menu_id(&id), Some("work/plan.md"));
+    assert_eq!(pinned_note_for_menu_id("open"), None);
+    assert_eq!(pinned_note_for_menu_id("pinned_none"), None);
+}
+
+#[test]
+#[serial]
+fn test_list_all_notes_ignores_pins() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_config.update_config(|config| {
+        config.preferences.note_sort_order = "name_asc".to_string();
+        config.preferences.pinned_notes_first = true;
+    });
+
+    for name in ["a.md", "b.md", "c.md"] {
+        test_create_new_note(name).expect("Failed to create note");
+    }
+    test_pin_note("c.md").expect("Failed to pin note");
+
+    assert_eq!(
+        test_search_notes_hybrid("", 10).expect("Failed to list notes"),
+        vec!["c.md", "a.md", "b.md"]
+    );
+    assert_eq!(
+        test_list_all_notes().expect("Failed to list notes"),
+        vec!["a.md", "b.md", "c.md"],
+        "The full listing keeps the sort order alone"
+    );
+}
@@ -0,0 +1,104 @@
+//! Recent Notes Unit Tests
+//!
+//! Tests recording opened notes, recents ordering and pruning of removed notes.
+
+use crate::tests::test_utils::{
+    test_archive_note, test_create_new_note, test_delete_note, test_list_recent_notes,
+    test_record_note_opened, test_rename_note, TestConfigOverride,
+};
+use serial_test::serial;
+use std::thread::sleep;
+use std::time::Duration;
+
+fn open_in_order(names: &[&str]) {
+    for name in names {
+        test_record_note_opened(name).expect("Failed to record opened note");
+        // Keep each open on its own millisecond
+        sleep(Duration::from_millis(5));
+    }
+}
+
+#[test]
+#[serial]
+fn test_recents_most_recent_first() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    for name in ["daily.md", "inbox.md", "ideas.md"] {
+        test_create_new_note(name).expect("Failed to create note");
+    }
+
+    open_in_order(&["daily.md", "inbox.md", "ideas.md", "daily.md"]);
+
+    assert_eq!(
+        test_list_recent_notes(10).expect("Failed to list recents"),
+        vec!["daily.md", "ideas.md", "inbox.md"]
+    );
+    assert_eq!(
+        test_list_recent_notes(2).expect("Failed to list recents"),
+        vec!["daily.md", "ideas.md"]
+    );
+    assert!(test_list_recent_notes(0).unwrap().is_empty());
+}
+
+#[test]
+#[serial]
+fn test_record_missing_note_fails() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let err =
+        test_record_note_opened("missing.md").expect_err("Recording a missing note should fail");
+    assert!(err.contains("Note not found"), "{}", err);
+    assert!(test_list_recent_notes(10).unwrap().is_empty());
+}
+
+#[test]
+#[serial]
+fn test_recents_follow_rename_delete_and_archive() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    for name in ["draft.md", "scratch.md", "old.md"] {
+        test_create_new_note(name).expect("Failed to create note");
+    }
+    open_in_order(&["draft.md", "scratch.md", "old.md"]);
+
+    test_rename_note("draft.md".to_string(), "final.md".to_string())
+        .expect("Failed to rename note");
+    test_delete_note("scratch.md").expect("Failed to delete note");
+    test_archive_note("old.md").expect("Failed to archive note");
+
+    assert_eq!(
+        test_list_recent_notes(10).expect("Failed to list recents"),
+        vec!["final.md"]
+    );
+}
+
+#[test]
+#[serial]
+fn test_recents_pruned_after_external_removal() {
+    use crate::core::state::AppState;
+    use crate::database::with_db_mut;
+    use crate::services::database_service::sync_notes_with_filesystem;
+
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    test_create_new_note("gone.md").expect("Failed to create note");
+    test_create_new_note("kept.md").expect("Failed to create note");
+    open_in_order(&["gone.md", "kept.md"]);
+
+    std::fs::remove_file(test_config.notes_dir().join("gone.md")).expect("Failed to remove note");
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Test database setup failed");
+    let remaining = with_db_mut(&app_state, |conn| {
+        sync_notes_with_filesystem(&app_state, conn, true, None)?;
+        let mut stmt = conn.prepare(
+            "SELECT note_ids.filename FROM recent_notes
+                 JOIN note_ids ON note_ids.id = recent_notes.note_id",
+        )?;
+        let filenames = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(filenames)
+    })
+    .expect("Sync should succeed");
+    assert_eq!(remaining, vec!["kept.md"]);
+}
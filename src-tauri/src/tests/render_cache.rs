@@ -0,0 +1,148 @@
+//! Render Cache Unit Tests
+//!
+//! Tests reuse of rendered HTML across unchanged content, invalidation by
+//! content and render settings, eviction and clearing the cache.
+
+use crate::core::state::AppState;
+use crate::database::with_db;
+use crate::services::render_cache_service::{render_cache_key, render_note_cached};
+use crate::tests::test_utils::{
+    test_clear_render_cache, test_create_new_note, test_get_note_html_content,
+    test_save_note_with_content_check, TestConfigOverride,
+};
+use crate::utilities::note_renderer::RenderOptions;
+use serial_test::serial;
+
+fn execute(sql: &str) {
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Failed to create app state");
+    with_db(&app_state, |conn| {
+        conn.execute(sql, [])?;
+        Ok(())
+    })
+    .expect("Failed to execute statement");
+}
+
+fn cached_entries() -> i64 {
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Failed to create app state");
+    with_db(&app_state, |conn| {
+        Ok(conn.query_row("SELECT COUNT(*) FROM render_cache", [], |row| row.get(0))?)
+    })
+    .expect("Failed to count cache entries")
+}
+
+// Saving renders into the index directly, so force the on-demand path
+fn mark_unrendered() {
+    execute("UPDATE notes SET is_indexed = 0");
+}
+
+#[test]
+fn test_render_cache_key_covers_settings_and_content() {
+    let options = RenderOptions::default();
+    let key = render_cache_key("note.md", "# Title", &options);
+
+    assert_eq!(key, render_cache_key("renamed.md", "# Title", &options));
+    assert_ne!(key, render_cache_key("note.md", "# Other", &options));
+    assert_ne!(key, render_cache_key("note.txt", "# Title", &options));
+    assert_ne!(
+        key,
+        render_cache_key(
+            "note.md",
+            "# Title",
+            &RenderOptions {
+                math: true,
+                ..RenderOptions::default()
+            }
+        )
+    );
+    assert_ne!(
+        key,
+        render_cache_key(
+            "note.md",
+            "# Title",
+            &RenderOptions {
+                code_theme: Some("nord".to_string()),
+                ..RenderOptions::default()
+            }
+        )
+    );
+}
+
+#[test]
+#[serial]
+fn test_unchanged_content_is_served_from_cache() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_create_new_note("cached.md").expect("Failed to create note");
+    test_save_note_with_content_check("cached.md", "# Cached", "").expect("Failed to save note");
+
+    mark_unrendered();
+    let first = test_get_note_html_content("cached.md").expect("Failed to render note");
+    assert!(first.contains("<h1"), "{}", first);
+    assert_eq!(cached_entries(), 1);
+
+    // A render would never produce this, so seeing it proves the cache was used
+    execute("UPDATE render_cache SET html = '<p>from cache</p>'");
+    mark_unrendered();
+    assert_eq!(
+        test_get_note_html_content("cached.md").unwrap(),
+        "<p>from cache</p>"
+    );
+
+    test_save_note_with_content_check("cached.md", "# Changed", "# Cached")
+        .expect("Failed to save note");
+    mark_unrendered();
+    let changed = test_get_note_html_content("cached.md").unwrap();
+    assert!(changed.contains("Changed"), "{}", changed);
+    assert_eq!(cached_entries(), 2);
+}
+
+#[test]
+#[serial]
+fn test_render_cache_evicts_least_recently_used() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Failed to create app state");
+    let options = RenderOptions::default();
+
+    with_db(&app_state, |conn| {
+        render_note_cached(conn, "a.md", "# A", &options, 2)?;
+        render_note_cached(conn, "b.md", "# B", &options, 2)?;
+        // Using A again leaves B as the oldest entry
+        render_note_cached(conn, "a.md", "# A", &options, 2)?;
+        render_note_cached(conn, "c.md", "# C", &options, 2)?;
+
+        let mut stmt = conn.prepare("SELECT key FROM render_cache")?;
+        let keys = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(keys.len(), 2);
+        assert!(keys.contains(&render_cache_key("a.md", "# A", &options)));
+        assert!(keys.contains(&render_cache_key("c.md", "# C", &options)));
+
+        // A zero limit renders without touching the cache
+        render_note_cached(conn, "d.md", "# D", &options, 0)?;
+        Ok(())
+    })
+    .expect("Failed to exercise render cache");
+    assert_eq!(cached_entries(), 2);
+}
+
+#[test]
+#[serial]
+fn test_clear_render_cache_removes_entries() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    for name in ["one.md", "two.md"] {
+        test_create_new_note(name).expect("Failed to create note");
+        test_save_note_with_content_check(name, &format!("# {}", name), "")
+            .expect("Failed to save note");
+    }
+    mark_unrendered();
+    for name in ["one.md", "two.md"] {
+        test_get_note_html_content(name).expect("Failed to render note");
+    }
+
+    assert_eq!(test_clear_render_cache().unwrap(), 2);
+    assert_eq!(cached_entries(), 0);
+    assert_eq!(test_clear_render_cache().unwrap(), 0);
+}
@@ -0,0 +1,141 @@
+//! Find and Replace Unit Tests
+//!
+//! Tests replacements across notes, dry run previews and the versions kept
+//! before each note is changed.
+
+use crate::services::replace_service::{ReplacePreviewLine, Replacement};
+use crate::tests::test_utils::{
+    test_create_new_note, test_get_note_content, test_replace_across_notes,
+    test_save_note_with_content_check, TestConfigOverride,
+};
+use crate::utilities::paths::get_backup_dir_for_notes_path;
+use serial_test::serial;
+use std::fs;
+
+#[test]
+fn test_replacement_literal_and_regex() {
+    let literal = Replacement::new("a.b", "$1", false).expect("Literal should compile");
+    assert_eq!(
+        literal.apply("a.b axb a.b"),
+        Some(("$1 axb $1".to_string(), 2))
+    );
+    assert_eq!(literal.apply("axb"), None);
+
+    let regex = Replacement::new(r"(\w+)@old\.com", "$1@new.com", true).expect("Valid regex");
+    assert_eq!(
+        regex.apply("ann@old.com, bob@old.com"),
+        Some(("ann@new.com, bob@new.com".to_string(), 2))
+    );
+    assert_eq!(
+        regex.preview("intro\nmail ann@old.com\nend"),
+        vec![ReplacePreviewLine {
+            line: 2,
+            before: "mail ann@old.com".to_string(),
+            after: "mail ann@new.com".to_string(),
+        }]
+    );
+
+    assert!(Replacement::new("(", "", true).is_err());
+    assert!(Replacement::new("x*", "y", true).is_err());
+    assert!(Replacement::new("", "y", false).is_err());
+}
+
+#[test]
+#[serial]
+fn test_replace_across_notes_dry_run_then_write() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_config.update_config(|config| config.preferences.version_min_interval_seconds = 3_600);
+    for (name, content) in [
+        ("a.md", "Project Falcon\nFalcon status"),
+        ("b.md", "Nothing here"),
+        ("c.md", "Falcon"),
+    ] {
+        test_create_new_note(name).expect("Failed to create note");
+        test_save_note_with_content_check(name, content, "").expect("Failed to save note");
+    }
+    let backup_dir = get_backup_dir_for_notes_path(&test_config.notes_dir()).unwrap();
+    let versions = |stem: &str| {
+        fs::read_dir(&backup_dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|entry| {
+                        entry
+                            .file_name()
+                            .to_string_lossy()
+                            .starts_with(&format!("{}.", stem))
+                    })
+                    .count()
+            })
+            .unwrap_or(0)
+    };
+
+    // A recent version would make a normal save skip keeping another one
+    for entry in fs::read_dir(&backup_dir).unwrap().flatten() {
+        fs::remove_file(entry.path()).unwrap();
+    }
+    let recent = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        - 60;
+    fs::write(
+        backup_dir.join(format!("a.rollback.{}.md", recent)),
+        "earlier",
+    )
+    .unwrap();
+
+    let preview =
+        test_replace_across_notes("Falcon", "Osprey", false, true).expect("Dry run failed");
+    let counts: Vec<(&str, usize)> = preview
+        .iter()
+        .map(|r| (r.filename.as_str(), r.replacements))
+        .collect();
+    assert_eq!(counts, vec![("a.md", 2), ("c.md", 1)]);
+    assert_eq!(preview[0].preview.as_ref().map(Vec::len), Some(2));
+    assert_eq!(
+        fs::read_to_string(test_config.notes_dir().join("a.md")).unwrap(),
+        "Project Falcon\nFalcon status",
+        "A dry run must not write"
+    );
+
+    let results =
+        test_replace_across_notes("Falcon", "Osprey", false, false).expect("Replace failed");
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|r| r.preview.is_none()));
+    assert_eq!(
+        fs::read_to_string(test_config.notes_dir().join("a.md")).unwrap(),
+        "Project Osprey\nOsprey status"
+    );
+    assert_eq!(test_get_note_content("c.md").unwrap(), "Osprey");
+    assert_eq!(
+        versions("a"),
+        2,
+        "A version is kept despite version_min_interval_seconds"
+    );
+    assert_eq!(versions("c"), 1);
+
+    let err = test_replace_across_notes("[", "x", true, true).unwrap_err();
+    assert!(err.contains("Invalid regex"), "{}", err);
+}
+
+#[test]
+#[serial]
+fn test_replace_reads_notes_from_disk() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let note_path = test_config.notes_dir().join("a.md");
+    test_create_new_note("a.md").expect("Failed to create note");
+    test_save_note_with_content_check("a.md", "Falcon one", "").expect("Failed to save note");
+
+    // Edited outside the app, before the index has caught up
+    fs::write(&note_path, "Falcon one\nFalcon two, added elsewhere").unwrap();
+
+    let results =
+        test_replace_across_notes("Falcon", "Osprey", false, false).expect("Replace failed");
+    assert_eq!(results[0].replacements, 2);
+    assert_eq!(
+        fs::read_to_string(&note_path).unwrap(),
+        "Osprey one\nOsprey two, added elsewhere",
+        "The edit made outside the app must not be lost"
+    );
+}
@@ -0,0 +1,82 @@
+//! Save Format Unit Tests
+//!
+//! Tests trailing whitespace trimming, final newlines and line endings applied
+//! to notes as they are saved.
+
+use crate::tests::test_utils::{
+    test_create_new_note, test_save_note_with_content_check, TestConfigOverride,
+};
+use crate::utilities::save_format::{ensure_final_newline, trim_trailing_whitespace, SaveFormat};
+use serial_test::serial;
+use std::fs;
+
+#[test]
+fn test_trim_trailing_whitespace_keeps_code_and_hard_breaks() {
+    let content = "title \t\nline break    \r\n```\ncode  \n```  \n  \nend";
+    assert_eq!(
+        trim_trailing_whitespace(content).as_deref(),
+        Some("title\nline break  \r\n```\ncode  \n```\n\nend")
+    );
+    assert_eq!(trim_trailing_whitespace("clean\nnote\n"), None);
+
+    // An unclosed fence protects the rest of the note
+    assert_eq!(trim_trailing_whitespace("~~~\nkeep  \n"), None);
+}
+
+#[test]
+fn test_ensure_final_newline() {
+    assert_eq!(ensure_final_newline("note").as_deref(), Some("note\n"));
+    assert_eq!(
+        ensure_final_newline("note\n\n\n").as_deref(),
+        Some("note\n")
+    );
+    assert_eq!(
+        ensure_final_newline("a\r\nb\r\n\r\n").as_deref(),
+        Some("a\r\nb\r\n")
+    );
+    assert_eq!(ensure_final_newline("note\n"), None);
+    assert_eq!(ensure_final_newline(""), None);
+}
+
+#[test]
+fn test_save_format_apply_is_idempotent() {
+    let format = SaveFormat {
+        line_ending: "crlf".to_string(),
+        trim_trailing_whitespace: true,
+        ensure_final_newline: true,
+    };
+    let once = format.apply("a \nb\n\n").into_owned();
+    assert_eq!(once, "a\r\nb\r\n");
+    assert_eq!(format.apply(&once), once);
+}
+
+#[test]
+#[serial]
+fn test_save_tidies_when_enabled() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let note_path = test_config.notes_dir().join("tidy.md");
+    test_create_new_note("tidy.md").expect("Failed to create note");
+
+    test_save_note_with_content_check("tidy.md", "draft  \t", "").expect("Failed to save");
+    assert_eq!(
+        fs::read_to_string(&note_path).unwrap(),
+        "draft  \t",
+        "Both options are off by default"
+    );
+
+    test_config.update_config(|config| {
+        config.preferences.trim_trailing_whitespace = true;
+        config.preferences.ensure_final_newline = true;
+    });
+    test_save_note_with_content_check("tidy.md", "draft \n```\nx  \n```\n\n", "draft  \t")
+        .expect("Failed to save");
+    assert_eq!(
+        fs::read_to_string(&note_path).unwrap(),
+        "draft\n```\nx  \n```\n"
+    );
+
+    // The editor's untidied copy still matches the note on disk
+    test_save_note_with_content_check("tidy.md", "final", "draft \n```\nx  \n```\n\n")
+        .expect("Saving after a tidy should not conflict");
+    assert_eq!(fs::read_to_string(&note_path).unwrap(), "final\n");
+}
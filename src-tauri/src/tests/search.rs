@@ -2,7 +2,13 @@
 //!
 //! Tests for search functionality, FTS security, and performance.
 
-use crate::tests::test_utils::{test_search_notes_hybrid, TestConfigOverride};
+use crate::tests::test_utils::{
+    test_create_new_note, test_list_all_notes, test_save_note_with_content_check,
+    test_search_notes_fuzzy, test_search_notes_highlights, test_search_notes_hybrid,
+    test_search_notes_in_folder, test_search_notes_regex, test_search_notes_whole_word,
+    test_search_within_note, TestConfigOverride,
+};
+use crate::utilities::strings::fold_diacritics;
 use serial_test::serial;
 use std::time::Instant;
 
@@ -227,3 +233,530 @@ fn test_search_performance_stress_queries() {
         }
     }
 }
+
+#[test]
+#[serial]
+fn test_regex_search_invalid_pattern_returns_error() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    for pattern in ["fn\\s+(", "[unclosed", "*invalid", ""] {
+        let result = std::panic::catch_unwind(|| test_search_notes_regex(pattern, 10));
+        assert!(result.is_ok(), "Regex pattern caused panic: {}", pattern);
+        assert!(
+            result.unwrap().is_err(),
+            "Invalid pattern should return an error: {}",
+            pattern
+        );
+    }
+}
+
+#[test]
+#[serial]
+fn test_regex_search_matches_content_and_respects_limit() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    for name in ["alpha.md", "beta.md", "gamma.md"] {
+        test_create_new_note(name).expect("Failed to create note");
+    }
+    test_save_note_with_content_check("alpha.md", "fn save_handler() {}", "")
+        .expect("Failed to save note");
+    test_save_note_with_content_check("beta.md", "fn load_handler() {}", "")
+        .expect("Failed to save note");
+    test_save_note_with_content_check("gamma.md", "no functions here", "")
+        .expect("Failed to save note");
+
+    let results = test_search_notes_regex(r"fn\s+\w+_handler", 10).expect("Regex search failed");
+    assert_eq!(results.len(), 2);
+    assert!(results.contains(&"alpha.md".to_string()));
+    assert!(results.contains(&"beta.md".to_string()));
+
+    let limited = test_search_notes_regex(r"fn\s+\w+_handler", 1).expect("Regex search failed");
+    assert_eq!(limited.len(), 1);
+}
+
+#[test]
+#[serial]
+fn test_search_scoped_to_folder() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    for name in [
+        "plan.md",
+        "work/plan.md",
+        "work/clients/acme.md",
+        "workshop/plan.md",
+    ] {
+        test_create_new_note(name).expect("Failed to create note");
+        test_save_note_with_content_check(name, "Quarterly roadmap", "")
+            .expect("Failed to save note");
+    }
+
+    let mut work = test_search_notes_in_folder("roadmap", "work", 10).expect("Search failed");
+    work.sort();
+    // A sibling folder sharing the name as a prefix is not inside it
+    assert_eq!(work, vec!["work/clients/acme.md", "work/plan.md"]);
+
+    let clients =
+        test_search_notes_in_folder("roadmap", "/work/clients/", 10).expect("Search failed");
+    assert_eq!(clients, vec!["work/clients/acme.md"]);
+
+    let limited = test_search_notes_in_folder("roadmap", "work", 1).expect("Search failed");
+    assert_eq!(limited.len(), 1);
+
+    let everywhere = test_search_notes_in_folder("roadmap", "", 10).expect("Search failed");
+    assert_eq!(everywhere.len(), 4);
+
+    let mut listed = test_search_notes_in_folder("", "work", 10).expect("Listing failed");
+    listed.sort();
+    assert_eq!(listed, vec!["work/clients/acme.md", "work/plan.md"]);
+
+    assert!(test_search_notes_in_folder("roadmap", "missing", 10)
+        .expect("Search failed")
+        .is_empty());
+}
+
+#[test]
+#[serial]
+fn test_search_folder_rejects_traversal() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_create_new_note("plan.md").expect("Failed to create note");
+
+    for prefix in ["..", "work/../..", "../../etc", "work\\clients"] {
+        assert!(
+            test_search_notes_in_folder("plan", prefix, 10).is_err(),
+            "'{}' should be rejected",
+            prefix
+        );
+    }
+}
+
+#[test]
+#[serial]
+fn test_fuzzy_search_ranks_by_title_score() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    for name in ["config_helpers.md", "chapter_fgh_lp.md", "unrelated.md"] {
+        test_create_new_note(name).expect("Failed to create note");
+    }
+
+    let results = test_search_notes_fuzzy("cfghlp", 10).expect("Fuzzy search failed");
+    let filenames: Vec<&str> = results.iter().map(|(f, _)| f.as_str()).collect();
+
+    assert!(filenames.contains(&"config_helpers.md"));
+    assert!(!filenames.contains(&"unrelated.md"));
+    assert!(
+        results.windows(2).all(|pair| pair[0].1 >= pair[1].1),
+        "Results should be sorted by descending score: {:?}",
+        results
+    );
+
+    let limited = test_search_notes_fuzzy("cfghlp", 1).expect("Fuzzy search failed");
+    assert_eq!(limited.len(), 1);
+}
+
+#[test]
+#[serial]
+fn test_fuzzy_search_empty_query_returns_recent_notes() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    test_create_new_note("recent.md").expect("Failed to create note");
+
+    let results = test_search_notes_fuzzy("  ", 10).expect("Fuzzy search failed");
+    assert!(results.iter().any(|(f, _)| f == "recent.md"));
+}
+
+#[test]
+fn test_fold_diacritics_strips_accents_only() {
+    assert_eq!(fold_diacritics("Café Crème"), "Cafe Creme");
+    assert_eq!(fold_diacritics("naïve façade"), "naive facade");
+    assert_eq!(fold_diacritics("plain ascii"), "plain ascii");
+
+    // CJK, Hangul syllables, kana voicing marks and emoji must survive untouched
+    for text in ["note-测试-🦀", "한국어 메모", "がぎぐげご", "パスワード"] {
+        assert_eq!(fold_diacritics(text), text);
+    }
+}
+
+#[test]
+#[serial]
+fn test_search_ignores_diacritics() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    test_create_new_note("menu.md").expect("Failed to create note");
+    test_save_note_with_content_check("menu.md", "Best Café in town", "")
+        .expect("Failed to save note");
+    test_create_new_note("cafe_list.md").expect("Failed to create note");
+    test_create_new_note("测试笔记.md").expect("Failed to create note");
+
+    let results = test_search_notes_hybrid("cafe", 10).expect("Search failed");
+    assert!(results.contains(&"menu.md".to_string()));
+    assert!(results.contains(&"cafe_list.md".to_string()));
+
+    let results = test_search_notes_hybrid("café", 10).expect("Search failed");
+    assert!(results.contains(&"menu.md".to_string()));
+    assert!(results.contains(&"cafe_list.md".to_string()));
+
+    let results = test_search_notes_hybrid("测试", 10).expect("Search failed");
+    assert!(results.contains(&"测试笔记.md".to_string()));
+}
+
+#[test]
+#[serial]
+fn test_whole_word_search_skips_partial_matches() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    test_create_new_note("partial.md").expect("Failed to create note");
+    test_save_note_with_content_check("partial.md", "Notes about testing strategy", "")
+        .expect("Failed to save note");
+    test_create_new_note("complete.md").expect("Failed to create note");
+    test_save_note_with_content_check("complete.md", "Write a unit test first", "")
+        .expect("Failed to save note");
+
+    let results = test_search_notes_whole_word("test", 10).expect("Search failed");
+    assert!(results.contains(&"complete.md".to_string()));
+    assert!(!results.contains(&"partial.md".to_string()));
+
+    let results = test_search_notes_hybrid("test", 10).expect("Search failed");
+    assert!(results.contains(&"partial.md".to_string()));
+}
+
+#[test]
+#[serial]
+fn test_whole_word_search_composes_with_diacritics_and_limit() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    for i in 0..3 {
+        let name = format!("cafe_{}.md", i);
+        test_create_new_note(&name).expect("Failed to create note");
+        test_save_note_with_content_check(&name, "Meet at the Café", "")
+            .expect("Failed to save note");
+    }
+    test_create_new_note("cafeteria.md").expect("Failed to create note");
+    test_save_note_with_content_check("cafeteria.md", "Cafeteria menu", "")
+        .expect("Failed to save note");
+
+    let results = test_search_notes_whole_word("CAFE", 10).expect("Search failed");
+    assert_eq!(results.len(), 3, "{:?}", results);
+    assert!(!results.contains(&"cafeteria.md".to_string()));
+
+    let limited = test_search_notes_whole_word("cafe", 2).expect("Search failed");
+    assert_eq!(limited.len(), 2);
+}
+
+#[test]
+#[serial]
+fn test_search_returns_highlight_snippets() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let filler = "lorem ipsum ".repeat(20);
+    let content = format!(
+        "Deploy <app> on Friday.\n{}deploy again\n{}deploy once more\n{}final deploy\n{}last deploy",
+        filler, filler, filler, filler
+    );
+    test_create_new_note("release.md").expect("Failed to create note");
+    test_save_note_with_content_check("release.md", &content, "").expect("Failed to save note");
+
+    let results = test_search_notes_highlights("DEPLOY", 10).expect("Search failed");
+    let (_, highlights) = results
+        .iter()
+        .find(|(name, _)| name == "release.md")
+        .expect("Note should match");
+    let highlights = highlights
+        .as_ref()
+        .expect("Search results carry highlights");
+
+    assert_eq!(highlights.match_count, 5);
+    assert_eq!(highlights.snippets.len(), 3);
+    assert!(
+        highlights.snippets[0].starts_with("<mark>Deploy</mark> &lt;app&gt; on Friday."),
+        "{}",
+        highlights.snippets[0]
+    );
+    assert!(highlights.snippets[1].starts_with('…'));
+    assert!(highlights.snippets[1].contains("<mark>deploy</mark> again"));
+}
+
+#[test]
+#[serial]
+fn test_highlights_map_folded_matches_to_original_text() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    test_create_new_note("menu.md").expect("Failed to create note");
+    test_save_note_with_content_check("menu.md", "Crème brûlée at the Café", "")
+        .expect("Failed to save note");
+
+    let results = test_search_notes_highlights("cafe", 10).expect("Search failed");
+    let (_, highlights) = results
+        .iter()
+        .find(|(name, _)| name == "menu.md")
+        .expect("Note should match");
+    let highlights = highlights
+        .as_ref()
+        .expect("Search results carry highlights");
+
+    assert_eq!(highlights.match_count, 1);
+    assert_eq!(
+        highlights.snippets,
+        vec!["Crème brûlée at the <mark>Café</mark>".to_string()]
+    );
+
+    let recent = test_search_notes_highlights("", 10).expect("Search failed");
+    assert!(recent.iter().all(|(_, highlights)| highlights.is_none()));
+}
+
+#[test]
+#[serial]
+fn test_note_list_honors_sort_order() {
+    use crate::core::state::AppState;
+    use crate::database::with_db_mut;
+    use crate::services::database_service::sync_notes_with_filesystem;
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let notes_dir = test_config.notes_dir();
+
+    // Written oldest-created first, with modified times in the opposite order
+    let notes = [("beta.md", 100), ("Alpha.md", 200), ("gamma.md", 300)];
+    for (name, age_secs) in notes {
+        let path = notes_dir.join(name);
+        fs::write(&path, format!("# {}", name)).expect("Failed to write note");
+        fs::OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now() - Duration::from_secs(age_secs)))
+            .expect("Failed to set modified time");
+        std::thread::sleep(Duration::from_millis(20));
+    }
+
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Test database setup failed");
+    with_db_mut(&app_state, |conn| {
+        sync_notes_with_filesystem(&app_state, conn, true, None).map_err(|e| e.into())
+    })
+    .expect("Sync should succeed");
+
+    let by_modified_desc = vec!["beta.md", "Alpha.md", "gamma.md"];
+    let created_supported = fs::metadata(notes_dir.join("beta.md"))
+        .and_then(|metadata| metadata.created())
+        .is_ok();
+    let by_created_desc = if created_supported {
+        vec!["gamma.md", "Alpha.md", "beta.md"]
+    } else {
+        by_modified_desc.clone()
+    };
+
+    for (order, expected) in [
+        ("modified_desc", by_modified_desc.clone()),
+        ("modified_asc", vec!["gamma.md", "Alpha.md", "beta.md"]),
+        ("name_asc", vec!["Alpha.md", "beta.md", "gamma.md"]),
+        ("name_desc", vec!["gamma.md", "beta.md", "Alpha.md"]),
+        ("created_desc", by_created_desc.clone()),
+    ] {
+        test_config.update_config(|config| config.preferences.note_sort_order = order.to_string());
+        assert_eq!(
+            test_list_all_notes().expect("Failed to list notes"),
+            expected,
+            "Unexpected order for {}",
+            order
+        );
+        assert_eq!(
+            test_search_notes_hybrid("", 10).expect("Failed to list notes"),
+            expected,
+            "Unexpected empty-query order for {}",
+            order
+        );
+    }
+}
+
+#[test]
+#[serial]
+fn test_rapid_searches_reuse_one_connection() {
+    use crate::core::state::AppState;
+    use crate::search::search_notes_hybrid;
+
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    for i in 0..20 {
+        let name = format!("note_{}.md", i);
+        test_create_new_note(&name).expect("Failed to create note");
+        test_save_note_with_content_check(
+            &name,
+            &format!("# Note {}\n\nsearchable keystroke text", i),
+            "",
+        )
+        .expect("Failed to save note");
+    }
+
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Test database setup failed");
+    let query = "searchable keystroke";
+    let connections_opened = || {
+        app_state
+            .database_manager
+            .lock()
+            .unwrap()
+            .connections_opened()
+    };
+    search_notes_hybrid(&app_state, query, 100, false, "").expect("Search should succeed");
+    let before = connections_opened();
+
+    for i in 0..1000 {
+        let typed = &query[..1 + i % query.len()];
+        let results =
+            search_notes_hybrid(&app_state, typed, 100, false, "").expect("Search should succeed");
+        if typed == query {
+            assert_eq!(results.len(), 20);
+        }
+    }
+
+    assert_eq!(
+        connections_opened(),
+        before,
+        "Searches must not open new connections"
+    );
+}
+
+#[test]
+#[serial]
+fn test_older_search_generations_are_superseded() {
+    use crate::core::state::AppState;
+    use crate::search::search_for_generation;
+    use std::sync::atomic::Ordering;
+
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Test database setup failed");
+    let run = |generation| search_for_generation(&app_state, generation, || Ok(generation));
+
+    assert_eq!(run(Some(2)).unwrap(), Some(Some(2)));
+    assert_eq!(
+        run(Some(1)).unwrap(),
+        None,
+        "An older query arriving late is dropped"
+    );
+    assert_eq!(run(Some(2)).unwrap(), Some(Some(2)));
+    assert_eq!(
+        run(None).unwrap(),
+        Some(None),
+        "Untracked searches always run"
+    );
+
+    // A newer query arriving while this one runs supersedes it too
+    let overtaken = search_for_generation(&app_state, Some(3), || {
+        app_state.search_generation.store(4, Ordering::SeqCst);
+        Ok(())
+    });
+    assert_eq!(overtaken.unwrap(), None);
+    assert_eq!(run(Some(4)).unwrap(), Some(Some(4)));
+}
+
+#[test]
+#[serial]
+fn test_title_matches_outrank_body_matches() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    test_create_new_note("plans.md").expect("Failed to create note");
+    test_save_note_with_content_check("plans.md", "# Roadmap\n\nGoals for next year", "")
+        .expect("Failed to save note");
+    test_create_new_note("weekly.md").expect("Failed to create note");
+    let buried = format!("# Weekly\n\n{}", "the roadmap slipped again. ".repeat(60));
+    test_save_note_with_content_check("weekly.md", &buried, "").expect("Failed to save note");
+
+    assert_eq!(
+        test_search_notes_hybrid("roadmap", 10).expect("Search failed"),
+        vec!["plans.md", "weekly.md"]
+    );
+
+    // A small enough boost lets the many body matches win
+    test_config.update_config(|config| config.preferences.search_title_boost = 0.5);
+    assert_eq!(
+        test_search_notes_hybrid("roadmap", 10).expect("Search failed"),
+        vec!["weekly.md", "plans.md"]
+    );
+}
+
+fn refresh_index() {
+    use crate::core::state::AppState;
+    use crate::database::with_db_mut;
+    use crate::services::database_service::sync_notes_with_filesystem;
+
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Test database setup failed");
+    with_db_mut(&app_state, |conn| {
+        Ok(sync_notes_with_filesystem(&app_state, conn, false, None)?)
+    })
+    .expect("Sync should succeed");
+}
+
+#[test]
+#[serial]
+fn test_stemming_matches_word_forms_when_enabled() {
+    use crate::tests::test_utils::{test_list_pinned_notes, test_pin_note};
+
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    for (name, content) in [
+        ("daily.md", "I run every morning"),
+        ("late.md", "She was running late"),
+        ("plan.md", "The plan for the week"),
+    ] {
+        test_create_new_note(name).expect("Failed to create note");
+        test_save_note_with_content_check(name, content, "").expect("Failed to save note");
+    }
+    test_pin_note("plan.md").expect("Failed to pin note");
+    let search = |query| {
+        let mut results = test_search_notes_hybrid(query, 10).expect("Search failed");
+        results.sort();
+        results
+    };
+
+    assert_eq!(search("running"), vec!["late.md"]);
+
+    test_config.update_config(|config| config.preferences.search_stemming = true);
+    refresh_index();
+    assert_eq!(search("running"), vec!["daily.md", "late.md"]);
+    assert_eq!(search("runs"), vec!["daily.md", "late.md"]);
+    // "the" is a stop word, so it no longer pulls in every note containing it
+    assert_eq!(search("the running"), vec!["daily.md", "late.md"]);
+    assert_eq!(search("the"), vec!["plan.md"]);
+    assert_eq!(
+        test_list_pinned_notes().expect("Failed to list pins"),
+        vec!["plan.md"],
+        "Rebuilding the index keeps pins"
+    );
+
+    test_config.update_config(|config| config.preferences.search_stemming = false);
+    refresh_index();
+    assert_eq!(search("running"), vec!["late.md"]);
+}
+
+#[test]
+#[serial]
+fn test_search_within_note_positions() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_create_new_note("long.md").expect("Failed to create note");
+    test_save_note_with_content_check(
+        "long.md",
+        "# Café notes\ncafé Café\nCafeteria near the café",
+        "",
+    )
+    .expect("Failed to save note");
+    let positions = |query, whole_word, case_sensitive| {
+        test_search_within_note("long.md", query, whole_word, case_sensitive)
+            .expect("Search failed")
+            .into_iter()
+            .map(|m| (m.line, m.start, m.end))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        positions("café", false, false),
+        vec![(1, 2, 6), (2, 0, 4), (2, 5, 9), (3, 19, 23)]
+    );
+    assert_eq!(positions("Café", false, true), vec![(1, 2, 6), (2, 5, 9)]);
+    assert_eq!(positions("cafe", false, false), vec![(3, 0, 4)]);
+    assert!(positions("cafe", true, false).is_empty());
+    assert_eq!(positions("near the", true, false), vec![(3, 10, 18)]);
+
+    let err = test_search_within_note("missing.md", "café", false, false).unwrap_err();
+    assert!(err.contains("Note not found"), "{}", err);
+    assert!(test_search_within_note("long.md", "", false, false).is_err());
+}
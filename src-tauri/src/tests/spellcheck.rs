@@ -0,0 +1,82 @@
+//! Spellcheck Unit Tests
+//!
+//! Tests flagging misspelled words outside code and choosing dictionaries.
+
+use crate::tests::test_utils::{test_spellcheck_text, TestConfigOverride};
+use crate::utilities::paths::get_dictionaries_dir;
+use crate::utilities::validation::validate_spellcheck_lang;
+use serial_test::serial;
+use std::fs;
+
+#[test]
+#[serial]
+fn test_misspellings_outside_code_are_flagged() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let text = "🙂 Recieve fox\n\nRun `teh --wrongg` or see https://exampel.com/teh\n\n```\nwrongg teh\n```\nIt's 2nd";
+
+    let issues = test_spellcheck_text(text, "en_US").expect("Spellcheck failed");
+
+    assert_eq!(issues.len(), 1, "{:?}", issues);
+    let issue = &issues[0];
+    assert_eq!(issue.word, "Recieve");
+    // The emoji is two UTF-16 code units
+    assert_eq!((issue.start, issue.end), (3, 10));
+    assert!(
+        issue.suggestions.iter().any(|s| s == "Receive"),
+        "{:?}",
+        issue.suggestions
+    );
+    assert!(issue.suggestions.len() <= crate::utilities::spellcheck::MAX_SPELL_SUGGESTIONS);
+}
+
+#[test]
+#[serial]
+fn test_spellcheck_uses_dictionaries_from_directory() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let dir = get_dictionaries_dir();
+    fs::create_dir_all(&dir).expect("Failed to create dictionaries dir");
+    fs::write(dir.join("xx_TEST.aff"), "SET UTF-8\n").expect("Failed to write aff");
+    fs::write(dir.join("xx_TEST.dic"), "2\nfoo\nbar\n").expect("Failed to write dic");
+    fs::write(dir.join("xx_HALF.aff"), "SET UTF-8\n").expect("Failed to write aff");
+
+    assert!(validate_spellcheck_lang("xx_TEST", None).is_ok());
+    assert!(validate_spellcheck_lang("xx_HALF", None).is_err());
+    assert!(validate_spellcheck_lang("xx_TEST", Some("/nonexistent/dictionaries")).is_err());
+
+    test_config.update_config(|config| config.editor.spellcheck_lang = "xx_TEST".into());
+    let issues = test_spellcheck_text("foo baz bar", "").expect("Spellcheck failed");
+    let words: Vec<&str> = issues.iter().map(|issue| issue.word.as_str()).collect();
+    assert_eq!(words, vec!["baz"]);
+
+    let err = test_spellcheck_text("foo", "xx_HALF").unwrap_err();
+    assert!(err.contains("No dictionary"), "{}", err);
+}
+
+#[test]
+#[serial]
+fn test_spellcheck_lang_must_be_a_plain_name() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    let dir = get_dictionaries_dir();
+    fs::create_dir_all(&dir).expect("Failed to create dictionaries dir");
+    fs::write(dir.join("pt-BR.aff"), "SET UTF-8\n").expect("Failed to write aff");
+    fs::write(dir.join("pt-BR.dic"), "1\nfoo\n").expect("Failed to write dic");
+    fs::write(dir.join("x.y.aff"), "SET UTF-8\n").expect("Failed to write aff");
+    fs::write(dir.join("x.y.dic"), "1\nfoo\n").expect("Failed to write dic");
+
+    assert!(validate_spellcheck_lang("pt-BR", None).is_ok());
+    for lang in ["", "x.y", "../en_US", "en US", "en_US/../en_US"] {
+        let err = validate_spellcheck_lang(lang, None).unwrap_err();
+        assert!(
+            err.to_string().contains("letters, '_' and '-' only"),
+            "{}: {}",
+            lang,
+            err
+        );
+    }
+
+    let err = test_spellcheck_text("foo", "../../en_US").unwrap_err();
+    assert!(err.contains("Invalid spellcheck language"), "{}", err);
+
+    let config = crate::config::load_config_from_content("[editor]\nspellcheck_lang = \"x.y\"\n");
+    assert_eq!(config.editor.spellcheck_lang, "en_US");
+}
@@ -0,0 +1,85 @@
+//! Template Unit Tests
+//!
+//! Tests note templates: placeholder substitution and creating notes from templates.
+
+use crate::tests::test_utils::{
+    test_create_note_from_template, test_get_note_content, TestConfigOverride,
+};
+use crate::utilities::paths::get_templates_dir;
+use crate::utilities::templates::{load_template, render_template, TimestampFormats};
+use chrono::{TimeZone, Utc};
+use serial_test::serial;
+use std::fs;
+
+#[test]
+fn test_render_template_placeholders() {
+    let now = Utc.with_ymd_and_hms(2024, 3, 9, 7, 5, 0).unwrap();
+    let body = "# {{title}}\nDate: {{date}} {{time}}\n{{unknown}} stays";
+
+    let rendered = render_template(
+        body,
+        "journal/daily_standup.md",
+        &now,
+        &TimestampFormats::default(),
+    );
+
+    assert_eq!(
+        rendered,
+        "# daily standup\nDate: 2024-03-09 07:05\n{{unknown}} stays"
+    );
+}
+
+#[test]
+fn test_render_template_uses_configured_formats() {
+    let now = Utc.with_ymd_and_hms(2024, 3, 9, 17, 5, 0).unwrap();
+    let formats = TimestampFormats {
+        date: "%d/%m/%Y".to_string(),
+        time: "%I:%M %p".to_string(),
+    };
+
+    assert_eq!(
+        render_template("{{date}} {{time}}", "note.md", &now, &formats),
+        "09/03/2024 05:05 PM"
+    );
+
+    let broken = TimestampFormats {
+        date: "%Q".to_string(),
+        time: "%H:%M".to_string(),
+    };
+    assert_eq!(
+        render_template("{{date}}", "note.md", &now, &broken),
+        "2024-03-09"
+    );
+}
+
+#[test]
+#[serial]
+fn test_load_template_rejects_missing_and_traversal() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    assert!(load_template("does-not-exist").is_err());
+    assert!(load_template("").is_err());
+    assert!(load_template("../config").is_err());
+    assert!(load_template("nested/template").is_err());
+}
+
+#[test]
+#[serial]
+fn test_template_note_is_prefilled() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let templates_dir = get_templates_dir();
+    fs::create_dir_all(&templates_dir).expect("Failed to create templates dir");
+    fs::write(templates_dir.join("daily.md"), "# {{title}}\n\n## Tasks\n")
+        .expect("Failed to write template");
+
+    test_create_note_from_template("standup.md", "daily").expect("Failed to create note");
+    let content = test_get_note_content("standup.md").expect("Failed to read note");
+    assert_eq!(content, "# standup\n\n## Tasks\n");
+
+    let duplicate = test_create_note_from_template("standup.md", "daily");
+    assert!(duplicate.is_err(), "Existing notes must not be overwritten");
+
+    let missing = test_create_note_from_template("other.md", "missing");
+    assert!(missing.is_err(), "Missing template should return an error");
+}
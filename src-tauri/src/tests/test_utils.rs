@@ -299,6 +299,18 @@ impl TestConfigOverride {
     pub fn notes_dir(&self) -> std::path::PathBuf {
         self._temp_dir.path().join("_tmp_notes")
     }
+
+    /// Rewrite the test config file; configs loaded afterwards see the change
+    pub fn update_config(&self, update: impl FnOnce(&mut AppConfig)) {
+        let mut config = crate::config::load_config();
+        update(&mut config);
+        let config_toml = toml::to_string(&config).expect("Failed to serialize test config");
+        std::fs::write(
+            self._temp_dir.path().join("config").join("config.toml"),
+            config_toml,
+        )
+        .expect("Failed to write test config file");
+    }
 }
 
 #[cfg(test)]
@@ -315,6 +327,7 @@ impl Drop for TestConfigOverride {
 #[cfg(test)]
 mod test_command_wrappers {
     use crate::core::state::AppState;
+    use crate::core::{AppError, BatchResults};
     use tauri::test::{mock_builder, mock_context, noop_assets, MockRuntime};
     use tauri::{App, Manager};
 
@@ -347,6 +360,21 @@ mod test_command_wrappers {
         crate::commands::notes::create_new_note(note_name, app_state)
     }
 
+    pub fn test_create_note_from_template(note_name: &str, template: &str) -> Result<(), String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_create_note_from_template() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::create_note_from_template(
+            note_name.to_string(),
+            template.to_string(),
+            app_state,
+        )
+    }
+
     pub fn test_get_note_content(note_name: &str) -> Result<String, String> {
         // SAFETY CHECK: Ensure we're in test mode before proceeding
         if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
@@ -358,6 +386,100 @@ mod test_command_wrappers {
         crate::commands::notes::get_note_content(note_name, app_state)
     }
 
+    pub fn test_get_note_stats(
+        note_name: &str,
+    ) -> Result<crate::utilities::note_stats::NoteStats, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_get_note_stats() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::get_note_stats(note_name.to_string(), app_state)
+    }
+
+    pub fn test_get_writing_progress(
+        note_name: &str,
+    ) -> Result<crate::utilities::note_stats::WritingProgress, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_get_writing_progress() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::get_writing_progress(note_name.to_string(), app_state)
+    }
+
+    pub fn test_spellcheck_text(
+        text: &str,
+        lang: &str,
+    ) -> Result<Vec<crate::utilities::spellcheck::SpellIssue>, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_spellcheck_text() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::spellcheck_text(text.to_string(), lang.to_string(), app_state)
+    }
+
+    pub fn test_get_note_toc(
+        note_name: &str,
+    ) -> Result<Vec<crate::utilities::note_renderer::TocEntry>, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_get_note_toc() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::get_note_toc(note_name.to_string(), app_state)
+    }
+
+    pub fn test_read_note_chunk(
+        note_name: &str,
+        offset: u64,
+        len: u64,
+    ) -> Result<crate::commands::notes::NoteChunk, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_read_note_chunk() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::read_note_chunk(note_name.to_string(), offset, len, app_state)
+    }
+
+    pub fn test_export_note(note_name: &str, format: &str) -> Result<String, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_export_note() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::export_note(note_name.to_string(), format.to_string(), app_state)
+    }
+
+    pub fn test_copy_note_as_html(note_name: &str) -> Result<(), String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_copy_note_as_html() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::copy_note_as_html(note_name.to_string(), app_state)
+    }
+
     pub fn test_delete_note(note_name: &str) -> Result<(), String> {
         // SAFETY CHECK: Ensure we're in test mode before proceeding
         if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
@@ -366,9 +488,86 @@ mod test_command_wrappers {
             );
         }
 
+        // Goes through confirmation like the frontend does
+        let app = create_test_mock_app();
+        let token = crate::commands::notes::request_delete_confirmation(
+            note_name,
+            app.state::<AppState>(),
+        )?;
+        crate::commands::notes::delete_note(note_name, Some(token), app.state::<AppState>())
+    }
+
+    pub fn test_delete_note_with_token(note_name: &str, token: Option<&str>) -> Result<(), String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_delete_note_with_token() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::delete_note(note_name, token.map(str::to_string), app_state)
+    }
+
+    pub fn test_get_active_config() -> crate::config::AppConfig {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_get_active_config() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        crate::commands::get_active_config(app.state::<AppState>())
+    }
+
+    pub fn test_delete_notes(note_names: &[&str]) -> Result<BatchResults, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_delete_notes() called outside of TestConfigOverride!"
+            );
+        }
+
+        // Goes through confirmation like the frontend does
+        let app = create_test_mock_app();
+        let names: Vec<String> = note_names.iter().map(|name| name.to_string()).collect();
+        let token = crate::commands::notes::request_batch_delete_confirmation(
+            names.clone(),
+            app.state::<AppState>(),
+        )?;
+        crate::commands::notes::delete_notes(names, Some(token), app.state::<AppState>())
+    }
+
+    pub fn test_delete_notes_with_token(
+        note_names: &[&str],
+        token: Option<&str>,
+    ) -> Result<BatchResults, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_delete_notes_with_token() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::delete_notes(
+            note_names.iter().map(|name| name.to_string()).collect(),
+            token.map(str::to_string),
+            app_state,
+        )
+    }
+
+    pub fn test_request_batch_delete_confirmation(note_names: &[&str]) -> Result<String, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_request_batch_delete_confirmation() called outside of TestConfigOverride!");
+        }
+
         let app = create_test_mock_app();
         let app_state = app.state::<AppState>();
-        crate::commands::notes::delete_note(note_name, app_state)
+        crate::commands::notes::request_batch_delete_confirmation(
+            note_names.iter().map(|name| name.to_string()).collect(),
+            app_state,
+        )
     }
 
     pub fn test_save_note_with_content_check(
@@ -389,6 +588,127 @@ mod test_command_wrappers {
             original_content,
             app_state,
         )
+        .map_err(|e| e.to_string())
+    }
+
+    pub fn test_save_note_detecting_conflict(
+        note_name: &str,
+        content: &str,
+        original_content: &str,
+    ) -> Result<(), AppError> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_save_note_detecting_conflict() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::save_note_with_content_check(
+            note_name,
+            content,
+            original_content,
+            app_state,
+        )
+    }
+
+    pub fn test_force_save_note(note_name: &str, content: &str) -> Result<(), String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_force_save_note() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::force_save_note(
+            note_name.to_string(),
+            content.to_string(),
+            app_state,
+        )
+    }
+
+    pub fn test_autosave_note(note_name: &str, content: &str) -> Result<i64, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_autosave_note() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::autosave_note(note_name.to_string(), content.to_string(), app_state)
+    }
+
+    pub fn test_append_to_note(
+        note_name: &str,
+        text: &str,
+        add_timestamp: bool,
+    ) -> Result<u64, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_append_to_note() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::append_to_note(
+            note_name.to_string(),
+            text.to_string(),
+            add_timestamp,
+            app_state,
+        )
+    }
+
+    pub fn test_duplicate_note(note_name: &str) -> Result<String, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_duplicate_note() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::duplicate_note(note_name.to_string(), app_state)
+    }
+
+    pub fn test_move_note(note_name: &str, target_dir: &str) -> Result<String, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_move_note() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::move_note(note_name.to_string(), target_dir.to_string(), app_state)
+    }
+
+    pub fn test_create_folder(relative_path: &str) -> Result<String, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_create_folder() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::create_folder(relative_path.to_string(), app_state)
+    }
+
+    pub fn test_list_folders() -> Result<Vec<crate::utilities::note_folders::FolderNode>, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_list_folders() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::list_folders(app_state)
     }
 
     pub fn test_rename_note(old_name: String, new_name: String) -> Result<(), String> {
@@ -404,6 +724,47 @@ mod test_command_wrappers {
         crate::commands::notes::rename_note(old_name, new_name, app_state)
     }
 
+    pub fn test_rename_note_with_relink(old_name: &str, new_name: &str) -> Result<usize, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_rename_note_with_relink() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::rename_note_with_relink(
+            old_name.to_string(),
+            new_name.to_string(),
+            app_state,
+        )
+    }
+
+    pub fn test_replace_across_notes(
+        find: &str,
+        replace: &str,
+        regex: bool,
+        dry_run: bool,
+    ) -> Result<Vec<crate::services::replace_service::ReplaceResult>, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_replace_across_notes() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::replace_across_notes(
+            find.to_string(),
+            replace.to_string(),
+            regex,
+            dry_run,
+            app_state,
+        )
+    }
+
     pub fn test_list_all_notes() -> Result<Vec<String>, String> {
         // SAFETY CHECK: Ensure we're in test mode before proceeding
         if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
@@ -415,30 +776,568 @@ mod test_command_wrappers {
         crate::commands::notes::list_all_notes(app_state)
     }
 
-    pub fn test_get_note_html_content(note_name: &str) -> Result<String, String> {
+    pub fn test_archive_note(note_name: &str) -> Result<(), String> {
         // SAFETY CHECK: Ensure we're in test mode before proceeding
         if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
-            panic!("CRITICAL SAFETY ERROR: test_get_note_html_content() called outside of TestConfigOverride!");
+            panic!(
+                "CRITICAL SAFETY ERROR: test_archive_note() called outside of TestConfigOverride!"
+            );
         }
 
         let app = create_test_mock_app();
         let app_state = app.state::<AppState>();
-        crate::commands::notes::get_note_html_content(note_name, app_state)
+        crate::commands::notes::archive_note(note_name.to_string(), app_state)
     }
 
-    pub fn test_search_notes_hybrid(
-        query: &str,
-        max_results: usize,
-    ) -> crate::core::AppResult<Vec<String>> {
+    pub fn test_pin_note(note_name: &str) -> Result<(), String> {
         // SAFETY CHECK: Ensure we're in test mode before proceeding
         if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
-            panic!("CRITICAL SAFETY ERROR: test_search_notes_hybrid() called outside of TestConfigOverride!");
+            panic!("CRITICAL SAFETY ERROR: test_pin_note() called outside of TestConfigOverride!");
         }
 
-        let config = crate::config::load_config();
-        let app_state = AppState::new_with_fallback(config).expect("Test database setup failed");
-        crate::search::search_notes_hybrid(&app_state, query, max_results)
-            .map(|notes| notes.into_iter().map(|n| n.filename).collect())
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::pin_note(note_name.to_string(), app_state)
+    }
+
+    pub fn test_unpin_note(note_name: &str) -> Result<(), String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_unpin_note() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::unpin_note(note_name.to_string(), app_state)
+    }
+
+    pub fn test_list_pinned_notes() -> Result<Vec<String>, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_list_pinned_notes() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::list_pinned_notes(app_state)
+    }
+
+    pub fn test_get_note_id(note_name: &str) -> Result<String, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_get_note_id() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::get_note_id(note_name.to_string(), app_state)
+    }
+
+    pub fn test_get_note_by_id(id: &str) -> Result<String, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_get_note_by_id() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::get_note_by_id(id.to_string(), app_state)
+    }
+
+    pub fn test_get_backlinks_by_id(id: &str) -> Result<Vec<String>, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_get_backlinks_by_id() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::get_backlinks_by_id(id.to_string(), app_state)
+    }
+
+    pub fn test_encrypt_note(note_name: &str, passphrase: &str) -> Result<(), String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_encrypt_note() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::encrypt_note(
+            note_name.to_string(),
+            passphrase.to_string(),
+            app_state,
+        )
+    }
+
+    pub fn test_decrypt_note(note_name: &str, passphrase: &str) -> Result<String, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_decrypt_note() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::decrypt_note(
+            note_name.to_string(),
+            passphrase.to_string(),
+            app_state,
+        )
+    }
+
+    pub fn test_record_note_opened(note_name: &str) -> Result<(), String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_record_note_opened() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::record_note_opened(note_name.to_string(), app_state)
+    }
+
+    pub fn test_list_recent_notes(limit: usize) -> Result<Vec<String>, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_list_recent_notes() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::list_recent_notes(limit, app_state)
+    }
+
+    pub fn test_save_pasted_image(note_name: &str, bytes: Vec<u8>) -> Result<String, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_save_pasted_image() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::save_pasted_image(note_name.to_string(), bytes, app_state)
+    }
+
+    pub fn test_get_note_hash(note_name: &str) -> Result<String, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_get_note_hash() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::get_note_hash(note_name.to_string(), app_state)
+    }
+
+    pub fn test_verify_notes_integrity() -> Result<Vec<String>, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_verify_notes_integrity() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::verify_notes_integrity(app_state)
+    }
+
+    pub fn test_get_index_stats() -> Result<crate::services::index_service::IndexStats, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_get_index_stats() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::get_index_stats(app_state)
+    }
+
+    pub fn test_export_index(path: &str) -> Result<usize, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_export_index() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::export_index(path.to_string(), app_state)
+    }
+
+    pub fn test_import_index(
+        path: &str,
+    ) -> Result<crate::services::index_service::IndexDrift, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_import_index() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::import_index(path.to_string(), app_state)
+    }
+
+    pub fn test_get_backlinks(note_name: &str) -> Result<Vec<String>, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_get_backlinks() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::get_backlinks(note_name.to_string(), app_state)
+    }
+
+    pub fn test_get_note_links(
+        note_name: &str,
+    ) -> Result<Vec<crate::utilities::note_links::NoteLink>, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_get_note_links() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::get_note_links(note_name.to_string(), app_state)
+    }
+
+    pub fn test_get_deleted_files(
+    ) -> Result<Vec<crate::commands::note_versions::DeletedFile>, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_get_deleted_files() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::get_deleted_files(app_state)
+    }
+
+    pub fn test_restore_deleted_note(id: &str, new_name: Option<&str>) -> Result<String, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_restore_deleted_note() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::restore_deleted_note(
+            id.to_string(),
+            new_name.map(str::to_string),
+            app_state,
+        )
+    }
+
+    pub fn test_diff_note_versions(
+        name: &str,
+        version_a: &str,
+        version_b: &str,
+    ) -> Result<Vec<crate::utilities::note_diff::DiffHunk>, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_diff_note_versions() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::diff_note_versions(
+            name.to_string(),
+            version_a.to_string(),
+            version_b.to_string(),
+            app_state,
+        )
+    }
+
+    pub fn test_empty_trash() -> Result<usize, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_empty_trash() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::empty_trash(app_state)
+    }
+
+    pub fn test_purge_trash_item(id: &str) -> Result<(), String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_purge_trash_item() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::purge_trash_item(id.to_string(), app_state)
+    }
+
+    pub fn test_get_trash_size() -> Result<crate::services::trash_service::TrashSize, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_get_trash_size() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::get_trash_size(app_state)
+    }
+
+    pub fn test_archive_notes(note_names: &[&str]) -> Result<BatchResults, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_archive_notes() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::archive_notes(
+            note_names.iter().map(|name| name.to_string()).collect(),
+            app_state,
+        )
+    }
+
+    pub fn test_unarchive_note(note_name: &str) -> Result<(), String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_unarchive_note() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::unarchive_note(note_name.to_string(), app_state)
+    }
+
+    pub fn test_list_archived_notes() -> Result<Vec<String>, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_list_archived_notes() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::list_archived_notes(app_state)
+    }
+
+    pub fn test_get_note_html_content(note_name: &str) -> Result<String, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_get_note_html_content() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::get_note_html_content(note_name, app_state)
+    }
+
+    pub fn test_clear_render_cache() -> Result<usize, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_clear_render_cache() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::clear_render_cache(app_state)
+    }
+
+    pub fn test_compact_cache() -> Result<crate::services::index_service::CacheCompaction, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_compact_cache() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::compact_cache(app_state)
+    }
+
+    pub fn test_search_notes_hybrid(
+        query: &str,
+        max_results: usize,
+    ) -> crate::core::AppResult<Vec<String>> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_search_notes_hybrid() called outside of TestConfigOverride!");
+        }
+
+        let config = crate::config::load_config();
+        let app_state = AppState::new_with_fallback(config).expect("Test database setup failed");
+        crate::search::search_notes_hybrid(&app_state, query, max_results, false, "")
+            .map(|notes| notes.into_iter().map(|n| n.filename).collect())
+    }
+
+    pub fn test_search_notes_in_folder(
+        query: &str,
+        path_prefix: &str,
+        max_results: usize,
+    ) -> crate::core::AppResult<Vec<String>> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_search_notes_in_folder() called outside of TestConfigOverride!");
+        }
+
+        let config = crate::config::load_config();
+        let app_state = AppState::new_with_fallback(config).expect("Test database setup failed");
+        crate::search::search_notes_hybrid(&app_state, query, max_results, false, path_prefix)
+            .map(|notes| notes.into_iter().map(|n| n.filename).collect())
+    }
+
+    pub fn test_search_note_versions(
+        query: &str,
+        max_results: usize,
+    ) -> crate::core::AppResult<Vec<(String, Option<String>)>> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_search_note_versions() called outside of TestConfigOverride!");
+        }
+
+        let config = crate::config::load_config();
+        let app_state = AppState::new_with_fallback(config).expect("Test database setup failed");
+        crate::search::search_note_versions(&app_state, query, max_results).map(|matches| {
+            matches
+                .into_iter()
+                .map(|m| (m.filename, m.version_filename))
+                .collect()
+        })
+    }
+
+    pub fn test_search_notes_whole_word(
+        query: &str,
+        max_results: usize,
+    ) -> crate::core::AppResult<Vec<String>> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_search_notes_whole_word() called outside of TestConfigOverride!");
+        }
+
+        let config = crate::config::load_config();
+        let app_state = AppState::new_with_fallback(config).expect("Test database setup failed");
+        crate::search::search_notes_hybrid(&app_state, query, max_results, true, "")
+            .map(|notes| notes.into_iter().map(|n| n.filename).collect())
+    }
+
+    pub fn test_search_notes_highlights(
+        query: &str,
+        max_results: usize,
+    ) -> crate::core::AppResult<Vec<(String, Option<crate::search::SearchHighlights>)>> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_search_notes_highlights() called outside of TestConfigOverride!");
+        }
+
+        let config = crate::config::load_config();
+        let app_state = AppState::new_with_fallback(config).expect("Test database setup failed");
+        crate::search::search_notes_hybrid(&app_state, query, max_results, false, "").map(|notes| {
+            notes
+                .into_iter()
+                .map(|n| (n.filename, n.highlights))
+                .collect()
+        })
+    }
+
+    pub fn test_search_notes_regex(
+        query: &str,
+        max_results: usize,
+    ) -> crate::core::AppResult<Vec<String>> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_search_notes_regex() called outside of TestConfigOverride!");
+        }
+
+        let config = crate::config::load_config();
+        let app_state = AppState::new_with_fallback(config).expect("Test database setup failed");
+        crate::search::search_notes_regex(&app_state, query, max_results)
+            .map(|notes| notes.into_iter().map(|n| n.filename).collect())
+    }
+
+    pub fn test_search_within_note(
+        note_name: &str,
+        query: &str,
+        whole_word: bool,
+        case_sensitive: bool,
+    ) -> Result<Vec<crate::search::LineMatch>, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_search_within_note() called outside of TestConfigOverride!");
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::notes::search_within_note(
+            note_name.to_string(),
+            query.to_string(),
+            Some(whole_word),
+            Some(case_sensitive),
+            app_state,
+        )
+    }
+
+    pub fn test_search_notes_fuzzy(
+        query: &str,
+        max_results: usize,
+    ) -> crate::core::AppResult<Vec<(String, u32)>> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_search_notes_fuzzy() called outside of TestConfigOverride!");
+        }
+
+        let config = crate::config::load_config();
+        let app_state = AppState::new_with_fallback(config).expect("Test database setup failed");
+        crate::search::search_notes_fuzzy(&app_state, query, max_results)
+            .map(|notes| notes.into_iter().map(|n| (n.filename, n.score)).collect())
+    }
+
+    pub fn test_search_notes_by_tag(
+        tag: &str,
+        max_results: usize,
+    ) -> crate::core::AppResult<Vec<String>> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!("CRITICAL SAFETY ERROR: test_search_notes_by_tag() called outside of TestConfigOverride!");
+        }
+
+        let config = crate::config::load_config();
+        let app_state = AppState::new_with_fallback(config).expect("Test database setup failed");
+        crate::search::search_notes_by_tag(&app_state, tag, max_results)
+            .map(|notes| notes.into_iter().map(|n| n.filename).collect())
+    }
+
+    pub fn test_list_all_tags() -> Result<Vec<(String, usize)>, String> {
+        // SAFETY CHECK: Ensure we're in test mode before proceeding
+        if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_err() {
+            panic!(
+                "CRITICAL SAFETY ERROR: test_list_all_tags() called outside of TestConfigOverride!"
+            );
+        }
+
+        let app = create_test_mock_app();
+        let app_state = app.state::<AppState>();
+        crate::commands::list_all_tags(app_state)
     }
 }
 
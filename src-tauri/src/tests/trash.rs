@@ -0,0 +1,175 @@
+//! Trash Unit Tests
+//!
+//! Tests trash retention, emptying or purging the trash and reporting its size.
+
+use crate::core::state::AppState;
+use crate::services::trash_service::purge_expired_trash;
+use crate::tests::test_utils::{
+    test_create_new_note, test_delete_note, test_empty_trash, test_get_deleted_files,
+    test_get_note_content, test_get_trash_size, test_purge_trash_item, test_restore_deleted_note,
+    test_save_note_with_content_check, test_search_notes_hybrid, TestConfigOverride,
+};
+use crate::utilities::paths::get_backup_dir_for_notes_path;
+use serial_test::serial;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn write_trashed_note(test_config: &TestConfigOverride, name: &str, days_ago: u64) {
+    let deleted_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        - days_ago * 24 * 60 * 60;
+    let backup_dir = get_backup_dir_for_notes_path(&test_config.notes_dir()).unwrap();
+    fs::create_dir_all(&backup_dir).unwrap();
+    fs::write(
+        backup_dir.join(format!("{}.delete_backup.{}.md", name, deleted_at)),
+        "trashed",
+    )
+    .unwrap();
+}
+
+#[test]
+#[serial]
+fn test_trash_size_counts_deleted_notes() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    test_create_new_note("doomed.md").expect("Failed to create note");
+    test_save_note_with_content_check("doomed.md", "12345", "").expect("Failed to save note");
+    test_delete_note("doomed.md").expect("Failed to delete note");
+
+    let size = test_get_trash_size().expect("Failed to get trash size");
+    assert_eq!(size.count, 1);
+    assert_eq!(size.total_bytes, 5);
+
+    assert_eq!(test_empty_trash().expect("Failed to empty trash"), 1);
+    let size = test_get_trash_size().expect("Failed to get trash size");
+    assert_eq!(size.count, 0);
+    assert_eq!(size.total_bytes, 0);
+}
+
+#[test]
+#[serial]
+fn test_expired_trash_is_purged() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    write_trashed_note(&test_config, "ancient", 45);
+    write_trashed_note(&test_config, "recent", 2);
+
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Test database setup failed");
+    assert_eq!(purge_expired_trash(&app_state).expect("Purge failed"), 1);
+    assert_eq!(test_get_trash_size().unwrap().count, 1);
+
+    test_config.update_config(|config| config.preferences.trash_retention_days = 0);
+    write_trashed_note(&test_config, "ancient", 400);
+    let app_state = AppState::new_with_fallback(crate::config::load_config())
+        .expect("Test database setup failed");
+    assert_eq!(purge_expired_trash(&app_state).expect("Purge failed"), 0);
+    assert_eq!(test_get_trash_size().unwrap().count, 2);
+}
+
+fn delete_note_with_content(name: &str, content: &str) -> String {
+    test_create_new_note(name).expect("Failed to create note");
+    test_save_note_with_content_check(name, content, "").expect("Failed to save note");
+    test_delete_note(name).expect("Failed to delete note");
+
+    test_get_deleted_files()
+        .expect("Failed to list deleted notes")
+        .into_iter()
+        .find(|file| file.filename == name)
+        .expect("Deleted note should be listed")
+        .backup_filename
+}
+
+#[test]
+#[serial]
+fn test_restore_deleted_note_suffixes_on_collision() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let id = delete_note_with_content("ideas/plan.md", "old plan with zeppelins");
+    test_create_new_note("ideas/plan.md").expect("Failed to recreate note");
+
+    let restored = test_restore_deleted_note(&id, None).expect("Failed to restore note");
+    assert_eq!(restored, "ideas/plan (restored).md");
+    assert_eq!(
+        test_get_note_content(&restored).unwrap(),
+        "old plan with zeppelins"
+    );
+    assert!(test_search_notes_hybrid("zeppelins", 10)
+        .unwrap()
+        .contains(&restored));
+
+    let err = test_restore_deleted_note(&id, None).expect_err("Restored item is gone");
+    assert!(err.contains("no longer exists"), "{}", err);
+}
+
+#[test]
+#[serial]
+fn test_restore_deleted_note_to_chosen_name() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let id = delete_note_with_content("draft.md", "draft body");
+    test_create_new_note("taken.md").expect("Failed to create note");
+
+    let err = test_restore_deleted_note(&id, Some("taken.md"))
+        .expect_err("Restoring over an existing note should fail");
+    assert!(err.contains("already exists"), "{}", err);
+
+    let restored =
+        test_restore_deleted_note(&id, Some("archive/final.md")).expect("Failed to restore");
+    assert_eq!(restored, "archive/final.md");
+    assert_eq!(test_get_note_content(&restored).unwrap(), "draft body");
+
+    test_empty_trash().expect("Failed to empty trash");
+    let id = delete_note_with_content("purged.md", "gone");
+    test_empty_trash().expect("Failed to empty trash");
+    let err = test_restore_deleted_note(&id, None).expect_err("Purged items cannot be restored");
+    assert!(err.contains("no longer exists"), "{}", err);
+}
+
+fn backup_files(test_config: &TestConfigOverride, stem: &str) -> Vec<String> {
+    let backup_dir = get_backup_dir_for_notes_path(&test_config.notes_dir()).unwrap();
+    let mut files: Vec<String> = fs::read_dir(&backup_dir)
+        .unwrap()
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with(&format!("{}.", stem)))
+        .collect();
+    files.sort();
+    files
+}
+
+#[test]
+#[serial]
+fn test_purge_trash_item_removes_note_and_versions() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let secret = delete_note_with_content("secret.md", "account numbers");
+    let kept = delete_note_with_content("kept.md", "harmless");
+    assert!(!backup_files(&test_config, "secret").is_empty());
+
+    test_purge_trash_item(&secret).expect("Failed to purge trashed note");
+    assert!(backup_files(&test_config, "secret").is_empty());
+    assert_eq!(test_get_trash_size().unwrap().count, 1);
+    assert!(backup_files(&test_config, "kept").contains(&kept));
+
+    let err = test_purge_trash_item(&secret).expect_err("Purged item is gone");
+    assert!(err.contains("not in the trash"), "{}", err);
+    assert!(test_purge_trash_item("kept.rollback.1700000000.md").is_err());
+}
+
+#[test]
+#[serial]
+fn test_purge_trash_item_keeps_versions_of_recreated_note() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let id = delete_note_with_content("plan.md", "first plan");
+    test_create_new_note("plan.md").expect("Failed to recreate note");
+    let versions_before = backup_files(&test_config, "plan");
+
+    test_purge_trash_item(&id).expect("Failed to purge trashed note");
+    let versions_after = backup_files(&test_config, "plan");
+    assert_eq!(versions_after.len(), versions_before.len() - 1);
+    assert!(!versions_after.contains(&id));
+}
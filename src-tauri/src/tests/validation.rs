@@ -2,7 +2,15 @@
 //!
 //! Tests for note name validation and security functions.
 
-use crate::utilities::validation::validate_note_name;
+use crate::utilities::external_editor::build_editor_command;
+use crate::utilities::validation::{
+    has_note_extension, validate_external_editor_command, validate_max_recursion_depth,
+    validate_note_extension, validate_note_extensions, validate_note_name,
+    validate_version_history_limit, validate_version_min_interval_seconds,
+    validate_watcher_debounce_ms, within_recursion_depth, MAX_VERSION_HISTORY_LIMIT,
+    MAX_VERSION_MIN_INTERVAL_SECONDS, MAX_WATCHER_DEBOUNCE_MS, MIN_VERSION_HISTORY_LIMIT,
+    MIN_WATCHER_DEBOUNCE_MS,
+};
 
 #[test]
 fn test_validate_note_name_valid_names() {
@@ -92,3 +100,107 @@ fn test_security_critical_functions_integration() {
     let error_msg = validate_note_name("../../../secret.txt").unwrap_err();
     assert!(error_msg.to_string().contains("Path traversal not allowed"));
 }
+
+#[test]
+fn test_validate_note_extensions() {
+    let valid = vec!["md".to_string(), "org".to_string()];
+    assert!(validate_note_extensions(&valid).is_ok());
+
+    assert!(validate_note_extensions(&[]).is_err());
+    assert!(validate_note_extensions(&["".to_string()]).is_err());
+    assert!(validate_note_extensions(&[".md".to_string()]).is_err());
+    assert!(validate_note_extensions(&["tar.gz".to_string()]).is_err());
+    assert!(validate_note_extensions(&["md/x".to_string()]).is_err());
+}
+
+#[test]
+fn test_note_extension_matching() {
+    let extensions = vec!["md".to_string(), "org".to_string()];
+
+    assert!(has_note_extension(
+        std::path::Path::new("folder/note.org"),
+        &extensions
+    ));
+    assert!(!has_note_extension(
+        std::path::Path::new("note.txt"),
+        &extensions
+    ));
+    assert!(!has_note_extension(
+        std::path::Path::new("README"),
+        &extensions
+    ));
+
+    assert!(validate_note_extension("note.md", &extensions).is_ok());
+    assert!(validate_note_extension("note.txt", &extensions).is_err());
+}
+
+#[test]
+fn test_validate_watcher_debounce_ms() {
+    assert!(validate_watcher_debounce_ms(500).is_ok());
+    assert!(validate_watcher_debounce_ms(MIN_WATCHER_DEBOUNCE_MS).is_ok());
+    assert!(validate_watcher_debounce_ms(MAX_WATCHER_DEBOUNCE_MS).is_ok());
+    assert!(validate_watcher_debounce_ms(0).is_err());
+    assert!(validate_watcher_debounce_ms(MAX_WATCHER_DEBOUNCE_MS + 1).is_err());
+}
+
+#[test]
+fn test_validate_version_history_preferences() {
+    assert!(validate_version_history_limit(20).is_ok());
+    assert!(validate_version_history_limit(MIN_VERSION_HISTORY_LIMIT).is_ok());
+    assert!(validate_version_history_limit(MAX_VERSION_HISTORY_LIMIT).is_ok());
+    assert!(validate_version_history_limit(0).is_err());
+    assert!(validate_version_history_limit(MAX_VERSION_HISTORY_LIMIT + 1).is_err());
+
+    assert!(validate_version_min_interval_seconds(0).is_ok());
+    assert!(validate_version_min_interval_seconds(MAX_VERSION_MIN_INTERVAL_SECONDS).is_ok());
+    assert!(validate_version_min_interval_seconds(MAX_VERSION_MIN_INTERVAL_SECONDS + 1).is_err());
+}
+
+#[test]
+fn test_max_recursion_depth() {
+    assert!(validate_max_recursion_depth(1).is_ok());
+    assert!(validate_max_recursion_depth(64).is_ok());
+    assert!(validate_max_recursion_depth(0).is_err());
+
+    assert!(within_recursion_depth(std::path::Path::new("note.md"), 1));
+    assert!(!within_recursion_depth(
+        std::path::Path::new("work/note.md"),
+        1
+    ));
+    assert!(within_recursion_depth(
+        std::path::Path::new("work/note.md"),
+        2
+    ));
+    assert!(!within_recursion_depth(
+        std::path::Path::new("work/deep/note.md"),
+        2
+    ));
+}
+
+#[test]
+fn test_validate_external_editor_command() {
+    assert!(validate_external_editor_command("").is_ok());
+    assert!(validate_external_editor_command("nvim {path}").is_ok());
+    assert!(validate_external_editor_command("code -g {path}:{line}").is_ok());
+
+    assert!(validate_external_editor_command("nvim").is_err());
+    assert!(validate_external_editor_command("code -g {line}").is_err());
+    assert!(validate_external_editor_command("\"unterminated {path}").is_err());
+}
+
+#[test]
+fn test_build_editor_command_substitutes_placeholders() {
+    let (program, args) = build_editor_command(
+        "alacritty -e nvim '+{line}' {path}",
+        "/notes/my notes/plan.md",
+        Some(12),
+    )
+    .unwrap();
+    assert_eq!(program, "alacritty");
+    assert_eq!(args, vec!["-e", "nvim", "+12", "/notes/my notes/plan.md"]);
+
+    let (program, args) =
+        build_editor_command("code -g {path}:{line}", "/notes/a.md", None).unwrap();
+    assert_eq!(program, "code");
+    assert_eq!(args, vec!["-g", "/notes/a.md:1"]);
+}
@@ -0,0 +1,376 @@
+//! Version Unit Tests
+//!
+//! Tests version pruning, editing session snapshots and line-level diffs
+//! between stored note versions.
+
+use crate::services::version_service::list_note_versions;
+use crate::tests::test_utils::{
+    test_autosave_note, test_create_new_note, test_diff_note_versions, test_get_note_content,
+    test_save_note_with_content_check, test_search_note_versions, TestConfigOverride,
+};
+use crate::utilities::file_safety::{continues_session, prune_old_backups};
+use crate::utilities::note_diff::{diff_lines, DiffHunk, DiffTag};
+use crate::utilities::paths::get_backup_dir_for_notes_path;
+use crate::utilities::strings::{format_local_date, format_timestamp_for_humans};
+use serial_test::serial;
+use std::fs;
+
+fn write_version(
+    test_config: &TestConfigOverride,
+    stem: &str,
+    timestamp: u64,
+    content: &str,
+) -> String {
+    let backup_dir = get_backup_dir_for_notes_path(&test_config.notes_dir()).unwrap();
+    fs::create_dir_all(&backup_dir).unwrap();
+    let filename = format!("{}.rollback.{}.md", stem, timestamp);
+    fs::write(backup_dir.join(&filename), content).unwrap();
+    filename
+}
+
+fn read_version(test_config: &TestConfigOverride, filename: &str) -> String {
+    let backup_dir = get_backup_dir_for_notes_path(&test_config.notes_dir()).unwrap();
+    fs::read_to_string(backup_dir.join(filename)).unwrap()
+}
+
+fn rollback_versions(test_config: &TestConfigOverride, stem: &str) -> Vec<String> {
+    let backup_dir = get_backup_dir_for_notes_path(&test_config.notes_dir()).unwrap();
+    let prefix = format!("{}.rollback.", stem);
+    let mut versions: Vec<String> = fs::read_dir(&backup_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .filter(|name| name.starts_with(&prefix))
+                .collect()
+        })
+        .unwrap_or_default();
+    versions.sort();
+    versions
+}
+
+#[test]
+fn test_prune_old_backups_removes_oldest_first() {
+    let dir = tempfile::tempdir().unwrap();
+    for timestamp in 1..=5 {
+        fs::write(
+            dir.path()
+                .join(format!("plan.rollback.170000000{}.md", timestamp)),
+            "v",
+        )
+        .unwrap();
+    }
+    fs::write(dir.path().join("plan.rename_backup.1700000000.md"), "r").unwrap();
+    fs::write(dir.path().join("other.rollback.1700000000.md"), "o").unwrap();
+
+    prune_old_backups(&dir.path().join("plan.rollback.1700000005.md"), 2).unwrap();
+
+    let mut remaining: Vec<String> = fs::read_dir(dir.path())
+        .unwrap()
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    remaining.sort();
+    assert_eq!(
+        remaining,
+        vec![
+            "other.rollback.1700000000.md",
+            "plan.rename_backup.1700000000.md",
+            "plan.rollback.1700000004.md",
+            "plan.rollback.1700000005.md",
+        ]
+    );
+}
+
+#[test]
+fn test_continues_session() {
+    assert!(!continues_session(None, 1_000, 60));
+    assert!(continues_session(Some(990), 1_000, 60));
+    assert!(continues_session(Some(1_000), 1_000, 60));
+    assert!(!continues_session(Some(940), 1_000, 60));
+    assert!(!continues_session(Some(1_000), 1_000, 0));
+}
+
+#[test]
+#[serial]
+fn test_saves_respect_version_history_limit() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_config.update_config(|config| config.preferences.version_history_limit = 2);
+
+    test_create_new_note("plan.md").expect("Failed to create note");
+    // Older versions than anything the saves below can produce
+    for timestamp in [1_600_000_000, 1_600_000_001, 1_600_000_002] {
+        write_version(&test_config, "plan", timestamp, "old");
+    }
+
+    test_save_note_with_content_check("plan.md", "new", "").expect("Failed to save note");
+
+    let versions = rollback_versions(&test_config, "plan");
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[0], "plan.rollback.1600000002.md");
+}
+
+#[test]
+#[serial]
+fn test_saves_within_session_update_one_snapshot() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_config.update_config(|config| config.preferences.version_min_interval_seconds = 3_600);
+
+    test_create_new_note("plan.md").expect("Failed to create note");
+    let recent = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        - 60;
+    let existing = write_version(&test_config, "plan", recent, "earlier");
+    // Matches the latest version, so the first save keeps no copy of it
+    fs::write(test_config.notes_dir().join("plan.md"), "earlier").unwrap();
+
+    test_save_note_with_content_check("plan.md", "first", "earlier").expect("Failed to save note");
+    test_save_note_with_content_check("plan.md", "second", "first").expect("Failed to save note");
+    test_save_note_with_content_check("plan.md", "third", "second").expect("Failed to save note");
+
+    let versions = rollback_versions(&test_config, "plan");
+    assert_eq!(versions.len(), 2, "{:?}", versions);
+    assert_eq!(versions[0], existing);
+    assert_eq!(read_version(&test_config, &versions[1]), "second");
+}
+
+#[test]
+#[serial]
+fn test_save_matching_previous_version_keeps_no_copy() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_config.update_config(|config| config.preferences.version_min_interval_seconds = 0);
+
+    test_create_new_note("plan.md").expect("Failed to create note");
+    let existing = write_version(&test_config, "plan", 1_700_000_000, "");
+
+    test_save_note_with_content_check("plan.md", "first", "").expect("Failed to save note");
+    assert_eq!(
+        rollback_versions(&test_config, "plan"),
+        vec![existing.clone()]
+    );
+
+    test_save_note_with_content_check("plan.md", "second", "first").expect("Failed to save note");
+    let versions = rollback_versions(&test_config, "plan");
+    assert_eq!(versions.len(), 2, "{:?}", versions);
+    assert_eq!(read_version(&test_config, &versions[1]), "first");
+}
+
+#[test]
+#[serial]
+fn test_version_list_skips_repeated_content() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    write_version(&test_config, "plan", 1_700_000_000, "a");
+    write_version(&test_config, "plan", 1_700_000_100, "b");
+    write_version(&test_config, "plan", 1_700_000_200, "b");
+    write_version(&test_config, "plan", 1_700_000_300, "a");
+
+    let timestamps: Vec<u64> = list_note_versions(&test_config.notes_dir(), "plan.md", "%d/%m/%Y")
+        .expect("Failed to list versions")
+        .iter()
+        .map(|version| version.timestamp)
+        .collect();
+    assert_eq!(
+        timestamps,
+        vec![1_700_000_300, 1_700_000_100, 1_700_000_000]
+    );
+}
+
+#[test]
+#[serial]
+fn test_autosaves_write_note_and_share_one_snapshot() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_config.update_config(|config| config.preferences.version_min_interval_seconds = 3_600);
+
+    test_create_new_note("plan.md").expect("Failed to create note");
+    let recent = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        - 60;
+    let existing = write_version(&test_config, "plan", recent, "earlier");
+    fs::write(test_config.notes_dir().join("plan.md"), "earlier").unwrap();
+
+    test_autosave_note("plan.md", "first").expect("Failed to autosave note");
+    let modified = test_autosave_note("plan.md", "second").expect("Failed to autosave note");
+
+    assert_eq!(test_get_note_content("plan.md").unwrap(), "second");
+    let versions = rollback_versions(&test_config, "plan");
+    assert_eq!(versions.len(), 2, "{:?}", versions);
+    assert_eq!(versions[0], existing);
+    assert_eq!(read_version(&test_config, &versions[1]), "first");
+
+    let file_modified = fs::metadata(test_config.notes_dir().join("plan.md"))
+        .and_then(|metadata| metadata.modified())
+        .unwrap()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    assert_eq!(modified, file_modified);
+}
+
+#[test]
+#[serial]
+fn test_autosave_missing_note_fails() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let err = test_autosave_note("missing.md", "content").expect_err("Autosave should fail");
+    assert!(err.contains("Note not found"), "{}", err);
+    assert!(!test_config.notes_dir().join("missing.md").exists());
+}
+
+fn hunk(tag: DiffTag, old_start: usize, new_start: usize, lines: &[&str]) -> DiffHunk {
+    DiffHunk {
+        tag,
+        old_start,
+        new_start,
+        lines: lines.iter().map(|line| line.to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_diff_lines_tags_hunks() {
+    let hunks = diff_lines("one\ntwo\nthree\n", "one\nthree\nfour\n");
+
+    assert_eq!(
+        hunks,
+        vec![
+            hunk(DiffTag::Equal, 1, 1, &["one"]),
+            hunk(DiffTag::Delete, 2, 2, &["two"]),
+            hunk(DiffTag::Equal, 3, 2, &["three"]),
+            hunk(DiffTag::Insert, 4, 3, &["four"]),
+        ]
+    );
+}
+
+#[test]
+fn test_diff_lines_splits_replacements() {
+    let hunks = diff_lines("title\nold line\n", "title\nnew line\n");
+    let tags: Vec<DiffTag> = hunks.iter().map(|hunk| hunk.tag).collect();
+
+    assert_eq!(tags, vec![DiffTag::Equal, DiffTag::Delete, DiffTag::Insert]);
+    assert_eq!(hunks[1].lines, vec!["old line"]);
+    assert_eq!(hunks[2].lines, vec!["new line"]);
+}
+
+#[test]
+fn test_diff_lines_identical_content() {
+    let hunks = diff_lines("same\ncontent", "same\ncontent");
+
+    assert_eq!(
+        hunks,
+        vec![hunk(DiffTag::Equal, 1, 1, &["same", "content"])]
+    );
+    assert!(diff_lines("", "").is_empty());
+}
+
+#[test]
+fn test_diff_hunk_serializes_lowercase_tags() {
+    let json = serde_json::to_value(hunk(DiffTag::Insert, 1, 1, &["added"])).unwrap();
+
+    assert_eq!(json["tag"], "insert");
+    assert_eq!(json["lines"][0], "added");
+}
+
+#[test]
+#[serial]
+fn test_diff_note_versions_compares_stored_versions() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let older = write_version(&test_config, "plan", 1_700_000_000, "a\nb\n");
+    let newer = write_version(&test_config, "plan", 1_700_000_100, "a\nc\n");
+
+    let hunks = test_diff_note_versions("plan.md", &older, &newer).expect("Diff failed");
+    let tags: Vec<DiffTag> = hunks.iter().map(|hunk| hunk.tag).collect();
+    assert_eq!(tags, vec![DiffTag::Equal, DiffTag::Delete, DiffTag::Insert]);
+}
+
+#[test]
+#[serial]
+fn test_diff_note_versions_missing_version() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let existing = write_version(&test_config, "plan", 1_700_000_000, "a\n");
+    let result = test_diff_note_versions("plan.md", &existing, "plan.rollback.1600000000.md");
+
+    let error = result.expect_err("Diff against a purged version should fail");
+    assert!(
+        error.contains("no longer exists"),
+        "Unexpected error: {}",
+        error
+    );
+}
+
+#[test]
+#[serial]
+fn test_diff_note_versions_rejects_other_notes_versions() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+
+    let own = write_version(&test_config, "plan", 1_700_000_000, "a\n");
+    let other = write_version(&test_config, "ideas", 1_700_000_000, "b\n");
+
+    assert!(test_diff_note_versions("plan.md", &own, &other).is_err());
+    assert!(test_diff_note_versions("plan.md", &own, "../../etc/passwd").is_err());
+}
+
+#[test]
+#[serial]
+fn test_search_finds_phrases_only_in_old_versions() {
+    let test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    test_create_new_note("plan.md").expect("Failed to create note");
+    test_create_new_note("other.md").expect("Failed to create note");
+    test_save_note_with_content_check("plan.md", "# Plan\n\nShip it", "")
+        .expect("Failed to save note");
+    test_save_note_with_content_check("other.md", "Keep the Blue   Heron idea", "")
+        .expect("Failed to save note");
+
+    let old = write_version(
+        &test_config,
+        "plan",
+        1_700_000_000,
+        "Keep the blue heron idea",
+    );
+    let older = write_version(
+        &test_config,
+        "plan",
+        1_600_000_000,
+        "blue heron, first draft",
+    );
+    write_version(&test_config, "plan", 1_500_000_000, "blue and heron apart");
+
+    // Newest first: the current content of other.md, then the matching plan.md versions
+    let matches = test_search_note_versions("blue heron", 10).expect("Search should succeed");
+    assert_eq!(
+        matches,
+        vec![
+            ("other.md".to_string(), None),
+            ("plan.md".to_string(), Some(old)),
+            ("plan.md".to_string(), Some(older)),
+        ]
+    );
+
+    let capped = test_search_note_versions("blue heron", 1).expect("Search should succeed");
+    assert_eq!(capped, vec![("other.md".to_string(), None)]);
+    assert!(test_search_note_versions("  ", 10).unwrap().is_empty());
+}
+
+#[test]
+fn test_old_version_times_use_date_format() {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    assert_eq!(
+        format_timestamp_for_humans(now - 2 * 3600, "%d/%m/%Y"),
+        "2h ago"
+    );
+
+    let old = now - 90 * 86400;
+    let formatted = format_timestamp_for_humans(old, "%d/%m/%Y");
+    assert_eq!(Some(formatted), format_local_date(old, "%d/%m/%Y"));
+    assert_eq!(format_local_date(old, "%Q"), None);
+    assert_eq!(format_timestamp_for_humans(old, "%Q"), "12w ago");
+}
@@ -147,3 +147,52 @@ mod serial_tests {
         );
     }
 }
+
+#[test]
+fn test_debounce_releases_path_only_after_quiet_period() {
+    use crate::watcher::DebouncedWatcher;
+    use std::path::PathBuf;
+    use std::time::{Duration, Instant};
+
+    let debounce = Duration::from_millis(500);
+    let watcher = DebouncedWatcher::new();
+    let path = PathBuf::from("/notes/a.md");
+    let start = Instant::now();
+
+    watcher.record_event(&path, start);
+    watcher.record_event(&path, start + Duration::from_millis(300));
+
+    let early = watcher.take_settled_paths(start + Duration::from_millis(600), debounce);
+    assert!(early.is_empty(), "Later events should restart the debounce");
+    assert_eq!(
+        watcher.next_poll_interval(start + Duration::from_millis(600), debounce),
+        Duration::from_millis(200)
+    );
+
+    let settled = watcher.take_settled_paths(start + Duration::from_millis(800), debounce);
+    assert_eq!(settled, vec![path]);
+    assert!(watcher
+        .take_settled_paths(start + Duration::from_secs(5), debounce)
+        .is_empty());
+}
+
+#[test]
+fn test_watcher_ignores_archive_and_temp_files() {
+    use crate::watcher::should_ignore_file;
+
+    for ignored in [
+        ".archive/old.md",
+        "projects/.trash/note.md",
+        ".note.md.swp",
+        "note.md~",
+        "drafts/note.md.swx",
+        "note.md.tmp",
+        "#note.md#",
+    ] {
+        assert!(should_ignore_file(ignored), "{} should be ignored", ignored);
+    }
+
+    for kept in ["note.md", "projects/plan.md", "notes/测试.md"] {
+        assert!(!should_ignore_file(kept), "{} should be processed", kept);
+    }
+}
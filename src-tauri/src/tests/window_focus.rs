@@ -0,0 +1,42 @@
+//! Window Focus Unit Tests
+//!
+//! Tests the permission report and the warnings logged for missing permissions.
+
+use crate::utilities::window_focus::{
+    check_macos_permissions, missing_permission_warning, PermissionStatus,
+};
+
+#[test]
+#[cfg(not(target_os = "macos"))]
+fn test_permissions_are_all_granted_off_macos() {
+    assert_eq!(check_macos_permissions(), PermissionStatus::ALL_GRANTED);
+}
+
+#[test]
+fn test_missing_permission_warnings() {
+    assert_eq!(
+        missing_permission_warning(PermissionStatus::ALL_GRANTED),
+        None
+    );
+
+    let no_accessibility = missing_permission_warning(PermissionStatus {
+        accessibility: false,
+        automation: true,
+    })
+    .unwrap();
+    assert!(no_accessibility.starts_with("Accessibility permission"));
+
+    let no_automation = missing_permission_warning(PermissionStatus {
+        accessibility: true,
+        automation: false,
+    })
+    .unwrap();
+    assert!(no_automation.starts_with("Automation permission"));
+
+    let neither = missing_permission_warning(PermissionStatus {
+        accessibility: false,
+        automation: false,
+    })
+    .unwrap();
+    assert!(neither.contains("Accessibility and Automation"));
+}
@@ -0,0 +1,88 @@
+//! Window Geometry Unit Tests
+//!
+//! Tests fitting saved window geometry onto the current screens and the
+//! window state file.
+
+use crate::tests::test_utils::TestConfigOverride;
+use crate::utilities::window_geometry::{
+    clamp_to_screens, clear_window_geometry, load_window_geometry, write_window_geometry,
+    ScreenArea, WindowGeometry,
+};
+use serial_test::serial;
+
+const LAPTOP: ScreenArea = ScreenArea {
+    x: 0,
+    y: 0,
+    width: 1920,
+    height: 1080,
+};
+const EXTERNAL: ScreenArea = ScreenArea {
+    x: 1920,
+    y: 0,
+    width: 2560,
+    height: 1440,
+};
+
+fn geometry(x: i32, y: i32, width: u32, height: u32) -> WindowGeometry {
+    WindowGeometry {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+#[test]
+fn test_visible_geometry_is_kept() {
+    let saved = geometry(2200, 100, 1200, 800);
+    assert_eq!(clamp_to_screens(saved, &[LAPTOP, EXTERNAL]), saved);
+}
+
+#[test]
+fn test_partly_offscreen_window_is_moved_onto_screen() {
+    assert_eq!(
+        clamp_to_screens(geometry(1500, -50, 1200, 800), &[LAPTOP]),
+        geometry(720, 0, 1200, 800)
+    );
+}
+
+#[test]
+fn test_window_on_disconnected_monitor_is_centered_on_primary() {
+    assert_eq!(
+        clamp_to_screens(geometry(2200, 100, 1200, 800), &[LAPTOP]),
+        geometry(360, 140, 1200, 800)
+    );
+}
+
+#[test]
+fn test_window_larger_than_screen_is_shrunk() {
+    assert_eq!(
+        clamp_to_screens(geometry(2000, 50, 2400, 1400), &[LAPTOP, EXTERNAL]),
+        geometry(2000, 40, 2400, 1400)
+    );
+    assert_eq!(
+        clamp_to_screens(geometry(100, 100, 2400, 1400), &[LAPTOP]),
+        geometry(0, 0, 1920, 1080)
+    );
+}
+
+#[test]
+fn test_no_screens_keeps_geometry() {
+    let saved = geometry(-4000, -4000, 1200, 800);
+    assert_eq!(clamp_to_screens(saved, &[]), saved);
+}
+
+#[test]
+#[serial]
+fn test_window_state_file_round_trip() {
+    let _test_config = TestConfigOverride::new().expect("Failed to setup test config");
+    assert_eq!(load_window_geometry(), None);
+
+    let saved = geometry(40, 60, 1000, 700);
+    write_window_geometry(&saved).expect("Failed to write window state");
+    assert_eq!(load_window_geometry(), Some(saved));
+
+    clear_window_geometry().expect("Failed to clear window state");
+    assert_eq!(load_window_geometry(), None);
+    clear_window_geometry().expect("Clearing twice should be a no-op");
+}
@@ -0,0 +1,76 @@
+use crate::core::{AppError, AppResult};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+pub const ATTACHMENTS_DIR_NAME: &str = "attachments";
+
+// 16 hex characters (64 bits) keeps links short while collisions stay out of reach
+const HASH_PREFIX_LEN: usize = 16;
+
+const IMAGE_SIGNATURES: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "png"),
+    (b"\xff\xd8\xff", "jpg"),
+    (b"GIF87a", "gif"),
+    (b"GIF89a", "gif"),
+];
+
+/// Returns the file extension for the image format the bytes start with, or `None`
+/// when they are not a supported image.
+pub fn detect_image_extension(bytes: &[u8]) -> Option<&'static str> {
+    if let Some((_, extension)) = IMAGE_SIGNATURES
+        .iter()
+        .find(|(signature, _)| bytes.starts_with(signature))
+    {
+        return Some(extension);
+    }
+    // WebP is a RIFF container, so the format tag sits after the chunk size
+    if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        return Some("webp");
+    }
+    None
+}
+
+/// Names the attachment after a hash of its content, so pasting the same image twice
+/// reuses one file.
+pub fn attachment_file_name(bytes: &[u8], extension: &str) -> String {
+    let digest = Sha256::digest(bytes);
+    let hash: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}.{}", &hash[..HASH_PREFIX_LEN], extension)
+}
+
+/// Builds a markdown image link from the note to a file in the attachments folder at
+/// the root of the notes directory.
+pub fn attachment_link(note_name: &str, file_name: &str) -> String {
+    let depth = Path::new(note_name).components().count().saturating_sub(1);
+    format!(
+        "![]({}{}/{})",
+        "../".repeat(depth),
+        ATTACHMENTS_DIR_NAME,
+        file_name
+    )
+}
+
+/// Writes the image into the attachments folder unless an identical one is already
+/// there, and returns its file name.
+pub fn write_attachment(notes_dir: &Path, bytes: &[u8], max_bytes: u64) -> AppResult<String> {
+    if bytes.len() as u64 > max_bytes {
+        return Err(AppError::Attachment(format!(
+            "Image is {} bytes, larger than the {} byte limit",
+            bytes.len(),
+            max_bytes
+        )));
+    }
+    let extension = detect_image_extension(bytes).ok_or_else(|| {
+        AppError::Attachment("Pasted data is not a PNG, JPEG, GIF or WebP image".to_string())
+    })?;
+
+    let file_name = attachment_file_name(bytes, extension);
+    let attachments_dir = notes_dir.join(ATTACHMENTS_DIR_NAME);
+    let path = attachments_dir.join(&file_name);
+    if !path.exists() {
+        fs::create_dir_all(&attachments_dir)?;
+        fs::write(&path, bytes)?;
+    }
+    Ok(file_name)
+}
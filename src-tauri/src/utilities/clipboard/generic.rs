@@ -0,0 +1,24 @@
+use crate::core::{AppError, AppResult};
+use arboard::Clipboard;
+use std::sync::Mutex;
+
+// On X11 the copied contents are served by whoever owns the clipboard, so the
+// handle is kept for the lifetime of the app rather than dropped after a copy
+static CLIPBOARD: Mutex<Option<Clipboard>> = Mutex::new(None);
+
+/// Writes `html` with `plain_text` as its alternative, letting the receiving
+/// app pick the richest format it understands.
+pub fn write_html(html: &str, plain_text: &str) -> AppResult<()> {
+    let mut guard = CLIPBOARD.lock().unwrap_or_else(|e| e.into_inner());
+    let clipboard = match guard.as_mut() {
+        Some(clipboard) => clipboard,
+        None => guard
+            .insert(Clipboard::new().map_err(|e| {
+                AppError::Clipboard(format!("Failed to open the clipboard: {}", e))
+            })?),
+    };
+
+    clipboard
+        .set_html(html, Some(plain_text))
+        .map_err(|e| AppError::Clipboard(format!("The clipboard rejected the copied note: {}", e)))
+}
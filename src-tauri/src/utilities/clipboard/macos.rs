@@ -0,0 +1,23 @@
+use objc2_app_kit::{NSPasteboard, NSPasteboardTypeHTML, NSPasteboardTypeString};
+use objc2_foundation::NSString;
+
+use crate::core::{AppError, AppResult};
+
+/// Writes `public.html` and `public.utf8-plain-text` to the general pasteboard,
+/// letting the receiving app pick the richest type it understands.
+pub fn write_html(html: &str, plain_text: &str) -> AppResult<()> {
+    let written = unsafe {
+        let pasteboard = NSPasteboard::generalPasteboard();
+        pasteboard.clearContents();
+        pasteboard.setString_forType(&NSString::from_str(html), NSPasteboardTypeHTML)
+            && pasteboard.setString_forType(&NSString::from_str(plain_text), NSPasteboardTypeString)
+    };
+
+    if written {
+        Ok(())
+    } else {
+        Err(AppError::Clipboard(
+            "The pasteboard rejected the copied note".to_string(),
+        ))
+    }
+}
@@ -0,0 +1,14 @@
+#[cfg(not(target_os = "macos"))]
+mod generic;
+#[cfg(target_os = "macos")]
+mod macos;
+
+use crate::core::AppResult;
+
+/// Replaces the clipboard with `html`, plus `plain_text` for apps that can't paste HTML.
+pub fn write_html(html: &str, plain_text: &str) -> AppResult<()> {
+    #[cfg(target_os = "macos")]
+    return macos::write_html(html, plain_text);
+    #[cfg(not(target_os = "macos"))]
+    return generic::write_html(html, plain_text);
+}
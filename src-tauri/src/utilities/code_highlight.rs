@@ -0,0 +1,82 @@
+use html_escape;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Color, Theme, ThemeSet};
+use syntect::html::{styled_line_to_highlighted_html, IncludeBackground};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
+
+// Fenced code blocks as they come out of the sanitizer
+static CODE_BLOCK_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r#"(?s)<pre><code class="language-([^"]+)">(.*?)</code></pre>"#));
+
+const FALLBACK_SYNTECT_THEME: &str = "base16-ocean.dark";
+
+/// Bundled syntect theme closest to a highlight.js theme from
+/// `get_available_code_themes()`.
+pub fn syntect_theme_name(code_theme: &str) -> &'static str {
+    match code_theme {
+        "gruvbox-dark-hard" | "gruvbox-dark-medium" | "gruvbox-dark-soft" => "base16-mocha.dark",
+        "gruvbox-light-hard" | "gruvbox-light-medium" => "Solarized (light)",
+        "dracula" | "monokai" | "vs2015" => "base16-eighties.dark",
+        "atom-one-light" | "github" | "vs" | "xcode" => "InspiredGitHub",
+        "tokyo-night-light" => "base16-ocean.light",
+        _ => FALLBACK_SYNTECT_THEME,
+    }
+}
+
+fn css_color(color: Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+fn highlight_code(code: &str, language: &str, theme: &Theme) -> Option<String> {
+    let syntax = SYNTAX_SET.find_syntax_by_token(language)?;
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    let mut highlighted = String::new();
+    for line in LinesWithEndings::from(code) {
+        let regions = highlighter.highlight_line(line, &SYNTAX_SET).ok()?;
+        highlighted
+            .push_str(&styled_line_to_highlighted_html(&regions, IncludeBackground::No).ok()?);
+    }
+
+    let mut style = Vec::new();
+    if let Some(background) = theme.settings.background {
+        style.push(format!("background-color:{}", css_color(background)));
+    }
+    if let Some(foreground) = theme.settings.foreground {
+        style.push(format!("color:{}", css_color(foreground)));
+    }
+
+    // `hljs` keeps the frontend highlighter off blocks that are already done
+    Some(format!(
+        r#"<pre><code class="hljs language-{}" style="{}">{}</code></pre>"#,
+        html_escape::encode_double_quoted_attribute(language),
+        style.join(";"),
+        highlighted
+    ))
+}
+
+/// Highlights fenced code blocks in sanitized HTML with inline styles from the
+/// syntect theme matching `code_theme`. Blocks in a language syntect doesn't
+/// know are left as plain preformatted text.
+pub fn highlight_code_blocks(html: &str, code_theme: &str) -> String {
+    let Ok(code_block_regex) = CODE_BLOCK_REGEX.as_ref() else {
+        return html.to_string();
+    };
+    let Some(theme) = THEME_SET.themes.get(syntect_theme_name(code_theme)) else {
+        return html.to_string();
+    };
+
+    code_block_regex
+        .replace_all(html, |caps: &regex::Captures| {
+            let code = html_escape::decode_html_entities(&caps[2]);
+            highlight_code(&code, &html_escape::decode_html_entities(&caps[1]), theme)
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .to_string()
+}
@@ -1,18 +1,173 @@
-use crate::logging::log;
+use crate::core::{AppError, AppResult};
+use crate::logging::{log, log_warn};
+use crate::utilities::paths::{
+    get_dictionaries_dir, get_editor_themes_dir, get_markdown_themes_dir,
+};
+use crate::utilities::spellcheck::get_available_dictionaries;
 use crate::utilities::validation::{
-    validate_basic_shortcut_format, validate_font_size, validate_notes_directory,
-    validate_shortcut_format,
+    validate_autosave_interval_seconds, validate_basic_shortcut_format,
+    validate_cache_database_path, validate_datetime_format, validate_external_editor_command,
+    validate_font_size, validate_index_threads, validate_log_archives, validate_log_file_size_mb,
+    validate_max_pasted_image_mb, validate_max_recursion_depth, validate_note_extensions,
+    validate_notes_directory, validate_render_cache_max_entries, validate_search_title_boost,
+    validate_shortcut_format, validate_spellcheck_lang, validate_stream_note_threshold_kb,
+    validate_version_history_limit, validate_version_min_interval_seconds, validate_vim_leader,
+    validate_vim_mapping, validate_watcher_debounce_ms, MAX_AUTOSAVE_INTERVAL_SECONDS,
+    MAX_INDEX_THREADS, MAX_LOG_ARCHIVES, MAX_LOG_FILE_SIZE_MB, MAX_PASTED_IMAGE_MB,
+    MAX_RENDER_CACHE_ENTRIES, MAX_SEARCH_TITLE_BOOST, MAX_STREAM_NOTE_THRESHOLD_KB,
+    MAX_VERSION_HISTORY_LIMIT, MAX_VERSION_MIN_INTERVAL_SECONDS, MAX_WATCHER_DEBOUNCE_MS,
+    MIN_LOG_FILE_SIZE_MB, MIN_PASTED_IMAGE_MB, MIN_SEARCH_TITLE_BOOST,
+    MIN_STREAM_NOTE_THRESHOLD_KB, MIN_VERSION_HISTORY_LIMIT, MIN_WATCHER_DEBOUNCE_MS,
 };
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
 use tauri_plugin_global_shortcut::Shortcut;
 
-use crate::config::{AppConfig, EditorConfig, InterfaceConfig, PreferencesConfig, ShortcutsConfig};
+use crate::config::{
+    AppConfig, ConfigWarning, EditorConfig, InterfaceConfig, LoggingConfig, PreferencesConfig,
+    ShortcutsConfig, VimConfig,
+};
 extern crate toml;
 
+// Last scan of the user editor themes directory, kept for `get_user_editor_themes`
+struct EditorThemeScan {
+    dir: PathBuf,
+    entries: Vec<ThemeFileStamp>,
+    themes: Vec<String>,
+}
+
+// Name, modification time and size of one file in the themes directory
+type ThemeFileStamp = (OsString, Option<SystemTime>, u64);
+
+static USER_EDITOR_THEMES: Mutex<Option<EditorThemeScan>> = Mutex::new(None);
+
+// Absolute upper bound accepted for preferences.max_search_results
+pub const MAX_SEARCH_RESULTS_CEILING: usize = 100_000;
+
 pub fn default_max_results() -> usize {
     100
 }
 
+pub fn default_note_extensions() -> Vec<String> {
+    vec!["md".to_string(), "txt".to_string(), "markdown".to_string()]
+}
+
+pub fn default_watcher_debounce_ms() -> u64 {
+    500
+}
+
+pub fn default_max_recursion_depth() -> usize {
+    64
+}
+
+pub fn default_search_normalize_diacritics() -> bool {
+    true
+}
+
+pub fn default_search_title_boost() -> f64 {
+    2.0
+}
+
+pub fn default_search_stemming() -> bool {
+    false
+}
+
+pub fn default_pinned_notes_first() -> bool {
+    true
+}
+
+pub fn default_confirm_before_delete() -> bool {
+    true
+}
+
+pub fn default_follow_symlinks() -> bool {
+    true
+}
+
+pub fn default_index_threads() -> usize {
+    0
+}
+
+pub fn default_trash_retention_days() -> u32 {
+    30
+}
+
+pub fn default_version_history_limit() -> usize {
+    20
+}
+
+pub fn default_version_min_interval_seconds() -> u64 {
+    0
+}
+
+pub fn default_max_pasted_image_mb() -> u64 {
+    10
+}
+
+pub fn default_stream_note_threshold_kb() -> u64 {
+    1024
+}
+
+pub fn default_date_format() -> String {
+    "%Y-%m-%d".to_string()
+}
+
+pub fn default_time_format() -> String {
+    "%H:%M".to_string()
+}
+
+pub fn default_render_cache_max_entries() -> usize {
+    500
+}
+
+pub fn default_note_sort_order() -> String {
+    "modified_desc".to_string()
+}
+
+pub fn default_cache_journal_mode() -> String {
+    "WAL".to_string()
+}
+
+pub fn default_cache_synchronous() -> String {
+    "NORMAL".to_string()
+}
+
+pub fn default_line_ending() -> String {
+    "preserve".to_string()
+}
+
+pub fn default_trim_trailing_whitespace() -> bool {
+    false
+}
+
+pub fn default_ensure_final_newline() -> bool {
+    false
+}
+
+pub fn default_external_editor_command() -> String {
+    String::new()
+}
+
+pub fn default_markdown_heading_anchors() -> bool {
+    false
+}
+
+pub fn default_markdown_render_math() -> bool {
+    false
+}
+
+pub fn default_markdown_flavor() -> String {
+    "gfm".to_string()
+}
+
+pub fn default_cache_database_path() -> Option<String> {
+    None
+}
+
 pub fn default_global_shortcut() -> String {
     "Ctrl+Shift+N".to_string()
 }
@@ -29,19 +184,178 @@ pub fn get_available_markdown_themes() -> Vec<&'static str> {
     vec!["modern-dark", "article", "gruvbox-dark"]
 }
 
+// Canonical modifier names in output order, with the spellings accepted for each
+const SHORTCUT_MODIFIERS: &[(&str, &[&str])] = &[
+    ("Ctrl", &["ctrl", "control"]),
+    ("Alt", &["alt", "option"]),
+    ("Shift", &["shift"]),
+    ("Meta", &["meta", "cmd", "command", "super"]),
+];
+const SHIFT_MODIFIER: usize = 2;
+const CMD_OR_CTRL_MODIFIER: usize = if cfg!(target_os = "macos") { 3 } else { 0 };
+
+const MULTI_WORD_KEYS: &[&str] = &[
+    "ArrowUp",
+    "ArrowDown",
+    "ArrowLeft",
+    "ArrowRight",
+    "PageUp",
+    "PageDown",
+    "CapsLock",
+    "NumLock",
+    "ScrollLock",
+    "PrintScreen",
+    "PauseBreak",
+];
+
+// Spellings of the modifier that is Cmd on macOS and Ctrl elsewhere
+const CMD_OR_CTRL_ALIASES: &[&str] = &[
+    "cmdorctrl",
+    "cmdorcontrol",
+    "commandorctrl",
+    "commandorcontrol",
+];
+
+/// Rewrites a shortcut the way the frontend spells a key press, so equivalent spellings
+/// compare equal on both sides: modifiers are deduplicated and ordered Ctrl, Alt, Shift,
+/// Meta, CmdOrCtrl becomes this platform's modifier, and a letter is uppercase only
+/// with Shift. "shift+ctrl+n" and "Control+Shift+N" both become "Ctrl+Shift+N", and
+/// "CmdOrCtrl+m" becomes "Ctrl+m" outside macOS.
+pub fn normalize_shortcut(shortcut: &str) -> String {
+    let mut modifiers = Vec::new();
+    let mut keys = Vec::new();
+
+    for token in shortcut.split('+').map(str::trim).filter(|t| !t.is_empty()) {
+        let lowercase = token.to_lowercase();
+        let modifier = if CMD_OR_CTRL_ALIASES.contains(&lowercase.as_str()) {
+            Some(CMD_OR_CTRL_MODIFIER)
+        } else {
+            SHORTCUT_MODIFIERS
+                .iter()
+                .position(|(_, aliases)| aliases.contains(&lowercase.as_str()))
+        };
+        match modifier {
+            Some(index) if !modifiers.contains(&index) => modifiers.push(index),
+            Some(_) => {}
+            None => keys.push(token),
+        }
+    }
+
+    modifiers.sort_unstable();
+    let shifted = modifiers.contains(&SHIFT_MODIFIER);
+    modifiers
+        .into_iter()
+        .map(|index| SHORTCUT_MODIFIERS[index].0.to_string())
+        .chain(
+            keys.into_iter()
+                .map(|key| normalize_shortcut_key(key, shifted)),
+        )
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+fn normalize_shortcut_key(key: &str, shifted: bool) -> String {
+    if key.chars().count() == 1 {
+        // Matches KeyboardEvent.key, which reports the shifted form of a letter
+        return if shifted {
+            key.to_uppercase()
+        } else {
+            key.to_lowercase()
+        };
+    }
+    if let Some(name) = MULTI_WORD_KEYS
+        .iter()
+        .find(|name| name.eq_ignore_ascii_case(key))
+    {
+        return name.to_string();
+    }
+
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
 pub fn parse_shortcut(shortcut_str: &str) -> Option<Shortcut> {
-    shortcut_str.parse().ok()
+    // The global shortcut parser knows the Meta key only as "Super"
+    normalize_shortcut(shortcut_str)
+        .split('+')
+        .map(|token| if token == "Meta" { "Super" } else { token })
+        .collect::<Vec<_>>()
+        .join("+")
+        .parse()
+        .ok()
 }
 
 pub fn get_config_notes_dir_from_config(notes_directory: &str) -> PathBuf {
     PathBuf::from(notes_directory)
 }
 
+pub fn get_available_cache_journal_modes() -> Vec<&'static str> {
+    vec!["WAL", "DELETE", "TRUNCATE", "PERSIST", "MEMORY"]
+}
+
+pub fn get_available_cache_synchronous_modes() -> Vec<&'static str> {
+    vec!["OFF", "NORMAL", "FULL"]
+}
+
+pub fn get_available_line_endings() -> Vec<&'static str> {
+    vec!["preserve", "lf", "crlf"]
+}
+
+pub fn get_available_markdown_flavors() -> Vec<&'static str> {
+    vec!["gfm", "commonmark"]
+}
+
 pub fn get_available_editor_modes() -> Vec<&'static str> {
     vec!["basic", "vim", "emacs"]
 }
 
-pub fn get_available_editor_themes() -> Vec<&'static str> {
+/// Actions the editor's vim engine defines for `[editor.vim.mappings]` to bind.
+pub fn get_available_vim_actions() -> Vec<&'static str> {
+    vec![
+        "save",
+        "exit",
+        "foldClose",
+        "foldOpen",
+        "foldToggle",
+        "foldCloseAll",
+        "foldOpenAll",
+        "foldMore",
+        "foldLess",
+    ]
+}
+
+pub fn get_available_note_sort_orders() -> Vec<&'static str> {
+    vec![
+        "modified_desc",
+        "modified_asc",
+        "name_asc",
+        "name_desc",
+        "created_desc",
+    ]
+}
+
+pub fn get_available_editor_themes() -> Vec<String> {
+    let mut themes: Vec<String> = get_builtin_editor_themes()
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+    for theme in get_user_editor_themes() {
+        if !themes.contains(&theme) {
+            themes.push(theme);
+        }
+    }
+
+    themes
+}
+
+pub fn get_builtin_editor_themes() -> Vec<&'static str> {
     vec![
         "abcdef",
         "abyss",
@@ -69,6 +383,130 @@ pub fn get_available_editor_themes() -> Vec<&'static str> {
     ]
 }
 
+/// User supplied editor themes from `themes/editor/` in the config directory.
+///
+/// The scan is kept until a theme file is added, removed, renamed or edited,
+/// since config validation asks for the list on every load. Only the file
+/// names, modification times and sizes are read to decide that.
+pub fn get_user_editor_themes() -> Vec<String> {
+    let themes_dir = get_editor_themes_dir();
+    let entries = theme_file_stamps(&themes_dir);
+
+    let mut cache = USER_EDITOR_THEMES.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(scan) = cache.as_ref() {
+        if scan.dir == themes_dir && scan.entries == entries {
+            return scan.themes.clone();
+        }
+    }
+
+    let themes = scan_user_editor_themes(&themes_dir);
+    *cache = Some(EditorThemeScan {
+        dir: themes_dir,
+        entries,
+        themes: themes.clone(),
+    });
+    themes
+}
+
+fn theme_file_stamps(themes_dir: &Path) -> Vec<ThemeFileStamp> {
+    let Ok(entries) = fs::read_dir(themes_dir) else {
+        return Vec::new();
+    };
+
+    let mut stamps: Vec<ThemeFileStamp> = entries
+        .flatten()
+        .map(|entry| {
+            let metadata = fs::metadata(entry.path()).ok();
+            (
+                entry.file_name(),
+                metadata.as_ref().and_then(|m| m.modified().ok()),
+                metadata.map_or(0, |m| m.len()),
+            )
+        })
+        .collect();
+    stamps.sort();
+    stamps
+}
+
+/// Theme names are taken from the file stem. Files that cannot be read or parsed are
+/// logged and skipped so one broken theme does not hide the others.
+fn scan_user_editor_themes(themes_dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(themes_dir) else {
+        return Vec::new();
+    };
+
+    let mut themes = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        let Some(theme_name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+
+        match validate_user_editor_theme(&path) {
+            Ok(true) => themes.push(theme_name.to_string()),
+            Ok(false) => {}
+            Err(e) => {
+                log_warn(
+                    "CONFIG_VALIDATION",
+                    &format!("Skipping invalid editor theme '{}'", path.display()),
+                    Some(&e.to_string()),
+                );
+            }
+        }
+    }
+
+    themes.sort();
+    themes.dedup();
+    themes
+}
+
+/// Names of the non-empty `.css` files in the user markdown themes directory,
+/// usable as a note's `render_theme`.
+pub fn get_user_markdown_themes() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(get_markdown_themes_dir()) else {
+        return Vec::new();
+    };
+
+    let mut themes: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "css"))
+        .filter(|path| fs::read_to_string(path).is_ok_and(|content| !content.trim().is_empty()))
+        .filter_map(|path| {
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .map(str::to_string)
+        })
+        .collect();
+    themes.sort();
+    themes
+}
+
+fn validate_user_editor_theme(path: &Path) -> AppResult<bool> {
+    let extension = path.extension().and_then(|ext| ext.to_str());
+    if !matches!(extension, Some("json") | Some("css")) {
+        return Ok(false);
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| AppError::FileRead(format!("Failed to read theme file: {}", e)))?;
+
+    if content.trim().is_empty() {
+        return Err(AppError::ConfigLoad("Theme file is empty".to_string()));
+    }
+
+    if extension == Some("json") {
+        serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&content)
+            .map_err(|e| AppError::ConfigLoad(format!("Invalid theme JSON: {}", e)))?;
+    }
+
+    Ok(true)
+}
+
 pub fn get_available_code_themes() -> Vec<&'static str> {
     vec![
         "gruvbox-dark-hard",
@@ -99,7 +537,7 @@ pub fn load_config_from_content(content: &str) -> AppConfig {
             config
         }
         Err(e) => {
-            log(
+            log_warn(
                 "CONFIG_PARSE",
                 "Failed to parse config TOML. Using defaults.",
                 Some(&e.to_string()),
@@ -109,183 +547,843 @@ pub fn load_config_from_content(content: &str) -> AppConfig {
     }
 }
 
-fn sanitize_config(config: &mut AppConfig) {
-    let defaults = AppConfig::default();
+/// Runs the same sanitizing as `load_config_from_content` and reports every rejected field.
+pub fn get_config_warnings(content: &str) -> AppResult<Vec<ConfigWarning>> {
+    let mut config = toml::from_str::<AppConfig>(content)
+        .map_err(|e| AppError::ConfigLoad(format!("TOML syntax error: {}", e)))?;
+    Ok(sanitize_config(&mut config))
+}
 
-    if validate_notes_directory(&config.notes_directory).is_err() {
-        log(
-            "CONFIG_VALIDATION",
+/// Serializes `AppConfig::default()` to TOML and annotates known keys with their valid options.
+///
+/// Keys come from serde, so newly added fields appear here without touching this function.
+pub fn generate_default_config_toml() -> AppResult<String> {
+    generate_config_toml(&AppConfig::default())
+}
+
+/// Serializes `config` with the same annotations as the default template.
+pub fn generate_config_toml(config: &AppConfig) -> AppResult<String> {
+    let toml_content = toml::to_string_pretty(config)
+        .map_err(|e| AppError::ConfigSave(format!("Failed to serialize config: {}", e)))?;
+    let toml_content = add_optional_field_examples(config, toml_content);
+
+    let mut output = String::new();
+    let mut section: Option<String> = None;
+
+    for line in toml_content.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            let name = trimmed.trim_matches(|c| c == '[' || c == ']').to_string();
+            output.push_str(line);
+            output.push('\n');
+            if let Some(comment) = config_section_comment(&name) {
+                output.push_str(&format!("# {}\n", comment));
+            }
+            section = Some(name);
+            continue;
+        }
+
+        if let Some((key, _)) = trimmed.split_once(" = ") {
+            if !key.starts_with('#') {
+                if let Some(comment) = config_field_comment(section.as_deref(), key) {
+                    output.push_str(&format!("# {}\n", comment));
+                }
+            }
+        }
+
+        output.push_str(line);
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+/// Adds commented-out examples for optional fields that serde skips when unset.
+pub fn add_optional_field_examples(config: &AppConfig, mut toml_content: String) -> String {
+    if config.global_shortcut_new_note.is_none() {
+        let global_line = format!(
+            "global_shortcut = {}",
+            toml::Value::String(config.global_shortcut.clone())
+        );
+        toml_content = toml_content.replacen(
+            &global_line,
             &format!(
-                "Invalid notes_directory '{}'. Using default.",
-                config.notes_directory
+                "{}\n# global_shortcut_new_note = \"Ctrl+Alt+N\"",
+                global_line
             ),
-            None,
+            1,
+        );
+    }
+    if config.editor.spellcheck_dictionary_dir.is_none() {
+        toml_content = toml_content.replacen(
+            "\n[editor.vim]",
+            "# spellcheck_dictionary_dir = \"path/to/dictionaries\"\n\n[editor.vim]",
+            1,
+        );
+    }
+    if config.preferences.cache_database_path.is_none() {
+        toml_content = toml_content.replacen(
+            "\n[logging]",
+            "# cache_database_path = \"/path/to/local/notes.sqlite\"\n\n[logging]",
+            1,
+        );
+    }
+    if config.interface.custom_ui_theme_path.is_none() {
+        toml_content = toml_content.replace(
+            "[interface]",
+            "[interface]\n# custom_ui_theme_path = \"path/to/custom/ui_theme.css\"",
+        );
+    }
+    if config.interface.custom_markdown_theme_path.is_none() {
+        toml_content = toml_content.replace(
+            "# custom_ui_theme_path = \"path/to/custom/ui_theme.css\"",
+            "# custom_ui_theme_path = \"path/to/custom/ui_theme.css\"\n# custom_markdown_theme_path = \"path/to/custom/markdown_theme.css\""
+        );
+    }
+    toml_content
+}
+
+fn config_section_comment(section: &str) -> Option<String> {
+    match section {
+        "editor.vim.mappings" => Some(format!(
+            "Normal mode key sequence to action, e.g. \"<leader>w\" = \"save\". Actions: {}",
+            get_available_vim_actions().join(", ")
+        )),
+        "shortcuts" => Some(
+            "Format: modifiers joined with '+' then a key, e.g. \"Ctrl+Enter\", \"Ctrl+Alt+h\", \"Meta+,\""
+                .to_string(),
+        ),
+        _ => None,
+    }
+}
+
+fn config_field_comment(section: Option<&str>, key: &str) -> Option<String> {
+    let options = |values: Vec<String>| format!("Options: {}", values.join(", "));
+    let to_strings = |values: Vec<&str>| values.iter().map(|v| v.to_string()).collect();
+
+    match (section, key) {
+        (None, "notes_directory") => Some("Directory where notes are stored".to_string()),
+        (None, "global_shortcut") => Some(
+            "Toggles the Symiosis window from anywhere, e.g. \"Ctrl+Shift+N\" or \"Alt+Space\""
+                .to_string(),
+        ),
+        (Some("interface"), "ui_theme") => Some(options(to_strings(get_available_ui_themes()))),
+        (Some("interface"), "markdown_render_theme") => {
+            Some(options(to_strings(get_available_markdown_themes())))
+        }
+        (Some("interface"), "md_render_code_theme") => {
+            Some(options(to_strings(get_available_code_themes())))
+        }
+        (Some("interface"), "font_size") | (Some("interface"), "editor_font_size") => {
+            Some("Font size in pixels (8-72)".to_string())
+        }
+        (Some("editor"), "mode") => Some(options(to_strings(get_available_editor_modes()))),
+        (Some("editor"), "theme") => Some(options(get_available_editor_themes())),
+        (Some("editor"), "tab_size") => Some("Spaces per tab (1-16)".to_string()),
+        (Some("editor"), "spellcheck_lang") => Some(format!(
+            "Dictionary for spellchecking. {}",
+            options(get_available_dictionaries(&get_dictionaries_dir()))
+        )),
+        (Some("editor.vim"), "leader") => {
+            Some("Single key that <leader> stands for in vim mappings".to_string())
+        }
+        (Some("editor"), "autosave_interval_seconds") => Some(format!(
+            "Seconds between autosaves while editing (0-{}, 0 disables autosave)",
+            MAX_AUTOSAVE_INTERVAL_SECONDS
+        )),
+        (Some("preferences"), "max_search_results") => Some(format!(
+            "Maximum number of search results (1-{})",
+            MAX_SEARCH_RESULTS_CEILING
+        )),
+        (Some("preferences"), "watcher_debounce_ms") => Some(format!(
+            "Milliseconds file changes must settle before re-indexing ({}-{})",
+            MIN_WATCHER_DEBOUNCE_MS, MAX_WATCHER_DEBOUNCE_MS
+        )),
+        (Some("preferences"), "search_title_boost") => Some(format!(
+            "How much more a match in the title or filename counts than one in the body ({}-{})",
+            MIN_SEARCH_TITLE_BOOST, MAX_SEARCH_TITLE_BOOST
+        )),
+        (Some("preferences"), "search_stemming") => Some(
+            "Match words by their stem, so \"running\" finds \"run\", and ignore common words in queries; changing it rebuilds the index"
+                .to_string(),
+        ),
+        (Some("preferences"), "search_normalize_diacritics") => {
+            Some("Match accented and unaccented letters alike when searching".to_string())
+        }
+        (Some("preferences"), "note_extensions") => {
+            Some("File extensions treated as notes, without the leading dot".to_string())
+        }
+        (Some("preferences"), "note_sort_order") => {
+            Some(options(to_strings(get_available_note_sort_orders())))
+        }
+        (Some("preferences"), "cache_journal_mode") => Some(format!(
+            "{}. WAL lets searches read while notes are indexed; avoid it when the database is on a network drive",
+            options(to_strings(get_available_cache_journal_modes()))
+        )),
+        (Some("preferences"), "cache_synchronous") => Some(format!(
+            "{}. OFF is fastest but a crash or power loss can corrupt the index (it is rebuilt from the notes); NORMAL is safe with WAL",
+            options(to_strings(get_available_cache_synchronous_modes()))
+        )),
+        (Some("preferences"), "line_ending") => Some(format!(
+            "{}. lf or crlf converts every line break of a note when it is saved; preserve writes them as they are",
+            options(to_strings(get_available_line_endings()))
+        )),
+        (Some("preferences"), "trim_trailing_whitespace") => Some(
+            "Strip trailing whitespace when saving; fenced code is untouched and markdown hard breaks keep two spaces"
+                .to_string(),
+        ),
+        (Some("preferences"), "ensure_final_newline") => Some(
+            "End notes with exactly one line break when saving".to_string(),
+        ),
+        (Some("preferences"), "max_recursion_depth") => Some(
+            "Folder levels indexed inside the notes directory; 1 indexes only notes at the top level"
+                .to_string(),
+        ),
+        (Some("preferences"), "trash_retention_days") => {
+            Some("Days to keep deleted notes before purging them on startup (0 keeps them forever)".to_string())
+        }
+        (Some("preferences"), "version_history_limit") => Some(format!(
+            "Versions kept per note and backup kind; the oldest are pruned first ({}-{})",
+            MIN_VERSION_HISTORY_LIMIT, MAX_VERSION_HISTORY_LIMIT
+        )),
+        (Some("preferences"), "version_min_interval_seconds") => Some(format!(
+            "Seconds between saves that end an editing session, which keeps one version (0-{}, 0 keeps every save)",
+            MAX_VERSION_MIN_INTERVAL_SECONDS
+        )),
+        (Some("preferences"), "max_pasted_image_mb") => Some(format!(
+            "Largest image in MB that can be pasted into a note as an attachment ({}-{})",
+            MIN_PASTED_IMAGE_MB, MAX_PASTED_IMAGE_MB
+        )),
+        (Some("preferences"), "stream_note_threshold_kb") => Some(format!(
+            "Notes larger than this many KB are loaded into the editor in chunks ({}-{})",
+            MIN_STREAM_NOTE_THRESHOLD_KB, MAX_STREAM_NOTE_THRESHOLD_KB
+        )),
+        (Some("preferences"), "date_format") => Some(
+            "strftime-style date for {{date}} in templates and older timestamps, e.g. \"%d/%m/%Y\""
+                .to_string(),
+        ),
+        (Some("preferences"), "time_format") => {
+            Some("strftime-style time for {{time}} in templates, e.g. \"%I:%M %p\"".to_string())
+        }
+        (Some("preferences"), "render_cache_max_entries") => Some(format!(
+            "How many rendered notes are cached by content and render settings (0-{}, 0 disables the cache)",
+            MAX_RENDER_CACHE_ENTRIES
+        )),
+        (Some("preferences"), "pinned_notes_first") => {
+            Some("List pinned notes above the rest when the search is empty".to_string())
+        }
+        (Some("preferences"), "follow_symlinks") => Some(
+            "Index notes that are symlinks to files; symlinked folders are never followed"
+                .to_string(),
+        ),
+        (Some("preferences"), "index_threads") => Some(format!(
+            "Threads used to read notes when rebuilding the index (0-{}, 0 uses every core)",
+            MAX_INDEX_THREADS
+        )),
+        (Some("preferences"), "confirm_before_delete") => Some(
+            "Require a confirmation token from request_delete_confirmation before deleting a note"
+                .to_string(),
+        ),
+        (Some("preferences"), "markdown_heading_anchors") => {
+            Some("Give rendered headings ids and anchor links for deep-linking sections".to_string())
+        }
+        (Some("preferences"), "markdown_flavor") => Some(format!(
+            "{}. gfm renders tables, task lists, strikethrough, autolinks and footnotes; commonmark renders none of them",
+            options(to_strings(get_available_markdown_flavors()))
+        )),
+        (Some("preferences"), "markdown_render_math") => {
+            Some("Wrap $inline$ and $$display$$ math in spans for typesetting".to_string())
+        }
+        (Some("preferences"), "cache_database_path") => Some(
+            "Absolute path of the search index database, e.g. on local storage when notes are on a network drive"
+                .to_string(),
+        ),
+        (Some("preferences"), "external_editor_command") => Some(
+            "Command used to open notes externally, with {path} and optional {line} placeholders; empty uses the OS default"
+                .to_string(),
+        ),
+        (Some("logging"), "max_file_size_mb") => Some(format!(
+            "Size in MB at which symiosis.log is rolled over to an archive ({}-{})",
+            MIN_LOG_FILE_SIZE_MB, MAX_LOG_FILE_SIZE_MB
+        )),
+        (Some("logging"), "max_archives") => Some(format!(
+            "How many rolled over log files are kept (0-{}, 0 keeps none)",
+            MAX_LOG_ARCHIVES
+        )),
+        _ => None,
+    }
+}
+
+fn reject_field<T: std::fmt::Display>(
+    warnings: &mut Vec<ConfigWarning>,
+    field: &str,
+    value: &T,
+    default: &T,
+) {
+    log_warn(
+        "CONFIG_VALIDATION",
+        &format!(
+            "Invalid {} '{}'. Using default '{}'.",
+            field, value, default
+        ),
+        None,
+    );
+    warnings.push(ConfigWarning {
+        field: field.to_string(),
+        value: value.to_string(),
+        default: default.to_string(),
+    });
+}
+
+fn sanitize_config(config: &mut AppConfig) -> Vec<ConfigWarning> {
+    let defaults = AppConfig::default();
+    let mut warnings = Vec::new();
+
+    if validate_notes_directory(&config.notes_directory).is_err() {
+        reject_field(
+            &mut warnings,
+            "notes_directory",
+            &config.notes_directory,
+            &defaults.notes_directory,
         );
-        config.notes_directory = defaults.notes_directory;
+        config.notes_directory = defaults.notes_directory.clone();
     }
 
     if validate_shortcut_format(&config.global_shortcut).is_err() {
-        log(
-            "CONFIG_VALIDATION",
-            &format!(
-                "Invalid global_shortcut '{}'. Using default.",
-                config.global_shortcut
-            ),
-            None,
+        reject_field(
+            &mut warnings,
+            "global_shortcut",
+            &config.global_shortcut,
+            &defaults.global_shortcut,
         );
-        config.global_shortcut = defaults.global_shortcut;
+        config.global_shortcut = defaults.global_shortcut.clone();
+    }
+    config.global_shortcut = normalize_shortcut(&config.global_shortcut);
+
+    if let Some(shortcut) = &config.global_shortcut_new_note {
+        if validate_shortcut_format(shortcut).is_err() {
+            reject_field(
+                &mut warnings,
+                "global_shortcut_new_note",
+                shortcut,
+                &String::new(),
+            );
+            config.global_shortcut_new_note = None;
+        }
     }
+    if let Some(shortcut) = &mut config.global_shortcut_new_note {
+        *shortcut = normalize_shortcut(shortcut);
+    }
+
+    sanitize_interface_config(&mut config.interface, &defaults.interface, &mut warnings);
+    sanitize_editor_config(&mut config.editor, &defaults.editor, &mut warnings);
+    sanitize_shortcuts_config(&mut config.shortcuts, &defaults.shortcuts, &mut warnings);
+    resolve_shortcut_conflicts(config, &defaults, &mut warnings);
+    sanitize_preferences_config(
+        &mut config.preferences,
+        &defaults.preferences,
+        &mut warnings,
+    );
+    sanitize_logging_config(&mut config.logging, &defaults.logging, &mut warnings);
 
-    sanitize_interface_config(&mut config.interface, &defaults.interface);
-    sanitize_editor_config(&mut config.editor, &defaults.editor);
-    sanitize_shortcuts_config(&mut config.shortcuts, &defaults.shortcuts);
-    sanitize_preferences_config(&mut config.preferences, &defaults.preferences);
+    warnings
 }
 
-fn sanitize_interface_config(config: &mut InterfaceConfig, defaults: &InterfaceConfig) {
+fn sanitize_interface_config(
+    config: &mut InterfaceConfig,
+    defaults: &InterfaceConfig,
+    warnings: &mut Vec<ConfigWarning>,
+) {
     if !get_available_ui_themes().contains(&config.ui_theme.as_str()) {
-        log(
-            "CONFIG_VALIDATION",
-            &format!("Invalid ui_theme '{}'. Using default.", config.ui_theme),
-            None,
+        reject_field(
+            warnings,
+            "interface.ui_theme",
+            &config.ui_theme,
+            &defaults.ui_theme,
         );
         config.ui_theme = defaults.ui_theme.clone();
     }
 
     if !get_available_markdown_themes().contains(&config.markdown_render_theme.as_str()) {
-        log(
-            "CONFIG_VALIDATION",
-            &format!(
-                "Invalid markdown_render_theme '{}'. Using default.",
-                config.markdown_render_theme
-            ),
-            None,
+        reject_field(
+            warnings,
+            "interface.markdown_render_theme",
+            &config.markdown_render_theme,
+            &defaults.markdown_render_theme,
         );
         config.markdown_render_theme = defaults.markdown_render_theme.clone();
     }
 
     if !get_available_code_themes().contains(&config.md_render_code_theme.as_str()) {
-        log(
-            "CONFIG_VALIDATION",
-            &format!(
-                "Invalid md_render_code_theme '{}'. Using default.",
-                config.md_render_code_theme
-            ),
-            None,
+        reject_field(
+            warnings,
+            "interface.md_render_code_theme",
+            &config.md_render_code_theme,
+            &defaults.md_render_code_theme,
         );
         config.md_render_code_theme = defaults.md_render_code_theme.clone();
     }
 
     if validate_font_size(config.font_size, "UI font size").is_err() {
-        log(
-            "CONFIG_VALIDATION",
-            &format!(
-                "Invalid font_size {}. Using default {}.",
-                config.font_size, defaults.font_size
-            ),
-            None,
+        reject_field(
+            warnings,
+            "interface.font_size",
+            &config.font_size,
+            &defaults.font_size,
         );
         config.font_size = defaults.font_size;
     }
 
     if validate_font_size(config.editor_font_size, "Editor font size").is_err() {
-        log(
-            "CONFIG_VALIDATION",
-            &format!(
-                "Invalid editor_font_size {}. Using default {}.",
-                config.editor_font_size, defaults.editor_font_size
-            ),
-            None,
+        reject_field(
+            warnings,
+            "interface.editor_font_size",
+            &config.editor_font_size,
+            &defaults.editor_font_size,
         );
         config.editor_font_size = defaults.editor_font_size;
     }
 }
 
-fn sanitize_editor_config(config: &mut EditorConfig, defaults: &EditorConfig) {
+/// Replaces invalid editor values with their defaults, recording a warning for each.
+pub fn sanitize_editor_config(
+    config: &mut EditorConfig,
+    defaults: &EditorConfig,
+    warnings: &mut Vec<ConfigWarning>,
+) {
     if !get_available_editor_modes().contains(&config.mode.as_str()) {
-        log(
-            "CONFIG_VALIDATION",
-            &format!("Invalid editor mode '{}'. Using default.", config.mode),
-            None,
-        );
+        reject_field(warnings, "editor.mode", &config.mode, &defaults.mode);
         config.mode = defaults.mode.clone();
     }
 
-    if !get_available_editor_themes().contains(&config.theme.as_str()) {
-        log(
-            "CONFIG_VALIDATION",
-            &format!("Invalid editor theme '{}'. Using default.", config.theme),
-            None,
-        );
+    if !get_available_editor_themes().contains(&config.theme) {
+        reject_field(warnings, "editor.theme", &config.theme, &defaults.theme);
         config.theme = defaults.theme.clone();
     }
 
     if config.tab_size == 0 || config.tab_size > 16 {
+        reject_field(
+            warnings,
+            "editor.tab_size",
+            &config.tab_size,
+            &defaults.tab_size,
+        );
+        config.tab_size = defaults.tab_size;
+    }
+
+    if validate_autosave_interval_seconds(config.autosave_interval_seconds).is_err() {
+        reject_field(
+            warnings,
+            "editor.autosave_interval_seconds",
+            &config.autosave_interval_seconds,
+            &defaults.autosave_interval_seconds,
+        );
+        config.autosave_interval_seconds = defaults.autosave_interval_seconds;
+    }
+
+    if validate_spellcheck_lang(
+        &config.spellcheck_lang,
+        config.spellcheck_dictionary_dir.as_deref(),
+    )
+    .is_err()
+    {
+        reject_field(
+            warnings,
+            "editor.spellcheck_lang",
+            &config.spellcheck_lang,
+            &defaults.spellcheck_lang,
+        );
+        config.spellcheck_lang = defaults.spellcheck_lang.clone();
+    }
+
+    sanitize_vim_config(&mut config.vim, &defaults.vim, warnings);
+}
+
+fn sanitize_vim_config(
+    config: &mut VimConfig,
+    defaults: &VimConfig,
+    warnings: &mut Vec<ConfigWarning>,
+) {
+    if validate_vim_leader(&config.leader).is_err() {
+        reject_field(
+            warnings,
+            "editor.vim.leader",
+            &config.leader,
+            &defaults.leader,
+        );
+        config.leader = defaults.leader.clone();
+    }
+
+    // A bad mapping is dropped on its own so the rest still apply
+    config.mappings.retain(|sequence, action| {
+        let Err(e) = validate_vim_mapping(sequence, action) else {
+            return true;
+        };
         log(
             "CONFIG_VALIDATION",
-            &format!(
-                "Invalid tab_size {}. Using default {}.",
-                config.tab_size, defaults.tab_size
-            ),
-            None,
+            &format!("Ignoring vim mapping '{}' = '{}'.", sequence, action),
+            Some(&e.to_string()),
         );
-        config.tab_size = defaults.tab_size;
+        warnings.push(ConfigWarning {
+            field: format!("editor.vim.mappings.{}", sequence),
+            value: action.clone(),
+            default: String::new(),
+        });
+        false
+    });
+}
+
+fn sanitize_shortcuts_config(
+    config: &mut ShortcutsConfig,
+    defaults: &ShortcutsConfig,
+    warnings: &mut Vec<ConfigWarning>,
+) {
+    for (field, value, default) in shortcut_fields(config, defaults) {
+        if validate_basic_shortcut_format(value).is_err() {
+            reject_field(warnings, field, &*value, default);
+            *value = default.clone();
+        }
+        *value = normalize_shortcut(value);
     }
 }
 
-fn sanitize_shortcuts_config(config: &mut ShortcutsConfig, defaults: &ShortcutsConfig) {
-    macro_rules! sanitize_shortcut {
-        ($field:ident) => {
-            if validate_basic_shortcut_format(&config.$field).is_err() {
-                log(
+/// Every configurable shortcut in config file order, paired with its default.
+fn shortcut_fields<'a>(
+    config: &'a mut ShortcutsConfig,
+    defaults: &'a ShortcutsConfig,
+) -> Vec<(&'static str, &'a mut String, &'a String)> {
+    macro_rules! shortcut_fields {
+        ($($field:ident),* $(,)?) => {
+            vec![$((
+                concat!("shortcuts.", stringify!($field)),
+                &mut config.$field,
+                &defaults.$field,
+            )),*]
+        };
+    }
+    shortcut_fields!(
+        create_note,
+        rename_note,
+        duplicate_note,
+        delete_note,
+        edit_note,
+        save_and_exit,
+        open_external,
+        open_folder,
+        refresh_cache,
+        scroll_up,
+        scroll_down,
+        up,
+        down,
+        navigate_previous,
+        navigate_next,
+        navigate_code_previous,
+        navigate_code_next,
+        navigate_link_previous,
+        navigate_link_next,
+        copy_current_section,
+        open_settings,
+        version_explorer,
+        recently_deleted,
+        toggle_always_on_top,
+    )
+}
+
+/// Resets any shortcut that reuses a key combination already bound earlier in the
+/// config to its default, even if that default is taken too.
+fn resolve_shortcut_conflicts(
+    config: &mut AppConfig,
+    defaults: &AppConfig,
+    warnings: &mut Vec<ConfigWarning>,
+) {
+    let mut bound_to: HashMap<String, &'static str> = HashMap::new();
+    bound_to.insert(
+        normalize_shortcut(&config.global_shortcut),
+        "global_shortcut",
+    );
+
+    // The new-note shortcut is optional, so a clash with the main one simply disables it
+    if let Some(new_note) = config.global_shortcut_new_note.take() {
+        match bound_to.get(&normalize_shortcut(&new_note)).copied() {
+            Some(first_field) => {
+                log_warn(
                     "CONFIG_VALIDATION",
                     &format!(
-                        "Invalid shortcut '{}' for {}. Using default '{}'.",
-                        config.$field,
-                        stringify!($field),
-                        defaults.$field
+                        "Shortcut conflict: global_shortcut_new_note '{}' is already bound to {}. Leaving it unset.",
+                        new_note, first_field
                     ),
                     None,
                 );
-                config.$field = defaults.$field.clone();
+                warnings.push(ConfigWarning {
+                    field: "global_shortcut_new_note".to_string(),
+                    value: new_note,
+                    default: String::new(),
+                });
             }
-        };
+            None => {
+                bound_to.insert(normalize_shortcut(&new_note), "global_shortcut_new_note");
+                config.global_shortcut_new_note = Some(new_note);
+            }
+        }
     }
 
-    sanitize_shortcut!(create_note);
-    sanitize_shortcut!(rename_note);
-    sanitize_shortcut!(delete_note);
-    sanitize_shortcut!(edit_note);
-    sanitize_shortcut!(save_and_exit);
-    sanitize_shortcut!(open_external);
-    sanitize_shortcut!(open_folder);
-    sanitize_shortcut!(refresh_cache);
-    sanitize_shortcut!(scroll_up);
-    sanitize_shortcut!(scroll_down);
-    sanitize_shortcut!(up);
-    sanitize_shortcut!(down);
-    sanitize_shortcut!(navigate_previous);
-    sanitize_shortcut!(navigate_next);
-    sanitize_shortcut!(navigate_code_previous);
-    sanitize_shortcut!(navigate_code_next);
-    sanitize_shortcut!(navigate_link_previous);
-    sanitize_shortcut!(navigate_link_next);
-    sanitize_shortcut!(copy_current_section);
-    sanitize_shortcut!(open_settings);
-    sanitize_shortcut!(version_explorer);
-    sanitize_shortcut!(recently_deleted);
-}
-
-fn sanitize_preferences_config(config: &mut PreferencesConfig, defaults: &PreferencesConfig) {
-    if config.max_search_results == 0 || config.max_search_results > 10000 {
-        log(
+    for (field, value, default) in shortcut_fields(&mut config.shortcuts, &defaults.shortcuts) {
+        let Some(first_field) = bound_to.get(&normalize_shortcut(value)).copied() else {
+            bound_to.insert(normalize_shortcut(value), field);
+            continue;
+        };
+
+        let replacement = normalize_shortcut(default);
+        log_warn(
             "CONFIG_VALIDATION",
             &format!(
-                "Invalid max_search_results {}. Using default {}.",
-                config.max_search_results, defaults.max_search_results
+                "Shortcut conflict: {} '{}' is already bound to {}. Using '{}' for {}.",
+                field, value, first_field, replacement, field
             ),
             None,
         );
+        warnings.push(ConfigWarning {
+            field: field.to_string(),
+            value: value.clone(),
+            default: replacement.clone(),
+        });
+
+        bound_to.entry(replacement.clone()).or_insert(field);
+        *value = replacement;
+    }
+}
+
+fn sanitize_preferences_config(
+    config: &mut PreferencesConfig,
+    defaults: &PreferencesConfig,
+    warnings: &mut Vec<ConfigWarning>,
+) {
+    if config.max_search_results == 0 || config.max_search_results > MAX_SEARCH_RESULTS_CEILING {
+        reject_field(
+            warnings,
+            "preferences.max_search_results",
+            &config.max_search_results,
+            &defaults.max_search_results,
+        );
         config.max_search_results = defaults.max_search_results;
     }
+
+    if validate_note_extensions(&config.note_extensions).is_err() {
+        reject_field(
+            warnings,
+            "preferences.note_extensions",
+            &config.note_extensions.join(", "),
+            &defaults.note_extensions.join(", "),
+        );
+        config.note_extensions = defaults.note_extensions.clone();
+    }
+
+    if validate_search_title_boost(config.search_title_boost).is_err() {
+        reject_field(
+            warnings,
+            "preferences.search_title_boost",
+            &config.search_title_boost,
+            &defaults.search_title_boost,
+        );
+        config.search_title_boost = defaults.search_title_boost;
+    }
+
+    if validate_watcher_debounce_ms(config.watcher_debounce_ms).is_err() {
+        reject_field(
+            warnings,
+            "preferences.watcher_debounce_ms",
+            &config.watcher_debounce_ms,
+            &defaults.watcher_debounce_ms,
+        );
+        config.watcher_debounce_ms = defaults.watcher_debounce_ms;
+    }
+
+    if validate_max_recursion_depth(config.max_recursion_depth).is_err() {
+        reject_field(
+            warnings,
+            "preferences.max_recursion_depth",
+            &config.max_recursion_depth,
+            &defaults.max_recursion_depth,
+        );
+        config.max_recursion_depth = defaults.max_recursion_depth;
+    }
+
+    if validate_index_threads(config.index_threads).is_err() {
+        reject_field(
+            warnings,
+            "preferences.index_threads",
+            &config.index_threads,
+            &defaults.index_threads,
+        );
+        config.index_threads = defaults.index_threads;
+    }
+
+    if validate_version_history_limit(config.version_history_limit).is_err() {
+        reject_field(
+            warnings,
+            "preferences.version_history_limit",
+            &config.version_history_limit,
+            &defaults.version_history_limit,
+        );
+        config.version_history_limit = defaults.version_history_limit;
+    }
+
+    if validate_version_min_interval_seconds(config.version_min_interval_seconds).is_err() {
+        reject_field(
+            warnings,
+            "preferences.version_min_interval_seconds",
+            &config.version_min_interval_seconds,
+            &defaults.version_min_interval_seconds,
+        );
+        config.version_min_interval_seconds = defaults.version_min_interval_seconds;
+    }
+
+    if validate_max_pasted_image_mb(config.max_pasted_image_mb).is_err() {
+        reject_field(
+            warnings,
+            "preferences.max_pasted_image_mb",
+            &config.max_pasted_image_mb,
+            &defaults.max_pasted_image_mb,
+        );
+        config.max_pasted_image_mb = defaults.max_pasted_image_mb;
+    }
+
+    if validate_stream_note_threshold_kb(config.stream_note_threshold_kb).is_err() {
+        reject_field(
+            warnings,
+            "preferences.stream_note_threshold_kb",
+            &config.stream_note_threshold_kb,
+            &defaults.stream_note_threshold_kb,
+        );
+        config.stream_note_threshold_kb = defaults.stream_note_threshold_kb;
+    }
+
+    if let Some(path) = &config.cache_database_path {
+        if validate_cache_database_path(path).is_err() {
+            reject_field(
+                warnings,
+                "preferences.cache_database_path",
+                path,
+                &String::new(),
+            );
+            config.cache_database_path = None;
+        }
+    }
+
+    if validate_datetime_format(&config.date_format).is_err() {
+        reject_field(
+            warnings,
+            "preferences.date_format",
+            &config.date_format,
+            &defaults.date_format,
+        );
+        config.date_format = defaults.date_format.clone();
+    }
+
+    if validate_datetime_format(&config.time_format).is_err() {
+        reject_field(
+            warnings,
+            "preferences.time_format",
+            &config.time_format,
+            &defaults.time_format,
+        );
+        config.time_format = defaults.time_format.clone();
+    }
+
+    if validate_render_cache_max_entries(config.render_cache_max_entries).is_err() {
+        reject_field(
+            warnings,
+            "preferences.render_cache_max_entries",
+            &config.render_cache_max_entries,
+            &defaults.render_cache_max_entries,
+        );
+        config.render_cache_max_entries = defaults.render_cache_max_entries;
+    }
+
+    if !get_available_cache_journal_modes().contains(&config.cache_journal_mode.as_str()) {
+        reject_field(
+            warnings,
+            "preferences.cache_journal_mode",
+            &config.cache_journal_mode,
+            &defaults.cache_journal_mode,
+        );
+        config.cache_journal_mode = defaults.cache_journal_mode.clone();
+    }
+
+    if !get_available_cache_synchronous_modes().contains(&config.cache_synchronous.as_str()) {
+        reject_field(
+            warnings,
+            "preferences.cache_synchronous",
+            &config.cache_synchronous,
+            &defaults.cache_synchronous,
+        );
+        config.cache_synchronous = defaults.cache_synchronous.clone();
+    }
+
+    if !get_available_line_endings().contains(&config.line_ending.as_str()) {
+        reject_field(
+            warnings,
+            "preferences.line_ending",
+            &config.line_ending,
+            &defaults.line_ending,
+        );
+        config.line_ending = defaults.line_ending.clone();
+    }
+
+    if !get_available_markdown_flavors().contains(&config.markdown_flavor.as_str()) {
+        reject_field(
+            warnings,
+            "preferences.markdown_flavor",
+            &config.markdown_flavor,
+            &defaults.markdown_flavor,
+        );
+        config.markdown_flavor = defaults.markdown_flavor.clone();
+    }
+
+    if !get_available_note_sort_orders().contains(&config.note_sort_order.as_str()) {
+        reject_field(
+            warnings,
+            "preferences.note_sort_order",
+            &config.note_sort_order,
+            &defaults.note_sort_order,
+        );
+        config.note_sort_order = defaults.note_sort_order.clone();
+    }
+
+    if validate_external_editor_command(&config.external_editor_command).is_err() {
+        reject_field(
+            warnings,
+            "preferences.external_editor_command",
+            &config.external_editor_command,
+            &defaults.external_editor_command,
+        );
+        config.external_editor_command = defaults.external_editor_command.clone();
+    }
+}
+
+fn sanitize_logging_config(
+    config: &mut LoggingConfig,
+    defaults: &LoggingConfig,
+    warnings: &mut Vec<ConfigWarning>,
+) {
+    if validate_log_file_size_mb(config.max_file_size_mb).is_err() {
+        reject_field(
+            warnings,
+            "logging.max_file_size_mb",
+            &config.max_file_size_mb,
+            &defaults.max_file_size_mb,
+        );
+        config.max_file_size_mb = defaults.max_file_size_mb;
+    }
+
+    if validate_log_archives(config.max_archives).is_err() {
+        reject_field(
+            warnings,
+            "logging.max_archives",
+            &config.max_archives,
+            &defaults.max_archives,
+        );
+        config.max_archives = defaults.max_archives;
+    }
 }
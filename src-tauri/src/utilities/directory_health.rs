@@ -0,0 +1,128 @@
+use crate::utilities::note_ignore::NoteIgnore;
+use crate::utilities::validation::has_note_extension;
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+// Hidden, so a probe left behind by a crash is never indexed as a note
+const WRITE_PROBE_PREFIX: &str = ".symiosis-write-check-";
+
+/// Folder name prefixes used by sync clients. macOS File Provider folders under
+/// `Library/CloudStorage` are named `<Provider>-<account>`.
+const CLOUD_SYNC_FOLDERS: &[(&str, &str)] = &[
+    ("com~apple~CloudDocs", "iCloud Drive"),
+    ("iCloud Drive", "iCloud Drive"),
+    ("iCloudDrive", "iCloud Drive"),
+    ("Dropbox", "Dropbox"),
+    ("OneDrive", "OneDrive"),
+    ("Google Drive", "Google Drive"),
+    ("GoogleDrive", "Google Drive"),
+    ("pCloud Drive", "pCloud"),
+    ("Box-Box", "Box"),
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DirectoryHealth {
+    pub path: String,
+    pub exists: bool,
+    pub is_directory: bool,
+    pub writable: bool,
+    pub cloud_sync_provider: Option<String>,
+    pub note_count: usize,
+    /// Why the write probe failed, when it did.
+    pub write_error: Option<String>,
+}
+
+/// Inspects the directory as it is on disk, unlike `validate_notes_directory`
+/// which only checks the path format.
+pub fn check_directory_health(
+    path: &Path,
+    note_extensions: &[String],
+    max_depth: usize,
+) -> DirectoryHealth {
+    let exists = path.exists();
+    let is_directory = path.is_dir();
+    let write_check = if is_directory {
+        probe_writable(path)
+    } else {
+        Err("Directory does not exist".to_string())
+    };
+
+    DirectoryHealth {
+        path: path.to_string_lossy().to_string(),
+        exists,
+        is_directory,
+        writable: write_check.is_ok(),
+        cloud_sync_provider: detect_cloud_sync_provider(path).map(str::to_string),
+        note_count: if is_directory {
+            count_notes(path, note_extensions, max_depth)
+        } else {
+            0
+        },
+        write_error: write_check.err(),
+    }
+}
+
+/// Creates, writes and removes a probe file, which catches read-only mounts and
+/// permission problems that directory metadata does not reveal.
+fn probe_writable(dir: &Path) -> Result<(), String> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let probe = dir.join(format!(
+        "{}{}-{}",
+        WRITE_PROBE_PREFIX,
+        std::process::id(),
+        nanos
+    ));
+
+    let written = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .and_then(|mut file| file.write_all(b"symiosis"));
+    // Clean up even when the write itself failed after creating the file
+    let removed = fs::remove_file(&probe);
+
+    written.map_err(|e| e.to_string())?;
+    removed.map_err(|e| e.to_string())
+}
+
+/// Names the sync client whose folder contains `path`, if any.
+pub fn detect_cloud_sync_provider(path: &Path) -> Option<&'static str> {
+    path.components().find_map(|component| {
+        let name = component.as_os_str().to_string_lossy();
+        CLOUD_SYNC_FOLDERS
+            .iter()
+            .find(|(prefix, _)| name.starts_with(prefix))
+            .map(|(_, provider)| *provider)
+    })
+}
+
+/// Counts the files the indexer would pick up, skipping hidden and ignored paths.
+fn count_notes(dir: &Path, note_extensions: &[String], max_depth: usize) -> usize {
+    let note_ignore = NoteIgnore::load(dir);
+
+    WalkDir::new(dir)
+        .follow_links(false)
+        .max_depth(max_depth)
+        .into_iter()
+        .filter_entry(|entry| {
+            let relative = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+            !note_ignore.is_ignored(relative, entry.file_type().is_dir())
+        })
+        .filter_map(|e| e.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            let path = entry.path();
+            let relative = path.strip_prefix(dir).unwrap_or(path);
+            let filename = relative.to_string_lossy();
+            !(filename.contains("/.") || filename.starts_with('.'))
+                && has_note_extension(path, note_extensions)
+        })
+        .count()
+}
@@ -0,0 +1,164 @@
+use crate::core::{AppError, AppResult};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+
+// Theme stylesheets are embedded because dev builds serve the frontend assets
+// from the dev server, so they cannot be resolved from the app bundle at runtime
+const MARKDOWN_THEMES: &[(&str, &str)] = &[
+    (
+        "modern-dark",
+        include_str!("../../../static/css/md_render_themes/modern-dark.css"),
+    ),
+    (
+        "article",
+        include_str!("../../../static/css/md_render_themes/article.css"),
+    ),
+    (
+        "gruvbox-dark",
+        include_str!("../../../static/css/md_render_themes/gruvbox-dark.css"),
+    ),
+];
+
+const CODE_THEMES: &[(&str, &str)] = &[
+    (
+        "gruvbox-dark-hard",
+        include_str!("../../../static/highlight-js-themes/base16/gruvbox-dark-hard.css"),
+    ),
+    (
+        "gruvbox-dark-medium",
+        include_str!("../../../static/highlight-js-themes/base16/gruvbox-dark-medium.css"),
+    ),
+    (
+        "gruvbox-dark-soft",
+        include_str!("../../../static/highlight-js-themes/base16/gruvbox-dark-soft.css"),
+    ),
+    (
+        "gruvbox-light-hard",
+        include_str!("../../../static/highlight-js-themes/base16/gruvbox-light-hard.css"),
+    ),
+    (
+        "gruvbox-light-medium",
+        include_str!("../../../static/highlight-js-themes/base16/gruvbox-light-medium.css"),
+    ),
+    (
+        "atom-one-dark",
+        include_str!("../../../static/highlight-js-themes/atom-one-dark.css"),
+    ),
+    (
+        "nord",
+        include_str!("../../../static/highlight-js-themes/nord.css"),
+    ),
+    (
+        "monokai",
+        include_str!("../../../static/highlight-js-themes/monokai.css"),
+    ),
+    (
+        "github-dark",
+        include_str!("../../../static/highlight-js-themes/github-dark.css"),
+    ),
+    (
+        "vs2015",
+        include_str!("../../../static/highlight-js-themes/vs2015.css"),
+    ),
+    (
+        "night-owl",
+        include_str!("../../../static/highlight-js-themes/night-owl.css"),
+    ),
+    (
+        "tokyo-night-dark",
+        include_str!("../../../static/highlight-js-themes/tokyo-night-dark.css"),
+    ),
+    (
+        "atom-one-light",
+        include_str!("../../../static/highlight-js-themes/atom-one-light.css"),
+    ),
+    (
+        "github",
+        include_str!("../../../static/highlight-js-themes/github.css"),
+    ),
+    (
+        "vs",
+        include_str!("../../../static/highlight-js-themes/vs.css"),
+    ),
+    (
+        "xcode",
+        include_str!("../../../static/highlight-js-themes/xcode.css"),
+    ),
+    (
+        "tokyo-night-light",
+        include_str!("../../../static/highlight-js-themes/tokyo-night-light.css"),
+    ),
+];
+
+pub fn markdown_theme_css(theme: &str) -> Option<&'static str> {
+    MARKDOWN_THEMES
+        .iter()
+        .find(|(name, _)| *name == theme)
+        .map(|(_, css)| *css)
+}
+
+pub fn code_theme_css(theme: &str) -> Option<&'static str> {
+    CODE_THEMES
+        .iter()
+        .find(|(name, _)| *name == theme)
+        .map(|(_, css)| *css)
+}
+
+/// Wraps rendered note HTML in a standalone page with the theme stylesheets inlined.
+pub fn build_export_html(
+    title: &str,
+    body_html: &str,
+    markdown_css: &str,
+    code_css: &str,
+) -> String {
+    // Highlight themes target `code.hljs`, which the frontend adds when it highlights.
+    // Blocks highlighted during rendering already have it.
+    let body_html = body_html
+        .replace(
+            "<pre><code class=\"language-",
+            "<pre><code class=\"hljs language-",
+        )
+        .replace("<pre><code>", "<pre><code class=\"hljs\">");
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}\n</style>\n<style>\n{}\n</style>\n</head>\n<body>\n<div class=\"markdown-body\">\n{}\n</div>\n</body>\n</html>\n",
+        html_escape::encode_text(title),
+        markdown_css,
+        code_css,
+        body_html
+    )
+}
+
+/// Writes a zip holding the note under its own name plus each version under `versions/`.
+pub fn write_note_bundle(
+    bundle_path: &Path,
+    note_name: &str,
+    content: &str,
+    versions: &[(String, String)],
+) -> AppResult<()> {
+    let file = fs::File::create(bundle_path)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let bundle_error = |e: zip::result::ZipError| {
+        AppError::Export(format!(
+            "Failed to write bundle '{}': {}",
+            bundle_path.display(),
+            e
+        ))
+    };
+
+    zip.start_file(note_name, options).map_err(bundle_error)?;
+    zip.write_all(content.as_bytes())?;
+
+    for (version_name, version_content) in versions {
+        zip.start_file(format!("versions/{}", version_name), options)
+            .map_err(bundle_error)?;
+        zip.write_all(version_content.as_bytes())?;
+    }
+
+    zip.finish().map_err(bundle_error)?;
+    Ok(())
+}
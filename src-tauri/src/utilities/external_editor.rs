@@ -0,0 +1,64 @@
+use crate::core::{AppError, AppResult};
+
+pub const PATH_PLACEHOLDER: &str = "{path}";
+pub const LINE_PLACEHOLDER: &str = "{line}";
+
+/// Splits a command template into arguments on whitespace, keeping quoted
+/// sections together. Quotes group characters but are not kept in the output.
+pub fn split_command_template(template: &str) -> AppResult<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+
+    for c in template.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            None if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err(AppError::ConfigLoad(
+            "External editor command has an unterminated quote".to_string(),
+        ));
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+/// Expands the template into a program and its arguments. Placeholders are
+/// substituted after splitting, so paths containing spaces stay one argument.
+pub fn build_editor_command(
+    template: &str,
+    path: &str,
+    line: Option<u32>,
+) -> AppResult<(String, Vec<String>)> {
+    let line = line.unwrap_or(1).to_string();
+    let mut args = split_command_template(template)?.into_iter().map(|arg| {
+        // Line first, so a literal "{line}" inside the path is left alone
+        arg.replace(LINE_PLACEHOLDER, &line)
+            .replace(PATH_PLACEHOLDER, path)
+    });
+
+    let program = args
+        .next()
+        .ok_or_else(|| AppError::ConfigLoad("External editor command is empty".to_string()))?;
+    Ok((program, args.collect()))
+}
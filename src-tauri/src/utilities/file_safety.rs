@@ -1,18 +1,21 @@
 use crate::{
-    config::get_config_notes_dir,
+    config::{get_config_notes_dir, load_config, PreferencesConfig},
     core::{AppError, AppResult},
-    logging::log,
-    utilities::paths::{get_backup_dir_for_notes_path, get_temp_dir},
+    logging::{log, log_error, log_warn},
+    utilities::{
+        paths::{get_backup_dir_for_notes_path, get_temp_dir},
+        strings::parse_backup_filename,
+    },
 };
+use once_cell::sync::Lazy;
 use std::{
+    collections::HashMap,
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    sync::Mutex,
     time::{SystemTime, UNIX_EPOCH},
 };
 
-// How many backup versions we keep
-const MAX_BACKUPS: usize = 20;
-
 #[derive(Debug, Clone)]
 pub enum BackupType {
     Rollback,       // For safe_write_note rollback protection
@@ -34,25 +37,46 @@ impl BackupType {
     }
 }
 
+/// Saves of a note less than `version_min_interval_seconds` apart form one
+/// editing session, which keeps a single in-progress version.
+#[derive(Debug)]
+struct VersionSession {
+    last_save: u64,
+    /// The content before the latest save of the session, rewritten by each save.
+    snapshot: Option<PathBuf>,
+}
+
+static VERSION_SESSIONS: Lazy<Mutex<HashMap<PathBuf, VersionSession>>> =
+    Lazy::new(Default::default);
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
 pub fn create_versioned_backup(
     note_path: &PathBuf,
     backup_type: BackupType,
     content_override: Option<&str>,
 ) -> AppResult<PathBuf> {
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-
-    let note_filename = note_path
-        .file_name()
-        .and_then(|s| s.to_str())
-        .ok_or_else(|| AppError::InvalidPath("Invalid filename".to_string()))?;
-
-    let backup_filename = generate_backup_filename(note_filename, &backup_type, timestamp);
+    let backup_path =
+        write_versioned_backup(note_path, &backup_type, content_override, unix_now())?;
+    prune_old_backups(
+        &backup_path,
+        load_config().preferences.version_history_limit,
+    )?;
+    Ok(backup_path)
+}
 
-    let mut backup_path = safe_backup_path(note_path)?;
-    backup_path.set_file_name(&backup_filename);
+fn write_versioned_backup(
+    note_path: &PathBuf,
+    backup_type: &BackupType,
+    content_override: Option<&str>,
+    timestamp: u64,
+) -> AppResult<PathBuf> {
+    let backup_path = versioned_backup_path(note_path, backup_type, timestamp)?;
 
     if let Some(backup_parent) = backup_path.parent() {
         fs::create_dir_all(backup_parent)?;
@@ -83,15 +107,139 @@ pub fn create_versioned_backup(
         }
     }
 
-    prune_old_backups(&backup_path, MAX_BACKUPS)?;
+    Ok(backup_path)
+}
 
+fn versioned_backup_path(
+    note_path: &PathBuf,
+    backup_type: &BackupType,
+    timestamp: u64,
+) -> AppResult<PathBuf> {
+    let note_filename = note_path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| AppError::InvalidPath("Invalid filename".to_string()))?;
+
+    let backup_filename = generate_backup_filename(note_filename, backup_type, timestamp);
+
+    let mut backup_path = safe_backup_path(note_path)?;
+    backup_path.set_file_name(&backup_filename);
     Ok(backup_path)
 }
 
-pub fn safe_write_note(note_path: &PathBuf, content: &str) -> AppResult<()> {
-    let rollback_backup_path = create_rollback_backup_if_exists(note_path)?;
+/// Writes `content` atomically, keeping versions as `preferences` say. The
+/// caller passes the preferences in effect, normally from `AppState.config`.
+pub fn safe_write_note(
+    note_path: &PathBuf,
+    content: &str,
+    preferences: &PreferencesConfig,
+) -> AppResult<()> {
+    write_note(note_path, content, preferences, false)
+}
+
+/// Like `safe_write_note`, but the previous content is kept as a finished
+/// version even in the middle of an editing session, which then starts over.
+pub fn safe_write_note_keeping_version(
+    note_path: &PathBuf,
+    content: &str,
+    preferences: &PreferencesConfig,
+) -> AppResult<()> {
+    write_note(note_path, content, preferences, true)
+}
+
+fn write_note(
+    note_path: &PathBuf,
+    content: &str,
+    preferences: &PreferencesConfig,
+    always_keep_version: bool,
+) -> AppResult<()> {
+    let now = unix_now();
+    let session = VERSION_SESSIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(note_path);
+    let continuing = !always_keep_version
+        && continues_session(
+            session.as_ref().map(|session| session.last_save),
+            now,
+            preferences.version_min_interval_seconds,
+        );
+    let superseded = session.and_then(|session| session.snapshot);
+
+    // Versions are named by the second, so one already taken this second
+    // other than the session's snapshot is kept, and this save's rollback
+    // copy is only held in the temp dir while writing
+    let keeps_earlier = note_path.exists() && {
+        let version_path = versioned_backup_path(note_path, &BackupType::Rollback, now)?;
+        version_path.exists() && superseded.as_ref() != Some(&version_path)
+    };
+    let rollback_backup_path = if keeps_earlier {
+        create_temp_rollback_if_exists(note_path)?
+    } else {
+        create_rollback_backup_if_exists(note_path, now)?
+    };
+
+    write_through_temp_file(note_path, content, rollback_backup_path.as_ref())?;
+
+    // The rollback copy of the previous content replaces the session's
+    // snapshot. Once the session is over it stays as that session's version.
+    let mut snapshot = None;
+    if keeps_earlier {
+        if let Some(temp_rollback) = rollback_backup_path {
+            cleanup_temp_file(&temp_rollback);
+        }
+        snapshot = superseded.filter(|_| continuing);
+    } else if let Some(backup_path) = rollback_backup_path {
+        if let Some(old) = superseded.filter(|old| *old != backup_path && old.exists()) {
+            remove_backup(&old, "Failed to remove superseded snapshot");
+        }
+        if repeats_previous_version(note_path, &backup_path, now) {
+            remove_backup(&backup_path, "Failed to remove repeated version");
+        } else {
+            prune_old_backups(&backup_path, preferences.version_history_limit)?;
+            snapshot = Some(backup_path).filter(|_| continuing);
+        }
+    }
+
+    VERSION_SESSIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(
+            note_path.clone(),
+            VersionSession {
+                last_save: now,
+                snapshot,
+            },
+        );
+    Ok(())
+}
+
+/// Writes `content` atomically without keeping the previous content as a
+/// version, then removes the versions already kept. Used when a note is
+/// encrypted, so no plaintext copy of it stays in the backup directory.
+pub fn write_note_discarding_versions(note_path: &PathBuf, content: &str) -> AppResult<()> {
+    VERSION_SESSIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(note_path);
+    write_through_temp_file(note_path, content, None)?;
+    remove_note_versions(note_path)
+}
 
-    let temp_path = match create_temp_file_with_content(content) {
+fn write_through_temp_file(
+    note_path: &PathBuf,
+    content: &str,
+    rollback_backup_path: Option<&PathBuf>,
+) -> AppResult<()> {
+    // Renaming onto a symlink would replace the link, so a symlinked note is
+    // written to the file it points at, from a temp file beside that file
+    let link_target = resolve_symlinked_note(note_path);
+    let write_path = link_target.as_ref().unwrap_or(note_path);
+    let temp_path = match &link_target {
+        Some(target) => create_temp_file_beside(target, content),
+        None => create_temp_file_with_content(content),
+    };
+    let temp_path = match temp_path {
         Ok(path) => path,
         Err(e) => {
             create_save_failure_backup(note_path, content);
@@ -99,16 +247,88 @@ pub fn safe_write_note(note_path: &PathBuf, content: &str) -> AppResult<()> {
         }
     };
 
-    perform_atomic_write_with_rollback(
-        note_path,
-        &temp_path,
-        content,
-        rollback_backup_path.as_ref(),
-    )?;
-    verify_written_content(note_path, content)?;
+    perform_atomic_write_with_rollback(write_path, &temp_path, content, rollback_backup_path)?;
+    verify_written_content(note_path, content)
+}
+
+fn remove_note_versions(note_path: &PathBuf) -> AppResult<()> {
+    let backup_path = safe_backup_path(note_path)?;
+    let Some(note_backup_dir) = backup_path.parent() else {
+        return Ok(());
+    };
+    let Ok(entries) = fs::read_dir(note_backup_dir) else {
+        return Ok(());
+    };
+    let base_name = note_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    for entry in entries.flatten() {
+        let filename = entry.file_name().to_string_lossy().to_string();
+        if parse_backup_filename(&filename, &base_name).is_some() {
+            fs::remove_file(entry.path())?;
+        }
+    }
     Ok(())
 }
 
+/// Whether a save at `now` belongs to the same editing session as the save
+/// at `last_save`. Without an earlier save, e.g. after a restart, a new
+/// session starts.
+pub fn continues_session(last_save: Option<u64>, now: u64, min_interval: u64) -> bool {
+    last_save.is_some_and(|last_save| now.saturating_sub(last_save) < min_interval)
+}
+
+fn remove_backup(backup_path: &PathBuf, message: &str) {
+    if let Err(e) = fs::remove_file(backup_path) {
+        log(
+            "BACKUP_CLEANUP",
+            &format!("{}: {:?}", message, backup_path),
+            Some(&e.to_string()),
+        );
+    }
+}
+
+// A version identical to the one before it adds nothing to the history
+fn repeats_previous_version(note_path: &PathBuf, backup_path: &PathBuf, timestamp: u64) -> bool {
+    let Some(previous) = previous_backup(note_path, &BackupType::Rollback, timestamp) else {
+        return false;
+    };
+    match (fs::read(&previous), fs::read(backup_path)) {
+        (Ok(previous), Ok(current)) => previous == current,
+        _ => false,
+    }
+}
+
+// The newest backup of this kind taken before `timestamp`
+fn previous_backup(
+    note_path: &PathBuf,
+    backup_type: &BackupType,
+    timestamp: u64,
+) -> Option<PathBuf> {
+    let backup_path = safe_backup_path(note_path).ok()?;
+    let parent = backup_path.parent()?;
+    let stem = note_path.file_stem()?.to_str()?;
+    let prefix = format!("{}.{}.", stem, backup_type.suffix());
+
+    fs::read_dir(parent)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let filename = entry.file_name().to_str()?.to_string();
+            let backup_timestamp = filename
+                .strip_prefix(&prefix)?
+                .strip_suffix(".md")?
+                .parse::<u64>()
+                .ok()?;
+            Some((backup_timestamp, entry.path()))
+        })
+        .filter(|(backup_timestamp, _)| *backup_timestamp < timestamp)
+        .max_by_key(|(backup_timestamp, _)| *backup_timestamp)
+        .map(|(_, path)| path)
+}
+
 pub fn safe_backup_path(note_path: &PathBuf) -> AppResult<PathBuf> {
     let notes_dir = get_config_notes_dir();
     let backup_dir = get_backup_dir_for_notes_path(&notes_dir)?;
@@ -136,7 +356,7 @@ pub fn cleanup_temp_files() -> AppResult<()> {
                     .starts_with("write_temp_")
                 {
                     if let Err(e) = fs::remove_file(entry.path()) {
-                        log(
+                        log_warn(
                             "TEMP_CLEANUP",
                             &format!("Failed to remove temp file: {:?}", entry.path()),
                             Some(&e.to_string()),
@@ -149,7 +369,9 @@ pub fn cleanup_temp_files() -> AppResult<()> {
     Ok(())
 }
 
-fn prune_old_backups(latest_backup: &PathBuf, max_backups: usize) -> AppResult<()> {
+/// Keeps the newest `max_backups` backups sharing the latest backup's note
+/// and kind, removing the rest oldest first.
+pub fn prune_old_backups(latest_backup: &PathBuf, max_backups: usize) -> AppResult<()> {
     let parent = latest_backup.parent().ok_or_else(|| {
         AppError::InvalidPath("Failed to get backup parent directory".to_string())
     })?;
@@ -188,7 +410,7 @@ fn prune_old_backups(latest_backup: &PathBuf, max_backups: usize) -> AppResult<(
     if backups.len() > max_backups {
         for old in &backups[..backups.len() - max_backups] {
             if let Err(e) = fs::remove_file(old.path()) {
-                log(
+                log_warn(
                     "BACKUP_CLEANUP",
                     &format!("Failed to remove old backup: {:?}", old.path()),
                     Some(&e.to_string()),
@@ -214,18 +436,30 @@ fn generate_backup_filename(
     format!("{}.{}.{}.md", base_name, backup_type.suffix(), timestamp)
 }
 
-fn create_rollback_backup_if_exists(note_path: &PathBuf) -> AppResult<Option<PathBuf>> {
+fn create_rollback_backup_if_exists(
+    note_path: &PathBuf,
+    timestamp: u64,
+) -> AppResult<Option<PathBuf>> {
     if note_path.exists() {
-        Ok(Some(create_versioned_backup(
+        Ok(Some(write_versioned_backup(
             note_path,
-            BackupType::Rollback,
+            &BackupType::Rollback,
             None,
+            timestamp,
         )?))
     } else {
         Ok(None)
     }
 }
 
+fn create_temp_rollback_if_exists(note_path: &PathBuf) -> AppResult<Option<PathBuf>> {
+    if note_path.exists() {
+        create_temp_file_with_content(&fs::read_to_string(note_path)?).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
 fn create_temp_file_with_content(content: &str) -> AppResult<PathBuf> {
     let temp_dir = get_temp_dir()?;
     fs::create_dir_all(&temp_dir)?;
@@ -242,6 +476,34 @@ fn create_temp_file_with_content(content: &str) -> AppResult<PathBuf> {
     Ok(temp_path)
 }
 
+fn resolve_symlinked_note(note_path: &Path) -> Option<PathBuf> {
+    let is_symlink =
+        fs::symlink_metadata(note_path).is_ok_and(|metadata| metadata.file_type().is_symlink());
+    if !is_symlink {
+        return None;
+    }
+    // A broken link has nothing to write through to, so it is replaced as before
+    fs::canonicalize(note_path).ok()
+}
+
+// Hidden and ending in .tmp, so neither the indexer nor the watcher picks it up
+fn create_temp_file_beside(target: &Path, content: &str) -> AppResult<PathBuf> {
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| AppError::InvalidPath("Invalid filename".to_string()))?;
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let temp_path = target.with_file_name(format!(".{}.write_temp_{}.tmp", file_name, timestamp));
+
+    fs::write(&temp_path, content)
+        .map_err(|e| AppError::FileWrite(format!("Failed to write temp file: {}", e)))?;
+
+    Ok(temp_path)
+}
+
 fn perform_atomic_write_with_rollback(
     note_path: &PathBuf,
     temp_path: &PathBuf,
@@ -264,7 +526,7 @@ fn perform_atomic_write_with_rollback(
             return Ok(());
         }
 
-        log(
+        log_error(
             "ATOMIC_WRITE_FAILURE",
             &format!(
                 "Rename operation failed: {:?} -> {:?}",
@@ -312,7 +574,7 @@ fn handle_rename_failure_with_rollback(
                 );
             }
             Err(rollback_err) => {
-                log(
+                log_error(
                     "ROLLBACK_CRITICAL_FAILURE",
                     &format!(
                         "CRITICAL: Failed to restore backup after rename failure: {:?} -> {:?}",
@@ -346,7 +608,7 @@ fn verify_written_content(note_path: &PathBuf, expected_content: &str) -> AppRes
             expected_content.len(),
             written_content.len()
         );
-        log(
+        log_error(
             "FILE_VERIFICATION",
             "Content verification failed",
             Some(&error_msg),
@@ -359,7 +621,7 @@ fn verify_written_content(note_path: &PathBuf, expected_content: &str) -> AppRes
 
 fn cleanup_temp_file(temp_path: &PathBuf) {
     if let Err(cleanup_err) = fs::remove_file(temp_path) {
-        log(
+        log_warn(
             "TEMP_CLEANUP",
             &format!("Failed to remove temp file: {:?}", temp_path),
             Some(&cleanup_err.to_string()),
@@ -377,7 +639,7 @@ fn create_save_failure_backup(note_path: &PathBuf, content: &str) {
             );
         }
         Err(e) => {
-            log(
+            log_warn(
                 "FILE_BACKUP",
                 &format!(
                     "Failed to create save failure backup for '{}'",
@@ -0,0 +1,143 @@
+use crate::logging::log;
+use serde::Deserialize;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Frontmatter {
+    pub tags: Vec<String>,
+    /// Each of `tags` as written in the note, in the same order.
+    pub tag_labels: Vec<String>,
+    pub created: Option<String>,
+    /// Markdown theme to render this note with instead of the configured one.
+    pub render_theme: Option<String>,
+    /// Word count the note is being written towards.
+    pub goal: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct RawFrontmatter {
+    #[serde(default)]
+    tags: Option<RawTags>,
+    #[serde(default)]
+    created: Option<serde_yaml::Value>,
+    #[serde(default)]
+    render_theme: Option<serde_yaml::Value>,
+    #[serde(default)]
+    goal: Option<serde_yaml::Value>,
+}
+
+// Accept both `tags: [a, b]` and `tags: a, b`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawTags {
+    List(Vec<serde_yaml::Value>),
+    Single(String),
+}
+
+/// Returns the raw YAML between a leading `---` line and the next `---` or `...` line.
+pub fn extract_frontmatter_block(content: &str) -> Option<&str> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut lines = content.split_inclusive('\n');
+    let first = lines.next()?;
+    if first.trim_end() != "---" {
+        return None;
+    }
+
+    let start = first.len();
+    let mut end = start;
+    for line in lines {
+        let trimmed = line.trim_end();
+        if trimmed == "---" || trimmed == "..." {
+            return Some(&content[start..end]);
+        }
+        end += line.len();
+    }
+
+    None
+}
+
+/// Parses the frontmatter of a note. Notes without frontmatter, or with malformed YAML,
+/// yield an empty `Frontmatter` so they are indexed like any other note.
+pub fn parse_frontmatter(filename: &str, content: &str) -> Frontmatter {
+    let Some(block) = extract_frontmatter_block(content) else {
+        return Frontmatter::default();
+    };
+
+    if block.trim().is_empty() {
+        return Frontmatter::default();
+    }
+
+    let raw = match serde_yaml::from_str::<RawFrontmatter>(block) {
+        Ok(raw) => raw,
+        Err(e) => {
+            log(
+                "FRONTMATTER",
+                &format!("Malformed frontmatter in '{}'. Ignoring.", filename),
+                Some(&e.to_string()),
+            );
+            return Frontmatter::default();
+        }
+    };
+
+    let written: Vec<String> = match raw.tags {
+        Some(RawTags::List(values)) => values.iter().filter_map(yaml_scalar_to_string).collect(),
+        Some(RawTags::Single(value)) => value.split(',').map(str::to_string).collect(),
+        None => Vec::new(),
+    };
+    let (tags, tag_labels) = dedup_tags(&written);
+
+    Frontmatter {
+        tags,
+        tag_labels,
+        created: raw.created.as_ref().and_then(yaml_scalar_to_string),
+        render_theme: raw
+            .render_theme
+            .as_ref()
+            .and_then(yaml_scalar_to_string)
+            .map(|theme| theme.trim().to_string())
+            .filter(|theme| !theme.is_empty()),
+        goal: raw
+            .goal
+            .as_ref()
+            .and_then(yaml_scalar_to_string)
+            .and_then(|goal| goal.trim().parse::<usize>().ok())
+            .filter(|&goal| goal > 0),
+    }
+}
+
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag_label(tag).to_lowercase();
+    if tag.is_empty() {
+        None
+    } else {
+        Some(tag)
+    }
+}
+
+// A tag as displayed: trimmed and without a leading '#', but keeping its case
+fn tag_label(tag: &str) -> &str {
+    tag.trim().trim_start_matches('#').trim()
+}
+
+// Normalized tags without duplicates, with the label each was first written as
+fn dedup_tags(written: &[String]) -> (Vec<String>, Vec<String>) {
+    let mut tags = Vec::with_capacity(written.len());
+    let mut labels = Vec::with_capacity(written.len());
+    for tag in written {
+        if let Some(normalized) = normalize_tag(tag) {
+            if !tags.contains(&normalized) {
+                tags.push(normalized);
+                labels.push(tag_label(tag).to_string());
+            }
+        }
+    }
+    (tags, labels)
+}
+
+fn yaml_scalar_to_string(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
@@ -1,7 +1,27 @@
+pub mod attachments;
+pub mod clipboard;
+pub mod code_highlight;
 pub mod config_helpers;
+pub mod directory_health;
+pub mod export;
+pub mod external_editor;
 pub mod file_safety;
-pub mod mac_focus;
+pub mod frontmatter;
+pub mod note_crypto;
+pub mod note_diff;
+pub mod note_folders;
+pub mod note_ignore;
+pub mod note_links;
 pub mod note_renderer;
+pub mod note_stats;
 pub mod paths;
+pub mod render_theme;
+pub mod save_format;
+pub mod spellcheck;
 pub mod strings;
+pub mod symlinks;
+pub mod templates;
+pub mod tray_pins;
 pub mod validation;
+pub mod window_focus;
+pub mod window_geometry;
@@ -0,0 +1,100 @@
+use crate::core::{AppError, AppResult};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, KeyInit, OsRng},
+    XChaCha20Poly1305, XNonce,
+};
+
+const ARMOR_BEGIN: &str = "-----BEGIN SYMIOSIS ENCRYPTED NOTE-----";
+const ARMOR_END: &str = "-----END SYMIOSIS ENCRYPTED NOTE-----";
+const FORMAT_VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const LINE_WIDTH: usize = 64;
+
+/// Whether `content` is a note locked with `encrypt_content`.
+pub fn is_encrypted_note(content: &str) -> bool {
+    content.trim_start().starts_with(ARMOR_BEGIN)
+}
+
+/// Encrypts `plaintext` with XChaCha20-Poly1305 under a key derived from
+/// `passphrase` with Argon2id. The result is plain text, so it is stored and
+/// synced like any other note: a version byte, the salt, the nonce and the
+/// ciphertext, base64 encoded between armor lines.
+pub fn encrypt_content(plaintext: &str, passphrase: &str) -> AppResult<String> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let cipher = note_cipher(passphrase, &salt)?;
+    let ciphertext = cipher
+        .encrypt(XNonce::from_slice(&nonce), plaintext.as_bytes())
+        .map_err(|_| AppError::Encryption("Failed to encrypt note".to_string()))?;
+
+    let mut payload = Vec::with_capacity(1 + SALT_LEN + NONCE_LEN + ciphertext.len());
+    payload.push(FORMAT_VERSION);
+    payload.extend_from_slice(&salt);
+    payload.extend_from_slice(&nonce);
+    payload.extend_from_slice(&ciphertext);
+
+    let encoded = STANDARD.encode(payload);
+    let mut armored = format!("{}\n", ARMOR_BEGIN);
+    for line in encoded.as_bytes().chunks(LINE_WIDTH) {
+        armored.push_str(&String::from_utf8_lossy(line));
+        armored.push('\n');
+    }
+    armored.push_str(ARMOR_END);
+    armored.push('\n');
+    Ok(armored)
+}
+
+/// Decrypts a note written by `encrypt_content`. The AEAD tag is checked
+/// before anything is returned, so a wrong passphrase or a tampered note
+/// yields `WrongPassphrase` and never partial plaintext.
+pub fn decrypt_content(armored: &str, passphrase: &str) -> AppResult<String> {
+    let body = armored
+        .trim()
+        .strip_prefix(ARMOR_BEGIN)
+        .and_then(|rest| rest.strip_suffix(ARMOR_END))
+        .ok_or_else(|| AppError::Encryption("Note is not encrypted".to_string()))?;
+    let encoded: String = body.split_whitespace().collect();
+    let payload = STANDARD
+        .decode(encoded)
+        .map_err(|e| AppError::Encryption(format!("Encrypted note is corrupt: {}", e)))?;
+
+    if payload.len() < 1 + SALT_LEN + NONCE_LEN {
+        return Err(AppError::Encryption(
+            "Encrypted note is truncated".to_string(),
+        ));
+    }
+    if payload[0] != FORMAT_VERSION {
+        return Err(AppError::Encryption(format!(
+            "Unsupported encrypted note version {}",
+            payload[0]
+        )));
+    }
+    let (salt, rest) = payload[1..].split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let cipher = note_cipher(passphrase, salt)?;
+    let plaintext = cipher
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| AppError::WrongPassphrase)?;
+    String::from_utf8(plaintext)
+        .map_err(|_| AppError::Encryption("Decrypted note is not valid UTF-8".to_string()))
+}
+
+fn note_cipher(passphrase: &str, salt: &[u8]) -> AppResult<XChaCha20Poly1305> {
+    if passphrase.is_empty() {
+        return Err(AppError::Encryption(
+            "Passphrase cannot be empty".to_string(),
+        ));
+    }
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| AppError::Encryption(format!("Failed to derive key: {}", e)))?;
+    Ok(XChaCha20Poly1305::new(&key.into()))
+}
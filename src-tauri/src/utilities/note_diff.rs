@@ -0,0 +1,64 @@
+use similar::{DiffOp, TextDiff};
+
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffTag {
+    Equal,
+    Insert,
+    Delete,
+}
+
+/// A run of consecutive lines sharing one tag. Line numbers are 1-based and
+/// point into the side the lines come from, so a side-by-side view can align
+/// both columns without recounting.
+#[derive(serde::Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct DiffHunk {
+    pub tag: DiffTag,
+    pub old_start: usize,
+    pub new_start: usize,
+    pub lines: Vec<String>,
+}
+
+fn collect_lines(slices: &[&str]) -> Vec<String> {
+    slices
+        .iter()
+        .map(|line| line.trim_end_matches(['\n', '\r']).to_string())
+        .collect()
+}
+
+/// Line-level diff of two texts. Replacements are reported as a delete
+/// followed by an insert so the frontend only has three tags to render.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffHunk> {
+    let diff = TextDiff::from_lines(old, new);
+    let old_lines = diff.old_slices();
+    let new_lines = diff.new_slices();
+    let mut hunks = Vec::new();
+
+    for op in diff.ops() {
+        let (old_range, new_range) = (op.old_range(), op.new_range());
+        let old_hunk = |tag| DiffHunk {
+            tag,
+            old_start: old_range.start + 1,
+            new_start: new_range.start + 1,
+            lines: collect_lines(&old_lines[old_range.clone()]),
+        };
+        let new_hunk = || DiffHunk {
+            tag: DiffTag::Insert,
+            old_start: old_range.end + 1,
+            new_start: new_range.start + 1,
+            lines: collect_lines(&new_lines[new_range.clone()]),
+        };
+
+        match op {
+            DiffOp::Equal { .. } => hunks.push(old_hunk(DiffTag::Equal)),
+            DiffOp::Delete { .. } => hunks.push(old_hunk(DiffTag::Delete)),
+            DiffOp::Insert { .. } => hunks.push(new_hunk()),
+            DiffOp::Replace { .. } => {
+                hunks.push(old_hunk(DiffTag::Delete));
+                hunks.push(new_hunk());
+            }
+        }
+    }
+
+    hunks
+}
@@ -0,0 +1,73 @@
+use crate::utilities::note_ignore::NoteIgnore;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FolderNode {
+    pub name: String,
+    /// Path relative to the notes directory, `/` separated.
+    pub path: String,
+    pub children: Vec<FolderNode>,
+}
+
+/// Lists the folders the indexer would descend into, sorted by name at every
+/// level. Folders at `max_depth` are left out, as notes inside them are not
+/// indexed.
+pub fn list_folder_tree(notes_dir: &Path, max_depth: usize) -> Vec<FolderNode> {
+    let note_ignore = NoteIgnore::load(notes_dir);
+    let paths: Vec<String> = WalkDir::new(notes_dir)
+        .follow_links(false)
+        .min_depth(1)
+        .max_depth(max_depth.saturating_sub(1))
+        .into_iter()
+        .filter_entry(|entry| {
+            let relative = entry.path().strip_prefix(notes_dir).unwrap_or(entry.path());
+            entry.file_type().is_dir()
+                && !entry.file_name().to_string_lossy().starts_with('.')
+                && !note_ignore.is_ignored(relative, true)
+        })
+        .filter_map(|e| e.ok())
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(notes_dir).ok()?;
+            let components: Vec<String> = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect();
+            Some(components.join("/"))
+        })
+        .collect();
+
+    build_tree(&paths)
+}
+
+fn build_tree(paths: &[String]) -> Vec<FolderNode> {
+    let mut children: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for path in paths {
+        let parent = path
+            .rsplit_once('/')
+            .map(|(parent, _)| parent)
+            .unwrap_or("");
+        children.entry(parent).or_default().push(path);
+    }
+    collect_children(&children, "")
+}
+
+fn collect_children(children: &BTreeMap<&str, Vec<&str>>, parent: &str) -> Vec<FolderNode> {
+    let mut nodes: Vec<FolderNode> = children
+        .get(parent)
+        .map(|paths| {
+            paths
+                .iter()
+                .map(|path| FolderNode {
+                    name: path.rsplit('/').next().unwrap_or(path).to_string(),
+                    path: path.to_string(),
+                    children: collect_children(children, path),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    nodes.sort_by_key(|node| node.name.to_lowercase());
+    nodes
+}
@@ -0,0 +1,76 @@
+use crate::logging::log_warn;
+use crate::utilities::paths::get_backup_dir_for_notes_path;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+
+pub const IGNORE_FILE_NAME: &str = ".symiosisignore";
+
+// Skipped at any depth, whatever the ignore file says
+const ALWAYS_IGNORED_DIRS: &[&str] = &[".git", ".archive"];
+
+/// Decides which paths in the notes directory are left out of the index: the
+/// gitignore-style patterns in `.symiosisignore` at the notes directory root,
+/// plus `.git/`, `.archive/` and the trash folder when it lives inside it.
+pub struct NoteIgnore {
+    gitignore: Gitignore,
+    trash_dir: Option<PathBuf>,
+}
+
+impl NoteIgnore {
+    pub fn load(notes_dir: &Path) -> Self {
+        let mut builder = GitignoreBuilder::new(notes_dir);
+        let ignore_file = notes_dir.join(IGNORE_FILE_NAME);
+        if ignore_file.exists() {
+            // Bad lines are reported but the rest of the file still applies
+            if let Some(e) = builder.add(&ignore_file) {
+                log_warn(
+                    "NOTE_IGNORE",
+                    "Some ignore patterns could not be parsed",
+                    Some(&format!("file: {}, error: {}", ignore_file.display(), e)),
+                );
+            }
+        }
+
+        let gitignore = builder.build().unwrap_or_else(|e| {
+            log_warn(
+                "NOTE_IGNORE",
+                "Failed to build ignore patterns, only the built-in ones apply",
+                Some(&e.to_string()),
+            );
+            Gitignore::empty()
+        });
+
+        let trash_dir = get_backup_dir_for_notes_path(notes_dir)
+            .ok()
+            .and_then(|dir| dir.strip_prefix(notes_dir).ok().map(Path::to_path_buf))
+            .filter(|dir| !dir.as_os_str().is_empty());
+
+        Self {
+            gitignore,
+            trash_dir,
+        }
+    }
+
+    /// `relative` is the path inside the notes directory.
+    pub fn is_ignored(&self, relative: &Path, is_dir: bool) -> bool {
+        if relative.as_os_str().is_empty() {
+            return false;
+        }
+
+        if relative.components().any(|component| {
+            ALWAYS_IGNORED_DIRS.contains(&component.as_os_str().to_string_lossy().as_ref())
+        }) {
+            return true;
+        }
+
+        if let Some(trash_dir) = &self.trash_dir {
+            if relative.starts_with(trash_dir) {
+                return true;
+            }
+        }
+
+        self.gitignore
+            .matched_path_or_any_parents(relative, is_dir)
+            .is_ignore()
+    }
+}
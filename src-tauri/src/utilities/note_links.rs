@@ -0,0 +1,244 @@
+use crate::utilities::strings::extract_title_from_filename;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Case-insensitive lookup from `[[link]]` targets to note filenames. A note
+/// answers to its filename, its name without the extension, and its title
+/// (the name with `-` and `_` read as spaces), so "Other Note", "other-note"
+/// and "Other Note.md" all reach `Other Note.md`. With `with_titles` it also
+/// answers to the title on its first line.
+#[derive(Debug, Default)]
+pub struct NoteLinkIndex {
+    by_key: HashMap<String, String>,
+}
+
+fn link_key(text: &str) -> String {
+    text.trim().to_lowercase()
+}
+
+impl NoteLinkIndex {
+    pub fn new<I, S>(filenames: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut filenames: Vec<String> = filenames.into_iter().map(Into::into).collect();
+        // Sorted so that when two notes share a title the same one always wins
+        filenames.sort();
+
+        let mut by_key = HashMap::new();
+        for filename in filenames {
+            let name = Path::new(&filename)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| filename.clone());
+            let stem = Path::new(&name)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| name.clone());
+
+            for key in [
+                link_key(&filename),
+                link_key(&name),
+                link_key(&stem),
+                link_key(&extract_title_from_filename(&stem)),
+            ] {
+                by_key.entry(key).or_insert_with(|| filename.clone());
+            }
+        }
+        Self { by_key }
+    }
+
+    /// Adds each note's title from its content, as `(filename, title)` pairs.
+    /// Anything a filename already answers to is kept, so a filename match
+    /// always wins over a title.
+    pub fn with_titles<I>(mut self, titles: I) -> Self
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut titles: Vec<(String, String)> = titles.into_iter().collect();
+        titles.sort();
+
+        for (filename, title) in titles {
+            self.by_key.entry(link_key(&title)).or_insert(filename);
+        }
+        self
+    }
+
+    /// Filename of the note a link target refers to, if one exists.
+    pub fn resolve(&self, target: &str) -> Option<&str> {
+        self.by_key
+            .get(&link_key(target))
+            .or_else(|| {
+                self.by_key
+                    .get(&link_key(&extract_title_from_filename(target.trim())))
+            })
+            .map(String::as_str)
+    }
+}
+
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkKind {
+    Markdown,
+    Wiki,
+}
+
+/// An outgoing link of a note. `note` is the filename the target resolves to;
+/// external links (anything with a URL scheme) never resolve.
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+pub struct NoteLink {
+    pub kind: LinkKind,
+    pub text: String,
+    pub target: String,
+    pub note: Option<String>,
+    pub external: bool,
+}
+
+impl NoteLink {
+    pub fn new(kind: LinkKind, text: String, target: String, index: &NoteLinkIndex) -> Self {
+        let external = is_external_target(&target);
+        let note = match kind {
+            _ if external => None,
+            LinkKind::Wiki => index.resolve(&target),
+            LinkKind::Markdown => markdown_link_path(&target).and_then(|path| index.resolve(&path)),
+        }
+        .map(str::to_string);
+
+        Self {
+            kind,
+            text,
+            target,
+            note,
+            external,
+        }
+    }
+}
+
+/// Rewrites link targets that refer to a note being renamed from
+/// `old_filename` to `new_filename`.
+pub struct LinkRename<'a> {
+    old_filename: &'a str,
+    new_filename: &'a str,
+    before: NoteLinkIndex,
+    after: NoteLinkIndex,
+}
+
+impl<'a> LinkRename<'a> {
+    /// `filenames` are the notes as they are before the rename.
+    pub fn new(filenames: &[String], old_filename: &'a str, new_filename: &'a str) -> Self {
+        let renamed = filenames.iter().map(|filename| {
+            if filename == old_filename {
+                new_filename.to_string()
+            } else {
+                filename.clone()
+            }
+        });
+
+        Self {
+            old_filename,
+            new_filename,
+            before: NoteLinkIndex::new(filenames.iter().cloned()),
+            after: NoteLinkIndex::new(renamed),
+        }
+    }
+
+    /// Replacement for a `[[wiki]]` target naming the old note. Targets written
+    /// without an extension stay that way while the new name is unambiguous.
+    pub fn wiki_target(&self, target: &str) -> Option<String> {
+        if self.before.resolve(target) != Some(self.old_filename) {
+            return None;
+        }
+
+        let key = link_key(target);
+        let with_extension =
+            key == link_key(self.old_filename) || key == link_key(file_name(self.old_filename));
+        let name = file_name(self.new_filename);
+        let candidate = if with_extension {
+            name.to_string()
+        } else {
+            Path::new(name)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| name.to_string())
+        };
+
+        if self.after.resolve(&candidate) == Some(self.new_filename) {
+            Some(candidate)
+        } else {
+            Some(self.new_filename.to_string())
+        }
+    }
+
+    /// Replacement for a relative markdown link destination pointing at the
+    /// old note, keeping its `#fragment` or `?query`.
+    pub fn markdown_target(&self, target: &str) -> Option<String> {
+        if is_external_target(target) {
+            return None;
+        }
+        let path = markdown_link_path(target)?;
+        if self.before.resolve(&path) != Some(self.old_filename) {
+            return None;
+        }
+
+        let prefix = if target.starts_with("./") { "./" } else { "" };
+        let suffix = &target[target.find(['#', '?']).unwrap_or(target.len())..];
+        Some(format!(
+            "{}{}{}",
+            prefix,
+            self.new_filename.replace(' ', "%20"),
+            suffix
+        ))
+    }
+}
+
+fn file_name(filename: &str) -> &str {
+    filename.rsplit('/').next().unwrap_or(filename)
+}
+
+/// Whether a link target starts with a URL scheme such as `https:` or `mailto:`.
+/// Single letters are not schemes, so Windows paths like `C:\notes` stay local.
+pub fn is_external_target(target: &str) -> bool {
+    match target.split_once(':') {
+        Some((scheme, _)) => {
+            scheme.len() > 1
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        None => false,
+    }
+}
+
+// The note path of a relative markdown link, without its fragment or query.
+// Links to a section of the same note (`#heading`) have no path.
+fn markdown_link_path(target: &str) -> Option<String> {
+    let path = target.split(['#', '?']).next().unwrap_or_default();
+    let path = percent_decode(path.trim_start_matches("./"));
+    (!path.is_empty()).then_some(path)
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
@@ -1,14 +1,64 @@
+use crate::config::AppConfig;
 use crate::core::errors::{AppError, AppResult};
+use crate::utilities::code_highlight::highlight_code_blocks;
+use crate::utilities::note_crypto::is_encrypted_note;
+use crate::utilities::note_links::{LinkKind, LinkRename, NoteLink, NoteLinkIndex};
 use ammonia::Builder;
 use html_escape;
 use once_cell::sync::Lazy;
-use pulldown_cmark::{html, Options, Parser};
+use pulldown_cmark::{html, CowStr, Event, HeadingLevel, LinkType, Options, Parser, Tag, TagEnd};
 use regex::Regex;
+use serde::Serialize;
 use std::collections::HashSet;
+use std::ops::Range;
+
+/// Rendering switches taken from the user's config. Code blocks are only
+/// highlighted server-side when `code_theme` is set.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenderOptions {
+    pub heading_anchors: bool,
+    pub math: bool,
+    pub code_theme: Option<String>,
+    /// Plain CommonMark, without the `GFM_EXTENSIONS`. Smart punctuation and
+    /// `[[wiki links]]` are part of the app rather than a flavor, so they stay on.
+    pub commonmark: bool,
+}
+
+impl RenderOptions {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            heading_anchors: config.preferences.markdown_heading_anchors,
+            math: config.preferences.markdown_render_math,
+            code_theme: Some(config.interface.md_render_code_theme.clone()),
+            commonmark: config.preferences.markdown_flavor == "commonmark",
+        }
+    }
+}
+
+/// GitHub Flavored Markdown extensions, all rendered unless the flavor is CommonMark.
+pub const GFM_EXTENSIONS: [&str; 5] = [
+    "tables",
+    "task_lists",
+    "strikethrough",
+    "autolinks",
+    "footnotes",
+];
+
+/// Extensions `render_note` applies to markdown notes with `options`.
+pub fn markdown_extensions(options: &RenderOptions) -> Vec<String> {
+    if options.commonmark {
+        return Vec::new();
+    }
+    GFM_EXTENSIONS.iter().map(|name| name.to_string()).collect()
+}
 
 static URL_REGEX: Lazy<Result<Regex, regex::Error>> =
     Lazy::new(|| Regex::new(r#"(?i)\b(https?://[^\s<>"'`()\[\]{}]+)\b"#));
 
+// Opening tag of a wiki link as it comes out of the sanitizer
+static WIKI_LINK_REGEX: Lazy<Result<Regex, regex::Error>> =
+    Lazy::new(|| Regex::new(r#"<a class="wiki-link" data-target="([^"]*)""#));
+
 pub(crate) fn linkify_urls_in_html(html: &str) -> AppResult<String> {
     let url_regex = URL_REGEX
         .as_ref()
@@ -52,6 +102,413 @@ pub(crate) fn linkify_urls_in_html(html: &str) -> AppResult<String> {
     Ok(result)
 }
 
+/// GitHub-style heading slug: lowercase, whitespace becomes hyphens and
+/// punctuation other than hyphens and underscores is dropped.
+pub fn heading_slug(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                Some(c)
+            } else if c.is_whitespace() {
+                Some('-')
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Hands out heading slugs in document order, numbering repeats like GitHub
+/// does (`intro`, `intro-1`, ...). An explicit `{#id}` is used as written.
+#[derive(Default)]
+struct HeadingSlugs {
+    used: HashSet<String>,
+}
+
+impl HeadingSlugs {
+    fn next(&mut self, id: Option<&str>, text: &str) -> Option<String> {
+        if let Some(id) = id {
+            return Some(id.to_string());
+        }
+
+        let slug = heading_slug(text);
+        if slug.is_empty() {
+            return None;
+        }
+        let mut candidate = slug.clone();
+        let mut suffix = 0;
+        while self.used.contains(&candidate) {
+            suffix += 1;
+            candidate = format!("{}-{}", slug, suffix);
+        }
+        self.used.insert(candidate.clone());
+        Some(candidate)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TocEntry {
+    pub level: u8,
+    pub text: String,
+    /// The id `markdown_heading_anchors` gives the heading; `None` when its
+    /// text has nothing to slug.
+    pub slug: Option<String>,
+}
+
+fn heading_level_number(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+fn note_events<'a>(content: &'a str, options: &RenderOptions) -> impl Iterator<Item = Event<'a>> {
+    let mut parser_options = markdown_options();
+    if options.commonmark {
+        parser_options.remove(gfm_options());
+    }
+    if options.math {
+        // Code spans and blocks are parsed first, so dollars inside them stay literal
+        parser_options.insert(Options::ENABLE_MATH);
+    }
+    mark_math(mark_wiki_links(Parser::new_ext(content, parser_options)))
+}
+
+/// Headings of a markdown note in document order, with the same text and
+/// slugs that `render_note` gives heading anchors. Headings in code blocks are
+/// not headings to the parser, so they are left out.
+pub fn note_toc(filename: &str, content: &str, options: &RenderOptions) -> Vec<TocEntry> {
+    if !is_markdown_note(filename) {
+        return Vec::new();
+    }
+
+    let mut slugs = HeadingSlugs::default();
+    let mut entries = Vec::new();
+    // The open heading's level, explicit id and plain text
+    let mut heading: Option<(HeadingLevel, Option<String>, String)> = None;
+
+    for event in note_events(content, options) {
+        match (event, heading.as_mut()) {
+            (Event::Start(Tag::Heading { level, id, .. }), None) => {
+                heading = Some((level, id.map(|id| id.to_string()), String::new()));
+            }
+            (Event::End(TagEnd::Heading(_)), Some(_)) => {
+                let Some((level, id, text)) = heading.take() else {
+                    continue;
+                };
+                entries.push(TocEntry {
+                    level: heading_level_number(level),
+                    slug: slugs.next(id.as_deref(), &text),
+                    text: text.trim().to_string(),
+                });
+            }
+            (Event::Text(content) | Event::Code(content), Some((_, _, text))) => {
+                text.push_str(&content);
+            }
+            _ => {}
+        }
+    }
+
+    entries
+}
+
+fn heading_anchor_html(slug: &str) -> String {
+    // Left empty so heading text (used for section navigation) stays unchanged;
+    // the visible marker comes from the stylesheet
+    format!(
+        r##"<a class="heading-anchor" href="#{}"></a>"##,
+        html_escape::encode_double_quoted_attribute(slug)
+    )
+}
+
+fn add_heading_anchors<'a>(events: impl Iterator<Item = Event<'a>>) -> Vec<Event<'a>> {
+    let mut output = Vec::new();
+    let mut slugs = HeadingSlugs::default();
+    // The open heading tag, its inner events and its plain text
+    let mut heading: Option<(Tag<'a>, Vec<Event<'a>>, String)> = None;
+
+    for event in events {
+        match (event, heading.as_mut()) {
+            (Event::Start(tag @ Tag::Heading { .. }), None) => {
+                heading = Some((tag, Vec::new(), String::new()));
+            }
+            (Event::End(TagEnd::Heading(level)), Some(_)) => {
+                let Some((tag, inner, text)) = heading.take() else {
+                    continue;
+                };
+                let Tag::Heading {
+                    id, classes, attrs, ..
+                } = tag
+                else {
+                    continue;
+                };
+
+                let slug = slugs.next(id.as_deref(), &text);
+
+                output.push(Event::Start(Tag::Heading {
+                    level,
+                    id: slug.clone().map(CowStr::from),
+                    classes,
+                    attrs,
+                }));
+                if let Some(slug) = slug {
+                    output.push(Event::InlineHtml(heading_anchor_html(&slug).into()));
+                }
+                output.extend(inner);
+                output.push(Event::End(TagEnd::Heading(level)));
+            }
+            (event, Some((_, inner, text))) => {
+                if let Event::Text(content) | Event::Code(content) = &event {
+                    text.push_str(content);
+                }
+                inner.push(event);
+            }
+            (event, None) => output.push(event),
+        }
+    }
+
+    output
+}
+
+// Autolinks are not a parser option; bare URLs are linked after sanitizing
+fn gfm_options() -> Options {
+    Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_TASKLISTS
+}
+
+// Smart punctuation and wiki links are rendered under every flavor
+fn markdown_options() -> Options {
+    let mut options = gfm_options();
+    options.insert(Options::ENABLE_SMART_PUNCTUATION);
+    options.insert(Options::ENABLE_WIKILINKS);
+    options
+}
+
+/// Targets of every `[[wiki link]]` in a markdown note, in document order.
+pub fn wiki_link_targets(content: &str) -> Vec<String> {
+    Parser::new_ext(content, markdown_options())
+        .filter_map(|event| match event {
+            Event::Start(Tag::Link {
+                link_type: LinkType::WikiLink { .. },
+                dest_url,
+                ..
+            }) => Some(dest_url.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Every markdown and `[[wiki]]` link in a markdown note, in document order,
+/// resolved against the existing notes. Image sources are not links.
+pub fn extract_note_links(content: &str, index: &NoteLinkIndex) -> Vec<NoteLink> {
+    let mut links = Vec::new();
+    let mut current: Option<(LinkKind, String, String)> = None;
+
+    for event in Parser::new_ext(content, markdown_options()) {
+        match event {
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                ..
+            }) => {
+                let (kind, target) = match link_type {
+                    LinkType::WikiLink { .. } => (LinkKind::Wiki, dest_url.to_string()),
+                    // `<name@example.com>` arrives without the scheme the rendered href gets
+                    LinkType::Email => (LinkKind::Markdown, format!("mailto:{}", dest_url)),
+                    _ => (LinkKind::Markdown, dest_url.to_string()),
+                };
+                current = Some((kind, target, String::new()));
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, _, link_text)) = current.as_mut() {
+                    link_text.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Link) => {
+                if let Some((kind, target, text)) = current.take() {
+                    links.push(NoteLink::new(kind, text, target, index));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    links
+}
+
+/// Rewrites the `[[wiki]]` and inline markdown links of a markdown note that
+/// point at a renamed note. Reference-style links and images are left alone.
+/// Returns `None` when no link needed rewriting.
+pub fn relink_note_content(content: &str, rename: &LinkRename) -> Option<String> {
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+    // Link type, target, source range, and where the link text ends
+    let mut current: Option<(LinkType, String, Range<usize>, usize)> = None;
+
+    for (event, range) in Parser::new_ext(content, markdown_options()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                ..
+            }) => {
+                let text_end = range.start + 1;
+                current = Some((link_type, dest_url.to_string(), range, text_end));
+            }
+            Event::End(TagEnd::Link) => {
+                let Some((link_type, target, link_range, text_end)) = current.take() else {
+                    continue;
+                };
+                let edit = match link_type {
+                    LinkType::WikiLink { has_pothole } => {
+                        rename.wiki_target(&target).map(|new_target| {
+                            let source = &content[link_range.clone()];
+                            let label = has_pothole
+                                .then(|| source[2..source.len() - 2].split_once('|'))
+                                .flatten();
+                            let replacement = match label {
+                                Some((_, label)) => format!("[[{}|{}]]", new_target, label),
+                                None => format!("[[{}]]", new_target),
+                            };
+                            (link_range.clone(), replacement)
+                        })
+                    }
+                    LinkType::Inline => rename.markdown_target(&target).and_then(|new_target| {
+                        inline_destination_range(content, text_end, &link_range)
+                            .map(|range| (range, new_target))
+                    }),
+                    _ => None,
+                };
+                edits.extend(edit);
+            }
+            _ => {
+                if let Some((_, _, _, text_end)) = current.as_mut() {
+                    *text_end = (*text_end).max(range.end);
+                }
+            }
+        }
+    }
+
+    if edits.is_empty() {
+        return None;
+    }
+
+    let mut relinked = String::with_capacity(content.len());
+    let mut copied_to = 0;
+    for (range, replacement) in edits {
+        relinked.push_str(&content[copied_to..range.start]);
+        relinked.push_str(&replacement);
+        copied_to = range.end;
+    }
+    relinked.push_str(&content[copied_to..]);
+    Some(relinked)
+}
+
+// Source range of the destination in `[text](destination "title")`, without
+// any angle brackets around it
+fn inline_destination_range(
+    content: &str,
+    text_end: usize,
+    link_range: &Range<usize>,
+) -> Option<Range<usize>> {
+    let rest = content.get(text_end..link_range.end)?.strip_prefix("](")?;
+    let offset = text_end + 2 + (rest.len() - rest.trim_start().len());
+    let rest = rest.trim_start();
+
+    if let Some(bracketed) = rest.strip_prefix('<') {
+        let end = bracketed.find('>')?;
+        return Some(offset + 1..offset + 1 + end);
+    }
+    // Without a title the destination runs up to the closing parenthesis
+    let end = rest
+        .find(char::is_whitespace)
+        .unwrap_or(rest.len().saturating_sub(1));
+    Some(offset..offset + end)
+}
+
+// Wiki links are written without an href; they only become navigable once
+// resolve_wiki_links has matched them against the current notes
+fn mark_wiki_links<'a>(events: impl Iterator<Item = Event<'a>>) -> impl Iterator<Item = Event<'a>> {
+    let mut in_wiki_link = false;
+    events.map(move |event| match event {
+        Event::Start(Tag::Link {
+            link_type: LinkType::WikiLink { .. },
+            dest_url,
+            ..
+        }) => {
+            in_wiki_link = true;
+            Event::InlineHtml(
+                format!(
+                    r#"<a class="wiki-link" data-target="{}">"#,
+                    html_escape::encode_double_quoted_attribute(&dest_url)
+                )
+                .into(),
+            )
+        }
+        Event::End(TagEnd::Link) if in_wiki_link => {
+            in_wiki_link = false;
+            Event::InlineHtml("</a>".into())
+        }
+        event => event,
+    })
+}
+
+// Math is written back with the \( \) and \[ \] delimiters KaTeX and MathJax
+// look for by default, inside spans marking which kind it is
+fn mark_math<'a>(events: impl Iterator<Item = Event<'a>>) -> impl Iterator<Item = Event<'a>> {
+    events.map(|event| match event {
+        Event::InlineMath(math) => Event::InlineHtml(
+            format!(
+                r#"<span class="math math-inline">\({}\)</span>"#,
+                html_escape::encode_text(&math)
+            )
+            .into(),
+        ),
+        Event::DisplayMath(math) => Event::InlineHtml(
+            format!(
+                r#"<span class="math math-display">\[{}\]</span>"#,
+                html_escape::encode_text(&math)
+            )
+            .into(),
+        ),
+        event => event,
+    })
+}
+
+/// Points rendered wiki links at the notes they name. Links without a matching
+/// note get the `wiki-link-broken` class instead. Runs on every read so cached
+/// renders pick up notes created or removed since they were rendered.
+pub fn resolve_wiki_links(html: &str, index: &NoteLinkIndex) -> AppResult<String> {
+    let wiki_link_regex = WIKI_LINK_REGEX
+        .as_ref()
+        .map_err(|e| AppError::SearchQuery(format!("Failed to compile wiki link regex: {}", e)))?;
+
+    let result = wiki_link_regex.replace_all(html, |caps: &regex::Captures| {
+        let target_attr = &caps[1];
+        let target = html_escape::decode_html_entities(target_attr);
+        match index.resolve(&target) {
+            Some(filename) => format!(
+                r##"<a class="wiki-link" href="#" data-note="{}" data-target="{}""##,
+                html_escape::encode_double_quoted_attribute(filename),
+                target_attr
+            ),
+            None => format!(
+                r#"<a class="wiki-link wiki-link-broken" data-target="{}""#,
+                target_attr
+            ),
+        }
+    });
+
+    Ok(result.to_string())
+}
+
 fn sanitize_html(html: &str) -> String {
     let allowed_tags: HashSet<&str> = [
         "a",
@@ -141,6 +598,8 @@ fn sanitize_html(html: &str) -> String {
         "input",
         ["type", "checked", "disabled"].into_iter().collect(),
     );
+    // Replacing the defaults drops `href` too, which links and heading anchors need
+    tag_attributes.insert("a", ["href", "title", "data-target"].into_iter().collect());
     tag_attributes.insert("td", ["align"].into_iter().collect());
     tag_attributes.insert("th", ["align"].into_iter().collect());
     tag_attributes.insert("img", ["src", "alt", "title"].into_iter().collect());
@@ -154,26 +613,41 @@ fn sanitize_html(html: &str) -> String {
         .to_string()
 }
 
-pub fn render_note(filename: &str, content: &str) -> String {
-    if filename.ends_with(".md") || filename.ends_with(".markdown") {
-        let mut options = Options::empty();
-        options.insert(Options::ENABLE_STRIKETHROUGH);
-        options.insert(Options::ENABLE_TABLES);
-        options.insert(Options::ENABLE_FOOTNOTES);
-        options.insert(Options::ENABLE_TASKLISTS);
-        options.insert(Options::ENABLE_SMART_PUNCTUATION);
+pub fn is_markdown_note(filename: &str) -> bool {
+    filename.ends_with(".md") || filename.ends_with(".markdown")
+}
+
+// Shown in place of an encrypted note, whose content never reaches the renderer
+pub const LOCKED_NOTE_HTML: &str =
+    "<p class=\"locked-note\">This note is encrypted. Unlock it to view its contents.</p>";
 
-        let parser = Parser::new_ext(content, options);
+pub fn render_note(filename: &str, content: &str, options: &RenderOptions) -> String {
+    if is_encrypted_note(content) {
+        return LOCKED_NOTE_HTML.to_string();
+    }
+    if is_markdown_note(filename) {
+        let events = note_events(content, options);
         let mut html_output = String::new();
-        html::push_html(&mut html_output, parser);
+        if options.heading_anchors {
+            html::push_html(&mut html_output, add_heading_anchors(events).into_iter());
+        } else {
+            html::push_html(&mut html_output, events);
+        }
 
-        let html_output = sanitize_html(&html_output);
+        let mut html_output = sanitize_html(&html_output);
+        if let Some(code_theme) = &options.code_theme {
+            // After sanitizing, which would strip the inline styles
+            html_output = highlight_code_blocks(&html_output, code_theme);
+        }
+        if options.commonmark {
+            return html_output;
+        }
 
         match linkify_urls_in_html(&html_output) {
             Ok(result) => result,
             Err(e) => {
-                crate::logging::log(
-                    "WARN",
+                crate::logging::log_warn(
+                    "NOTE_RENDERING",
                     &format!("URL linkification failed: {}", e),
                     Some("render_note"),
                 );
@@ -185,8 +659,8 @@ pub fn render_note(filename: &str, content: &str) -> String {
         match linkify_urls_in_html(&escaped) {
             Ok(linkified) => format!("<pre>{}</pre>", linkified),
             Err(e) => {
-                crate::logging::log(
-                    "WARN",
+                crate::logging::log_warn(
+                    "NOTE_RENDERING",
                     &format!("URL linkification failed: {}", e),
                     Some("render_note"),
                 );
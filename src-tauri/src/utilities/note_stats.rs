@@ -0,0 +1,84 @@
+use crate::utilities::frontmatter::parse_frontmatter;
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use serde::Serialize;
+
+const READING_WORDS_PER_MINUTE: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NoteStats {
+    pub word_count: usize,
+    pub character_count: usize,
+    pub character_count_no_whitespace: usize,
+    pub reading_time_minutes: usize,
+}
+
+/// Words written towards the `goal:` set in a note's frontmatter.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WritingProgress {
+    pub words: usize,
+    pub goal: Option<usize>,
+    /// Can exceed 100 once the goal is passed. `None` without a goal.
+    pub percentage: Option<f64>,
+}
+
+/// Counts the rendered text of a note, ignoring markdown syntax and frontmatter.
+/// Code blocks count towards words and characters but not reading time.
+pub fn compute_note_stats(content: &str) -> NoteStats {
+    let mut prose = String::new();
+    let mut code = String::new();
+    let mut in_code_block = false;
+    let mut in_metadata = false;
+
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS;
+
+    for event in Parser::new_ext(content, options) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => {
+                in_code_block = false;
+                code.push('\n');
+            }
+            Event::Start(Tag::MetadataBlock(_)) => in_metadata = true,
+            Event::End(TagEnd::MetadataBlock(_)) => in_metadata = false,
+            Event::Text(_) if in_metadata => {}
+            Event::Text(text) if in_code_block => code.push_str(&text),
+            Event::Text(text) | Event::Code(text) => prose.push_str(&text),
+            Event::SoftBreak | Event::HardBreak => prose.push('\n'),
+            Event::End(TagEnd::Paragraph)
+            | Event::End(TagEnd::Heading(_))
+            | Event::End(TagEnd::Item)
+            | Event::End(TagEnd::TableCell) => prose.push('\n'),
+            _ => {}
+        }
+    }
+
+    let prose = prose.trim_end();
+    let code = code.trim_end();
+    let prose_words = prose.split_whitespace().count();
+
+    NoteStats {
+        word_count: prose_words + code.split_whitespace().count(),
+        character_count: prose.chars().count() + code.chars().count(),
+        character_count_no_whitespace: prose
+            .chars()
+            .chain(code.chars())
+            .filter(|c| !c.is_whitespace())
+            .count(),
+        reading_time_minutes: prose_words.div_ceil(READING_WORDS_PER_MINUTE),
+    }
+}
+
+/// Progress of a note towards its word goal, counting words as `compute_note_stats` does.
+pub fn compute_writing_progress(filename: &str, content: &str) -> WritingProgress {
+    let words = compute_note_stats(content).word_count;
+    let goal = parse_frontmatter(filename, content).goal;
+
+    WritingProgress {
+        words,
+        goal,
+        percentage: goal.map(|goal| words as f64 * 100.0 / goal as f64),
+    }
+}
@@ -96,7 +96,67 @@ fn get_config_dir() -> Option<PathBuf> {
     None
 }
 
+/// Points Symiosis at a config file other than the platform default.
+pub const CONFIG_PATH_ENV: &str = "SYMIOSIS_CONFIG_PATH";
+/// Overrides `notes_directory` from the config file when set.
+pub const NOTES_DIR_ENV: &str = "SYMIOSIS_NOTES_DIR";
+
+/// Accepts an absolute path that is not an existing directory. The file itself
+/// may be missing, in which case a default config is written there.
+pub fn parse_config_path_override(value: &str) -> Option<PathBuf> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    let path = PathBuf::from(value);
+    if !path.is_absolute() || path.is_dir() {
+        return None;
+    }
+    Some(path)
+}
+
+/// Resolves the config file in order of precedence: the test override (test
+/// builds only), `SYMIOSIS_CONFIG_PATH`, the `symiosis-dev` config (debug
+/// builds only), then the platform default.
 pub fn get_config_path() -> PathBuf {
+    let env_override = std::env::var(CONFIG_PATH_ENV).ok();
+    if let Some(value) = &env_override {
+        if parse_config_path_override(value).is_none() {
+            static INVALID_OVERRIDE_LOGGED: std::sync::Once = std::sync::Once::new();
+            INVALID_OVERRIDE_LOGGED.call_once(|| {
+                crate::logging::log(
+                    "CONFIG_PATH",
+                    &format!(
+                        "Ignoring {}, it must be an absolute path to a file",
+                        CONFIG_PATH_ENV
+                    ),
+                    Some(value),
+                );
+            });
+        }
+    }
+
+    resolve_config_path(
+        test_config_path_override(),
+        env_override.as_deref(),
+        default_config_path,
+    )
+}
+
+/// The precedence behind `get_config_path`, without reading the environment:
+/// the test override, then a valid `SYMIOSIS_CONFIG_PATH` value, then `default`.
+pub fn resolve_config_path(
+    test_override: Option<PathBuf>,
+    env_override: Option<&str>,
+    default: impl FnOnce() -> PathBuf,
+) -> PathBuf {
+    test_override
+        .or_else(|| env_override.and_then(parse_config_path_override))
+        .unwrap_or_else(default)
+}
+
+fn test_config_path_override() -> Option<PathBuf> {
     #[cfg(test)]
     {
         if std::env::var("SYMIOSIS_TEST_MODE_ENABLED").is_ok() {
@@ -105,9 +165,9 @@ pub fn get_config_path() -> PathBuf {
                     || test_config_path.contains("tmp")
                     || test_config_path.contains("/T/")
                 {
-                    return PathBuf::from(test_config_path);
+                    return Some(PathBuf::from(test_config_path));
                 } else {
-                    crate::logging::log(
+                    crate::logging::log_error(
                         "PATH_SAFETY",
                         &format!(
                             "SAFETY ERROR: Test config path '{}' is not in temp directory!",
@@ -120,6 +180,10 @@ pub fn get_config_path() -> PathBuf {
         }
     }
 
+    None
+}
+
+fn default_config_path() -> PathBuf {
     #[cfg(debug_assertions)]
     {
         if let Some(config_dir) = get_config_dir() {
@@ -141,8 +205,62 @@ pub fn get_config_path() -> PathBuf {
     }
 }
 
+pub fn get_editor_themes_dir() -> PathBuf {
+    get_config_path()
+        .parent()
+        .map(|dir| dir.to_path_buf())
+        .unwrap_or_default()
+        .join("themes")
+        .join("editor")
+}
+
+pub fn get_window_state_path() -> PathBuf {
+    get_config_path()
+        .parent()
+        .map(|dir| dir.to_path_buf())
+        .unwrap_or_default()
+        .join("window_state.json")
+}
+
+pub fn get_markdown_themes_dir() -> PathBuf {
+    get_config_path()
+        .parent()
+        .map(|dir| dir.to_path_buf())
+        .unwrap_or_default()
+        .join("themes")
+        .join("markdown")
+}
+
+pub fn get_dictionaries_dir() -> PathBuf {
+    get_config_path()
+        .parent()
+        .map(|dir| dir.to_path_buf())
+        .unwrap_or_default()
+        .join("dictionaries")
+}
+
+pub fn get_templates_dir() -> PathBuf {
+    get_config_path()
+        .parent()
+        .map(|dir| dir.to_path_buf())
+        .unwrap_or_default()
+        .join("templates")
+}
+
+/// The configured `cache_database_path`, or a database per notes directory
+/// under the app data directory.
 pub fn get_database_path() -> AppResult<PathBuf> {
-    let notes_dir = crate::config::get_config_notes_dir();
+    let config = crate::config::load_config();
+    if let Some(path) = config
+        .preferences
+        .cache_database_path
+        .as_deref()
+        .filter(|path| !path.trim().is_empty())
+    {
+        return Ok(PathBuf::from(path));
+    }
+
+    let notes_dir = crate::config::get_config_notes_dir_from_config(&config);
     get_database_path_for_notes_dir(&notes_dir)
 }
 
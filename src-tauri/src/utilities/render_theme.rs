@@ -0,0 +1,68 @@
+use crate::logging::{log, log_warn};
+use crate::utilities::config_helpers::{get_available_markdown_themes, get_user_markdown_themes};
+use crate::utilities::export::markdown_theme_css;
+use crate::utilities::frontmatter::parse_frontmatter;
+use crate::utilities::paths::get_markdown_themes_dir;
+use serde::Serialize;
+use std::fs;
+
+/// A markdown theme chosen by a note's frontmatter.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RenderTheme {
+    pub name: String,
+    /// Stylesheet of a user theme. Built-in themes are `None` since the
+    /// frontend already ships them.
+    pub css: Option<String>,
+}
+
+impl RenderTheme {
+    /// The stylesheet to inline when the note leaves the app, e.g. on export.
+    pub fn stylesheet(&self) -> String {
+        match &self.css {
+            Some(css) => css.clone(),
+            None => markdown_theme_css(&self.name)
+                .unwrap_or_default()
+                .to_string(),
+        }
+    }
+}
+
+/// The `render_theme` a note's frontmatter asks for, when it names a built-in
+/// or user markdown theme. Unknown names are logged and the note falls back to
+/// `markdown_render_theme`.
+pub fn note_render_theme(filename: &str, content: &str) -> Option<RenderTheme> {
+    let name = parse_frontmatter(filename, content).render_theme?;
+
+    if get_available_markdown_themes().contains(&name.as_str()) {
+        return Some(RenderTheme { name, css: None });
+    }
+    if get_user_markdown_themes().contains(&name) {
+        let path = get_markdown_themes_dir().join(format!("{}.css", name));
+        match fs::read_to_string(&path) {
+            Ok(css) => {
+                return Some(RenderTheme {
+                    name,
+                    css: Some(css),
+                })
+            }
+            Err(e) => {
+                log_warn(
+                    "RENDER_THEME",
+                    &format!("Failed to read markdown theme '{}'", path.display()),
+                    Some(&e.to_string()),
+                );
+                return None;
+            }
+        }
+    }
+
+    log(
+        "RENDER_THEME",
+        &format!(
+            "Unknown render_theme '{}' in '{}', using markdown_render_theme",
+            name, filename
+        ),
+        None,
+    );
+    None
+}
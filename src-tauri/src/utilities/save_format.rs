@@ -0,0 +1,110 @@
+use crate::config::AppConfig;
+use crate::utilities::strings::normalize_line_endings;
+use std::borrow::Cow;
+
+/// How note content is tidied before it is saved, from the preferences.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveFormat {
+    pub line_ending: String,
+    pub trim_trailing_whitespace: bool,
+    pub ensure_final_newline: bool,
+}
+
+impl SaveFormat {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            line_ending: config.preferences.line_ending.clone(),
+            trim_trailing_whitespace: config.preferences.trim_trailing_whitespace,
+            ensure_final_newline: config.preferences.ensure_final_newline,
+        }
+    }
+
+    /// `content` as it should be written. Applying it twice changes nothing more.
+    pub fn apply<'a>(&self, content: &'a str) -> Cow<'a, str> {
+        let mut tidied = Cow::Borrowed(content);
+        if self.trim_trailing_whitespace {
+            if let Some(trimmed) = trim_trailing_whitespace(&tidied) {
+                tidied = Cow::Owned(trimmed);
+            }
+        }
+        if self.ensure_final_newline {
+            if let Some(ended) = ensure_final_newline(&tidied) {
+                tidied = Cow::Owned(ended);
+            }
+        }
+        if let Cow::Owned(normalized) = normalize_line_endings(&tidied, &self.line_ending) {
+            return Cow::Owned(normalized);
+        }
+        tidied
+    }
+}
+
+/// Strips spaces and tabs from the end of each line, or `None` if there were
+/// none. Lines inside fenced code blocks are left alone, since whitespace can
+/// matter there, and a markdown hard line break of two or more trailing spaces
+/// is kept as exactly two.
+pub fn trim_trailing_whitespace(content: &str) -> Option<String> {
+    let mut trimmed = String::with_capacity(content.len());
+    let mut fence: Option<&str> = None;
+
+    for line in content.split_inclusive('\n') {
+        let (body, ending) = split_line_ending(line);
+        let marker = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| body.trim_start().starts_with(marker));
+
+        let in_code = match (fence, marker) {
+            (Some(open), Some(close)) if open == close => {
+                fence = None;
+                false
+            }
+            (Some(_), _) => true,
+            (None, Some(open)) => {
+                fence = Some(open);
+                false
+            }
+            (None, None) => false,
+        };
+
+        if in_code {
+            trimmed.push_str(body);
+        } else {
+            let text = body.trim_end_matches([' ', '\t']);
+            trimmed.push_str(text);
+            let hard_break = marker.is_none() && !text.trim_start().is_empty();
+            if hard_break && body[text.len()..].starts_with("  ") {
+                trimmed.push_str("  ");
+            }
+        }
+        trimmed.push_str(ending);
+    }
+
+    (trimmed != content).then_some(trimmed)
+}
+
+/// Ends non-empty `content` with exactly one line break, or `None` if it
+/// already does. A CRLF note gets CRLF.
+pub fn ensure_final_newline(content: &str) -> Option<String> {
+    if content.is_empty() {
+        return None;
+    }
+
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let body = content.trim_end_matches(['\r', '\n']);
+    let ended = format!("{}{}", body, newline);
+    (ended != content).then_some(ended)
+}
+
+fn split_line_ending(line: &str) -> (&str, &str) {
+    if let Some(body) = line.strip_suffix("\r\n") {
+        (body, "\r\n")
+    } else if let Some(body) = line.strip_suffix('\n') {
+        (body, "\n")
+    } else {
+        (line, "")
+    }
+}
@@ -0,0 +1,269 @@
+use crate::core::{AppError, AppResult};
+use crate::utilities::paths::get_dictionaries_dir;
+use serde::Serialize;
+use spellbook::Dictionary;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Language of the dictionary compiled into the app, available without any files.
+pub const BUNDLED_DICTIONARY: &str = "en_US";
+const BUNDLED_AFF: &str = include_str!("../../dictionaries/en_US/en_US.aff");
+const BUNDLED_DIC: &str = include_str!("../../dictionaries/en_US/en_US.dic");
+
+/// Most suggestions returned for one misspelled word.
+pub const MAX_SPELL_SUGGESTIONS: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpellIssue {
+    pub word: String,
+    /// Start of the word in UTF-16 code units, matching JavaScript string indices.
+    pub start: usize,
+    /// End of the word (exclusive) in UTF-16 code units.
+    pub end: usize,
+    pub suggestions: Vec<String>,
+}
+
+/// Where dictionaries are looked up: the configured directory, or `dictionaries`
+/// next to the config file.
+pub fn dictionary_dir(configured: Option<&str>) -> PathBuf {
+    match configured {
+        Some(dir) if !dir.trim().is_empty() => PathBuf::from(dir),
+        _ => get_dictionaries_dir(),
+    }
+}
+
+/// The bundled dictionary plus every `<lang>.aff` in `dir` that has a matching `<lang>.dic`.
+pub fn get_available_dictionaries(dir: &Path) -> Vec<String> {
+    let mut languages = vec![BUNDLED_DICTIONARY.to_string()];
+    let Ok(entries) = fs::read_dir(dir) else {
+        return languages;
+    };
+
+    let mut found: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "aff") && path.with_extension("dic").is_file()
+        })
+        .filter_map(|path| {
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .map(str::to_string)
+        })
+        .filter(|lang| lang != BUNDLED_DICTIONARY)
+        .collect();
+    found.sort();
+    languages.extend(found);
+    languages
+}
+
+// A dictionary in `dir` takes precedence, so the bundled en_US can be replaced
+fn load_dictionary(lang: &str, dir: &Path) -> AppResult<Dictionary> {
+    let aff_path = dir.join(format!("{}.aff", lang));
+    let dic_path = dir.join(format!("{}.dic", lang));
+
+    let parsed = if aff_path.is_file() && dic_path.is_file() {
+        let aff = fs::read_to_string(&aff_path)?;
+        let dic = fs::read_to_string(&dic_path)?;
+        Dictionary::new(&aff, &dic)
+    } else if lang == BUNDLED_DICTIONARY {
+        Dictionary::new(BUNDLED_AFF, BUNDLED_DIC)
+    } else {
+        return Err(AppError::FileNotFound(format!(
+            "No dictionary for '{}'. Available: {}",
+            lang,
+            get_available_dictionaries(dir).join(", ")
+        )));
+    };
+
+    parsed.map_err(|e| AppError::FileRead(format!("Invalid dictionary '{}': {}", lang, e)))
+}
+
+struct LoadedDictionary {
+    lang: String,
+    dir: PathBuf,
+    dictionary: Dictionary,
+}
+
+/// Keeps the most recently used dictionary, since parsing one takes far longer
+/// than checking a note with it.
+#[derive(Default)]
+pub struct SpellChecker {
+    loaded: Option<LoadedDictionary>,
+}
+
+impl SpellChecker {
+    /// Flags words in `text` that the `lang` dictionary does not know, skipping
+    /// fenced code blocks, inline code and URLs.
+    pub fn check(&mut self, text: &str, lang: &str, dir: &Path) -> AppResult<Vec<SpellIssue>> {
+        let dictionary = self.dictionary(lang, dir)?;
+
+        let mut issues = Vec::new();
+        let mut utf16_cursor = (0, 0);
+        for (start, word) in prose_words(text) {
+            if dictionary.check(word) {
+                continue;
+            }
+
+            let mut suggestions = Vec::new();
+            dictionary.suggest(word, &mut suggestions);
+            suggestions.truncate(MAX_SPELL_SUGGESTIONS);
+
+            let (byte, units) = utf16_cursor;
+            let start_units = units + text[byte..start].encode_utf16().count();
+            let end_units = start_units + word.encode_utf16().count();
+            utf16_cursor = (start + word.len(), end_units);
+
+            issues.push(SpellIssue {
+                word: word.to_string(),
+                start: start_units,
+                end: end_units,
+                suggestions,
+            });
+        }
+        Ok(issues)
+    }
+
+    fn dictionary(&mut self, lang: &str, dir: &Path) -> AppResult<&Dictionary> {
+        let cached = self
+            .loaded
+            .as_ref()
+            .is_some_and(|loaded| loaded.lang == lang && loaded.dir == dir);
+        if !cached {
+            self.loaded = None;
+        }
+
+        let loaded = match self.loaded.take() {
+            Some(loaded) => loaded,
+            None => LoadedDictionary {
+                lang: lang.to_string(),
+                dir: dir.to_path_buf(),
+                dictionary: load_dictionary(lang, dir)?,
+            },
+        };
+        Ok(&self.loaded.insert(loaded).dictionary)
+    }
+}
+
+/// Words outside code, with their byte offsets in `text`.
+fn prose_words(text: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut line_start = 0;
+
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let marker = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker));
+
+        match (fence, marker) {
+            (Some(open), Some(close)) if open == close => fence = None,
+            (Some(_), _) => {}
+            (None, Some(open)) => fence = Some(open),
+            (None, None) => {
+                for (start, segment) in outside_inline_code(line) {
+                    words.extend(
+                        segment_words(segment)
+                            .into_iter()
+                            .map(|(offset, word)| (line_start + start + offset, word)),
+                    );
+                }
+            }
+        }
+        line_start += line.len();
+    }
+    words
+}
+
+// A run of backticks opens a code span that the next run of the same length
+// closes; an unmatched run is left as text, as in CommonMark
+fn outside_inline_code(line: &str) -> Vec<(usize, &str)> {
+    let mut segments = Vec::new();
+    let mut segment_start = 0;
+    let mut pos = 0;
+
+    while let Some(found) = line[pos..].find('`') {
+        let open = pos + found;
+        let run = line[open..].bytes().take_while(|&b| b == b'`').count();
+        let closing = find_backtick_run(line, open + run, run);
+
+        match closing {
+            Some(close) => {
+                segments.push((segment_start, &line[segment_start..open]));
+                segment_start = close + run;
+                pos = segment_start;
+            }
+            None => pos = open + run,
+        }
+    }
+    segments.push((segment_start, &line[segment_start..]));
+    segments
+}
+
+fn find_backtick_run(line: &str, from: usize, run: usize) -> Option<usize> {
+    let mut pos = from;
+    while let Some(found) = line[pos..].find('`') {
+        let start = pos + found;
+        let len = line[start..].bytes().take_while(|&b| b == b'`').count();
+        if len == run {
+            return Some(start);
+        }
+        pos = start + len;
+    }
+    None
+}
+
+fn segment_words(segment: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut token_start = None;
+
+    for (i, c) in segment.char_indices().chain([(segment.len(), ' ')]) {
+        match (c.is_whitespace(), token_start) {
+            (false, None) => token_start = Some(i),
+            (true, Some(start)) => {
+                token_words(&segment[start..i], start, &mut words);
+                token_start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+// Splits a whitespace-separated token into letter runs, joined by apostrophes
+// inside a word as in "don't". URLs, emails and runs touching digits are skipped.
+fn token_words<'a>(token: &'a str, offset: usize, words: &mut Vec<(usize, &'a str)>) {
+    if token.contains("://") || token.starts_with("www.") || token.contains('@') {
+        return;
+    }
+
+    let chars: Vec<(usize, char)> = token.char_indices().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        if !chars[i].1.is_alphabetic() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < chars.len()
+            && (chars[i].1.is_alphabetic()
+                || (matches!(chars[i].1, '\'' | '’')
+                    && chars.get(i + 1).is_some_and(|(_, c)| c.is_alphabetic())))
+        {
+            i += 1;
+        }
+
+        let touches = |index: Option<&(usize, char)>| {
+            index.is_some_and(|(_, c)| c.is_ascii_digit() || *c == '_')
+        };
+        if touches(start.checked_sub(1).and_then(|s| chars.get(s))) || touches(chars.get(i)) {
+            continue;
+        }
+
+        let begin = chars[start].0;
+        let end = chars.get(i).map_or(token.len(), |(byte, _)| *byte);
+        words.push((offset + begin, &token[begin..end]));
+    }
+}
@@ -1,5 +1,7 @@
-use chrono::Utc;
+use chrono::{DateTime, Local, Utc};
+use std::borrow::Cow;
 use std::time::{SystemTime, UNIX_EPOCH};
+use unicode_normalization::UnicodeNormalization;
 
 pub fn extract_title_from_filename(filename: &str) -> String {
     filename
@@ -18,6 +20,31 @@ pub fn extract_title_from_content(content: &str) -> Option<String> {
         .filter(|title| !title.is_empty())
 }
 
+/// Strips accents so "Café" and "Cafe" compare equal. Only marks from the combining
+/// diacritics blocks are dropped and the text is recomposed afterwards, so Hangul
+/// syllables and kana voicing marks come through unchanged.
+pub fn fold_diacritics(text: &str) -> String {
+    if text.is_ascii() {
+        return text.to_string();
+    }
+
+    text.nfkd()
+        .filter(|c| !is_combining_diacritic(*c))
+        .nfc()
+        .collect()
+}
+
+fn is_combining_diacritic(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
 pub fn sanitize_fts_query(query: &str) -> String {
     // First pass: remove dangerous characters and special syntax
     let cleaned_chars: String = query
@@ -47,7 +74,20 @@ pub fn sanitize_fts_query(query: &str) -> String {
     filtered_words.join(" ").trim().to_string()
 }
 
-pub fn format_timestamp_for_humans(timestamp: u64) -> String {
+// Common English words dropped from stemmed queries, as they match nearly every note
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "from", "had", "has", "have",
+    "he", "her", "his", "i", "in", "is", "it", "its", "of", "on", "or", "our", "she", "so", "that",
+    "the", "their", "them", "then", "there", "they", "this", "to", "was", "we", "were", "with",
+    "you",
+];
+
+pub fn is_stop_word(word: &str) -> bool {
+    STOP_WORDS.contains(&word.to_lowercase().as_str())
+}
+
+/// Relative times for the last month, then the date in `date_format`.
+pub fn format_timestamp_for_humans(timestamp: u64, date_format: &str) -> String {
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map(|d| d.as_secs())
@@ -60,14 +100,79 @@ pub fn format_timestamp_for_humans(timestamp: u64) -> String {
         60..=3599 => format!("{}m ago", diff / 60),
         3600..=86399 => format!("{}h ago", diff / 3600),
         86400..=2591999 => format!("{}d ago", diff / 86400),
-        _ => format!("{}w ago", diff / 604800),
+        _ => format_local_date(timestamp, date_format)
+            .unwrap_or_else(|| format!("{}w ago", diff / 604800)),
     }
 }
 
+/// Formats a unix timestamp in local time, or `None` when the timestamp is out
+/// of range or `format` has an unsupported specifier.
+pub fn format_local_date(timestamp: u64, format: &str) -> Option<String> {
+    use std::fmt::Write;
+
+    let date = DateTime::from_timestamp(i64::try_from(timestamp).ok()?, 0)?.with_timezone(&Local);
+    let mut formatted = String::new();
+    write!(formatted, "{}", date.format(format)).ok()?;
+    Some(formatted)
+}
+
 pub fn get_log_timestamp() -> String {
     Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string()
 }
 
+/// Converts every line break in `content` to `line_ending` ("lf" or "crlf").
+/// Any other value, like "preserve", leaves the content unchanged.
+pub fn normalize_line_endings<'a>(content: &'a str, line_ending: &str) -> Cow<'a, str> {
+    let lf = if content.contains('\r') {
+        Cow::Owned(content.replace("\r\n", "\n").replace('\r', "\n"))
+    } else {
+        Cow::Borrowed(content)
+    };
+    match line_ending {
+        "lf" => lf,
+        "crlf" => Cow::Owned(lf.replace('\n', "\r\n")),
+        _ => Cow::Borrowed(content),
+    }
+}
+
+/// Builds the name for the nth copy of a note, keeping its folder and extension:
+/// "ideas/plan.md" becomes "ideas/plan copy.md", then "ideas/plan copy 2.md".
+pub fn duplicate_note_name(note_name: &str, copy_number: usize) -> String {
+    let suffix = if copy_number <= 1 {
+        " copy".to_string()
+    } else {
+        format!(" copy {}", copy_number)
+    };
+    suffixed_note_name(note_name, &suffix)
+}
+
+/// Builds the name for a restored note that collides with an existing one:
+/// "ideas/plan.md" becomes "ideas/plan (restored).md", then "ideas/plan (restored 2).md".
+pub fn restored_note_name(note_name: &str, attempt: usize) -> String {
+    let suffix = if attempt <= 1 {
+        " (restored)".to_string()
+    } else {
+        format!(" (restored {})", attempt)
+    };
+    suffixed_note_name(note_name, &suffix)
+}
+
+fn suffixed_note_name(note_name: &str, suffix: &str) -> String {
+    let (folder, file_name) = match note_name.rsplit_once('/') {
+        Some((folder, file_name)) => (Some(folder), file_name),
+        None => (None, note_name),
+    };
+    let file_name = match file_name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => format!("{}{}.{}", stem, suffix, extension),
+        _ => format!("{}{}", file_name, suffix),
+    };
+
+    match folder {
+        Some(folder) => format!("{}/{}", folder, file_name),
+        None => file_name,
+    }
+}
+
 pub fn parse_backup_filename(filename: &str, base_name: &str) -> Option<(String, u64)> {
     let without_ext = filename.strip_suffix(".md")?;
     let parts: Vec<&str> = without_ext.rsplitn(3, '.').collect();
@@ -0,0 +1,51 @@
+use std::collections::HashSet;
+use std::fs::{self, Metadata};
+use std::path::{Path, PathBuf};
+
+// The limit most Unix kernels apply before reporting a loop
+const MAX_SYMLINK_HOPS: usize = 40;
+
+/// What a symlink in the notes directory ends up pointing at.
+#[derive(Debug)]
+pub enum SymlinkTarget {
+    File(Metadata),
+    Directory,
+    /// The chain of links leads back to a link already followed.
+    Loop,
+    /// The target is missing, unreadable or not a regular file.
+    Broken,
+}
+
+/// Follows `link` one hop at a time, remembering every link visited, so a
+/// cycle is reported as a loop instead of surfacing as an OS error.
+pub fn resolve_symlink(link: &Path) -> SymlinkTarget {
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    let mut current = link.to_path_buf();
+
+    loop {
+        if visited.len() >= MAX_SYMLINK_HOPS || !visited.insert(current.clone()) {
+            return SymlinkTarget::Loop;
+        }
+
+        let Ok(metadata) = fs::symlink_metadata(&current) else {
+            return SymlinkTarget::Broken;
+        };
+        if !metadata.file_type().is_symlink() {
+            return if metadata.is_dir() {
+                SymlinkTarget::Directory
+            } else if metadata.is_file() {
+                SymlinkTarget::File(metadata)
+            } else {
+                SymlinkTarget::Broken
+            };
+        }
+
+        let Ok(target) = fs::read_link(&current) else {
+            return SymlinkTarget::Broken;
+        };
+        current = match current.parent() {
+            Some(parent) if target.is_relative() => parent.join(target),
+            _ => target,
+        };
+    }
+}
@@ -0,0 +1,101 @@
+use crate::config::AppConfig;
+use crate::core::{AppError, AppResult};
+use crate::utilities::config_helpers::{default_date_format, default_time_format};
+use crate::utilities::paths::get_templates_dir;
+use crate::utilities::strings::extract_title_from_filename;
+use chrono::{DateTime, TimeZone};
+use std::fmt::Write;
+use std::fs;
+
+/// Reads `templates/<name>.md` from the config directory.
+pub fn load_template(name: &str) -> AppResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::InvalidNoteName(
+            "Template name cannot be empty".to_string(),
+        ));
+    }
+
+    if name.contains("..") || name.contains('/') || name.contains('\\') {
+        return Err(AppError::PathTraversal);
+    }
+
+    let file_name = if name.ends_with(".md") {
+        name.to_string()
+    } else {
+        format!("{}.md", name)
+    };
+    let template_path = get_templates_dir().join(file_name);
+
+    fs::read_to_string(&template_path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => {
+            AppError::FileNotFound(format!("Template '{}' does not exist", name))
+        }
+        _ => AppError::FileRead(format!("Failed to read template '{}': {}", name, e)),
+    })
+}
+
+/// strftime-style patterns for `{{date}}` and `{{time}}`, from the preferences.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimestampFormats {
+    pub date: String,
+    pub time: String,
+}
+
+impl TimestampFormats {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            date: config.preferences.date_format.clone(),
+            time: config.preferences.time_format.clone(),
+        }
+    }
+}
+
+impl Default for TimestampFormats {
+    fn default() -> Self {
+        Self {
+            date: default_date_format(),
+            time: default_time_format(),
+        }
+    }
+}
+
+/// Replaces `{{date}}`, `{{time}}` and `{{title}}` in a template body.
+///
+/// Unknown placeholders are left as-is so templates can contain literal braces.
+pub fn render_template<Tz: TimeZone>(
+    body: &str,
+    note_name: &str,
+    now: &DateTime<Tz>,
+    formats: &TimestampFormats,
+) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let title = std::path::Path::new(note_name)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(extract_title_from_filename)
+        .unwrap_or_default();
+
+    body.replace(
+        "{{date}}",
+        &format_or_default(now, &formats.date, "%Y-%m-%d"),
+    )
+    .replace("{{time}}", &format_or_default(now, &formats.time, "%H:%M"))
+    .replace("{{title}}", &title)
+}
+
+// Formats are validated when the config loads, but a bad pattern must never
+// panic while creating a note
+fn format_or_default<Tz: TimeZone>(now: &DateTime<Tz>, format: &str, fallback: &str) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let mut formatted = String::new();
+    if write!(formatted, "{}", now.format(format)).is_ok() {
+        formatted
+    } else {
+        now.format(fallback).to_string()
+    }
+}
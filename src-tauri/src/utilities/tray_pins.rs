@@ -0,0 +1,103 @@
+use crate::logging::{log, log_warn};
+use crate::services::pin_service::{list_pinned_notes, on_pins_changed};
+use rusqlite::Connection;
+use std::path::Path;
+use std::sync::OnceLock;
+use tauri::menu::{MenuItem, Submenu};
+use tauri::{AppHandle, Wry};
+
+/// Menu ids of pinned note entries are this prefix followed by the note name.
+pub const PINNED_NOTE_MENU_PREFIX: &str = "pinned-note:";
+
+// Only set once the tray exists, so refreshing is a no-op without one
+static PINNED_SUBMENU: OnceLock<Submenu<Wry>> = OnceLock::new();
+
+/// Builds the tray's "Pinned Notes" submenu and keeps it up to date as pins change.
+pub fn pinned_notes_submenu(app: &AppHandle, pinned: &[String]) -> tauri::Result<Submenu<Wry>> {
+    let submenu = Submenu::with_id(app, "pinned_notes", "Pinned Notes", true)?;
+    fill_submenu(&submenu, pinned)?;
+    if PINNED_SUBMENU.set(submenu.clone()).is_err() {
+        log(
+            "TRAY_SETUP",
+            "Pinned notes submenu was already created",
+            None,
+        );
+    }
+    on_pins_changed(refresh_pinned_notes_menu);
+    Ok(submenu)
+}
+
+// Rebuilds the pinned notes submenu from the database after pins change
+fn refresh_pinned_notes_menu(conn: &Connection) {
+    let Some(submenu) = PINNED_SUBMENU.get() else {
+        return;
+    };
+    let pinned = match list_pinned_notes(conn) {
+        Ok(pinned) => pinned,
+        Err(e) => {
+            log_warn(
+                "TRAY_UPDATE",
+                "Failed to read pinned notes for the tray menu",
+                Some(&e.to_string()),
+            );
+            return;
+        }
+    };
+
+    // Menus live on the main thread. Queue the update rather than wait for it,
+    // since the caller holds the database lock a main thread command may want.
+    let update = move || -> tauri::Result<()> {
+        while submenu.remove_at(0)?.is_some() {}
+        fill_submenu(submenu, &pinned)
+    };
+    let queued = submenu.app_handle().run_on_main_thread(move || {
+        if let Err(e) = update() {
+            log_warn(
+                "TRAY_UPDATE",
+                "Failed to refresh pinned notes menu",
+                Some(&e.to_string()),
+            );
+        }
+    });
+    if let Err(e) = queued {
+        log_warn(
+            "TRAY_UPDATE",
+            "Failed to schedule pinned notes menu refresh",
+            Some(&e.to_string()),
+        );
+    }
+}
+
+/// The note a tray menu id opens, if it is a pinned note entry.
+pub fn pinned_note_for_menu_id(id: &str) -> Option<&str> {
+    id.strip_prefix(PINNED_NOTE_MENU_PREFIX)
+}
+
+fn fill_submenu(submenu: &Submenu<Wry>, pinned: &[String]) -> tauri::Result<()> {
+    let app = submenu.app_handle();
+    if pinned.is_empty() {
+        let empty = MenuItem::with_id(app, "pinned_none", "No pinned notes", false, None::<&str>)?;
+        return submenu.append(&empty);
+    }
+
+    for name in pinned {
+        let item = MenuItem::with_id(
+            app,
+            format!("{}{}", PINNED_NOTE_MENU_PREFIX, name),
+            menu_label(name),
+            true,
+            None::<&str>,
+        )?;
+        submenu.append(&item)?;
+    }
+    Ok(())
+}
+
+// Folders stay visible so same-named notes can be told apart
+fn menu_label(name: &str) -> String {
+    let path = Path::new(name);
+    match path.extension() {
+        Some(_) => path.with_extension("").to_string_lossy().to_string(),
+        None => name.to_string(),
+    }
+}
@@ -1,19 +1,42 @@
 use crate::config::{
+    get_available_cache_journal_modes, get_available_cache_synchronous_modes,
     get_available_code_themes, get_available_editor_modes, get_available_editor_themes,
-    get_available_markdown_themes, get_available_ui_themes, parse_shortcut, AppConfig,
-    EditorConfig, GeneralConfig, InterfaceConfig, PreferencesConfig, ShortcutsConfig,
+    get_available_line_endings, get_available_markdown_flavors, get_available_markdown_themes,
+    get_available_note_sort_orders, get_available_ui_themes, get_available_vim_actions,
+    normalize_shortcut, parse_shortcut, AppConfig, EditorConfig, GeneralConfig, InterfaceConfig,
+    LoggingConfig, PreferencesConfig, ShortcutsConfig, VimConfig,
 };
 use crate::core::{AppError, AppResult};
 use crate::logging::log;
+use crate::utilities::config_helpers::MAX_SEARCH_RESULTS_CEILING;
+use crate::utilities::external_editor::{split_command_template, PATH_PLACEHOLDER};
+use crate::utilities::spellcheck::{dictionary_dir, get_available_dictionaries};
 
 pub fn validate_config(config: &AppConfig) -> AppResult<()> {
     validate_notes_directory(&config.notes_directory)?;
     validate_shortcut_format(&config.global_shortcut)?;
+    validate_new_note_shortcut(config)?;
     validate_general_config(&config.general)?;
     validate_interface_config(&config.interface)?;
     validate_editor_config(&config.editor)?;
     validate_shortcuts_config(&config.shortcuts)?;
     validate_preferences_config(&config.preferences)?;
+    validate_logging_config(&config.logging)?;
+    Ok(())
+}
+
+pub fn validate_new_note_shortcut(config: &AppConfig) -> AppResult<()> {
+    let Some(new_note) = &config.global_shortcut_new_note else {
+        return Ok(());
+    };
+
+    validate_shortcut_format(new_note)?;
+    if normalize_shortcut(new_note) == normalize_shortcut(&config.global_shortcut) {
+        return Err(AppError::ConfigLoad(format!(
+            "global_shortcut_new_note '{}' is already used by global_shortcut",
+            new_note
+        )));
+    }
     Ok(())
 }
 
@@ -68,6 +91,7 @@ pub fn validate_font_size(size: u16, context: &str) -> AppResult<()> {
 pub fn validate_shortcuts_config(shortcuts: &ShortcutsConfig) -> AppResult<()> {
     validate_basic_shortcut_format(&shortcuts.create_note)?;
     validate_basic_shortcut_format(&shortcuts.rename_note)?;
+    validate_basic_shortcut_format(&shortcuts.duplicate_note)?;
     validate_basic_shortcut_format(&shortcuts.delete_note)?;
     validate_basic_shortcut_format(&shortcuts.edit_note)?;
     validate_basic_shortcut_format(&shortcuts.save_and_exit)?;
@@ -88,6 +112,7 @@ pub fn validate_shortcuts_config(shortcuts: &ShortcutsConfig) -> AppResult<()> {
     validate_basic_shortcut_format(&shortcuts.open_settings)?;
     validate_basic_shortcut_format(&shortcuts.version_explorer)?;
     validate_basic_shortcut_format(&shortcuts.recently_deleted)?;
+    validate_basic_shortcut_format(&shortcuts.toggle_always_on_top)?;
 
     Ok(())
 }
@@ -103,7 +128,7 @@ pub fn validate_editor_config(editor: &EditorConfig) -> AppResult<()> {
     }
 
     let valid_themes = get_available_editor_themes();
-    if !valid_themes.contains(&editor.theme.as_str()) {
+    if !valid_themes.contains(&editor.theme) {
         return Err(AppError::ConfigLoad(format!(
             "Invalid editor theme '{}'. Valid themes: {}",
             editor.theme,
@@ -117,6 +142,75 @@ pub fn validate_editor_config(editor: &EditorConfig) -> AppResult<()> {
         ));
     }
 
+    validate_autosave_interval_seconds(editor.autosave_interval_seconds)?;
+    validate_spellcheck_lang(
+        &editor.spellcheck_lang,
+        editor.spellcheck_dictionary_dir.as_deref(),
+    )?;
+    validate_vim_config(&editor.vim)?;
+
+    Ok(())
+}
+
+/// A language names dictionary files, so it is limited to letters, `_` and `-`
+/// and must be one of the dictionaries that are installed.
+pub fn validate_spellcheck_lang(lang: &str, configured_dir: Option<&str>) -> AppResult<()> {
+    if lang.is_empty()
+        || !lang
+            .chars()
+            .all(|c| c.is_ascii_alphabetic() || c == '_' || c == '-')
+    {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid spellcheck language '{}'. Use letters, '_' and '-' only",
+            lang
+        )));
+    }
+
+    let available = get_available_dictionaries(&dictionary_dir(configured_dir));
+    if !available.iter().any(|available| available == lang) {
+        return Err(AppError::ConfigLoad(format!(
+            "No dictionary for spellcheck language '{}'. Available dictionaries: {}",
+            lang,
+            available.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_vim_config(vim: &VimConfig) -> AppResult<()> {
+    validate_vim_leader(&vim.leader)?;
+    for (sequence, action) in &vim.mappings {
+        validate_vim_mapping(sequence, action)?;
+    }
+    Ok(())
+}
+
+pub fn validate_vim_leader(leader: &str) -> AppResult<()> {
+    let mut chars = leader.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if !c.is_control() => Ok(()),
+        _ => Err(AppError::ConfigLoad(format!(
+            "Vim leader must be a single key, got '{}'",
+            leader
+        ))),
+    }
+}
+
+pub fn validate_vim_mapping(sequence: &str, action: &str) -> AppResult<()> {
+    if sequence.trim().is_empty() {
+        return Err(AppError::ConfigLoad(
+            "Vim mapping sequence cannot be empty".to_string(),
+        ));
+    }
+
+    let valid_actions = get_available_vim_actions();
+    if !valid_actions.contains(&action) {
+        return Err(AppError::ConfigLoad(format!(
+            "Unknown vim action '{}'. Valid actions: {}",
+            action,
+            valid_actions.join(", ")
+        )));
+    }
     Ok(())
 }
 
@@ -126,14 +220,352 @@ pub fn validate_preferences_config(preferences: &PreferencesConfig) -> AppResult
             "Max search results must be greater than 0".to_string(),
         ));
     }
-    if preferences.max_search_results > 10000 {
+    if preferences.max_search_results > MAX_SEARCH_RESULTS_CEILING {
+        return Err(AppError::ConfigLoad(format!(
+            "Max search results too large (max: {})",
+            MAX_SEARCH_RESULTS_CEILING
+        )));
+    }
+    validate_note_extensions(&preferences.note_extensions)?;
+    validate_search_title_boost(preferences.search_title_boost)?;
+    validate_watcher_debounce_ms(preferences.watcher_debounce_ms)?;
+    validate_max_recursion_depth(preferences.max_recursion_depth)?;
+    validate_index_threads(preferences.index_threads)?;
+    validate_version_history_limit(preferences.version_history_limit)?;
+    validate_version_min_interval_seconds(preferences.version_min_interval_seconds)?;
+    validate_max_pasted_image_mb(preferences.max_pasted_image_mb)?;
+    validate_stream_note_threshold_kb(preferences.stream_note_threshold_kb)?;
+    let valid_sort_orders = get_available_note_sort_orders();
+    if !valid_sort_orders.contains(&preferences.note_sort_order.as_str()) {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid note sort order '{}'. Valid orders: {}",
+            preferences.note_sort_order,
+            valid_sort_orders.join(", ")
+        )));
+    }
+    let journal_modes = get_available_cache_journal_modes();
+    if !journal_modes.contains(&preferences.cache_journal_mode.as_str()) {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid cache journal mode '{}'. Valid modes: {}",
+            preferences.cache_journal_mode,
+            journal_modes.join(", ")
+        )));
+    }
+    let synchronous_modes = get_available_cache_synchronous_modes();
+    if !synchronous_modes.contains(&preferences.cache_synchronous.as_str()) {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid cache synchronous mode '{}'. Valid modes: {}",
+            preferences.cache_synchronous,
+            synchronous_modes.join(", ")
+        )));
+    }
+    let line_endings = get_available_line_endings();
+    if !line_endings.contains(&preferences.line_ending.as_str()) {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid line ending '{}'. Valid values: {}",
+            preferences.line_ending,
+            line_endings.join(", ")
+        )));
+    }
+    let markdown_flavors = get_available_markdown_flavors();
+    if !markdown_flavors.contains(&preferences.markdown_flavor.as_str()) {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid markdown flavor '{}'. Valid values: {}",
+            preferences.markdown_flavor,
+            markdown_flavors.join(", ")
+        )));
+    }
+    validate_external_editor_command(&preferences.external_editor_command)?;
+    validate_render_cache_max_entries(preferences.render_cache_max_entries)?;
+    validate_datetime_format(&preferences.date_format)?;
+    validate_datetime_format(&preferences.time_format)?;
+    if let Some(path) = &preferences.cache_database_path {
+        validate_cache_database_path(path)?;
+    }
+    Ok(())
+}
+
+/// The database file need not exist yet, but its folder has to take new files,
+/// since SQLite keeps journal files next to it. An empty path means unset.
+pub fn validate_cache_database_path(path: &str) -> AppResult<()> {
+    if path.trim().is_empty() {
+        return Ok(());
+    }
+
+    let path = std::path::Path::new(path);
+    if !path.is_absolute() {
+        return Err(AppError::ConfigLoad(format!(
+            "Cache database path must be absolute: {}",
+            path.display()
+        )));
+    }
+    if path.is_dir() {
+        return Err(AppError::ConfigLoad(format!(
+            "Cache database path is a directory, expected a file: {}",
+            path.display()
+        )));
+    }
+
+    let parent = path.parent().ok_or_else(|| {
+        AppError::ConfigLoad(format!(
+            "Cache database path has no parent directory: {}",
+            path.display()
+        ))
+    })?;
+    let probe = parent.join(format!(".symiosis-write-test-{}", std::process::id()));
+    std::fs::create_dir_all(parent)
+        .and_then(|_| std::fs::write(&probe, b""))
+        .map_err(|e| {
+            AppError::ConfigLoad(format!(
+                "Cache database directory is not writable: {}: {}",
+                parent.display(),
+                e
+            ))
+        })?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+/// An empty command means notes open with the OS default application.
+pub fn validate_external_editor_command(template: &str) -> AppResult<()> {
+    if template.trim().is_empty() {
+        return Ok(());
+    }
+    if !template.contains(PATH_PLACEHOLDER) {
+        return Err(AppError::ConfigLoad(format!(
+            "External editor command must contain the {} placeholder",
+            PATH_PLACEHOLDER
+        )));
+    }
+    split_command_template(template)?;
+    Ok(())
+}
+
+pub const MIN_SEARCH_TITLE_BOOST: f64 = 0.1;
+pub const MAX_SEARCH_TITLE_BOOST: f64 = 100.0;
+
+pub fn validate_search_title_boost(boost: f64) -> AppResult<()> {
+    if !(MIN_SEARCH_TITLE_BOOST..=MAX_SEARCH_TITLE_BOOST).contains(&boost) {
+        return Err(AppError::ConfigLoad(format!(
+            "Search title boost must be between {} and {}",
+            MIN_SEARCH_TITLE_BOOST, MAX_SEARCH_TITLE_BOOST
+        )));
+    }
+    Ok(())
+}
+
+pub const MIN_WATCHER_DEBOUNCE_MS: u64 = 50;
+pub const MAX_WATCHER_DEBOUNCE_MS: u64 = 10_000;
+
+pub fn validate_watcher_debounce_ms(debounce_ms: u64) -> AppResult<()> {
+    if !(MIN_WATCHER_DEBOUNCE_MS..=MAX_WATCHER_DEBOUNCE_MS).contains(&debounce_ms) {
+        return Err(AppError::ConfigLoad(format!(
+            "Watcher debounce must be between {} and {} ms",
+            MIN_WATCHER_DEBOUNCE_MS, MAX_WATCHER_DEBOUNCE_MS
+        )));
+    }
+    Ok(())
+}
+
+pub const MIN_VERSION_HISTORY_LIMIT: usize = 1;
+pub const MAX_VERSION_HISTORY_LIMIT: usize = 1_000;
+
+pub fn validate_version_history_limit(limit: usize) -> AppResult<()> {
+    if !(MIN_VERSION_HISTORY_LIMIT..=MAX_VERSION_HISTORY_LIMIT).contains(&limit) {
+        return Err(AppError::ConfigLoad(format!(
+            "Version history limit must be between {} and {}",
+            MIN_VERSION_HISTORY_LIMIT, MAX_VERSION_HISTORY_LIMIT
+        )));
+    }
+    Ok(())
+}
+
+pub const MIN_PASTED_IMAGE_MB: u64 = 1;
+pub const MAX_PASTED_IMAGE_MB: u64 = 100;
+
+pub fn validate_max_pasted_image_mb(megabytes: u64) -> AppResult<()> {
+    if !(MIN_PASTED_IMAGE_MB..=MAX_PASTED_IMAGE_MB).contains(&megabytes) {
+        return Err(AppError::ConfigLoad(format!(
+            "Max pasted image size must be between {} and {} MB",
+            MIN_PASTED_IMAGE_MB, MAX_PASTED_IMAGE_MB
+        )));
+    }
+    Ok(())
+}
+
+pub const MIN_STREAM_NOTE_THRESHOLD_KB: u64 = 64;
+pub const MAX_STREAM_NOTE_THRESHOLD_KB: u64 = 1024 * 1024;
+
+pub fn validate_stream_note_threshold_kb(kilobytes: u64) -> AppResult<()> {
+    if !(MIN_STREAM_NOTE_THRESHOLD_KB..=MAX_STREAM_NOTE_THRESHOLD_KB).contains(&kilobytes) {
+        return Err(AppError::ConfigLoad(format!(
+            "Note streaming threshold must be between {} and {} KB",
+            MIN_STREAM_NOTE_THRESHOLD_KB, MAX_STREAM_NOTE_THRESHOLD_KB
+        )));
+    }
+    Ok(())
+}
+
+/// Formats a fixed date with `format`, since chrono only reports unsupported
+/// specifiers when formatting and `to_string()` panics on them.
+pub fn validate_datetime_format(format: &str) -> AppResult<()> {
+    use chrono::TimeZone;
+    use std::fmt::Write;
+
+    let sample = chrono::FixedOffset::east_opt(0)
+        .and_then(|offset| offset.with_ymd_and_hms(2024, 1, 31, 13, 45, 30).single());
+    let mut formatted = String::new();
+    let valid = !format.trim().is_empty()
+        && sample.is_some_and(|date| write!(formatted, "{}", date.format(format)).is_ok());
+
+    if !valid {
+        return Err(AppError::ConfigLoad(format!(
+            "Invalid date/time format '{}'",
+            format
+        )));
+    }
+    Ok(())
+}
+
+pub const MAX_RENDER_CACHE_ENTRIES: usize = 100_000;
+
+pub fn validate_render_cache_max_entries(entries: usize) -> AppResult<()> {
+    if entries > MAX_RENDER_CACHE_ENTRIES {
+        return Err(AppError::ConfigLoad(format!(
+            "Render cache size must be at most {} entries",
+            MAX_RENDER_CACHE_ENTRIES
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_max_recursion_depth(depth: usize) -> AppResult<()> {
+    if depth == 0 {
+        return Err(AppError::ConfigLoad(
+            "Max recursion depth must be at least 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub const MAX_INDEX_THREADS: usize = 256;
+
+pub fn validate_index_threads(threads: usize) -> AppResult<()> {
+    if threads > MAX_INDEX_THREADS {
+        return Err(AppError::ConfigLoad(format!(
+            "Index threads must be at most {}",
+            MAX_INDEX_THREADS
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_logging_config(logging: &LoggingConfig) -> AppResult<()> {
+    validate_log_file_size_mb(logging.max_file_size_mb)?;
+    validate_log_archives(logging.max_archives)?;
+    Ok(())
+}
+
+pub const MIN_LOG_FILE_SIZE_MB: u64 = 1;
+pub const MAX_LOG_FILE_SIZE_MB: u64 = 100;
+
+pub fn validate_log_file_size_mb(megabytes: u64) -> AppResult<()> {
+    if !(MIN_LOG_FILE_SIZE_MB..=MAX_LOG_FILE_SIZE_MB).contains(&megabytes) {
+        return Err(AppError::ConfigLoad(format!(
+            "Log file size must be between {} and {} MB",
+            MIN_LOG_FILE_SIZE_MB, MAX_LOG_FILE_SIZE_MB
+        )));
+    }
+    Ok(())
+}
+
+pub const MAX_LOG_ARCHIVES: usize = 20;
+
+pub fn validate_log_archives(count: usize) -> AppResult<()> {
+    if count > MAX_LOG_ARCHIVES {
+        return Err(AppError::ConfigLoad(format!(
+            "Log archives must be at most {}",
+            MAX_LOG_ARCHIVES
+        )));
+    }
+    Ok(())
+}
+
+pub const MAX_AUTOSAVE_INTERVAL_SECONDS: u64 = 3_600;
+
+pub fn validate_autosave_interval_seconds(seconds: u64) -> AppResult<()> {
+    if seconds > MAX_AUTOSAVE_INTERVAL_SECONDS {
+        return Err(AppError::ConfigLoad(format!(
+            "Autosave interval must be at most {} seconds",
+            MAX_AUTOSAVE_INTERVAL_SECONDS
+        )));
+    }
+    Ok(())
+}
+
+// One day; anything longer would leave most edits without a version
+pub const MAX_VERSION_MIN_INTERVAL_SECONDS: u64 = 86_400;
+
+pub fn validate_version_min_interval_seconds(seconds: u64) -> AppResult<()> {
+    if seconds > MAX_VERSION_MIN_INTERVAL_SECONDS {
+        return Err(AppError::ConfigLoad(format!(
+            "Minimum version interval must be at most {} seconds",
+            MAX_VERSION_MIN_INTERVAL_SECONDS
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_note_extensions(extensions: &[String]) -> AppResult<()> {
+    if extensions.is_empty() {
         return Err(AppError::ConfigLoad(
-            "Max search results too large (max: 10000)".to_string(),
+            "At least one note extension is required".to_string(),
         ));
     }
+
+    for extension in extensions {
+        if extension.trim().is_empty()
+            || extension.contains('.')
+            || extension.contains('/')
+            || extension.contains('\\')
+        {
+            return Err(AppError::ConfigLoad(format!(
+                "Invalid note extension '{}'. Use the extension without a dot, e.g. \"md\"",
+                extension
+            )));
+        }
+    }
     Ok(())
 }
 
+/// Whether a path relative to the notes directory is at most `max_depth` levels
+/// deep, counting the file itself; depth 1 is a note directly in the directory.
+pub fn within_recursion_depth(relative: &std::path::Path, max_depth: usize) -> bool {
+    relative.components().count() <= max_depth
+}
+
+pub fn has_note_extension(path: &std::path::Path, extensions: &[String]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| extensions.iter().any(|allowed| allowed == ext))
+        .unwrap_or(false)
+}
+
+pub fn validate_note_extension(note_name: &str, extensions: &[String]) -> AppResult<()> {
+    if has_note_extension(std::path::Path::new(note_name), extensions) {
+        return Ok(());
+    }
+
+    Err(AppError::InvalidNoteName(format!(
+        "Note name must end with one of: {}",
+        extensions
+            .iter()
+            .map(|ext| format!(".{}", ext))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )))
+}
+
 pub fn validate_shortcut_format(shortcut: &str) -> AppResult<()> {
     if shortcut.trim().is_empty() {
         return Err(AppError::ConfigLoad("Shortcut cannot be empty".to_string()));
@@ -143,7 +575,7 @@ pub fn validate_shortcut_format(shortcut: &str) -> AppResult<()> {
         return Err(AppError::ConfigLoad("Invalid shortcut format".to_string()));
     }
 
-    match parse_shortcut(shortcut) {
+    match parse_shortcut(&normalize_shortcut(shortcut)) {
         Some(_) => Ok(()),
         None => Err(AppError::ConfigLoad(format!(
             "Invalid global shortcut format: '{}'",
@@ -219,6 +651,84 @@ pub fn validate_notes_directory(dir: &str) -> AppResult<()> {
     Ok(())
 }
 
+/// Checks a folder inside the notes directory that notes can be moved into.
+/// An empty path means the notes directory itself.
+pub fn validate_note_folder(folder: &str) -> AppResult<()> {
+    if folder.contains('\\') {
+        return Err(AppError::InvalidPath(format!(
+            "Invalid folder '{}'",
+            folder
+        )));
+    }
+
+    let path = std::path::Path::new(folder);
+    if path.is_absolute() {
+        return Err(AppError::InvalidPath(
+            "Absolute paths not allowed".to_string(),
+        ));
+    }
+
+    for component in path.components() {
+        match component {
+            std::path::Component::Normal(name) if !name.to_string_lossy().starts_with('.') => {}
+            std::path::Component::ParentDir => return Err(AppError::PathTraversal),
+            // Hidden folders are never indexed, so a note moved there would vanish
+            _ => {
+                return Err(AppError::InvalidPath(format!(
+                    "Invalid folder '{}'",
+                    folder
+                )))
+            }
+        }
+    }
+
+    if folder.len() > 255 {
+        return Err(AppError::InvalidPath("Folder path too long".to_string()));
+    }
+    Ok(())
+}
+
+// Device names Windows refuses as file names, with or without an extension
+const RESERVED_FOLDER_NAMES: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// Checks a folder about to be created, which on top of `validate_note_folder`
+/// must be non-empty, portable across platforms and not the attachments folder.
+pub fn validate_new_folder(folder: &str) -> AppResult<()> {
+    if folder.trim().is_empty() {
+        return Err(AppError::InvalidPath(
+            "Folder name cannot be empty".to_string(),
+        ));
+    }
+    validate_note_folder(folder)?;
+
+    for name in folder.split('/') {
+        let stem = name.split('.').next().unwrap_or(name).to_lowercase();
+        if RESERVED_FOLDER_NAMES.contains(&stem.as_str()) {
+            return Err(AppError::InvalidPath(format!(
+                "'{}' is a reserved name",
+                name
+            )));
+        }
+        if name.ends_with('.') || name.ends_with(' ') {
+            return Err(AppError::InvalidPath(format!(
+                "Folder names cannot end with a dot or space: '{}'",
+                name
+            )));
+        }
+    }
+
+    if folder == crate::utilities::attachments::ATTACHMENTS_DIR_NAME {
+        return Err(AppError::InvalidPath(format!(
+            "'{}' is reserved for pasted images",
+            folder
+        )));
+    }
+    Ok(())
+}
+
 pub fn validate_note_name(note_name: &str) -> AppResult<()> {
     // Check for empty name
     if note_name.trim().is_empty() {
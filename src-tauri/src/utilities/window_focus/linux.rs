@@ -0,0 +1,152 @@
+use std::sync::Mutex;
+
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{
+    AtomEnum, ClientMessageEvent, ConnectionExt, EventMask, Window, CLIENT_MESSAGE_EVENT,
+};
+use x11rb::CURRENT_TIME;
+
+use super::{hide_window, recover_poisoned, FocusManager};
+use crate::logging::{log, log_warn};
+
+static PREV_X11_WINDOW: Mutex<Option<Window>> = Mutex::new(None);
+
+type X11Result<T> = Result<T, Box<dyn std::error::Error>>;
+
+pub struct LinuxFocusManager;
+
+impl FocusManager for LinuxFocusManager {
+    /// Saves the active X11 window.
+    ///
+    /// Wayland has no protocol for querying or handing back focus, so it is skipped there.
+    fn save_current_frontmost(&self) {
+        if is_wayland_session() {
+            return;
+        }
+
+        match get_active_x11_window() {
+            Ok(Some(window)) => {
+                *recover_poisoned(PREV_X11_WINDOW.lock(), "PREV_X11_WINDOW") = Some(window);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                log_warn(
+                    "WINDOW_FOCUS",
+                    "Failed to query active X11 window",
+                    Some(&e.to_string()),
+                );
+            }
+        }
+    }
+
+    /// Asks the window manager to re-activate the previous X11 window.
+    fn hide_and_restore(&self, window: &tauri::WebviewWindow) {
+        hide_window(window);
+
+        let prev_window_opt = recover_poisoned(PREV_X11_WINDOW.lock(), "PREV_X11_WINDOW").take();
+
+        let Some(prev_window) = prev_window_opt else {
+            return;
+        };
+
+        match activate_x11_window(prev_window) {
+            Ok(true) => {}
+            Ok(false) => {
+                log(
+                    "WINDOW_FOCUS",
+                    "Previous window no longer exists",
+                    Some(&format!("Window: {:#x}", prev_window)),
+                );
+            }
+            Err(e) => {
+                log_warn(
+                    "WINDOW_FOCUS",
+                    "Failed to activate previous X11 window",
+                    Some(&e.to_string()),
+                );
+            }
+        }
+    }
+
+    fn log_limitations(&self) {
+        if is_wayland_session() {
+            log(
+                "WINDOW_FOCUS",
+                "Focus restoration is unsupported on Wayland",
+                None,
+            );
+        }
+    }
+}
+
+fn is_wayland_session() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_some()
+        || std::env::var("XDG_SESSION_TYPE").is_ok_and(|t| t.eq_ignore_ascii_case("wayland"))
+}
+
+fn get_window_property_u32<C: Connection>(
+    conn: &C,
+    window: Window,
+    name: &[u8],
+    property_type: AtomEnum,
+) -> X11Result<Option<u32>> {
+    let atom = conn.intern_atom(false, name)?.reply()?.atom;
+    let reply = conn
+        .get_property(false, window, atom, property_type, 0, 1)?
+        .reply()?;
+    Ok(reply.value32().and_then(|mut values| values.next()))
+}
+
+fn get_active_x11_window() -> X11Result<Option<Window>> {
+    let (conn, screen_num) = x11rb::connect(None)?;
+    let root = conn.setup().roots[screen_num].root;
+
+    let Some(active) =
+        get_window_property_u32(&conn, root, b"_NET_ACTIVE_WINDOW", AtomEnum::WINDOW)?
+    else {
+        return Ok(None);
+    };
+    if active == x11rb::NONE {
+        return Ok(None);
+    }
+
+    let pid = get_window_property_u32(&conn, active, b"_NET_WM_PID", AtomEnum::CARDINAL)?;
+    if pid == Some(std::process::id()) {
+        return Ok(None);
+    }
+
+    Ok(Some(active))
+}
+
+fn activate_x11_window(window: Window) -> X11Result<bool> {
+    let (conn, screen_num) = x11rb::connect(None)?;
+    let root = conn.setup().roots[screen_num].root;
+
+    // The window manager owns focus under EWMH, so a closed window just fails this lookup
+    if conn.get_window_attributes(window)?.reply().is_err() {
+        return Ok(false);
+    }
+
+    let net_active_window = conn
+        .intern_atom(false, b"_NET_ACTIVE_WINDOW")?
+        .reply()?
+        .atom;
+    // Source indication 2 marks the request as coming from a pager so WMs honour it
+    let event = ClientMessageEvent {
+        response_type: CLIENT_MESSAGE_EVENT,
+        format: 32,
+        sequence: 0,
+        window,
+        type_: net_active_window,
+        data: [2, CURRENT_TIME, 0, 0, 0].into(),
+    };
+    conn.send_event(
+        false,
+        root,
+        EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+        event,
+    )?;
+    conn.flush()?;
+
+    Ok(true)
+}
@@ -0,0 +1,284 @@
+use std::ffi::c_void;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+use objc2_app_kit::{
+    NSApplication, NSApplicationActivationOptions, NSRunningApplication, NSWorkspace,
+};
+use objc2_foundation::{MainThreadMarker, NSString};
+
+use super::{hide_window, recover_poisoned, FocusManager, PermissionStatus};
+use crate::logging::{log, log_warn};
+
+type CFTypeRef = *const c_void;
+type CFStringRef = *const c_void;
+type AXUIElementRef = *const c_void;
+type AXError = i32;
+
+const AX_ERROR_SUCCESS: AXError = 0;
+
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    fn AXIsProcessTrusted() -> bool;
+    fn AXUIElementCreateApplication(pid: i32) -> AXUIElementRef;
+    fn AXUIElementCopyAttributeValue(
+        element: AXUIElementRef,
+        attribute: CFStringRef,
+        value: *mut CFTypeRef,
+    ) -> AXError;
+    fn AXUIElementSetAttributeValue(
+        element: AXUIElementRef,
+        attribute: CFStringRef,
+        value: CFTypeRef,
+    ) -> AXError;
+    fn AXUIElementPerformAction(element: AXUIElementRef, action: CFStringRef) -> AXError;
+}
+
+#[repr(C)]
+struct AEDesc {
+    descriptor_type: u32,
+    data_handle: *mut c_void,
+}
+
+const TYPE_APPLICATION_BUNDLE_ID: u32 = u32::from_be_bytes(*b"bund");
+const TYPE_WILD_CARD: u32 = u32::from_be_bytes(*b"****");
+const NO_ERR: i32 = 0;
+const SYSTEM_EVENTS_BUNDLE_ID: &str = "com.apple.systemevents";
+
+#[link(name = "CoreServices", kind = "framework")]
+extern "C" {
+    fn AECreateDesc(
+        type_code: u32,
+        data_ptr: *const c_void,
+        data_size: isize,
+        result: *mut AEDesc,
+    ) -> i16;
+    fn AEDisposeDesc(desc: *mut AEDesc) -> i16;
+    fn AEDeterminePermissionToAutomateTarget(
+        target: *const AEDesc,
+        event_class: u32,
+        event_id: u32,
+        ask_user_if_needed: bool,
+    ) -> i32;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    static kCFBooleanTrue: CFTypeRef;
+    fn CFRelease(cf: CFTypeRef);
+}
+
+/// A window of another app, retained through the Accessibility API.
+struct AxWindow(AXUIElementRef);
+
+// AXUIElements are CFTypes, which can be used and released from any thread
+unsafe impl Send for AxWindow {}
+
+impl Drop for AxWindow {
+    fn drop(&mut self) {
+        unsafe { CFRelease(self.0) };
+    }
+}
+
+struct PreviousFocus {
+    pid: i32,
+    /// `None` when the window could not be captured, e.g. without Accessibility permission.
+    window: Option<AxWindow>,
+}
+
+lazy_static! {
+    static ref PREV_FOCUS: Mutex<Option<PreviousFocus>> = Mutex::new(None);
+}
+
+// NSString is toll-free bridged with CFString
+fn cf_string(string: &NSString) -> CFStringRef {
+    string as *const NSString as CFStringRef
+}
+
+/// The focused window of the app with `pid`, if Symiosis is trusted for Accessibility.
+fn capture_focused_window(pid: i32) -> Option<AxWindow> {
+    if !unsafe { AXIsProcessTrusted() } {
+        return None;
+    }
+
+    let app = unsafe { AXUIElementCreateApplication(pid) };
+    if app.is_null() {
+        return None;
+    }
+    let attribute = NSString::from_str("AXFocusedWindow");
+    let mut value: CFTypeRef = std::ptr::null();
+    let err = unsafe { AXUIElementCopyAttributeValue(app, cf_string(&attribute), &mut value) };
+    unsafe { CFRelease(app) };
+
+    (err == AX_ERROR_SUCCESS && !value.is_null()).then(|| AxWindow(value))
+}
+
+/// Whether Apple Events may be sent to System Events, without prompting the user.
+/// Undecided consent and a target that is not running both count as not granted.
+fn automation_granted() -> bool {
+    let bundle_id = SYSTEM_EVENTS_BUNDLE_ID.as_bytes();
+    let mut target = AEDesc {
+        descriptor_type: 0,
+        data_handle: std::ptr::null_mut(),
+    };
+    let created = unsafe {
+        AECreateDesc(
+            TYPE_APPLICATION_BUNDLE_ID,
+            bundle_id.as_ptr() as *const c_void,
+            bundle_id.len() as isize,
+            &mut target,
+        )
+    };
+    if created as i32 != NO_ERR {
+        return false;
+    }
+
+    let status = unsafe {
+        AEDeterminePermissionToAutomateTarget(&target, TYPE_WILD_CARD, TYPE_WILD_CARD, false)
+    };
+    unsafe { AEDisposeDesc(&mut target) };
+    status == NO_ERR
+}
+
+/// Makes `window` the main window of its app and raises it above the app's others.
+fn raise_window(window: &AxWindow) -> bool {
+    let main = NSString::from_str("AXMain");
+    let raise = NSString::from_str("AXRaise");
+    unsafe {
+        AXUIElementSetAttributeValue(window.0, cf_string(&main), kCFBooleanTrue);
+        AXUIElementPerformAction(window.0, cf_string(&raise)) == AX_ERROR_SUCCESS
+    }
+}
+
+pub struct MacFocusManager;
+
+impl FocusManager for MacFocusManager {
+    /// Saves the frontmost app's PID and, with Accessibility permission, its focused window.
+    ///
+    /// If Symiosis is already frontmost, preserves the existing saved target to handle
+    /// rapid toggle scenarios (prevents losing the restoration target).
+    fn save_current_frontmost(&self) {
+        let frontmost = unsafe {
+            let workspace = NSWorkspace::sharedWorkspace();
+            workspace.frontmostApplication()
+        };
+
+        let Some(frontmost) = frontmost else {
+            return;
+        };
+
+        let pid = unsafe { frontmost.processIdentifier() };
+        let our_pid = std::process::id() as i32;
+
+        if pid == our_pid {
+            return;
+        }
+
+        let window = capture_focused_window(pid);
+        *recover_poisoned(PREV_FOCUS.lock(), "PREV_FOCUS") = Some(PreviousFocus { pid, window });
+    }
+
+    fn permission_status(&self) -> PermissionStatus {
+        PermissionStatus {
+            accessibility: unsafe { AXIsProcessTrusted() },
+            automation: automation_granted(),
+        }
+    }
+
+    fn show(&self, window: &tauri::WebviewWindow) {
+        if let Err(e) = window.show() {
+            log_warn(
+                "WINDOW_FOCUS",
+                "Failed to show window",
+                Some(&e.to_string()),
+            );
+        }
+        if let Err(e) = window.set_focus() {
+            log_warn(
+                "WINDOW_FOCUS",
+                "Failed to set window focus",
+                Some(&e.to_string()),
+            );
+        }
+
+        // set_focus alone doesn't fully activate the app at macOS level
+        let mtm = unsafe { MainThreadMarker::new_unchecked() };
+        let app = NSApplication::sharedApplication(mtm);
+        unsafe { app.activate() };
+    }
+
+    fn hide_and_restore(&self, window: &tauri::WebviewWindow) {
+        hide_window(window);
+
+        let mtm = unsafe { MainThreadMarker::new_unchecked() };
+        let app = NSApplication::sharedApplication(mtm);
+        app.hide(None);
+
+        let prev_focus_opt = recover_poisoned(PREV_FOCUS.lock(), "PREV_FOCUS").take();
+
+        let Some(PreviousFocus {
+            pid: prev_pid,
+            window: prev_window,
+        }) = prev_focus_opt
+        else {
+            return;
+        };
+
+        let prev_app =
+            unsafe { NSRunningApplication::runningApplicationWithProcessIdentifier(prev_pid) };
+
+        match prev_app {
+            Some(prev_app) => {
+                // With the exact window raised, activating the app without
+                // ActivateAllWindows keeps its other windows where they were
+                let window_raised = match prev_window {
+                    Some(prev_window) => {
+                        let raised = raise_window(&prev_window);
+                        if !raised {
+                            log_warn(
+                                "WINDOW_FOCUS",
+                                "Failed to raise previous window, falling back to app activation",
+                                Some(&format!("PID: {}", prev_pid)),
+                            );
+                        }
+                        raised
+                    }
+                    None => {
+                        let reason = if unsafe { AXIsProcessTrusted() } {
+                            "no focused window was captured"
+                        } else {
+                            "Accessibility permission not granted"
+                        };
+                        log(
+                            "WINDOW_FOCUS",
+                            &format!("Restoring previous app without its window: {}", reason),
+                            Some(&format!("PID: {}", prev_pid)),
+                        );
+                        false
+                    }
+                };
+
+                let options = if window_raised {
+                    NSApplicationActivationOptions::empty()
+                } else {
+                    NSApplicationActivationOptions::ActivateAllWindows
+                };
+                let success = unsafe { prev_app.activateWithOptions(options) };
+                if !success {
+                    log_warn(
+                        "WINDOW_FOCUS",
+                        "Failed to activate previous app",
+                        Some(&format!("PID: {}", prev_pid)),
+                    );
+                }
+            }
+            None => {
+                log(
+                    "WINDOW_FOCUS",
+                    "Previous app no longer running",
+                    Some(&format!("PID: {}", prev_pid)),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+mod unsupported;
+#[cfg(target_os = "windows")]
+mod windows;
+
+use crate::logging::log_warn;
+use crate::utilities::window_geometry::save_window_geometry;
+use serde::Serialize;
+use std::sync::{Mutex, Once};
+
+static MISSING_PERMISSION_WARNING: Once = Once::new();
+// Serializes toggles so rapid presses can't both see the window as hidden
+static TOGGLE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Whether the OS permissions that focus handling relies on are granted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PermissionStatus {
+    /// Needed to capture and raise a specific window of the previous app.
+    pub accessibility: bool,
+    /// Needed to send Apple Events to other apps, checked against System Events.
+    pub automation: bool,
+}
+
+impl PermissionStatus {
+    pub const ALL_GRANTED: PermissionStatus = PermissionStatus {
+        accessibility: true,
+        automation: true,
+    };
+}
+
+/// Platform hooks for handing focus back to whatever was active before Symiosis was shown.
+pub trait FocusManager: Sync {
+    /// Remember the currently frontmost app or window, ignoring Symiosis itself.
+    fn save_current_frontmost(&self);
+
+    /// Show and focus the app window.
+    fn show(&self, window: &tauri::WebviewWindow) {
+        if let Err(e) = window.show() {
+            log_warn(
+                "WINDOW_FOCUS",
+                "Failed to show window",
+                Some(&e.to_string()),
+            );
+        }
+        if let Err(e) = window.set_focus() {
+            log_warn(
+                "WINDOW_FOCUS",
+                "Failed to set window focus",
+                Some(&e.to_string()),
+            );
+        }
+    }
+
+    /// Hide the app window and restore focus to the saved app or window.
+    fn hide_and_restore(&self, window: &tauri::WebviewWindow);
+
+    /// Log what focus handling can't do on this system, once at startup.
+    fn log_limitations(&self) {}
+
+    /// Report which permissions are granted. Only macOS gates focus handling behind any.
+    fn permission_status(&self) -> PermissionStatus {
+        PermissionStatus::ALL_GRANTED
+    }
+}
+
+fn active_focus_manager() -> &'static dyn FocusManager {
+    #[cfg(target_os = "macos")]
+    return &macos::MacFocusManager;
+    #[cfg(target_os = "windows")]
+    return &windows::WindowsFocusManager;
+    #[cfg(target_os = "linux")]
+    return &linux::LinuxFocusManager;
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    return &unsupported::UnsupportedFocusManager;
+}
+
+fn hide_window(window: &tauri::WebviewWindow) {
+    save_window_geometry(window);
+    if let Err(e) = window.hide() {
+        log_warn(
+            "WINDOW_FOCUS",
+            "Failed to hide window",
+            Some(&e.to_string()),
+        );
+    }
+}
+
+/// The warning to log for the permissions `status` is missing, if any.
+pub fn missing_permission_warning(status: PermissionStatus) -> Option<&'static str> {
+    match (status.accessibility, status.automation) {
+        (true, true) => None,
+        (false, true) => Some(
+            "Accessibility permission not granted, the previous app is restored without its window",
+        ),
+        (true, false) => {
+            Some("Automation permission not granted, System Events can't be asked to restore focus")
+        }
+        (false, false) => Some(
+            "Accessibility and Automation permissions not granted, focus is restored at app level only",
+        ),
+    }
+}
+
+// Focus still works at app level without the permissions, so this is a
+// warning logged the first time it matters rather than an error on every toggle
+fn warn_missing_permissions_once() {
+    MISSING_PERMISSION_WARNING.call_once(|| {
+        if let Some(warning) =
+            missing_permission_warning(active_focus_manager().permission_status())
+        {
+            log_warn("WINDOW_FOCUS", warning, None);
+        }
+    });
+}
+
+fn recover_poisoned<T>(result: std::sync::LockResult<T>, name: &str) -> T {
+    result.unwrap_or_else(|e| {
+        log_warn(
+            "WINDOW_FOCUS",
+            &format!("{} mutex was poisoned, recovering", name),
+            None,
+        );
+        e.into_inner()
+    })
+}
+
+/// Logs the focus limitations of the running platform, if it has any.
+pub fn log_focus_limitations() {
+    active_focus_manager().log_limitations();
+}
+
+/// Save the currently frontmost app so we can restore it later.
+#[tauri::command]
+pub fn save_current_frontmost_app() {
+    active_focus_manager().save_current_frontmost();
+}
+
+/// Show and activate the app window.
+#[tauri::command]
+pub fn show_app(window: tauri::WebviewWindow) {
+    warn_missing_permissions_once();
+    active_focus_manager().show(&window);
+}
+
+/// Hide this app and restore focus to the previously-frontmost app.
+#[tauri::command]
+pub fn hide_app_and_restore_previous(window: tauri::WebviewWindow) {
+    warn_missing_permissions_once();
+    active_focus_manager().hide_and_restore(&window);
+}
+
+/// Hide the app window if it is visible and focused, otherwise remember the
+/// frontmost app and show it. Returns whether the window is now shown.
+#[tauri::command]
+pub fn toggle_app_visibility(window: tauri::WebviewWindow) -> bool {
+    let _guard = recover_poisoned(TOGGLE_LOCK.lock(), "TOGGLE_LOCK");
+
+    let is_visible = window.is_visible().unwrap_or(false);
+    let is_focused = window.is_focused().unwrap_or(false);
+
+    if is_visible && is_focused {
+        hide_app_and_restore_previous(window);
+        false
+    } else {
+        save_current_frontmost_app();
+        show_app(window);
+        true
+    }
+}
+
+/// Report the macOS Accessibility and Automation grants. Other platforms need
+/// neither, so they always report both as granted.
+#[tauri::command]
+pub fn check_macos_permissions() -> PermissionStatus {
+    active_focus_manager().permission_status()
+}
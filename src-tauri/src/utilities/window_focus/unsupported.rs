@@ -0,0 +1,13 @@
+use super::{hide_window, FocusManager};
+
+pub struct UnsupportedFocusManager;
+
+impl FocusManager for UnsupportedFocusManager {
+    fn save_current_frontmost(&self) {
+        // No-op on platforms without focus restoration
+    }
+
+    fn hide_and_restore(&self, window: &tauri::WebviewWindow) {
+        hide_window(window);
+    }
+}
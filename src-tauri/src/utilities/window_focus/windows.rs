@@ -0,0 +1,66 @@
+use std::sync::Mutex;
+
+use windows::Win32::Foundation::HWND;
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetForegroundWindow, GetWindowThreadProcessId, IsWindow, SetForegroundWindow,
+};
+
+use super::{hide_window, recover_poisoned, FocusManager};
+use crate::logging::{log, log_warn};
+
+// HWND wraps a raw pointer and isn't Send, so the handle is stored as an integer
+static PREV_HWND: Mutex<Option<isize>> = Mutex::new(None);
+
+pub struct WindowsFocusManager;
+
+impl FocusManager for WindowsFocusManager {
+    /// Saves the current foreground window.
+    ///
+    /// Windows belonging to Symiosis are ignored so rapid toggles keep the original target.
+    fn save_current_frontmost(&self) {
+        let hwnd = unsafe { GetForegroundWindow() };
+        if hwnd.0.is_null() {
+            return;
+        }
+
+        let mut pid = 0u32;
+        unsafe { GetWindowThreadProcessId(hwnd, Some(&mut pid)) };
+
+        let mut lock = recover_poisoned(PREV_HWND.lock(), "PREV_HWND");
+
+        if pid != std::process::id() {
+            *lock = Some(hwnd.0 as isize);
+        }
+    }
+
+    /// Brings the previously foreground window back.
+    ///
+    /// If that window has since been closed, Windows picks the next window as usual.
+    fn hide_and_restore(&self, window: &tauri::WebviewWindow) {
+        hide_window(window);
+
+        let prev_hwnd_opt = recover_poisoned(PREV_HWND.lock(), "PREV_HWND").take();
+
+        let Some(prev_hwnd) = prev_hwnd_opt else {
+            return;
+        };
+
+        let hwnd = HWND(prev_hwnd as *mut std::ffi::c_void);
+        if !unsafe { IsWindow(hwnd) }.as_bool() {
+            log(
+                "WINDOW_FOCUS",
+                "Previous window no longer exists",
+                Some(&format!("HWND: {:#x}", prev_hwnd)),
+            );
+            return;
+        }
+
+        if !unsafe { SetForegroundWindow(hwnd) }.as_bool() {
+            log_warn(
+                "WINDOW_FOCUS",
+                "Failed to activate previous window",
+                Some(&format!("HWND: {:#x}", prev_hwnd)),
+            );
+        }
+    }
+}
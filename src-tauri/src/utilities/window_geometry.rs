@@ -0,0 +1,197 @@
+use crate::{
+    core::AppResult,
+    logging::{log, log_warn},
+    utilities::paths::get_window_state_path,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use tauri::{PhysicalPosition, PhysicalSize};
+
+pub const DEFAULT_WINDOW_WIDTH: f64 = 1200.0;
+pub const DEFAULT_WINDOW_HEIGHT: f64 = 800.0;
+
+/// Outer position and outer size of the main window, in physical pixels, so
+/// the whole frame is what gets kept on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A monitor's position and size, in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScreenArea {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ScreenArea {
+    fn overlap(&self, geometry: &WindowGeometry) -> u64 {
+        let span = |start: i32, len: u32, other_start: i32, other_len: u32| {
+            let end = (start as i64 + len as i64).min(other_start as i64 + other_len as i64);
+            (end - (start as i64).max(other_start as i64)).max(0) as u64
+        };
+        span(self.x, self.width, geometry.x, geometry.width)
+            * span(self.y, self.height, geometry.y, geometry.height)
+    }
+}
+
+/// Fits a saved geometry onto the current screens: the window is shrunk to
+/// the screen it overlaps most and moved fully onto it. A window that overlaps
+/// no screen, e.g. one saved on a disconnected monitor, is centered on the
+/// first screen, so callers should pass the primary monitor first.
+pub fn clamp_to_screens(geometry: WindowGeometry, screens: &[ScreenArea]) -> WindowGeometry {
+    let best = screens
+        .iter()
+        .max_by_key(|screen| screen.overlap(&geometry))
+        .filter(|screen| screen.overlap(&geometry) > 0);
+
+    let (screen, centered) = match (best, screens.first()) {
+        (Some(screen), _) => (screen, false),
+        (None, Some(primary)) => (primary, true),
+        (None, None) => return geometry,
+    };
+
+    let width = geometry.width.min(screen.width);
+    let height = geometry.height.min(screen.height);
+    let max_x = screen.x + (screen.width - width) as i32;
+    let max_y = screen.y + (screen.height - height) as i32;
+
+    let (x, y) = if centered {
+        (
+            screen.x + ((screen.width - width) / 2) as i32,
+            screen.y + ((screen.height - height) / 2) as i32,
+        )
+    } else {
+        (
+            geometry.x.clamp(screen.x, max_x),
+            geometry.y.clamp(screen.y, max_y),
+        )
+    };
+
+    WindowGeometry {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+pub fn load_window_geometry() -> Option<WindowGeometry> {
+    let path = get_window_state_path();
+    let content = fs::read_to_string(&path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(geometry) => Some(geometry),
+        Err(e) => {
+            log(
+                "WINDOW_GEOMETRY",
+                "Ignoring unreadable window state file",
+                Some(&format!("{}: {}", path.display(), e)),
+            );
+            None
+        }
+    }
+}
+
+pub fn write_window_geometry(geometry: &WindowGeometry) -> AppResult<()> {
+    let path = get_window_state_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = serde_json::to_string_pretty(geometry)
+        .map_err(|e| crate::core::AppError::FileWrite(e.to_string()))?;
+    fs::write(path, content)?;
+    Ok(())
+}
+
+pub fn clear_window_geometry() -> AppResult<()> {
+    let path = get_window_state_path();
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Remembers where the window is, called before it is hidden or the app quits.
+pub fn save_window_geometry(window: &tauri::WebviewWindow) {
+    // Minimized or maximized windows report sizes that shouldn't be restored
+    if window.is_minimized().unwrap_or(false) || window.is_maximized().unwrap_or(false) {
+        return;
+    }
+    let (Ok(position), Ok(size)) = (window.outer_position(), window.outer_size()) else {
+        return;
+    };
+    if size.width == 0 || size.height == 0 {
+        return;
+    }
+
+    let geometry = WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    };
+    if let Err(e) = write_window_geometry(&geometry) {
+        log_warn(
+            "WINDOW_GEOMETRY",
+            "Failed to save window geometry",
+            Some(&e.to_string()),
+        );
+    }
+}
+
+/// Moves and resizes the window to the saved geometry, kept on a visible screen.
+pub fn restore_window_geometry(window: &tauri::WebviewWindow) {
+    let Some(geometry) = load_window_geometry() else {
+        return;
+    };
+
+    let primary = window.primary_monitor().ok().flatten();
+    let mut monitors = window.available_monitors().unwrap_or_default();
+    if let Some(primary) = &primary {
+        if let Some(index) = monitors
+            .iter()
+            .position(|m| m.position() == primary.position())
+        {
+            let primary = monitors.remove(index);
+            monitors.insert(0, primary);
+        }
+    }
+    let screens: Vec<ScreenArea> = monitors
+        .iter()
+        .map(|monitor| ScreenArea {
+            x: monitor.position().x,
+            y: monitor.position().y,
+            width: monitor.size().width,
+            height: monitor.size().height,
+        })
+        .collect();
+
+    let geometry = clamp_to_screens(geometry, &screens);
+    // set_size takes the inner size, so the frame around it is taken off
+    let (frame_width, frame_height) = match (window.outer_size(), window.inner_size()) {
+        (Ok(outer), Ok(inner)) => (
+            outer.width.saturating_sub(inner.width),
+            outer.height.saturating_sub(inner.height),
+        ),
+        _ => (0, 0),
+    };
+    let inner = PhysicalSize::new(
+        geometry.width.saturating_sub(frame_width).max(1),
+        geometry.height.saturating_sub(frame_height).max(1),
+    );
+    let result = window
+        .set_size(inner)
+        .and_then(|_| window.set_position(PhysicalPosition::new(geometry.x, geometry.y)));
+    if let Err(e) = result {
+        log_warn(
+            "WINDOW_GEOMETRY",
+            "Failed to restore window geometry",
+            Some(&e.to_string()),
+        );
+    }
+}
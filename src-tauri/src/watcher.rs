@@ -1,6 +1,6 @@
 use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
@@ -9,76 +9,85 @@ use tauri::{AppHandle, Emitter};
 use crate::{
     config::get_config_notes_dir,
     database::with_db,
-    logging::log,
-    services::note_service::update_note_in_database,
-    utilities::file_safety::{create_versioned_backup, BackupType},
+    logging::{log, log_warn},
+    services::{
+        hash_service::delete_note_hash, note_service::update_note_in_database,
+        tag_service::delete_note_tags,
+    },
+    utilities::{
+        file_safety::{create_versioned_backup, BackupType},
+        note_ignore::NoteIgnore,
+        validation::{has_note_extension, within_recursion_depth},
+    },
 };
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::Ordering;
 
-struct DebouncedWatcher {
-    pending_events: Arc<Mutex<HashMap<PathBuf, Instant>>>,
-    debounce_duration: Duration,
-    cleanup_counter: AtomicU32,
+// How long the event loop sleeps when nothing is waiting to settle
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Collects paths from watcher events and releases each one only after it has been
+/// quiet for the debounce interval, so a burst of writes is indexed once with its
+/// final content.
+pub(crate) struct DebouncedWatcher {
+    pending_events: Mutex<HashMap<PathBuf, Instant>>,
 }
 
 impl DebouncedWatcher {
-    fn new(debounce_ms: u64) -> Self {
+    pub(crate) fn new() -> Self {
         Self {
-            pending_events: Arc::new(Mutex::new(HashMap::new())),
-            debounce_duration: Duration::from_millis(debounce_ms),
-            cleanup_counter: AtomicU32::new(0),
+            pending_events: Mutex::new(HashMap::new()),
         }
     }
 
-    fn should_process_event(&self, path: &PathBuf) -> bool {
-        let now = Instant::now();
-        let mut pending = match self.pending_events.lock() {
-            Ok(pending) => pending,
-            Err(e) => {
-                log(
-                    "WATCHER_ERROR",
-                    "Watcher lock poisoned, recovering",
-                    Some(&e.to_string()),
-                );
-                e.into_inner()
-            }
-        };
-
-        if let Some(last_event) = pending.get(path) {
-            if now.duration_since(*last_event) < self.debounce_duration {
-                return false;
-            }
-        }
+    fn lock_pending(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, Instant>> {
+        self.pending_events.lock().unwrap_or_else(|e| {
+            log_warn(
+                "WATCHER_ERROR",
+                "Watcher lock poisoned, recovering",
+                Some(&e.to_string()),
+            );
+            e.into_inner()
+        })
+    }
 
-        pending.insert(path.clone(), now);
-        true
+    pub(crate) fn record_event(&self, path: &Path, now: Instant) {
+        self.lock_pending().insert(path.to_path_buf(), now);
     }
 
-    fn cleanup_old_events(&self) {
-        let now = Instant::now();
-        let mut pending = match self.pending_events.lock() {
-            Ok(pending) => pending,
-            Err(e) => {
-                log(
-                    "WATCHER_ERROR",
-                    "Watcher cleanup lock poisoned, recovering",
-                    Some(&e.to_string()),
-                );
-                e.into_inner()
-            }
-        };
+    pub(crate) fn take_settled_paths(&self, now: Instant, debounce: Duration) -> Vec<PathBuf> {
+        let mut pending = self.lock_pending();
+        let settled: Vec<PathBuf> = pending
+            .iter()
+            .filter(|(_, &last_event)| now.duration_since(last_event) >= debounce)
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        for path in &settled {
+            pending.remove(path);
+        }
+        settled
+    }
 
-        let cleanup_threshold = self.debounce_duration * 10;
-        pending.retain(|_, &mut last_event| now.duration_since(last_event) < cleanup_threshold);
+    pub(crate) fn next_poll_interval(&self, now: Instant, debounce: Duration) -> Duration {
+        self.lock_pending()
+            .values()
+            .map(|&last_event| debounce.saturating_sub(now.duration_since(last_event)))
+            .min()
+            .unwrap_or(IDLE_POLL_INTERVAL)
     }
 }
 
+fn configured_debounce(app_state: &crate::core::state::AppState) -> Duration {
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    Duration::from_millis(config.preferences.watcher_debounce_ms)
+}
+
 pub fn setup_notes_watcher(
     app_handle: AppHandle,
     app_state: Arc<crate::core::state::AppState>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let canonical_notes_dir = setup_canonical_notes_directory()?;
-    let debounced_watcher = Arc::new(DebouncedWatcher::new(500));
+    let debounced_watcher = Arc::new(DebouncedWatcher::new());
     let (mut watcher, rx) = create_watcher_and_channel()?;
 
     watcher.watch(&canonical_notes_dir, RecursiveMode::Recursive)?;
@@ -102,7 +111,7 @@ fn setup_canonical_notes_directory() -> Result<PathBuf, Box<dyn std::error::Erro
     std::fs::create_dir_all(&notes_dir)?;
 
     let canonical_notes_dir = notes_dir.canonicalize().map_err(|e| {
-        log(
+        log_warn(
             "WATCHER_ERROR",
             &format!("Failed to resolve notes directory symlinks: {}", e),
             Some(&notes_dir.display().to_string()),
@@ -139,31 +148,31 @@ fn spawn_watcher_event_loop(
     rx: mpsc::Receiver<Event>,
     watcher: RecommendedWatcher,
 ) {
-    let app_handle_clone = app_handle.clone();
-    let debounced_watcher_clone = debounced_watcher.clone();
-    let app_state_clone = app_state.clone();
-    let canonical_notes_dir_for_processing = canonical_notes_dir.clone();
-
     thread::spawn(move || {
         let _watcher = watcher;
 
-        for event in rx {
-            match event.kind {
-                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {
-                    if involves_note_files(&event) {
-                        handle_file_system_event(
-                            &event,
-                            &app_state_clone,
-                            &debounced_watcher_clone,
-                            &app_handle_clone,
-                            &canonical_notes_dir_for_processing,
-                        );
+        loop {
+            // Re-read each pass so preference changes apply without restarting the watcher
+            let debounce = configured_debounce(&app_state);
+            let timeout = debounced_watcher.next_poll_interval(Instant::now(), debounce);
+
+            match rx.recv_timeout(timeout) {
+                Ok(event) => match event.kind {
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {
+                        if involves_note_files(&event, &app_state) {
+                            handle_file_system_event(&event, &app_state, &debounced_watcher);
+                        }
                     }
-                }
-                _ => {}
+                    _ => {}
+                },
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
             }
 
-            handle_periodic_cleanup(&debounced_watcher_clone);
+            let settled = debounced_watcher.take_settled_paths(Instant::now(), debounce);
+            if !settled.is_empty() {
+                process_file_event_async(settled, &app_handle, &app_state, &canonical_notes_dir);
+            }
         }
     });
 }
@@ -172,8 +181,6 @@ fn handle_file_system_event(
     event: &Event,
     app_state: &Arc<crate::core::state::AppState>,
     debounced_watcher: &Arc<DebouncedWatcher>,
-    app_handle: &AppHandle,
-    canonical_notes_dir: &PathBuf,
 ) {
     #[cfg(debug_assertions)]
     log(
@@ -186,46 +193,29 @@ fn handle_file_system_event(
         .programmatic_operation_in_progress()
         .load(Ordering::Relaxed);
 
-    #[cfg(debug_assertions)]
     if prog_op_count > 0 {
+        #[cfg(debug_assertions)]
         log(
             "WATCHER_EVENT",
             "⏸️  Skipping - programmatic operation in progress",
             None,
         );
+        return;
     }
 
-    if prog_op_count == 0 {
-        let should_process = event
-            .paths
-            .iter()
-            .any(|path| debounced_watcher.should_process_event(path));
-
-        #[cfg(debug_assertions)]
-        log(
-            "WATCHER_EVENT",
-            if should_process {
-                "✅ Processing event"
-            } else {
-                "⏭️  Skipping - debounced"
-            },
-            None,
-        );
-
-        if should_process {
-            process_file_event_async(event, app_handle, app_state, canonical_notes_dir);
-        }
+    let now = Instant::now();
+    for path in &event.paths {
+        debounced_watcher.record_event(path, now);
     }
 }
 
 fn process_file_event_async(
-    event: &Event,
+    paths_to_update: Vec<PathBuf>,
     app_handle: &AppHandle,
     app_state: &Arc<crate::core::state::AppState>,
     canonical_notes_dir: &PathBuf,
 ) {
     let app_handle_for_refresh = app_handle.clone();
-    let paths_to_update = event.paths.clone();
     let app_state_for_task = app_state.clone();
     let canonical_dir = canonical_notes_dir.clone();
 
@@ -237,8 +227,10 @@ fn process_file_event_async(
             None,
         );
 
-        process_file_paths(&paths_to_update, &canonical_dir, &app_state_for_task);
-        emit_cache_refresh_notification(&app_handle_for_refresh);
+        let changed = process_file_paths(&paths_to_update, &canonical_dir, &app_state_for_task);
+        if !changed.is_empty() {
+            emit_notes_changed(&app_handle_for_refresh, &changed);
+        }
     });
 }
 
@@ -258,17 +250,32 @@ fn create_watcher_and_channel(
     Ok((watcher, rx))
 }
 
-fn involves_note_files(event: &Event) -> bool {
-    event.paths.iter().any(|path| {
-        path.extension()
-            .and_then(|ext| ext.to_str())
-            .map(|ext| matches!(ext, "md" | "txt" | "markdown"))
-            .unwrap_or(false)
-    })
+fn involves_note_files(event: &Event, app_state: &crate::core::state::AppState) -> bool {
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    event
+        .paths
+        .iter()
+        .any(|path| has_note_extension(path, &config.preferences.note_extensions))
 }
 
-fn should_ignore_file(filename: &str) -> bool {
-    filename.contains("/.") || filename.starts_with('.')
+/// Hidden paths cover the `.archive/` folder, trash folders and editor lock files;
+/// the suffix checks catch swap and backup files written next to the note.
+pub(crate) fn should_ignore_file(filename: &str) -> bool {
+    if filename.contains("/.") || filename.starts_with('.') {
+        return true;
+    }
+
+    let name = filename.rsplit(['/', '\\']).next().unwrap_or(filename);
+    name.ends_with('~')
+        || name.ends_with(".swp")
+        || name.ends_with(".swx")
+        || name.ends_with(".tmp")
+        || (name.starts_with('#') && name.ends_with('#'))
+}
+
+fn within_max_depth(relative: &Path, app_state: &crate::core::state::AppState) -> bool {
+    let config = app_state.config.read().unwrap_or_else(|e| e.into_inner());
+    within_recursion_depth(relative, config.preferences.max_recursion_depth)
 }
 
 fn get_file_modification_time(path: &PathBuf) -> i64 {
@@ -307,7 +314,7 @@ fn create_backup_if_content_changed(
                             );
                         }
                         Err(e) => {
-                            log(
+                            log_warn(
                                 "FILE_BACKUP",
                                 &format!(
                                     "Failed to create external change backup for {}",
@@ -324,7 +331,7 @@ fn create_backup_if_content_changed(
         Ok(())
     })
     .unwrap_or_else(|e| {
-        log(
+        log_warn(
             "FILE_BACKUP",
             "Failed to check for existing content before external change backup",
             Some(&e.to_string()),
@@ -343,7 +350,7 @@ fn process_existing_file(
         create_backup_if_content_changed(path, filename, &content, app_state);
 
         if let Err(e) = update_note_in_database(app_state, filename, &content, modified) {
-            log(
+            log_warn(
                 "DATABASE_UPDATE",
                 &format!("Failed to update note {}", filename),
                 Some(&e.to_string()),
@@ -359,9 +366,11 @@ fn process_deleted_file(filename: &str, app_state: &Arc<crate::core::state::AppS
             rusqlite::params![filename],
         )
         .map_err(|e| format!("Database error: {}", e))?;
+        delete_note_tags(conn, filename).map_err(|e| format!("Database error: {}", e))?;
+        delete_note_hash(conn, filename).map_err(|e| format!("Database error: {}", e))?;
         Ok(())
     }) {
-        log(
+        log_warn(
             "DATABASE_DELETE",
             &format!("Failed to delete note {}", filename),
             Some(&e.to_string()),
@@ -369,11 +378,11 @@ fn process_deleted_file(filename: &str, app_state: &Arc<crate::core::state::AppS
     }
 }
 
-fn emit_cache_refresh_notification(app_handle: &AppHandle) {
-    if let Err(e) = app_handle.emit("cache-refreshed", ()) {
-        log(
+fn emit_notes_changed(app_handle: &AppHandle, filenames: &[String]) {
+    if let Err(e) = app_handle.emit("notes-changed", filenames) {
+        log_warn(
             "UI_EVENT",
-            "Failed to emit cache-refreshed event",
+            "Failed to emit notes-changed event",
             Some(&e.to_string()),
         );
     }
@@ -383,13 +392,19 @@ fn process_file_paths(
     paths: &[PathBuf],
     canonical_notes_dir: &PathBuf,
     app_state: &Arc<crate::core::state::AppState>,
-) {
+) -> Vec<String> {
+    let mut changed = Vec::new();
+    let note_ignore = NoteIgnore::load(canonical_notes_dir);
+
     for path in paths {
         match path.strip_prefix(canonical_notes_dir) {
             Ok(relative) => {
                 let filename = relative.to_string_lossy().to_string();
 
-                if should_ignore_file(&filename) {
+                if should_ignore_file(&filename)
+                    || !within_max_depth(relative, app_state)
+                    || note_ignore.is_ignored(relative, path.is_dir())
+                {
                     continue;
                 }
 
@@ -398,6 +413,7 @@ fn process_file_paths(
                 } else {
                     process_deleted_file(&filename, app_state);
                 }
+                changed.push(filename);
             }
             Err(_) => {
                 #[cfg(debug_assertions)]
@@ -412,16 +428,6 @@ fn process_file_paths(
             }
         }
     }
-}
 
-fn handle_periodic_cleanup(debounced_watcher: &Arc<DebouncedWatcher>) {
-    let counter = debounced_watcher
-        .cleanup_counter
-        .fetch_add(1, Ordering::Relaxed);
-    if counter >= 100 {
-        debounced_watcher.cleanup_old_events();
-        debounced_watcher
-            .cleanup_counter
-            .store(0, Ordering::Relaxed);
-    }
+    changed
 }
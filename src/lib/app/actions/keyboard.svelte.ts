@@ -6,6 +6,7 @@
 
 import type { ShortcutsConfig } from '../../types/config'
 import type { NoteMetadata } from '../../types/note'
+import { normalizeShortcut, shortcutFromEvent } from '../../utils/shortcuts'
 
 export interface KeyboardActionDeps {
   focusManager: ReturnType<
@@ -226,6 +227,9 @@ export function createKeyboardActions(
           actions.dialogManager.openRenameDialog(state.selectedNote)
         }
       },
+      duplicateNote: async ({ state, actions }: ActionContext) => {
+        await actions.noteActions.duplicateNote(state.selectedNote)
+      },
     },
 
     search: {
@@ -263,6 +267,9 @@ export function createKeyboardActions(
       openRecentlyDeleted: async ({ actions }: ActionContext) => {
         await actions.recentlyDeletedManager.openDialog()
       },
+      toggleAlwaysOnTop: async ({ actions }: ActionContext) => {
+        await actions.settingsActions.toggleAlwaysOnTop()
+      },
     },
   }
 
@@ -271,6 +278,7 @@ export function createKeyboardActions(
       [shortcuts.edit_note]: 'editing.enterEdit',
       [shortcuts.create_note]: 'notes.createNote',
       [shortcuts.rename_note]: 'notes.renameNote',
+      [shortcuts.duplicate_note]: 'notes.duplicateNote',
       [shortcuts.open_external]: 'notes.openExternal',
       [shortcuts.open_folder]: 'notes.openFolder',
       [shortcuts.refresh_cache]: 'notes.refreshCache',
@@ -293,6 +301,7 @@ export function createKeyboardActions(
       [shortcuts.open_settings]: 'settings.openSettings',
       [shortcuts.version_explorer]: 'settings.openVersionExplorer',
       [shortcuts.recently_deleted]: 'settings.openRecentlyDeleted',
+      [shortcuts.toggle_always_on_top]: 'settings.toggleAlwaysOnTop',
     }
   }
 
@@ -318,6 +327,7 @@ export function createKeyboardActions(
       [shortcuts.copy_current_section]: 'navigation.copyCurrentSection',
       [shortcuts.version_explorer]: 'settings.openVersionExplorer',
       [shortcuts.recently_deleted]: 'settings.openRecentlyDeleted',
+      [shortcuts.toggle_always_on_top]: 'settings.toggleAlwaysOnTop',
     }
   }
 
@@ -327,10 +337,12 @@ export function createKeyboardActions(
       ArrowDown: 'navigation.moveDown',
       Enter: 'editing.enterEdit',
       [shortcuts.create_note]: 'notes.createNote',
+      [shortcuts.duplicate_note]: 'notes.duplicateNote',
       [shortcuts.delete_note]: 'notes.deleteNote',
       Escape: 'navigation.focusSearch',
       [shortcuts.open_settings]: 'settings.openSettings',
       [shortcuts.recently_deleted]: 'settings.openRecentlyDeleted',
+      [shortcuts.toggle_always_on_top]: 'settings.toggleAlwaysOnTop',
     }
   }
 
@@ -345,16 +357,14 @@ export function createKeyboardActions(
     }
   }
 
-  function formatKeyCombo(event: KeyboardEvent): string {
-    const modifiers: string[] = []
-    if (event.ctrlKey) modifiers.push('Ctrl')
-    if (event.altKey) modifiers.push('Alt')
-    if (event.shiftKey) modifiers.push('Shift')
-    if (event.metaKey) modifiers.push('Meta')
-
-    return modifiers.length > 0
-      ? `${modifiers.join('+')}+${event.key}`
-      : event.key
+  // Both sides are normalized, so "Ctrl+M" in the config matches Ctrl and m
+  function findAction(
+    mappings: KeyMappings,
+    keyString: string
+  ): string | undefined {
+    return Object.entries(mappings).find(
+      ([shortcut]) => normalizeShortcut(shortcut) === keyString
+    )?.[1]
   }
 
   async function handleKeyAction(
@@ -362,8 +372,8 @@ export function createKeyboardActions(
     event: KeyboardEvent,
     context: ActionContext
   ): Promise<boolean> {
-    const keyString = formatKeyCombo(event)
-    const actionPath = mappings[keyString]
+    const keyString = shortcutFromEvent(event)
+    const actionPath = findAction(mappings, keyString)
 
     if (actionPath) {
       event.preventDefault()
@@ -29,8 +29,12 @@ interface NoteActions {
   createNote(noteNameParam?: string): Promise<void>
   deleteNote(selectedNote: string | null): Promise<void>
   renameNote(selectedNote: string | null, newNameParam?: string): Promise<void>
+  duplicateNote(selectedNote: string | null): Promise<void>
+  openNote(filename: string): Promise<void>
   enterEditMode(noteName: string): Promise<void>
   saveNote(): Promise<void>
+  forceSaveNote(): Promise<void>
+  dismissSaveConflict(): void
 }
 
 export function createNoteActions(deps: NoteActionDeps): NoteActions {
@@ -103,6 +107,37 @@ export function createNoteActions(deps: NoteActionDeps): NoteActions {
     }
   }
 
+  async function duplicateNote(selectedNote: string | null): Promise<void> {
+    if (!selectedNote) return
+
+    const result = await noteService.duplicate(selectedNote)
+
+    if (result.success) {
+      await searchManager.executeSearch(searchManager.searchInput)
+
+      const noteIndex = searchManager.filteredNotes.findIndex(
+        (note) => note.filename === result.newName
+      )
+      if (noteIndex >= 0) {
+        focusManager.setSelectedIndex(noteIndex)
+      }
+    }
+  }
+
+  async function openNote(filename: string): Promise<void> {
+    // Clear the query so the linked note is in the list even if it didn't match
+    if (searchManager.searchInput) {
+      await searchManager.executeSearch('')
+    }
+
+    const noteIndex = searchManager.filteredNotes.findIndex(
+      (note) => note.filename === filename
+    )
+    if (noteIndex >= 0) {
+      focusManager.setSelectedIndex(noteIndex)
+    }
+  }
+
   async function enterEditMode(noteName: string): Promise<void> {
     await editorManager.enterEditMode(noteName, contentManager.noteContent)
   }
@@ -110,6 +145,9 @@ export function createNoteActions(deps: NoteActionDeps): NoteActions {
   async function saveNote(): Promise<void> {
     const result = await editorManager.saveNote()
     if (!result.success) {
+      if (result.conflict) {
+        dialogManager.openSaveConflictDialog()
+      }
       console.error('Failed to save note:', result.error)
       return
     }
@@ -118,6 +156,23 @@ export function createNoteActions(deps: NoteActionDeps): NoteActions {
     await refreshSearchAfterSave()
   }
 
+  async function forceSaveNote(): Promise<void> {
+    dialogManager.closeSaveConflictDialog()
+    const result = await editorManager.forceSaveNote()
+    if (!result.success) {
+      console.error('Failed to force save note:', result.error)
+      return
+    }
+
+    searchManager.clearSearch()
+    await refreshSearchAfterSave()
+  }
+
+  function dismissSaveConflict(): void {
+    dialogManager.closeSaveConflictDialog()
+    editorManager.clearSaveConflict()
+  }
+
   async function refreshSearchAfterSave(): Promise<void> {
     const noteToRefresh = editorManager.editingNoteName
     if (!noteToRefresh) return
@@ -137,7 +192,11 @@ export function createNoteActions(deps: NoteActionDeps): NoteActions {
     createNote,
     deleteNote,
     renameNote,
+    duplicateNote,
+    openNote,
     enterEditMode,
     saveNote,
+    forceSaveNote,
+    dismissSaveConflict,
   }
 }
@@ -12,17 +12,22 @@ interface SettingsActionDeps {
   focusManager: {
     focusSearch: () => void
   }
+  windowService: {
+    isAlwaysOnTop: () => Promise<boolean>
+    setAlwaysOnTop: (enabled: boolean) => Promise<void>
+  }
 }
 
 interface SettingsActions {
   openSettingsPane(): Promise<void>
   closeSettingsPane(): void
+  toggleAlwaysOnTop(): Promise<void>
 }
 
 export function createSettingsActions(
   deps: SettingsActionDeps
 ): SettingsActions {
-  const { configManager, focusManager } = deps
+  const { configManager, focusManager, windowService } = deps
 
   async function openSettingsPane(): Promise<void> {
     await configManager.openPane()
@@ -34,8 +39,18 @@ export function createSettingsActions(
     focusManager.focusSearch()
   }
 
+  async function toggleAlwaysOnTop(): Promise<void> {
+    try {
+      const enabled = await windowService.isAlwaysOnTop()
+      await windowService.setAlwaysOnTop(!enabled)
+    } catch (e) {
+      console.error('Failed to toggle always on top:', e)
+    }
+  }
+
   return {
     openSettingsPane,
     closeSettingsPane,
+    toggleAlwaysOnTop,
   }
 }
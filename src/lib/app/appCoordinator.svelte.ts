@@ -23,13 +23,15 @@ import { createVersionExplorerManager } from '../core/versionExplorerManager.sve
 import { createRecentlyDeletedManager } from '../core/recentlyDeletedManager.svelte'
 import { noteService } from '../services/noteService.svelte'
 import { configService } from '../services/configService.svelte'
+import { windowService } from '../services/windowService.svelte'
 import { versionService } from '../services/versionService.svelte'
 import { createNoteActions } from './actions/note.svelte'
 import { createSearchActions } from './actions/search.svelte'
 import { createSettingsActions } from './actions/settings.svelte'
 import { createKeyboardActions } from './actions/keyboard.svelte'
 import { setupAppEffects } from './effects/app.svelte'
-import type { NoteMetadata } from '../types/note'
+import { loadMarkdownTheme } from '../utils/themeLoader'
+import type { NoteMetadata, RenderTheme } from '../types/note'
 
 // eslint-disable-next-line @typescript-eslint/no-empty-object-type
 interface AppCoordinatorDeps {}
@@ -46,8 +48,11 @@ export interface AppActions {
   deleteNote: () => Promise<void>
   createNote: (noteName?: string) => Promise<void>
   renameNote: (newName?: string) => Promise<void>
+  openNote: (filename: string) => Promise<void>
   saveNote: () => Promise<void>
   saveAndExitNote: () => Promise<void>
+  forceSaveNote: () => Promise<void>
+  dismissSaveConflict: () => void
   enterEditMode: () => Promise<void>
   exitEditMode: () => void
   refreshCacheAndUI: () => Promise<void>
@@ -117,11 +122,14 @@ export function createAppCoordinator(
   const contentNavigationManager = createContentNavigationManager({
     focusManager,
     searchManager,
+    openNote: (filename: string) => noteActions.openNote(filename),
   })
 
   const editorManager = createEditorManager({
     noteService,
     contentNavigationManager,
+    getStreamThresholdKb: () =>
+      configManager.preferences.stream_note_threshold_kb,
   })
 
   const dialogManager = createDialogManager({
@@ -148,6 +156,7 @@ export function createAppCoordinator(
   let contentRequestController: AbortController | null = null
   let contentRequestSequence = 0
   let currentLoadedNote: string | null = null
+  let noteRenderTheme: string | null = null
 
   let isFirstRun = false
 
@@ -190,6 +199,7 @@ export function createAppCoordinator(
   const settingsActions = createSettingsActions({
     configManager,
     focusManager,
+    windowService,
   })
 
   function exitEditMode(): void {
@@ -279,6 +289,31 @@ export function createAppCoordinator(
     }
   }
 
+  // A frontmatter render_theme replaces the configured markdown theme while
+  // its note is shown; other notes restore the configured one
+  async function applyNoteRenderTheme(note: string): Promise<void> {
+    let override: RenderTheme | null = null
+    try {
+      override = await noteService.getRenderTheme(note)
+    } catch (e) {
+      console.error('Failed to get note render theme:', e)
+    }
+    if (currentLoadedNote !== note) return
+
+    const themeName = override?.name ?? null
+    if (themeName === noteRenderTheme) return
+    noteRenderTheme = themeName
+
+    if (override) {
+      await loadMarkdownTheme(override.name, override.css ?? undefined)
+    } else {
+      await configManager.loadMarkdownTheme(
+        configManager.currentMarkdownTheme,
+        configManager.interface.custom_markdown_theme_path
+      )
+    }
+  }
+
   async function loadNoteContent(note: string): Promise<void> {
     abortPreviousContentRequest()
 
@@ -304,6 +339,10 @@ export function createAppCoordinator(
 
       if (isRequestStillValid(controller, currentSequence)) {
         scheduleScrollToFirstMatch(currentSequence)
+        void applyNoteRenderTheme(note)
+        if (isNoteSwitching) {
+          void noteService.recordOpened(note)
+        }
       }
     } catch (e) {
       await handleContentLoadError(e, controller, currentSequence)
@@ -312,6 +351,8 @@ export function createAppCoordinator(
 
   async function saveAndExitNote(): Promise<void> {
     await noteActions.saveNote()
+    // Stay in the editor so the user can resolve the conflict
+    if (editorManager.saveConflict) return
     exitEditMode()
     // An empty search shows notes in order
     // of most recent and we just saved it.
@@ -342,16 +383,19 @@ export function createAppCoordinator(
     unlisten: () => void
     unlistenCacheRefresh: () => void
     unlistenFirstRun: () => void
+    unlistenNewNote: () => void
     unlistenDbLoadingStart: () => void
     unlistenDbLoadingProgress: () => void
     unlistenDbLoadingComplete: () => void
     unlistenDbLoadingError: () => void
+    unlistenDatabaseRecovered: () => void
+    unlistenOpenPinnedNote: () => void
   }> {
     const unlisten = await listen('open-preferences', async () => {
       await settingsActions.openSettingsPane()
     })
 
-    const unlistenCacheRefresh = await listen('cache-refreshed', async () => {
+    const unlistenCacheRefresh = await listen('notes-changed', async () => {
       await refreshUI()
     })
 
@@ -359,6 +403,10 @@ export function createAppCoordinator(
       isFirstRun = true
     })
 
+    const unlistenNewNote = await listen('create-new-note', () => {
+      dialogManager.openCreateDialog()
+    })
+
     const unlistenDbLoadingStart = await listen<string>(
       'db-loading-start',
       (event) => {
@@ -387,14 +435,33 @@ export function createAppCoordinator(
       }
     )
 
+    const unlistenDatabaseRecovered = await listen<string>(
+      'database-recovered',
+      async (event) => {
+        const { notification } = await import('../utils/notification')
+        await notification.info(event.payload)
+      }
+    )
+
+    // Sent by the tray's Pinned Notes menu after it shows the window
+    const unlistenOpenPinnedNote = await listen<string>(
+      'open-pinned-note',
+      async (event) => {
+        await noteActions.openNote(event.payload)
+      }
+    )
+
     return {
       unlisten,
       unlistenCacheRefresh,
       unlistenFirstRun,
+      unlistenNewNote,
       unlistenDbLoadingStart,
       unlistenDbLoadingProgress,
       unlistenDbLoadingComplete,
       unlistenDbLoadingError,
+      unlistenDatabaseRecovered,
+      unlistenOpenPinnedNote,
     }
   }
 
@@ -413,6 +480,17 @@ export function createAppCoordinator(
     }
 
     if (!configExists) {
+      // Set up the default notes directory so the app is usable straight
+      // away, then show the settings so it can be changed
+      try {
+        const defaults = await configService.getActiveConfig()
+        await configService.initializeFirstRun(defaults.notes_directory)
+      } catch (e) {
+        console.error('Failed to set up first run:', e)
+        await notification.error(
+          'Failed to set up the notes directory. Please check the settings.'
+        )
+      }
       await settingsActions.openSettingsPane()
     } else {
       const result = await noteService.initializeDatabase()
@@ -437,10 +515,13 @@ export function createAppCoordinator(
       unlisten: () => void
       unlistenCacheRefresh: () => void
       unlistenFirstRun: () => void
+      unlistenNewNote: () => void
       unlistenDbLoadingStart: () => void
       unlistenDbLoadingProgress: () => void
       unlistenDbLoadingComplete: () => void
       unlistenDbLoadingError: () => void
+      unlistenDatabaseRecovered: () => void
+      unlistenOpenPinnedNote: () => void
     },
     cleanupEffects: () => void
   ): () => void {
@@ -454,10 +535,13 @@ export function createAppCoordinator(
       listeners.unlisten()
       listeners.unlistenCacheRefresh()
       listeners.unlistenFirstRun()
+      listeners.unlistenNewNote()
       listeners.unlistenDbLoadingStart()
       listeners.unlistenDbLoadingProgress()
       listeners.unlistenDbLoadingComplete()
       listeners.unlistenDbLoadingError()
+      listeners.unlistenDatabaseRecovered()
+      listeners.unlistenOpenPinnedNote()
       configManager.cleanup()
     }
   }
@@ -519,6 +603,8 @@ export function createAppCoordinator(
       contentManager,
       searchManager,
       contentNavigationManager,
+      editorManager,
+      configManager,
     })
   }
 
@@ -602,8 +688,11 @@ export function createAppCoordinator(
         createNote: noteActions.createNote,
         renameNote: (newName?: string) =>
           noteActions.renameNote(selectedNote, newName),
+        openNote: noteActions.openNote,
         saveNote: () => noteActions.saveNote(),
         saveAndExitNote,
+        forceSaveNote: () => noteActions.forceSaveNote(),
+        dismissSaveConflict: () => noteActions.dismissSaveConflict(),
         enterEditMode: () =>
           selectedNote
             ? noteActions.enterEditMode(selectedNote)
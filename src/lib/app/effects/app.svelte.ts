@@ -18,10 +18,23 @@ interface AppEffectsDeps {
   contentNavigationManager: {
     showHighlights(): void
   }
+  editorManager: {
+    readonly isEditMode: boolean
+    autosaveNote(): Promise<number | null>
+  }
+  configManager: {
+    readonly editor: { autosave_interval_seconds: number }
+  }
 }
 
 export function setupAppEffects(deps: AppEffectsDeps): () => void {
-  const { focusManager, searchManager, contentNavigationManager } = deps
+  const {
+    focusManager,
+    searchManager,
+    contentNavigationManager,
+    editorManager,
+    configManager,
+  } = deps
 
   $effect(() => {
     const selectedIndex = focusManager.selectedIndex
@@ -38,5 +51,16 @@ export function setupAppEffects(deps: AppEffectsDeps): () => void {
     }
   })
 
+  // Autosave timer runs only while editing; changing the interval restarts it
+  $effect(() => {
+    const intervalSeconds = configManager.editor.autosave_interval_seconds
+    if (!editorManager.isEditMode || intervalSeconds <= 0) return
+
+    const timer = setInterval(() => {
+      void editorManager.autosaveNote()
+    }, intervalSeconds * 1000)
+    return () => clearInterval(timer)
+  })
+
   return function cleanup(): void {}
 }
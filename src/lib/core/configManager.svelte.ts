@@ -1,6 +1,7 @@
 import { listen } from '@tauri-apps/api/event'
 import type {
   AppConfig,
+  AvailableThemes,
   GeneralConfig,
   InterfaceConfig,
   EditorConfig,
@@ -18,6 +19,7 @@ import { applyInterfaceConfig } from '../utils/cssVariables'
 export interface ConfigManagerDeps {
   configService: {
     refreshCache(): Promise<void>
+    getActiveConfig(): Promise<AppConfig>
     getGeneralConfig(): Promise<GeneralConfig>
     getInterfaceConfig(): Promise<InterfaceConfig>
     getEditorConfig(): Promise<EditorConfig>
@@ -30,6 +32,8 @@ export interface ConfigManagerDeps {
     loadCustomThemeFile(path: string): Promise<string>
     getConfigContent(): Promise<string>
     saveConfigContent(content: string): Promise<void>
+    openConfigInEditor(): Promise<void>
+    listThemeOptions(): Promise<AvailableThemes>
     initDefaults(): Promise<void>
   }
 }
@@ -79,6 +83,7 @@ export interface ConfigManager {
   closePane(): void
   updateContent(content: string): void
   saveConfig(): Promise<{ success: boolean; error?: string }>
+  openInExternalEditor(): Promise<{ success: boolean; error?: string }>
 }
 
 export function createConfigManager(deps: ConfigManagerDeps): ConfigManager {
@@ -103,10 +108,14 @@ export function createConfigManager(deps: ConfigManagerDeps): ConfigManager {
       tab_size: 0,
       expand_tabs: false,
       show_line_numbers: false,
+      autosave_interval_seconds: 0,
+      spellcheck_lang: '',
+      vim: { leader: '', mappings: {} },
     },
     shortcuts: {
       create_note: '',
       rename_note: '',
+      duplicate_note: '',
       delete_note: '',
       edit_note: '',
       save_and_exit: '',
@@ -127,9 +136,38 @@ export function createConfigManager(deps: ConfigManagerDeps): ConfigManager {
       open_settings: '',
       version_explorer: '',
       recently_deleted: '',
+      toggle_always_on_top: '',
     },
     preferences: {
       max_search_results: 0,
+      note_extensions: [],
+      search_normalize_diacritics: false,
+      search_title_boost: 0,
+      search_stemming: false,
+      watcher_debounce_ms: 0,
+      max_recursion_depth: 0,
+      follow_symlinks: false,
+      index_threads: 0,
+      trash_retention_days: 0,
+      version_history_limit: 0,
+      version_min_interval_seconds: 0,
+      max_pasted_image_mb: 0,
+      stream_note_threshold_kb: 0,
+      render_cache_max_entries: 0,
+      date_format: '',
+      time_format: '',
+      note_sort_order: '',
+      pinned_notes_first: false,
+      confirm_before_delete: false,
+      cache_journal_mode: '',
+      cache_synchronous: '',
+      line_ending: '',
+      trim_trailing_whitespace: false,
+      ensure_final_newline: false,
+      external_editor_command: '',
+      markdown_heading_anchors: false,
+      markdown_render_math: false,
+      markdown_flavor: 'gfm',
     },
     isLoading: false,
     error: null,
@@ -141,6 +179,8 @@ export function createConfigManager(deps: ConfigManagerDeps): ConfigManager {
   })
 
   let unlistenConfigChanged: (() => void) | null = null
+  let unlistenConfigReloaded: (() => void) | null = null
+  let unlistenEditorConfigChanged: (() => void) | null = null
 
   let validUIThemes: string[] = []
 
@@ -150,7 +190,12 @@ export function createConfigManager(deps: ConfigManagerDeps): ConfigManager {
       validUIThemes = themes.ui_themes
     } catch (error) {
       console.warn('Failed to fetch available themes, using defaults:', error)
-      validUIThemes = ['gruvbox-dark', 'article']
+      // An empty list skips theme validation rather than rejecting a theme
+      // that is installed
+      validUIThemes = await deps.configService
+        .listThemeOptions()
+        .then((options) => options.ui_themes)
+        .catch(() => [])
     }
   }
 
@@ -221,6 +266,7 @@ export function createConfigManager(deps: ConfigManagerDeps): ConfigManager {
   }
 
   async function loadAllConfigs(): Promise<{
+    activeConfig: AppConfig
     generalConfig: GeneralConfig
     interfaceConfig: InterfaceConfig
     editorConfig: EditorConfig
@@ -228,12 +274,14 @@ export function createConfigManager(deps: ConfigManagerDeps): ConfigManager {
     preferencesConfig: PreferencesConfig
   }> {
     const [
+      activeConfig,
       generalConfig,
       interfaceConfig,
       editorConfig,
       shortcutsConfig,
       preferencesConfig,
     ] = await Promise.all([
+      deps.configService.getActiveConfig(),
       deps.configService.getGeneralConfig(),
       deps.configService.getInterfaceConfig(),
       deps.configService.getEditorConfig(),
@@ -242,6 +290,7 @@ export function createConfigManager(deps: ConfigManagerDeps): ConfigManager {
     ])
 
     return {
+      activeConfig,
       generalConfig,
       interfaceConfig,
       editorConfig,
@@ -251,12 +300,17 @@ export function createConfigManager(deps: ConfigManagerDeps): ConfigManager {
   }
 
   function updateStateWithConfigs(configs: {
+    activeConfig: AppConfig
     generalConfig: GeneralConfig
     interfaceConfig: InterfaceConfig
     editorConfig: EditorConfig
     shortcutsConfig: ShortcutsConfig
     preferencesConfig: PreferencesConfig
   }): void {
+    // The sections come from their own commands, the top-level settings only
+    // from the sanitized config the backend is running with
+    state.notesDirectory = configs.activeConfig.notes_directory
+    state.globalShortcut = configs.activeConfig.global_shortcut
     state.general = configs.generalConfig
     state.interface = configs.interfaceConfig
     state.editor = configs.editorConfig
@@ -288,6 +342,19 @@ export function createConfigManager(deps: ConfigManagerDeps): ConfigManager {
         updateConfigState(event.payload)
       }
     )
+    unlistenConfigReloaded = await listen<AppConfig>(
+      'config-reloaded',
+      (event) => {
+        updateConfigState(event.payload)
+      }
+    )
+    // apply_editor_config pushes the editor settings without the rest
+    unlistenEditorConfigChanged = await listen<EditorConfig>(
+      'editor-config-changed',
+      (event) => {
+        state.editor = event.payload
+      }
+    )
   }
 
   async function initialize(): Promise<void> {
@@ -348,6 +415,14 @@ export function createConfigManager(deps: ConfigManagerDeps): ConfigManager {
       unlistenConfigChanged()
       unlistenConfigChanged = null
     }
+    if (unlistenConfigReloaded) {
+      unlistenConfigReloaded()
+      unlistenConfigReloaded = null
+    }
+    if (unlistenEditorConfigChanged) {
+      unlistenEditorConfigChanged()
+      unlistenEditorConfigChanged = null
+    }
 
     removeAllThemes()
 
@@ -399,6 +474,25 @@ export function createConfigManager(deps: ConfigManagerDeps): ConfigManager {
     }
   }
 
+  // The file is edited outside the app from here on, and the config watcher
+  // picks up the changes, so the pane closes rather than going stale
+  async function openInExternalEditor(): Promise<{
+    success: boolean
+    error?: string
+  }> {
+    state.error = null
+    try {
+      await deps.configService.openConfigInEditor()
+      closePane()
+      return { success: true }
+    } catch (e) {
+      const error = `Failed to open config in editor: ${e}`
+      state.error = error
+      console.error('Failed to open config in editor:', e)
+      return { success: false, error }
+    }
+  }
+
   return {
     // Reactive getters following existing manager patterns
     get notesDirectory() {
@@ -489,5 +583,6 @@ export function createConfigManager(deps: ConfigManagerDeps): ConfigManager {
     closePane,
     updateContent,
     saveConfig,
+    openInExternalEditor,
   }
 }
@@ -33,6 +33,7 @@ interface NavigationDeps {
     readonly searchInput: string
     clearSearch(): void
   }
+  openNote?(filename: string): void
 }
 
 export type EscapeAction =
@@ -513,6 +514,9 @@ export function createContentNavigationManager(
       contentElement.querySelectorAll('h1, h2, h3, h4, h5, h6')
     )
 
+    const anchoredMatch = headers.find((header) => header.id === sectionName)
+    if (anchoredMatch) return anchoredMatch
+
     const exactMatch = findExactHeaderMatch(headers, sectionName)
     if (exactMatch) return exactMatch
 
@@ -600,6 +604,13 @@ export function createContentNavigationManager(
   }
 
   function openCurrentLink(): void {
+    // Resolved wiki links carry the target note instead of a usable href
+    const linkedNote = state.linkElement?.getAttribute('data-note')
+    if (state.navigationMode === 'links' && linkedNote && deps.openNote) {
+      deps.openNote(linkedNote)
+      return
+    }
+
     const href = validateLinkForOpening()
     if (!href) return
 
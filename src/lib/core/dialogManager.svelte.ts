@@ -13,6 +13,7 @@ export interface DialogManager {
   readonly showRenameDialog: boolean
   readonly showDeleteDialog: boolean
   readonly showUnsavedChangesDialog: boolean
+  readonly showSaveConflictDialog: boolean
   newNoteName: string
   newNoteNameForRename: string
   readonly deleteKeyPressCount: number
@@ -24,6 +25,8 @@ export interface DialogManager {
   closeDeleteDialog(): void
   openUnsavedChangesDialog(): void
   closeUnsavedChangesDialog(): void
+  openSaveConflictDialog(): void
+  closeSaveConflictDialog(): void
   handleSaveAndExit(saveAndExitNote: () => Promise<void>): Promise<void>
   handleDiscardAndExit(exitEditMode: () => void): void
   handleDeleteKeyPress(onConfirmDelete: () => Promise<void>): void
@@ -34,6 +37,7 @@ export function createDialogManager(deps: DialogManagerDeps): DialogManager {
   let showRenameDialog = $state(false)
   let showDeleteDialog = $state(false)
   let showUnsavedChangesDialog = $state(false)
+  let showSaveConflictDialog = $state(false)
 
   let newNoteName = $state('')
   let newNoteNameForRename = $state('')
@@ -122,6 +126,14 @@ export function createDialogManager(deps: DialogManagerDeps): DialogManager {
     deps.focusSearch()
   }
 
+  function openSaveConflictDialog(): void {
+    showSaveConflictDialog = true
+  }
+
+  function closeSaveConflictDialog(): void {
+    showSaveConflictDialog = false
+  }
+
   async function handleSaveAndExit(
     saveAndExitNote: () => Promise<void>
   ): Promise<void> {
@@ -143,6 +155,8 @@ export function createDialogManager(deps: DialogManagerDeps): DialogManager {
     closeDeleteDialog,
     openUnsavedChangesDialog,
     closeUnsavedChangesDialog,
+    openSaveConflictDialog,
+    closeSaveConflictDialog,
     handleSaveAndExit,
     handleDiscardAndExit,
     handleDeleteKeyPress,
@@ -159,6 +173,9 @@ export function createDialogManager(deps: DialogManagerDeps): DialogManager {
     get showUnsavedChangesDialog(): boolean {
       return showUnsavedChangesDialog
     },
+    get showSaveConflictDialog(): boolean {
+      return showSaveConflictDialog
+    },
     get newNoteName(): string {
       return newNoteName
     },
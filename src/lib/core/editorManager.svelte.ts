@@ -8,6 +8,12 @@ import type { createNoteService } from '../services/noteService.svelte'
 import type { EditorView } from 'codemirror'
 import { convertHtmlToText } from '../utils/htmlParser'
 
+interface SaveConflict {
+  noteName: string
+  content: string
+  diskContent: string
+}
+
 interface EditorState {
   isEditMode: boolean
   editContent: string
@@ -17,11 +23,14 @@ interface EditorState {
   exitHeaderText: string
   exitCaptured: boolean
   editorView: EditorView | null
+  saveConflict: SaveConflict | null
+  isLoadingContent: boolean
 }
 
 interface SaveResult {
   success: boolean
   error?: string
+  conflict?: boolean
 }
 
 interface EditorManagerDeps {
@@ -29,6 +38,8 @@ interface EditorManagerDeps {
   contentNavigationManager: {
     getCurrentHeaderText(): string
   }
+  /** `stream_note_threshold_kb`; notes above it are loaded in chunks */
+  getStreamThresholdKb?: () => number
 }
 
 export interface EditorManager {
@@ -37,10 +48,17 @@ export interface EditorManager {
   readonly isDirty: boolean
   readonly nearestHeaderText: string
   readonly editingNoteName: string | null
+  readonly saveConflict: SaveConflict | null
+  /** True while a note above the stream threshold is still arriving */
+  readonly isLoadingContent: boolean
   enterEditMode(noteName: string, fallbackHtmlContent?: string): Promise<void>
   exitEditMode(): string
   updateContent(newContent: string): void
   saveNote(): Promise<SaveResult>
+  forceSaveNote(): Promise<SaveResult>
+  clearSaveConflict(): void
+  autosaveNote(): Promise<number | null>
+  pasteImage(image: Uint8Array): Promise<boolean>
   setExitHeaderText(headerText: string): void
   setEditorView(editorView: EditorView | null): void
   captureExitPosition(
@@ -49,6 +67,20 @@ export interface EditorManager {
   ): void
 }
 
+// Save conflicts arrive as { type: 'SaveConflict', details } from the backend
+function saveConflictDiskContent(error: unknown): string | null {
+  if (
+    typeof error === 'object' &&
+    error !== null &&
+    'type' in error &&
+    error.type === 'SaveConflict'
+  ) {
+    const { details } = error as { details: { disk_content: string } }
+    return details.disk_content
+  }
+  return null
+}
+
 export function createEditorManager(deps: EditorManagerDeps): EditorManager {
   const state = $state<EditorState>({
     isEditMode: false,
@@ -59,8 +91,14 @@ export function createEditorManager(deps: EditorManagerDeps): EditorManager {
     exitHeaderText: '',
     exitCaptured: false,
     editorView: null,
+    saveConflict: null,
+    isLoadingContent: false,
   })
 
+  // Bumped on every load and exit, so chunks of a note that is no longer
+  // being opened are dropped
+  let contentLoad = 0
+
   async function enterEditMode(
     noteName: string,
     fallbackHtmlContent?: string
@@ -95,8 +133,29 @@ export function createEditorManager(deps: EditorManagerDeps): EditorManager {
   }
 
   async function loadRawContentForEdit(noteName: string): Promise<void> {
-    const rawContent = await deps.noteService.getRawContent(noteName)
-    setEditState(rawContent, noteName)
+    const load = ++contentLoad
+    const thresholdKb = deps.getStreamThresholdKb?.() ?? 0
+    if (thresholdKb <= 0) {
+      const rawContent = await deps.noteService.getRawContent(noteName)
+      if (load === contentLoad) setEditState(rawContent, noteName)
+      return
+    }
+
+    // A note within the threshold arrives whole in the first chunk. A larger
+    // one is shown as its chunks arrive, and can't be saved until all have.
+    state.isLoadingContent = true
+    try {
+      const rawContent = await deps.noteService.getRawContent(
+        noteName,
+        thresholdKb * 1024,
+        (contentSoFar) => {
+          if (load === contentLoad) setEditState(contentSoFar, noteName)
+        }
+      )
+      if (load === contentLoad) setEditState(rawContent, noteName)
+    } finally {
+      if (load === contentLoad) state.isLoadingContent = false
+    }
   }
 
   function handleRawContentLoadFailure(
@@ -118,6 +177,8 @@ export function createEditorManager(deps: EditorManagerDeps): EditorManager {
 
   function exitEditMode(): string {
     const exitHeader = state.exitHeaderText
+    contentLoad += 1
+    state.isLoadingContent = false
     state.isEditMode = false
     state.editContent = ''
     state.originalContent = ''
@@ -222,6 +283,12 @@ export function createEditorManager(deps: EditorManagerDeps): EditorManager {
         error: 'No note being edited',
       }
     }
+    if (state.isLoadingContent) {
+      return {
+        success: false,
+        error: 'Note is still loading',
+      }
+    }
 
     try {
       await deps.noteService.save(
@@ -234,6 +301,21 @@ export function createEditorManager(deps: EditorManagerDeps): EditorManager {
 
       return { success: true }
     } catch (e) {
+      const diskContent = saveConflictDiskContent(e)
+      if (diskContent !== null) {
+        // Kept so the user can still overwrite after leaving edit mode
+        state.saveConflict = {
+          noteName: state.editingNoteName,
+          content: state.editContent,
+          diskContent,
+        }
+        return {
+          success: false,
+          error: 'Note was changed outside the editor',
+          conflict: true,
+        }
+      }
+
       const errorMessage = e instanceof Error ? e.message : 'Save failed'
       console.error('Failed to save note:', e)
 
@@ -244,6 +326,81 @@ export function createEditorManager(deps: EditorManagerDeps): EditorManager {
     }
   }
 
+  async function forceSaveNote(): Promise<SaveResult> {
+    const conflict = state.saveConflict
+    if (!conflict) {
+      return {
+        success: false,
+        error: 'No save conflict to resolve',
+      }
+    }
+
+    try {
+      await deps.noteService.forceSave(conflict.noteName, conflict.content)
+      state.saveConflict = null
+      if (state.editingNoteName === conflict.noteName) {
+        state.originalContent = conflict.content
+      }
+      return { success: true }
+    } catch (e) {
+      const errorMessage = e instanceof Error ? e.message : 'Save failed'
+      console.error('Failed to force save note:', e)
+
+      return {
+        success: false,
+        error: errorMessage,
+      }
+    }
+  }
+
+  function clearSaveConflict(): void {
+    state.saveConflict = null
+  }
+
+  async function autosaveNote(): Promise<number | null> {
+    const noteName = state.editingNoteName
+    const content = state.editContent
+    if (
+      !noteName ||
+      content === state.originalContent ||
+      state.isLoadingContent
+    ) {
+      return null
+    }
+
+    try {
+      const modified = await deps.noteService.autosave(noteName, content)
+      // Edit mode may have been left or switched to another note meanwhile
+      if (state.editingNoteName === noteName) {
+        state.originalContent = content
+      }
+      return modified
+    } catch (e) {
+      console.error('Failed to autosave note:', e)
+      return null
+    }
+  }
+
+  async function pasteImage(image: Uint8Array): Promise<boolean> {
+    const noteName = state.editingNoteName
+    if (!noteName || !state.editorView) {
+      return false
+    }
+
+    try {
+      const link = await deps.noteService.savePastedImage(noteName, image)
+      // The editor may have closed or moved to another note while saving
+      if (state.editingNoteName !== noteName || !state.editorView) {
+        return false
+      }
+      state.editorView.dispatch(state.editorView.state.replaceSelection(link))
+      return true
+    } catch (e) {
+      console.error('Failed to paste image:', e)
+      return false
+    }
+  }
+
   return {
     get isEditMode() {
       return state.isEditMode
@@ -265,10 +422,22 @@ export function createEditorManager(deps: EditorManagerDeps): EditorManager {
       return state.editingNoteName
     },
 
+    get isLoadingContent() {
+      return state.isLoadingContent
+    },
+
+    get saveConflict() {
+      return state.saveConflict
+    },
+
     enterEditMode,
     exitEditMode,
     updateContent,
     saveNote,
+    forceSaveNote,
+    clearSaveConflict,
+    autosaveNote,
+    pasteImage,
     setExitHeaderText,
     setEditorView,
     captureExitPosition,
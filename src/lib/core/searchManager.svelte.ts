@@ -4,7 +4,10 @@
  * Handles hybrid search queries to Rust backend and search state management.
  */
 
-import type { createNoteService } from '../services/noteService.svelte'
+import {
+  SEARCH_SUPERSEDED,
+  type createNoteService,
+} from '../services/noteService.svelte'
 import type { NoteMetadata } from '../types/note'
 
 /** Minimum characters required before triggering a filtered search query */
@@ -94,7 +97,8 @@ export function createSearchManager(deps: SearchManagerDeps): SearchManager {
   }
 
   function handleSearchError(e: unknown, controller: AbortController): void {
-    if (!controller.signal.aborted) {
+    // A superseded search leaves the results to the newer one
+    if (!controller.signal.aborted && e !== SEARCH_SUPERSEDED) {
       console.error('❌ Failed to load notes:', e)
       deps.progressManager.setError('Failed to search notes')
       handleFailedSearch()
@@ -12,6 +12,9 @@ import type {
   EditorConfig,
   ShortcutsConfig,
   PreferencesConfig,
+  ConfigWarning,
+  DirectoryHealth,
+  AvailableThemes,
 } from '../types/config'
 
 interface ConfigServiceState {
@@ -23,11 +26,13 @@ export interface ConfigService {
   readonly isLoading: boolean
   readonly error: string | null
   exists(): Promise<boolean>
-  refreshCache(): Promise<void>
+  initializeFirstRun(notesDirectory: string): Promise<AppConfig>
+  refreshCache(force?: boolean): Promise<void>
   clearError(): void
   getGeneralConfig(): Promise<GeneralConfig>
   getInterfaceConfig(): Promise<InterfaceConfig>
   getEditorConfig(): Promise<EditorConfig>
+  applyEditorConfig(): Promise<EditorConfig>
   getShortcutsConfig(): Promise<ShortcutsConfig>
   getPreferencesConfig(): Promise<PreferencesConfig>
   getAvailableThemes(): Promise<{
@@ -35,8 +40,14 @@ export interface ConfigService {
     markdown_themes: string[]
   }>
   loadCustomThemeFile(path: string): Promise<string>
+  getActiveConfig(): Promise<AppConfig>
   getConfigContent(): Promise<string>
   saveConfigContent(content: string): Promise<void>
+  openConfigInEditor(): Promise<void>
+  validateConfigContent(content: string): Promise<ConfigWarning[]>
+  checkNotesDirectoryHealth(path: string): Promise<DirectoryHealth>
+  listThemeOptions(): Promise<AvailableThemes>
+  getMarkdownExtensions(): Promise<string[]>
   initDefaults(): Promise<void>
   getDefaultConfig(): AppConfig
 }
@@ -65,6 +76,16 @@ export function createConfigService(): ConfigService {
     return await invoke<boolean>('config_exists')
   }
 
+  async function initializeFirstRun(
+    notesDirectory: string
+  ): Promise<AppConfig> {
+    return await invoke<AppConfig>('initialize_first_run', { notesDirectory })
+  }
+
+  async function getActiveConfig(): Promise<AppConfig> {
+    return await invoke<AppConfig>('get_active_config')
+  }
+
   async function getConfigContent(): Promise<string> {
     return await invoke<string>('get_config_content')
   }
@@ -73,9 +94,38 @@ export function createConfigService(): ConfigService {
     await invoke<void>('save_config_content', { content })
   }
 
-  async function refreshCache(): Promise<void> {
+  async function openConfigInEditor(): Promise<void> {
+    await invoke<void>('open_config_in_editor')
+  }
+
+  async function validateConfigContent(
+    content: string
+  ): Promise<ConfigWarning[]> {
+    return await invoke<ConfigWarning[]>('validate_config_content', { content })
+  }
+
+  async function checkNotesDirectoryHealth(
+    path: string
+  ): Promise<DirectoryHealth> {
+    return await invoke<DirectoryHealth>('check_notes_directory_health', {
+      path,
+    })
+  }
+
+  // Every theme the config accepts, unlike getAvailableThemes which scans the
+  // bundled stylesheets
+  async function listThemeOptions(): Promise<AvailableThemes> {
+    return await invoke<AvailableThemes>('get_available_themes')
+  }
+
+  // Extensions the renderer applies under the configured markdown_flavor
+  async function getMarkdownExtensions(): Promise<string[]> {
+    return await invoke<string[]>('get_markdown_extensions')
+  }
+
+  async function refreshCache(force = false): Promise<void> {
     try {
-      await invoke<void>('refresh_cache')
+      await invoke<void>('refresh_cache', { force })
     } catch (e) {
       console.error('Failed to refresh cache:', e)
       throw e
@@ -113,6 +163,10 @@ export function createConfigService(): ConfigService {
     }
   }
 
+  async function applyEditorConfig(): Promise<EditorConfig> {
+    return await invoke<EditorConfig>('apply_editor_config')
+  }
+
   async function getShortcutsConfig(): Promise<ShortcutsConfig> {
     try {
       return await invoke<ShortcutsConfig>('get_shortcuts_config')
@@ -133,6 +187,7 @@ export function createConfigService(): ConfigService {
 
   return {
     exists,
+    initializeFirstRun,
     refreshCache,
     clearError,
     initDefaults,
@@ -140,10 +195,17 @@ export function createConfigService(): ConfigService {
     getGeneralConfig,
     getInterfaceConfig,
     getEditorConfig,
+    applyEditorConfig,
     getShortcutsConfig,
     getPreferencesConfig,
+    getActiveConfig,
     getConfigContent,
     saveConfigContent,
+    openConfigInEditor,
+    validateConfigContent,
+    checkNotesDirectoryHealth,
+    listThemeOptions,
+    getMarkdownExtensions,
 
     get isLoading(): boolean {
       return state.isLoading
@@ -165,9 +227,10 @@ export function createConfigService(): ConfigService {
         return result
       } catch (error) {
         console.error('Failed to scan available themes:', error)
+        const options = await listThemeOptions()
         return {
-          ui_themes: ['gruvbox-dark', 'article', 'modern-dark'],
-          markdown_themes: ['modern-dark', 'article', 'gruvbox-dark'],
+          ui_themes: options.ui_themes,
+          markdown_themes: options.markdown_themes,
         }
       }
     },
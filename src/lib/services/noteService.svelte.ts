@@ -6,13 +6,34 @@
 
 import { invoke } from '@tauri-apps/api/core'
 import { notification } from '../utils/notification'
-import type { NoteMetadata } from '../types/note'
+import type {
+  FolderNode,
+  LineMatch,
+  NoteChunk,
+  NoteMetadata,
+  RenderTheme,
+  ReplaceResult,
+  SpellIssue,
+  TocEntry,
+} from '../types/note'
+
+/** Rejection from `search` when a newer search was sent before it finished */
+export const SEARCH_SUPERSEDED = 'superseded'
+
+/** Bytes requested per `read_note_chunk` call once a note is being streamed */
+export const NOTE_CHUNK_BYTES = 512 * 1024
 
 export function createNoteService() {
   const state = $state({
     isLoading: false,
     error: null as string | null,
-    lastOperation: null as 'create' | 'delete' | 'rename' | null,
+    lastOperation: null as
+      | 'create'
+      | 'delete'
+      | 'rename'
+      | 'move'
+      | 'duplicate'
+      | null,
   })
 
   function clearError(): void {
@@ -54,7 +75,12 @@ export function createNoteService() {
     state.lastOperation = 'delete'
 
     try {
-      await invoke<void>('delete_note', { noteName })
+      // The UI has already asked the user, so confirm straight away
+      const confirmationToken = await invoke<string>(
+        'request_delete_confirmation',
+        { noteName }
+      )
+      await invoke<void>('delete_note', { noteName, confirmationToken })
       return { success: true }
     } catch (e) {
       const error = `Failed to delete note: ${e}`
@@ -93,6 +119,75 @@ export function createNoteService() {
     }
   }
 
+  async function move(
+    noteName: string,
+    targetDir: string
+  ): Promise<{ success: boolean; newName?: string; error?: string }> {
+    if (!noteName) return { success: false, error: 'Note name cannot be empty' }
+
+    state.isLoading = true
+    state.error = null
+    state.lastOperation = 'move'
+
+    try {
+      const newName = await invoke<string>('move_note', {
+        name: noteName,
+        targetDir,
+      })
+      return { success: true, newName }
+    } catch (e) {
+      const error = `Failed to move note: ${e}`
+      state.error = error
+      console.error('Failed to move note:', e)
+      notification.error(`Move failed: ${e}`)
+      return { success: false, error }
+    } finally {
+      state.isLoading = false
+    }
+  }
+
+  async function createFolder(relativePath: string): Promise<string> {
+    try {
+      return await invoke<string>('create_folder', { relativePath })
+    } catch (e) {
+      console.error('Failed to create folder:', e)
+      notification.error(`Create folder failed: ${e}`)
+      throw e
+    }
+  }
+
+  async function listFolders(): Promise<FolderNode[]> {
+    try {
+      return await invoke<FolderNode[]>('list_folders')
+    } catch (e) {
+      console.error('Failed to list folders:', e)
+      throw e
+    }
+  }
+
+  async function duplicate(
+    noteName: string
+  ): Promise<{ success: boolean; newName?: string; error?: string }> {
+    if (!noteName) return { success: false, error: 'Note name cannot be empty' }
+
+    state.isLoading = true
+    state.error = null
+    state.lastOperation = 'duplicate'
+
+    try {
+      const newName = await invoke<string>('duplicate_note', { name: noteName })
+      return { success: true, newName }
+    } catch (e) {
+      const error = `Failed to duplicate note: ${e}`
+      state.error = error
+      console.error('Failed to duplicate note:', e)
+      notification.error(`Duplicate failed: ${e}`)
+      return { success: false, error }
+    } finally {
+      state.isLoading = false
+    }
+  }
+
   async function getContent(noteName: string): Promise<string> {
     try {
       return await invoke<string>('get_note_html_content', { noteName })
@@ -102,15 +197,178 @@ export function createNoteService() {
     }
   }
 
-  async function getRawContent(noteName: string): Promise<string> {
+  // Null when the note has no valid frontmatter render_theme
+  async function getRenderTheme(noteName: string): Promise<RenderTheme | null> {
+    return await invoke<RenderTheme | null>('get_note_render_theme', {
+      name: noteName,
+    })
+  }
+
+  async function getToc(noteName: string): Promise<TocEntry[]> {
+    return await invoke<TocEntry[]>('get_note_toc', { name: noteName })
+  }
+
+  async function findInNote(
+    noteName: string,
+    query: string,
+    options: { wholeWord?: boolean; caseSensitive?: boolean } = {}
+  ): Promise<LineMatch[]> {
+    return await invoke<LineMatch[]>('search_within_note', {
+      name: noteName,
+      query,
+      wholeWord: options.wholeWord ?? false,
+      caseSensitive: options.caseSensitive ?? false,
+    })
+  }
+
+  async function replaceAcrossNotes(
+    find: string,
+    replace: string,
+    options: { regex?: boolean; dryRun?: boolean } = {}
+  ): Promise<ReplaceResult[]> {
+    return await invoke<ReplaceResult[]>('replace_across_notes', {
+      find,
+      replace,
+      regex: options.regex ?? false,
+      dryRun: options.dryRun ?? false,
+    })
+  }
+
+  // Tags as [label, note count], most used first
+  async function listAllTags(): Promise<[string, number][]> {
+    return await invoke<[string, number][]>('list_all_tags')
+  }
+
+  async function getRawContent(
+    noteName: string,
+    streamAboveBytes?: number,
+    onChunk?: (contentSoFar: string) => void
+  ): Promise<string> {
     try {
-      return await invoke<string>('get_note_content', { noteName })
+      if (streamAboveBytes === undefined) {
+        return await invoke<string>('get_note_content', { noteName })
+      }
+      return await readNoteInChunks(noteName, streamAboveBytes, onChunk)
     } catch (e) {
       console.error('Failed to get raw note content:', e)
       throw e
     }
   }
 
+  // The first chunk covers any note up to the threshold in one call; larger
+  // notes are read from disk piece by piece so no single reply is huge, and
+  // `onChunk` sees the content so far after every chunk but the last
+  async function readNoteInChunks(
+    noteName: string,
+    firstChunkBytes: number,
+    onChunk?: (contentSoFar: string) => void
+  ): Promise<string> {
+    let content = ''
+    let offset = 0
+    let len = firstChunkBytes
+    for (;;) {
+      const chunk = await invoke<NoteChunk>('read_note_chunk', {
+        name: noteName,
+        offset,
+        len,
+      })
+      content += chunk.content
+      if (chunk.eof) return content
+      onChunk?.(content)
+      offset = chunk.next_offset
+      len = NOTE_CHUNK_BYTES
+    }
+  }
+
+  async function forceSave(noteName: string, content: string): Promise<void> {
+    try {
+      await invoke<void>('force_save_note', { name: noteName, content })
+    } catch (e) {
+      console.error('Failed to force save note:', e)
+      throw e
+    }
+  }
+
+  async function autosave(noteName: string, content: string): Promise<number> {
+    try {
+      return await invoke<number>('autosave_note', { name: noteName, content })
+    } catch (e) {
+      console.error('Failed to autosave note:', e)
+      throw e
+    }
+  }
+
+  async function append(
+    noteName: string,
+    text: string,
+    addTimestamp = false
+  ): Promise<number> {
+    try {
+      return await invoke<number>('append_to_note', {
+        name: noteName,
+        text,
+        addTimestamp,
+      })
+    } catch (e) {
+      console.error('Failed to append to note:', e)
+      throw e
+    }
+  }
+
+  async function savePastedImage(
+    noteName: string,
+    image: Uint8Array
+  ): Promise<string> {
+    try {
+      return await invoke<string>('save_pasted_image', {
+        note: noteName,
+        pngBytes: Array.from(image),
+      })
+    } catch (e) {
+      console.error('Failed to save pasted image:', e)
+      notification.error(`Paste image failed: ${e}`)
+      throw e
+    }
+  }
+
+  async function copyAsHtml(noteName: string): Promise<void> {
+    try {
+      await invoke<void>('copy_note_as_html', { name: noteName })
+    } catch (e) {
+      console.error('Failed to copy note as HTML:', e)
+      notification.error(`Copy failed: ${e}`)
+      throw e
+    }
+  }
+
+  // An empty lang uses editor.spellcheck_lang
+  async function spellcheck(text: string, lang = ''): Promise<SpellIssue[]> {
+    try {
+      return await invoke<SpellIssue[]>('spellcheck_text', { text, lang })
+    } catch (e) {
+      console.error('Failed to spellcheck text:', e)
+      throw e
+    }
+  }
+
+  async function recordOpened(noteName: string): Promise<void> {
+    try {
+      await invoke<void>('record_note_opened', { name: noteName })
+    } catch (e) {
+      // Recents are best effort and never block showing the note
+      console.error('Failed to record opened note:', e)
+    }
+  }
+
+  async function listRecent(limit: number): Promise<string[]> {
+    try {
+      return await invoke<string[]>('list_recent_notes', { limit })
+    } catch (e) {
+      console.error('Failed to list recent notes:', e)
+      throw e
+    }
+  }
+
   async function save(
     noteName: string,
     content: string,
@@ -146,11 +404,27 @@ export function createNoteService() {
     }
   }
 
-  async function search(query: string): Promise<NoteMetadata[]> {
+  // Every search gets a higher generation, so the backend can tell which
+  // in-flight searches have been overtaken and reject them as superseded.
+  // It starts from the clock rather than zero, or after a reload every search
+  // would look older than the ones the backend saw before it.
+  let searchGeneration = Date.now()
+
+  async function search(
+    query: string,
+    pathPrefix?: string
+  ): Promise<NoteMetadata[]> {
+    searchGeneration += 1
     try {
-      return await invoke<NoteMetadata[]>('search_notes', { query })
+      return await invoke<NoteMetadata[]>('search_notes', {
+        query,
+        pathPrefix,
+        generation: searchGeneration,
+      })
     } catch (e) {
-      console.error('Failed to search notes:', e)
+      if (e !== SEARCH_SUPERSEDED) {
+        console.error('Failed to search notes:', e)
+      }
       throw e
     }
   }
@@ -179,10 +453,27 @@ export function createNoteService() {
     create,
     delete: deleteNote,
     rename,
+    move,
+    duplicate,
+    createFolder,
+    listFolders,
 
     getContent,
+    getRenderTheme,
+    getToc,
+    findInNote,
+    replaceAcrossNotes,
+    listAllTags,
     getRawContent,
+    autosave,
+    append,
+    savePastedImage,
+    copyAsHtml,
+    spellcheck,
+    recordOpened,
+    listRecent,
     save,
+    forceSave,
 
     search,
 
@@ -5,7 +5,12 @@
  */
 
 import { invoke } from '@tauri-apps/api/core'
-import type { NoteVersion, DeletedFile } from '../types/note'
+import type {
+  NoteVersion,
+  DeletedFile,
+  DiffHunk,
+  VersionMatch,
+} from '../types/note'
 
 export function createVersionService() {
   const state = $state({
@@ -84,6 +89,32 @@ export function createVersionService() {
     }
   }
 
+  async function searchVersions(query: string): Promise<VersionMatch[]> {
+    try {
+      return await invoke<VersionMatch[]>('search_note_versions', { query })
+    } catch (e) {
+      console.error('Failed to search note versions:', e)
+      throw e
+    }
+  }
+
+  async function diffVersions(
+    noteName: string,
+    versionA: string,
+    versionB: string
+  ): Promise<DiffHunk[]> {
+    try {
+      return await invoke<DiffHunk[]>('diff_note_versions', {
+        name: noteName,
+        versionA,
+        versionB,
+      })
+    } catch (e) {
+      console.error('Failed to diff versions:', e)
+      throw e
+    }
+  }
+
   async function getDeletedFiles(): Promise<{
     success: boolean
     files?: DeletedFile[]
@@ -140,6 +171,8 @@ export function createVersionService() {
   return {
     getVersions,
     getVersionContent,
+    searchVersions,
+    diffVersions,
     recoverVersion,
 
     getDeletedFiles,
@@ -0,0 +1,41 @@
+/**
+ * Service Layer - Window Service
+ * Pure backend wrapper for main window operations.
+ * Provides invoke wrappers for window state that can change during a session.
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+
+export interface PermissionStatus {
+  accessibility: boolean
+  automation: boolean
+}
+
+export interface WindowService {
+  isAlwaysOnTop(): Promise<boolean>
+  setAlwaysOnTop(enabled: boolean): Promise<void>
+  checkMacosPermissions(): Promise<PermissionStatus>
+}
+
+export function createWindowService(): WindowService {
+  async function isAlwaysOnTop(): Promise<boolean> {
+    return await invoke<boolean>('is_always_on_top')
+  }
+
+  async function setAlwaysOnTop(enabled: boolean): Promise<void> {
+    await invoke<void>('set_always_on_top', { enabled })
+  }
+
+  // Always fully granted outside macOS
+  async function checkMacosPermissions(): Promise<PermissionStatus> {
+    return await invoke<PermissionStatus>('check_macos_permissions')
+  }
+
+  return {
+    isAlwaysOnTop,
+    setAlwaysOnTop,
+    checkMacosPermissions,
+  }
+}
+
+export const windowService = createWindowService()
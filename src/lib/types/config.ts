@@ -23,11 +23,22 @@ export interface EditorConfig {
   tab_size: number
   expand_tabs: boolean
   show_line_numbers: boolean
+  autosave_interval_seconds: number
+  spellcheck_lang: string
+  spellcheck_dictionary_dir?: string
+  vim: VimConfig
+}
+
+export interface VimConfig {
+  leader: string
+  // Normal mode key sequence to editor action, e.g. '<leader>w' -> 'save'
+  mappings: Record<string, string>
 }
 
 export interface ShortcutsConfig {
   create_note: string
   rename_note: string
+  duplicate_note: string
   delete_note: string
   edit_note: string
   save_and_exit: string
@@ -48,18 +59,78 @@ export interface ShortcutsConfig {
   open_settings: string
   version_explorer: string
   recently_deleted: string
+  toggle_always_on_top: string
 }
 
 export interface PreferencesConfig {
   max_search_results: number
+  note_extensions: string[]
+  search_normalize_diacritics: boolean
+  search_title_boost: number
+  search_stemming: boolean
+  watcher_debounce_ms: number
+  max_recursion_depth: number
+  follow_symlinks: boolean
+  index_threads: number
+  trash_retention_days: number
+  version_history_limit: number
+  version_min_interval_seconds: number
+  max_pasted_image_mb: number
+  stream_note_threshold_kb: number
+  render_cache_max_entries: number
+  date_format: string
+  time_format: string
+  note_sort_order: string
+  pinned_notes_first: boolean
+  confirm_before_delete: boolean
+  cache_journal_mode: string
+  cache_synchronous: string
+  line_ending: string
+  trim_trailing_whitespace: boolean
+  ensure_final_newline: boolean
+  external_editor_command: string
+  cache_database_path?: string
+  markdown_heading_anchors: boolean
+  markdown_render_math: boolean
+  markdown_flavor: string
+}
+
+export interface ConfigWarning {
+  field: string
+  value: string
+  default: string
+}
+
+export interface DirectoryHealth {
+  path: string
+  exists: boolean
+  is_directory: boolean
+  writable: boolean
+  cloud_sync_provider: string | null
+  note_count: number
+  write_error: string | null
+}
+
+export interface AvailableThemes {
+  ui_themes: string[]
+  markdown_themes: string[]
+  editor_themes: string[]
+  code_themes: string[]
+}
+
+export interface LoggingConfig {
+  max_file_size_mb: number
+  max_archives: number
 }
 
 export interface AppConfig {
   notes_directory: string
   global_shortcut: string
+  global_shortcut_new_note?: string | null
   general: GeneralConfig
   interface: InterfaceConfig
   editor: EditorConfig
   shortcuts: ShortcutsConfig
   preferences: PreferencesConfig
+  logging: LoggingConfig
 }
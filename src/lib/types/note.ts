@@ -1,6 +1,21 @@
 export interface NoteMetadata {
   filename: string
   modified: number
+  // When the note was first seen, kept through edits unlike `modified`
+  created_at?: number
+  highlights?: SearchHighlights
+}
+
+export interface SearchHighlights {
+  match_count: number
+  snippets: string[]
+}
+
+export interface FolderNode {
+  name: string
+  // Relative to the notes directory, `/` separated
+  path: string
+  children: FolderNode[]
 }
 
 export interface NoteVersion {
@@ -11,6 +26,71 @@ export interface NoteVersion {
   formatted_time: string
 }
 
+export interface VersionMatch {
+  filename: string
+  // Backup file for get_version_content; null when the current content matched
+  version_filename: string | null
+  timestamp: number
+  highlights: SearchHighlights
+}
+
+export interface DiffHunk {
+  tag: 'equal' | 'insert' | 'delete'
+  old_start: number
+  new_start: number
+  lines: string[]
+}
+
+export interface RenderTheme {
+  name: string
+  // Stylesheet of a user theme; null for themes shipped with the app
+  css: string | null
+}
+
+export interface TocEntry {
+  level: number
+  text: string
+  // Id of the rendered heading anchor; null when the heading has none
+  slug: string | null
+}
+
+export interface SpellIssue {
+  word: string
+  // UTF-16 offsets into the checked text, like string indices
+  start: number
+  end: number
+  suggestions: string[]
+}
+
+export interface LineMatch {
+  // 1-based line number
+  line: number
+  // UTF-16 offsets within the line, like string indices
+  start: number
+  end: number
+}
+
+export interface ReplacePreviewLine {
+  // 1-based line number
+  line: number
+  before: string
+  after: string
+}
+
+export interface ReplaceResult {
+  filename: string
+  replacements: number
+  // The first changed lines, only sent by a dry run
+  preview?: ReplacePreviewLine[]
+}
+
+export interface NoteChunk {
+  content: string
+  // Byte offset to read the next chunk from
+  next_offset: number
+  eof: boolean
+}
+
 export interface DeletedFile {
   filename: string
   backup_filename: string
@@ -0,0 +1,97 @@
+// Canonical modifier names in output order, with the spellings accepted for
+// each. Mirrors normalize_shortcut in the backend's config helpers.
+const MODIFIERS: [string, string[]][] = [
+  ['Ctrl', ['ctrl', 'control']],
+  ['Alt', ['alt', 'option']],
+  ['Shift', ['shift']],
+  ['Meta', ['meta', 'cmd', 'command', 'super']],
+]
+const CTRL = 0
+const SHIFT = 2
+const META = 3
+
+const CMD_OR_CTRL_ALIASES = [
+  'cmdorctrl',
+  'cmdorcontrol',
+  'commandorctrl',
+  'commandorcontrol',
+]
+
+const MULTI_WORD_KEYS = [
+  'ArrowUp',
+  'ArrowDown',
+  'ArrowLeft',
+  'ArrowRight',
+  'PageUp',
+  'PageDown',
+  'CapsLock',
+  'NumLock',
+  'ScrollLock',
+  'PrintScreen',
+  'PauseBreak',
+]
+
+function isMac(): boolean {
+  return typeof navigator !== 'undefined' && /Mac/.test(navigator.platform)
+}
+
+function normalizeKey(key: string, shifted: boolean): string {
+  if ([...key].length === 1) {
+    // KeyboardEvent.key reports the shifted form of a letter
+    return shifted ? key.toUpperCase() : key.toLowerCase()
+  }
+  const named = MULTI_WORD_KEYS.find(
+    (name) => name.toLowerCase() === key.toLowerCase()
+  )
+  if (named) {
+    return named
+  }
+  return key.charAt(0).toUpperCase() + key.slice(1).toLowerCase()
+}
+
+/**
+ * Rewrites a shortcut so equivalent spellings compare equal, the same way the
+ * backend stores them: modifiers are deduplicated and ordered Ctrl, Alt,
+ * Shift, Meta, CmdOrCtrl becomes this platform's modifier, and a letter is
+ * uppercase only with Shift. "shift+ctrl+n" becomes "Ctrl+Shift+N".
+ */
+export function normalizeShortcut(shortcut: string): string {
+  const modifiers: number[] = []
+  const keys: string[] = []
+
+  for (const token of shortcut.split('+')) {
+    const trimmed = token.trim()
+    if (!trimmed) continue
+
+    const lowercase = trimmed.toLowerCase()
+    const index = CMD_OR_CTRL_ALIASES.includes(lowercase)
+      ? isMac()
+        ? META
+        : CTRL
+      : MODIFIERS.findIndex(([, aliases]) => aliases.includes(lowercase))
+    if (index === -1) {
+      keys.push(trimmed)
+    } else if (!modifiers.includes(index)) {
+      modifiers.push(index)
+    }
+  }
+
+  modifiers.sort((a, b) => a - b)
+  const shifted = modifiers.includes(SHIFT)
+  return [
+    ...modifiers.map((index) => MODIFIERS[index][0]),
+    ...keys.map((key) => normalizeKey(key, shifted)),
+  ].join('+')
+}
+
+/** The shortcut a key press produces, in normalized form. */
+export function shortcutFromEvent(event: KeyboardEvent): string {
+  const parts: string[] = []
+  if (event.ctrlKey) parts.push('Ctrl')
+  if (event.altKey) parts.push('Alt')
+  if (event.shiftKey) parts.push('Shift')
+  if (event.metaKey) parts.push('Meta')
+  parts.push(event.key === ' ' ? 'Space' : event.key)
+
+  return normalizeShortcut(parts.join('+'))
+}
@@ -44,6 +44,7 @@ describe('keyboard actions', () => {
           edit_note: 'Enter',
           create_note: 'Ctrl+Enter',
           rename_note: 'Ctrl+m',
+          duplicate_note: 'Ctrl+Alt+d',
           delete_note: 'Ctrl+x',
           save_and_exit: 'Ctrl+s',
           open_external: 'Ctrl+o',
@@ -61,6 +62,7 @@ describe('keyboard actions', () => {
           open_settings: 'Meta+,',
           version_explorer: 'Ctrl+/',
           recently_deleted: 'Ctrl+Shift+d',
+          toggle_always_on_top: 'Ctrl+t',
         },
         // eslint-disable-next-line @typescript-eslint/no-explicit-any
       } as any,
@@ -81,11 +83,13 @@ describe('keyboard actions', () => {
       } as any,
       noteActions: {
         enterEditMode: vi.fn(),
+        duplicateNote: vi.fn(),
         // eslint-disable-next-line @typescript-eslint/no-explicit-any
       } as any,
       settingsActions: {
         openSettingsPane: vi.fn(),
         closeSettingsPane: vi.fn(),
+        toggleAlwaysOnTop: vi.fn(),
         // eslint-disable-next-line @typescript-eslint/no-explicit-any
       } as any,
       noteService: {
@@ -411,6 +415,16 @@ describe('keyboard actions', () => {
       expect(mockDeps.appCoordinator.refreshCacheAndUI).toHaveBeenCalled()
     })
 
+    it('duplicateNote should call noteActions.duplicateNote with selectedNote', async () => {
+      const context: ActionContext = { state: mockState, actions: mockDeps }
+
+      await keyboardActions.actionRegistry.notes.duplicateNote(context)
+
+      expect(mockDeps.noteActions.duplicateNote).toHaveBeenCalledWith(
+        'note1.md'
+      )
+    })
+
     it('deleteNote should call dialogManager.openDeleteDialog when selectedNote exists', () => {
       const context: ActionContext = { state: mockState, actions: mockDeps }
 
@@ -556,6 +570,14 @@ describe('keyboard actions', () => {
 
       expect(mockDeps.settingsActions.openSettingsPane).toHaveBeenCalled()
     })
+
+    it('toggleAlwaysOnTop should call settingsActions', async () => {
+      const context: ActionContext = { state: mockState, actions: mockDeps }
+
+      await keyboardActions.actionRegistry.settings.toggleAlwaysOnTop(context)
+
+      expect(mockDeps.settingsActions.toggleAlwaysOnTop).toHaveBeenCalled()
+    })
   })
 
   describe('key mappings', () => {
@@ -571,11 +593,13 @@ describe('keyboard actions', () => {
       expect(mappings.searchInput.ArrowDown).toBe('navigation.moveDown')
       expect(mappings.searchInput.Escape).toBe('search.handleEscape')
       expect(mappings.searchInput['Meta+,']).toBe('settings.openSettings')
+      expect(mappings.searchInput['Ctrl+t']).toBe('settings.toggleAlwaysOnTop')
 
       // Test editMode context mappings
       expect(mappings.editMode.Escape).toBe('editing.smartExitEdit')
       expect(mappings.editMode['Ctrl+s']).toBe('editing.saveAndExit')
       expect(mappings.editMode['Meta+,']).toBe('settings.openSettings')
+      expect(mappings.editMode['Ctrl+t']).toBeUndefined()
 
       // Test noteContent context mappings
       expect(mappings.noteContent.Escape).toBe('navigation.focusSearch')
@@ -602,6 +626,9 @@ describe('keyboard actions', () => {
       expect(
         mappings.searchInput[mockDeps.configManager.shortcuts.rename_note]
       ).toBe('notes.renameNote')
+      expect(
+        mappings.searchInput[mockDeps.configManager.shortcuts.duplicate_note]
+      ).toBe('notes.duplicateNote')
       expect(
         mappings.searchInput[mockDeps.configManager.shortcuts.delete_note]
       ).toBe('notes.deleteNote')
@@ -686,6 +713,24 @@ describe('keyboard actions', () => {
       expect(mockDeps.noteActions.enterEditMode).toHaveBeenCalled()
     })
 
+    it('should match shortcuts regardless of how they are spelled', async () => {
+      mockDeps.configManager.shortcuts.navigate_next = 'ctrl+N'
+      const noteContentState = { ...mockState, isNoteContentFocused: true }
+      getStateMock = vi.fn(() => noteContentState)
+      handler = keyboardActions.createKeyboardHandler(getStateMock)
+
+      const ctrlNEvent = new KeyboardEvent('keydown', {
+        key: 'n',
+        ctrlKey: true,
+      })
+      const preventDefaultSpy = vi.spyOn(ctrlNEvent, 'preventDefault')
+
+      await handler(ctrlNEvent)
+
+      expect(preventDefaultSpy).toHaveBeenCalled()
+      expect(mockDeps.contentNavigationManager.navigateNext).toHaveBeenCalled()
+    })
+
     it('should handle Meta+, shortcut globally', async () => {
       const globalState = {
         ...mockState,
@@ -14,6 +14,10 @@ describe('settings actions', () => {
       focusManager: {
         focusSearch: vi.fn(),
       },
+      windowService: {
+        isAlwaysOnTop: vi.fn().mockResolvedValue(false),
+        setAlwaysOnTop: vi.fn().mockResolvedValue(undefined),
+      },
     }
 
     settingsActions = createSettingsActions(mockDeps)
@@ -68,10 +72,33 @@ describe('settings actions', () => {
     })
   })
 
+  describe('toggleAlwaysOnTop', () => {
+    it('should flip the current always on top state', async () => {
+      await settingsActions.toggleAlwaysOnTop()
+      expect(mockDeps.windowService.setAlwaysOnTop).toHaveBeenCalledWith(true)
+
+      vi.mocked(mockDeps.windowService.isAlwaysOnTop).mockResolvedValue(true)
+      await settingsActions.toggleAlwaysOnTop()
+      expect(mockDeps.windowService.setAlwaysOnTop).toHaveBeenLastCalledWith(
+        false
+      )
+    })
+
+    it('should not throw when the window state cannot be read', async () => {
+      vi.mocked(mockDeps.windowService.isAlwaysOnTop).mockRejectedValue(
+        new Error('No window')
+      )
+
+      await expect(settingsActions.toggleAlwaysOnTop()).resolves.toBeUndefined()
+      expect(mockDeps.windowService.setAlwaysOnTop).not.toHaveBeenCalled()
+    })
+  })
+
   describe('interface compliance', () => {
     it('should expose all required methods', () => {
       expect(settingsActions).toHaveProperty('openSettingsPane')
       expect(settingsActions).toHaveProperty('closeSettingsPane')
+      expect(settingsActions).toHaveProperty('toggleAlwaysOnTop')
       expect(typeof settingsActions.openSettingsPane).toBe('function')
       expect(typeof settingsActions.closeSettingsPane).toBe('function')
     })
@@ -8,6 +8,7 @@ const toMetadata = (filenames: string[]): NoteMetadata[] =>
 // Mock all the services and managers
 const mockNoteService = {
   getContent: vi.fn(),
+  recordOpened: vi.fn(),
 }
 
 const mockSearchManager = {
@@ -67,6 +68,7 @@ const mockNoteActions = {
 const mockSettingsActions = {
   openSettingsPane: vi.fn(),
   closeSettingsPane: vi.fn(),
+  toggleAlwaysOnTop: vi.fn(),
 }
 
 // Mock all the modules
@@ -235,6 +237,7 @@ describe('Content Loading Integration', () => {
           shortcuts: {
             create_note: 'Ctrl+Enter',
             rename_note: 'Ctrl+m',
+            duplicate_note: 'Ctrl+Alt+d',
             delete_note: 'Ctrl+x',
             save_and_exit: 'Ctrl+s',
             open_external: 'Ctrl+o',
@@ -403,6 +406,7 @@ describe('Content Loading Integration', () => {
           shortcuts: {
             create_note: 'Ctrl+Enter',
             rename_note: 'Ctrl+m',
+            duplicate_note: 'Ctrl+Alt+d',
             delete_note: 'Ctrl+x',
             save_and_exit: 'Ctrl+s',
             open_external: 'Ctrl+o',
@@ -33,6 +33,7 @@ describe('Keyboard Shortcut Isolation', () => {
         shortcuts: {
           create_note: 'Ctrl+Enter',
           rename_note: 'Ctrl+m',
+          duplicate_note: 'Ctrl+Alt+d',
           delete_note: 'Ctrl+x',
           save_and_exit: 'Ctrl+s',
           open_external: 'Ctrl+o',
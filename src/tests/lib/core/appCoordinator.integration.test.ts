@@ -45,6 +45,7 @@ const mockNoteService = {
   create: vi.fn(),
   delete: vi.fn(),
   rename: vi.fn(),
+  recordOpened: vi.fn(),
   isLoading: false,
   error: null,
   lastOperation: null,
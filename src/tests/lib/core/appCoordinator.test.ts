@@ -23,6 +23,7 @@ vi.mock('../../../lib/app/effects/app.svelte', () => ({
 
 const mockNoteService = {
   getRawContent: vi.fn(),
+  recordOpened: vi.fn(),
   save: vi.fn(),
   search: vi.fn(),
   initializeDatabase: vi.fn(),
@@ -46,10 +46,14 @@ const mockDefaultConfig = {
     tab_size: 2,
     expand_tabs: true,
     show_line_numbers: true,
+    autosave_interval_seconds: 0,
+    spellcheck_lang: 'en_US',
+    vim: { leader: '\\', mappings: {} },
   },
   shortcuts: {
     create_note: 'Ctrl+Enter',
     rename_note: 'Ctrl+m',
+    duplicate_note: 'Ctrl+Alt+d',
     delete_note: 'Ctrl+x',
     edit_note: 'Enter',
     save_and_exit: 'Ctrl+s',
@@ -70,8 +74,43 @@ const mockDefaultConfig = {
     open_settings: 'Meta+,',
     version_explorer: 'Ctrl+/',
     recently_deleted: 'Ctrl+.',
+    toggle_always_on_top: 'Ctrl+t',
+  },
+  preferences: {
+    max_search_results: 100,
+    note_extensions: ['md'],
+    search_normalize_diacritics: true,
+    search_title_boost: 2,
+    search_stemming: false,
+    watcher_debounce_ms: 500,
+    max_recursion_depth: 64,
+    follow_symlinks: true,
+    index_threads: 0,
+    trash_retention_days: 30,
+    version_history_limit: 20,
+    version_min_interval_seconds: 0,
+    max_pasted_image_mb: 10,
+    stream_note_threshold_kb: 1024,
+    render_cache_max_entries: 500,
+    date_format: '%Y-%m-%d',
+    time_format: '%H:%M',
+    note_sort_order: 'modified_desc',
+    pinned_notes_first: true,
+    confirm_before_delete: true,
+    cache_journal_mode: 'WAL',
+    cache_synchronous: 'NORMAL',
+    line_ending: 'preserve',
+    trim_trailing_whitespace: false,
+    ensure_final_newline: false,
+    external_editor_command: '',
+    markdown_heading_anchors: false,
+    markdown_render_math: false,
+    markdown_flavor: 'gfm',
+  },
+  logging: {
+    max_file_size_mb: 5,
+    max_archives: 3,
   },
-  preferences: { max_search_results: 100 },
 }
 
 describe('configManager', () => {
@@ -94,6 +133,7 @@ describe('configManager', () => {
 
     mockConfigService = {
       initDefaults: vi.fn().mockResolvedValue(undefined),
+      getActiveConfig: vi.fn().mockResolvedValue(mockDefaultConfig),
       getGeneralConfig: vi.fn().mockResolvedValue({ scroll_amount: 0.4 }),
       getInterfaceConfig: vi.fn().mockResolvedValue({
         ui_theme: 'gruvbox-dark',
@@ -112,6 +152,9 @@ describe('configManager', () => {
         tab_size: 2,
         expand_tabs: true,
         show_line_numbers: true,
+        autosave_interval_seconds: 0,
+        spellcheck_lang: 'en_US',
+        vim: { leader: '\\', mappings: {} },
       }),
       getShortcutsConfig: vi
         .fn()
@@ -126,6 +169,13 @@ describe('configManager', () => {
       loadCustomThemeFile: vi.fn().mockResolvedValue(''),
       getConfigContent: vi.fn().mockResolvedValue('notes_directory = "/test"'),
       saveConfigContent: vi.fn().mockResolvedValue(undefined),
+      openConfigInEditor: vi.fn().mockResolvedValue(undefined),
+      listThemeOptions: vi.fn().mockResolvedValue({
+        ui_themes: ['article', 'gruvbox-dark', 'modern-dark'],
+        markdown_themes: ['article', 'gruvbox-dark', 'modern-dark'],
+        editor_themes: ['gruvbox-dark'],
+        code_themes: ['atom-one-dark'],
+      }),
       refreshCache: vi.fn().mockResolvedValue(undefined),
     }
 
@@ -159,12 +209,17 @@ describe('configManager', () => {
         tab_size: 2,
         expand_tabs: true,
         show_line_numbers: true,
+        autosave_interval_seconds: 0,
+        spellcheck_lang: 'en_US',
+        vim: { leader: '\\', mappings: {} },
       })
 
       await manager.initialize()
 
       expect(manager.editor.mode).toBe('vim')
       expect(manager.interface.markdown_render_theme).toBe('modern-dark')
+      expect(manager.notesDirectory).toBe('/default/notes')
+      expect(manager.globalShortcut).toBe('Ctrl+Shift+N')
       expect(manager.isInitialized).toBe(true)
       expect(manager.isThemeInitialized).toBe(true)
       expect(manager.isLoading).toBe(false)
@@ -244,9 +299,46 @@ describe('configManager', () => {
           word_wrap: true,
           tab_size: 2,
           show_line_numbers: true,
+          autosave_interval_seconds: 0,
+          spellcheck_lang: 'en_US',
+          vim: { leader: '\\', mappings: {} },
         },
         shortcuts: {},
-        preferences: { max_search_results: 50 },
+        preferences: {
+          max_search_results: 50,
+          note_extensions: ['md'],
+          search_normalize_diacritics: true,
+          search_title_boost: 2,
+          search_stemming: false,
+          watcher_debounce_ms: 500,
+          max_recursion_depth: 64,
+          follow_symlinks: true,
+          index_threads: 0,
+          trash_retention_days: 30,
+          version_history_limit: 20,
+          version_min_interval_seconds: 0,
+          max_pasted_image_mb: 10,
+          stream_note_threshold_kb: 1024,
+          render_cache_max_entries: 500,
+          date_format: '%Y-%m-%d',
+          time_format: '%H:%M',
+          note_sort_order: 'modified_desc',
+          pinned_notes_first: true,
+          confirm_before_delete: true,
+          cache_journal_mode: 'WAL',
+          cache_synchronous: 'NORMAL',
+          line_ending: 'preserve',
+          trim_trailing_whitespace: false,
+          ensure_final_newline: false,
+          external_editor_command: '',
+          markdown_heading_anchors: false,
+          markdown_render_math: false,
+          markdown_flavor: 'gfm',
+        },
+        logging: {
+          max_file_size_mb: 5,
+          max_archives: 3,
+        },
       }
 
       configChangeHandler!({ payload: newConfig })
@@ -280,11 +372,18 @@ describe('configManager', () => {
         tab_size: 2,
         expand_tabs: true,
         show_line_numbers: true,
+        autosave_interval_seconds: 0,
+        spellcheck_lang: 'en_US',
+        vim: { leader: ' ', mappings: { '<leader>w': 'save' } },
       })
 
       await manager.forceRefresh()
 
       expect(manager.editor.mode).toBe('vim')
+      expect(manager.editor.vim).toEqual({
+        leader: ' ',
+        mappings: { '<leader>w': 'save' },
+      })
       expect(manager.interface.ui_theme).toBe('article')
       expect(manager.error).toBe(null)
     })
@@ -303,6 +402,30 @@ describe('configManager', () => {
     })
   })
 
+  describe('editor config updates', () => {
+    it('should replace editor settings on editor-config-changed', async () => {
+      let editorChangeHandler: (event: { payload: unknown }) => void
+
+      mockListen.mockImplementation((eventName, handler) => {
+        if (eventName === 'editor-config-changed') {
+          editorChangeHandler = handler
+        }
+        return Promise.resolve(mockUnlisten)
+      })
+
+      await manager.initialize()
+      const notesDirectory = manager.notesDirectory
+
+      editorChangeHandler!({
+        payload: { ...mockDefaultConfig.editor, mode: 'emacs', tab_size: 8 },
+      })
+
+      expect(manager.editor.mode).toBe('emacs')
+      expect(manager.editor.tab_size).toBe(8)
+      expect(manager.notesDirectory).toBe(notesDirectory)
+    })
+  })
+
   describe('cleanup', () => {
     it('should cleanup listeners and reset state', async () => {
       await manager.initialize()
@@ -337,6 +460,30 @@ describe('configManager', () => {
       expect(manager.currentCodeTheme).toBe('atom-one-dark')
     })
 
+    it('should use backend theme options when the scan fails', async () => {
+      const { loadUITheme } = await import('$lib/utils/themeLoader')
+      vi.mocked(mockConfigService.getAvailableThemes).mockRejectedValue(
+        'no resources'
+      )
+      vi.mocked(mockConfigService.listThemeOptions).mockResolvedValue({
+        ui_themes: ['article', 'team-light'],
+        markdown_themes: ['article'],
+        editor_themes: [],
+        code_themes: [],
+      })
+      const consoleSpy = vi.spyOn(console, 'warn').mockImplementation(() => {})
+
+      await manager.initialize()
+
+      expect(mockConfigService.listThemeOptions).toHaveBeenCalled()
+      expect(loadUITheme).toHaveBeenCalledWith(
+        expect.any(String),
+        ['article', 'team-light'],
+        undefined
+      )
+      consoleSpy.mockRestore()
+    })
+
     it('should handle mixed success/failure during initialization', async () => {
       vi.mocked(mockConfigService.getEditorConfig).mockResolvedValue({
         mode: 'vim',
@@ -345,6 +492,9 @@ describe('configManager', () => {
         tab_size: 2,
         expand_tabs: true,
         show_line_numbers: true,
+        autosave_interval_seconds: 0,
+        spellcheck_lang: 'en_US',
+        vim: { leader: '\\', mappings: {} },
       })
       vi.mocked(mockConfigService.getShortcutsConfig).mockResolvedValue(
         mockDefaultConfig.shortcuts
@@ -416,6 +566,31 @@ describe('configManager', () => {
       expect(manager.content).toBe('')
     })
 
+    it('should open the config in the external editor and close', async () => {
+      await manager.openPane()
+
+      const result = await manager.openInExternalEditor()
+
+      expect(result.success).toBe(true)
+      expect(mockConfigService.openConfigInEditor).toHaveBeenCalled()
+      expect(manager.isVisible).toBe(false)
+    })
+
+    it('should stay open when the external editor fails', async () => {
+      await manager.openPane()
+      vi.mocked(mockConfigService.openConfigInEditor).mockRejectedValue(
+        'no editor'
+      )
+      const consoleSpy = vi.spyOn(console, 'error').mockImplementation(() => {})
+
+      const result = await manager.openInExternalEditor()
+
+      expect(result.success).toBe(false)
+      expect(manager.isVisible).toBe(true)
+      expect(manager.error).toBe('Failed to open config in editor: no editor')
+      consoleSpy.mockRestore()
+    })
+
     it('should handle save errors', async () => {
       await manager.openPane()
       manager.content = 'test content'
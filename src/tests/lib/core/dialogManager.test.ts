@@ -88,6 +88,16 @@ describe('dialogManager (factory-based - TDD)', () => {
       dialogManager.closeUnsavedChangesDialog()
       expect(dialogManager.showUnsavedChangesDialog).toBe(false)
     })
+
+    it('should track save conflict dialog state', () => {
+      expect(dialogManager.showSaveConflictDialog).toBe(false)
+
+      dialogManager.openSaveConflictDialog()
+      expect(dialogManager.showSaveConflictDialog).toBe(true)
+
+      dialogManager.closeSaveConflictDialog()
+      expect(dialogManager.showSaveConflictDialog).toBe(false)
+    })
   })
 
   describe('delete key press timing and cleanup', () => {
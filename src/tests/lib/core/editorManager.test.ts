@@ -12,6 +12,9 @@ let editorManager: EditorManager
 let mockNoteService: {
   getRawContent: ReturnType<typeof vi.fn>
   save: ReturnType<typeof vi.fn>
+  forceSave: ReturnType<typeof vi.fn>
+  autosave: ReturnType<typeof vi.fn>
+  savePastedImage: ReturnType<typeof vi.fn>
 }
 
 describe('editorManager', () => {
@@ -25,6 +28,9 @@ describe('editorManager', () => {
     mockNoteService = {
       getRawContent: vi.fn(),
       save: vi.fn(),
+      forceSave: vi.fn(),
+      autosave: vi.fn(),
+      savePastedImage: vi.fn(),
     }
 
     mockContentNavigationManager = {
@@ -88,6 +94,58 @@ describe('editorManager', () => {
       expect(editorManager.nearestHeaderText).toBe('Header 2')
     })
 
+    it('should ask for streaming above the configured threshold', async () => {
+      mockNoteService.getRawContent.mockResolvedValue('# Large')
+      editorManager = createEditorManager({
+        // eslint-disable-next-line @typescript-eslint/no-explicit-any
+        noteService: mockNoteService as any,
+        contentNavigationManager: mockContentNavigationManager,
+        getStreamThresholdKb: () => 64,
+      })
+
+      await editorManager.enterEditMode('large.md')
+
+      expect(mockNoteService.getRawContent).toHaveBeenCalledWith(
+        'large.md',
+        64 * 1024
+      )
+      expect(editorManager.editContent).toBe('# Large')
+    })
+
+    it('should show a streamed note as its chunks arrive', async () => {
+      let finish: (content: string) => void = () => {}
+      mockNoteService.getRawContent.mockImplementation(
+        (_name: string, _bytes: number, onChunk: (s: string) => void) => {
+          onChunk('# Large\n')
+          return new Promise((resolve) => (finish = resolve))
+        }
+      )
+      editorManager = createEditorManager({
+        // eslint-disable-next-line @typescript-eslint/no-explicit-any
+        noteService: mockNoteService as any,
+        contentNavigationManager: mockContentNavigationManager,
+        getStreamThresholdKb: () => 64,
+      })
+
+      const entering = editorManager.enterEditMode('large.md')
+      await Promise.resolve()
+
+      expect(editorManager.isEditMode).toBe(true)
+      expect(editorManager.editContent).toBe('# Large\n')
+      expect(editorManager.isLoadingContent).toBe(true)
+      expect(await editorManager.saveNote()).toEqual({
+        success: false,
+        error: 'Note is still loading',
+      })
+
+      finish('# Large\n\nthe rest')
+      await entering
+
+      expect(editorManager.editContent).toBe('# Large\n\nthe rest')
+      expect(editorManager.isLoadingContent).toBe(false)
+      expect(editorManager.isDirty).toBe(false)
+    })
+
     it('should handle missing note name', async () => {
       await editorManager.enterEditMode('')
 
@@ -194,6 +252,154 @@ describe('editorManager', () => {
     })
   })
 
+  describe('save conflicts', () => {
+    const mockNoteName = 'test-note.md'
+    const conflictError = {
+      type: 'SaveConflict',
+      details: { note: mockNoteName, disk_content: 'changed in vim' },
+    }
+
+    it('should report a conflict with the on-disk content', async () => {
+      mockNoteService.getRawContent.mockResolvedValue('original content')
+      await editorManager.enterEditMode(mockNoteName)
+      editorManager.updateContent('modified content')
+
+      mockNoteService.save.mockRejectedValue(conflictError)
+      const result = await editorManager.saveNote()
+
+      expect(result.success).toBe(false)
+      expect(result.conflict).toBe(true)
+      expect(editorManager.saveConflict).toEqual({
+        noteName: mockNoteName,
+        content: 'modified content',
+        diskContent: 'changed in vim',
+      })
+    })
+
+    it('should overwrite with the edited content on force save', async () => {
+      mockNoteService.getRawContent.mockResolvedValue('original content')
+      await editorManager.enterEditMode(mockNoteName)
+      editorManager.updateContent('modified content')
+      mockNoteService.save.mockRejectedValue(conflictError)
+      await editorManager.saveNote()
+
+      mockNoteService.forceSave.mockResolvedValue(undefined)
+      const result = await editorManager.forceSaveNote()
+
+      expect(mockNoteService.forceSave).toHaveBeenCalledWith(
+        mockNoteName,
+        'modified content'
+      )
+      expect(result.success).toBe(true)
+      expect(editorManager.saveConflict).toBe(null)
+      expect(editorManager.isDirty).toBe(false)
+    })
+
+    it('should not force save without a conflict', async () => {
+      const result = await editorManager.forceSaveNote()
+
+      expect(result.success).toBe(false)
+      expect(mockNoteService.forceSave).not.toHaveBeenCalled()
+    })
+  })
+
+  describe('autosaveNote', () => {
+    const mockNoteName = 'test-note.md'
+
+    it('should autosave edits and return the modified time', async () => {
+      mockNoteService.getRawContent.mockResolvedValue('original content')
+      await editorManager.enterEditMode(mockNoteName)
+      editorManager.updateContent('modified content')
+
+      mockNoteService.autosave.mockResolvedValue(1700000000)
+      const modified = await editorManager.autosaveNote()
+
+      expect(mockNoteService.autosave).toHaveBeenCalledWith(
+        mockNoteName,
+        'modified content'
+      )
+      expect(modified).toBe(1700000000)
+      expect(editorManager.isDirty).toBe(false)
+    })
+
+    it('should skip autosave when there are no changes', async () => {
+      mockNoteService.getRawContent.mockResolvedValue('original content')
+      await editorManager.enterEditMode(mockNoteName)
+
+      const modified = await editorManager.autosaveNote()
+
+      expect(modified).toBe(null)
+      expect(mockNoteService.autosave).not.toHaveBeenCalled()
+    })
+
+    it('should stay dirty if autosave fails', async () => {
+      mockNoteService.getRawContent.mockResolvedValue('original content')
+      await editorManager.enterEditMode(mockNoteName)
+      editorManager.updateContent('modified content')
+
+      mockNoteService.autosave.mockRejectedValue(new Error('Disk full'))
+      const modified = await editorManager.autosaveNote()
+
+      expect(modified).toBe(null)
+      expect(editorManager.isDirty).toBe(true)
+    })
+  })
+
+  describe('pasteImage', () => {
+    const image = new Uint8Array([0x89, 0x50, 0x4e, 0x47])
+
+    function createMockEditorView() {
+      return {
+        state: { replaceSelection: vi.fn().mockReturnValue('transaction') },
+        dispatch: vi.fn(),
+      }
+    }
+
+    it('should save the image and insert its link at the cursor', async () => {
+      const view = createMockEditorView()
+      mockNoteService.getRawContent.mockResolvedValue('original content')
+      await editorManager.enterEditMode('test-note.md')
+      // eslint-disable-next-line @typescript-eslint/no-explicit-any
+      editorManager.setEditorView(view as any)
+
+      mockNoteService.savePastedImage.mockResolvedValue(
+        '![](attachments/a.png)'
+      )
+      const inserted = await editorManager.pasteImage(image)
+
+      expect(inserted).toBe(true)
+      expect(mockNoteService.savePastedImage).toHaveBeenCalledWith(
+        'test-note.md',
+        image
+      )
+      expect(view.state.replaceSelection).toHaveBeenCalledWith(
+        '![](attachments/a.png)'
+      )
+      expect(view.dispatch).toHaveBeenCalledWith('transaction')
+    })
+
+    it('should not insert anything when saving fails', async () => {
+      const view = createMockEditorView()
+      mockNoteService.getRawContent.mockResolvedValue('original content')
+      await editorManager.enterEditMode('test-note.md')
+      // eslint-disable-next-line @typescript-eslint/no-explicit-any
+      editorManager.setEditorView(view as any)
+
+      mockNoteService.savePastedImage.mockRejectedValue(new Error('Too large'))
+      const inserted = await editorManager.pasteImage(image)
+
+      expect(inserted).toBe(false)
+      expect(view.dispatch).not.toHaveBeenCalled()
+    })
+
+    it('should ignore pastes outside edit mode', async () => {
+      const inserted = await editorManager.pasteImage(image)
+
+      expect(inserted).toBe(false)
+      expect(mockNoteService.savePastedImage).not.toHaveBeenCalled()
+    })
+  })
+
   describe('openUnsavedChangesDialog integration', () => {
     it('should work with dirty state', async () => {
       mockNoteService.getRawContent.mockResolvedValue('content')
@@ -200,5 +200,18 @@ describe('searchManager', () => {
       expect(result).toEqual(notes)
       expect(searchManager.filteredNotes).toEqual(notes)
     })
+
+    it('should keep results when a search is superseded', async () => {
+      const notes = ['kept.md']
+      mockNoteService.search
+        .mockResolvedValueOnce(notes)
+        .mockRejectedValueOnce('superseded')
+
+      await searchManager.executeSearch('first')
+      const result = await searchManager.executeSearch('second')
+
+      expect(result).toEqual(notes)
+      expect(mockProgressManager.setError).not.toHaveBeenCalled()
+    })
   })
 })
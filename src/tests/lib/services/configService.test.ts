@@ -34,7 +34,7 @@ describe('configService', () => {
 
       await configService.refreshCache()
 
-      expect(mockInvoke).toHaveBeenCalledWith('refresh_cache')
+      expect(mockInvoke).toHaveBeenCalledWith('refresh_cache', { force: false })
     })
 
     it('should handle refresh cache errors', async () => {
@@ -52,6 +52,120 @@ describe('configService', () => {
     })
   })
 
+  describe('checkNotesDirectoryHealth', () => {
+    it('should report the health of the picked folder', async () => {
+      const health = {
+        path: '/Users/me/Dropbox/Notes',
+        exists: true,
+        is_directory: true,
+        writable: true,
+        cloud_sync_provider: 'Dropbox',
+        note_count: 12,
+        write_error: null,
+      }
+      mockInvoke.mockResolvedValueOnce(health)
+
+      const result = await configService.checkNotesDirectoryHealth(
+        '/Users/me/Dropbox/Notes'
+      )
+
+      expect(mockInvoke).toHaveBeenCalledWith('check_notes_directory_health', {
+        path: '/Users/me/Dropbox/Notes',
+      })
+      expect(result).toEqual(health)
+    })
+  })
+
+  describe('initializeFirstRun', () => {
+    it('should set up the chosen notes directory', async () => {
+      const config = { notes_directory: '/home/user/notes' }
+      mockInvoke.mockResolvedValueOnce(config)
+
+      const result = await configService.initializeFirstRun('/home/user/notes')
+
+      expect(mockInvoke).toHaveBeenCalledWith('initialize_first_run', {
+        notesDirectory: '/home/user/notes',
+      })
+      expect(result).toEqual(config)
+    })
+  })
+
+  describe('getActiveConfig', () => {
+    it('should return the config the backend is running with', async () => {
+      const active = { notes_directory: '/home/user/notes' }
+      mockInvoke.mockResolvedValueOnce(active)
+
+      const result = await configService.getActiveConfig()
+
+      expect(mockInvoke).toHaveBeenCalledWith('get_active_config')
+      expect(result).toEqual(active)
+    })
+  })
+
+  describe('applyEditorConfig', () => {
+    it('should push the editor settings to the open editor', async () => {
+      const editor = { mode: 'vim', tab_size: 4 }
+      mockInvoke.mockResolvedValueOnce(editor)
+
+      const result = await configService.applyEditorConfig()
+
+      expect(mockInvoke).toHaveBeenCalledWith('apply_editor_config')
+      expect(result).toEqual(editor)
+    })
+  })
+
+  describe('listThemeOptions', () => {
+    it('should list the themes accepted by the backend', async () => {
+      const themes = {
+        ui_themes: ['article', 'gruvbox-dark'],
+        markdown_themes: ['modern-dark'],
+        editor_themes: ['gruvbox-dark', 'team-dark'],
+        code_themes: ['nord'],
+      }
+      mockInvoke.mockResolvedValueOnce(themes)
+
+      const result = await configService.listThemeOptions()
+
+      expect(mockInvoke).toHaveBeenCalledWith('get_available_themes')
+      expect(result).toEqual(themes)
+    })
+  })
+
+  describe('getAvailableThemes', () => {
+    it('should fall back to the backend theme options', async () => {
+      mockInvoke.mockRejectedValueOnce('no resources').mockResolvedValueOnce({
+        ui_themes: ['article'],
+        markdown_themes: ['modern-dark'],
+        editor_themes: [],
+        code_themes: [],
+      })
+      const consoleSpy = vi
+        .spyOn(console, 'error')
+        .mockImplementation(() => {})
+
+      const result = await configService.getAvailableThemes()
+
+      expect(mockInvoke).toHaveBeenCalledWith('scan_available_themes')
+      expect(mockInvoke).toHaveBeenCalledWith('get_available_themes')
+      expect(result).toEqual({
+        ui_themes: ['article'],
+        markdown_themes: ['modern-dark'],
+      })
+      consoleSpy.mockRestore()
+    })
+  })
+
+  describe('getMarkdownExtensions', () => {
+    it('should list the extensions the renderer applies', async () => {
+      mockInvoke.mockResolvedValueOnce(['tables', 'task_lists'])
+
+      const result = await configService.getMarkdownExtensions()
+
+      expect(mockInvoke).toHaveBeenCalledWith('get_markdown_extensions')
+      expect(result).toEqual(['tables', 'task_lists'])
+    })
+  })
+
   describe('loadCustomThemeFile', () => {
     it('should load custom theme file', async () => {
       const cssContent = '.custom { color: red; }'
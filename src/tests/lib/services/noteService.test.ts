@@ -5,7 +5,12 @@ vi.mock('@tauri-apps/api/core', () => ({
   invoke: mockInvoke,
 }))
 
-import { noteService } from '../../../lib/services/noteService.svelte'
+import {
+  createNoteService,
+  noteService,
+  NOTE_CHUNK_BYTES,
+  SEARCH_SUPERSEDED,
+} from '../../../lib/services/noteService.svelte'
 
 describe('noteService', () => {
   beforeEach(() => {
@@ -74,12 +79,19 @@ describe('noteService', () => {
     it('should delete a note successfully', async () => {
       const noteName = 'test-note.md'
 
+      mockInvoke.mockResolvedValueOnce('token-123')
       mockInvoke.mockResolvedValueOnce(undefined)
 
       const result = await noteService.delete(noteName)
 
       expect(result.success).toBe(true)
-      expect(mockInvoke).toHaveBeenCalledWith('delete_note', { noteName })
+      expect(mockInvoke).toHaveBeenCalledWith('request_delete_confirmation', {
+        noteName,
+      })
+      expect(mockInvoke).toHaveBeenCalledWith('delete_note', {
+        noteName,
+        confirmationToken: 'token-123',
+      })
       expect(noteService.lastOperation).toBe('delete')
     })
 
@@ -141,6 +153,148 @@ describe('noteService', () => {
     })
   })
 
+  describe('duplicate', () => {
+    it('should duplicate a note and return the new name', async () => {
+      mockInvoke.mockResolvedValueOnce('note copy.md')
+
+      const result = await noteService.duplicate('note.md')
+
+      expect(result.success).toBe(true)
+      expect(result.newName).toBe('note copy.md')
+      expect(mockInvoke).toHaveBeenCalledWith('duplicate_note', {
+        name: 'note.md',
+      })
+      expect(noteService.lastOperation).toBe('duplicate')
+    })
+
+    it('should handle duplicate errors', async () => {
+      mockInvoke.mockRejectedValueOnce(new Error('Note not found'))
+
+      const result = await noteService.duplicate('missing.md')
+
+      expect(result.success).toBe(false)
+      expect(result.error).toBe(
+        'Failed to duplicate note: Error: Note not found'
+      )
+    })
+  })
+
+  describe('move', () => {
+    it('should move a note and return its new name', async () => {
+      mockInvoke.mockResolvedValueOnce('projects/note.md')
+
+      const result = await noteService.move('note.md', 'projects')
+
+      expect(result.success).toBe(true)
+      expect(result.newName).toBe('projects/note.md')
+      expect(mockInvoke).toHaveBeenCalledWith('move_note', {
+        name: 'note.md',
+        targetDir: 'projects',
+      })
+      expect(noteService.lastOperation).toBe('move')
+    })
+
+    it('should handle collisions in the target folder', async () => {
+      mockInvoke.mockRejectedValueOnce('already exists in projects')
+
+      const result = await noteService.move('note.md', 'projects')
+
+      expect(result.success).toBe(false)
+      expect(result.error).toBe(
+        'Failed to move note: already exists in projects'
+      )
+    })
+  })
+
+  describe('folders', () => {
+    it('should create a folder through the backend', async () => {
+      mockInvoke.mockResolvedValueOnce('projects/2024')
+
+      const path = await noteService.createFolder('/projects/2024/')
+
+      expect(path).toBe('projects/2024')
+      expect(mockInvoke).toHaveBeenCalledWith('create_folder', {
+        relativePath: '/projects/2024/',
+      })
+    })
+
+    it('should propagate folder creation failures', async () => {
+      mockInvoke.mockRejectedValueOnce('Path traversal not allowed')
+
+      await expect(noteService.createFolder('../outside')).rejects.toBe(
+        'Path traversal not allowed'
+      )
+    })
+
+    it('should list the folder tree', async () => {
+      const tree = [
+        {
+          name: 'work',
+          path: 'work',
+          children: [{ name: 'admin', path: 'work/admin', children: [] }],
+        },
+      ]
+      mockInvoke.mockResolvedValueOnce(tree)
+
+      expect(await noteService.listFolders()).toEqual(tree)
+      expect(mockInvoke).toHaveBeenCalledWith('list_folders')
+    })
+  })
+
+  describe('search', () => {
+    it('should scope search to a folder', async () => {
+      mockInvoke.mockResolvedValueOnce([{ filename: 'work/plan.md' }])
+
+      const results = await noteService.search('plan', 'work')
+
+      expect(results).toEqual([{ filename: 'work/plan.md' }])
+      expect(mockInvoke).toHaveBeenCalledWith('search_notes', {
+        query: 'plan',
+        pathPrefix: 'work',
+        generation: expect.any(Number),
+      })
+    })
+
+    it('should send a higher generation with each search', async () => {
+      mockInvoke.mockResolvedValue([])
+
+      await noteService.search('pl')
+      await noteService.search('plan')
+
+      const [first, second] = mockInvoke.mock.calls.map(
+        ([, args]) => args.generation
+      )
+      expect(second).toBeGreaterThan(first)
+    })
+
+    it('should keep generations rising across a reload', async () => {
+      vi.useFakeTimers()
+      mockInvoke.mockResolvedValue([])
+      const generations = () =>
+        mockInvoke.mock.calls.map(([, args]) => args.generation)
+
+      const beforeReload = createNoteService()
+      await beforeReload.search('p')
+      await beforeReload.search('pl')
+      const [, lastBeforeReload] = generations()
+
+      vi.advanceTimersByTime(1000)
+      await createNoteService().search('plan')
+
+      expect(generations()[2]).toBeGreaterThan(lastBeforeReload)
+      vi.useRealTimers()
+    })
+
+    it('should rethrow superseded searches without logging', async () => {
+      const consoleSpy = vi.spyOn(console, 'error').mockImplementation(() => {})
+      mockInvoke.mockRejectedValueOnce(SEARCH_SUPERSEDED)
+
+      await expect(noteService.search('plan')).rejects.toBe(SEARCH_SUPERSEDED)
+      expect(consoleSpy).not.toHaveBeenCalled()
+      consoleSpy.mockRestore()
+    })
+  })
+
   describe('content operations', () => {
     it('should get note content', async () => {
       const content = 'Note content'
@@ -166,6 +320,108 @@ describe('noteService', () => {
       expect(result).toBe(content)
     })
 
+    it('should get the frontmatter render theme of a note', async () => {
+      const theme = { name: 'article', css: null }
+      mockInvoke.mockResolvedValueOnce(theme)
+
+      const result = await noteService.getRenderTheme('resume.md')
+
+      expect(mockInvoke).toHaveBeenCalledWith('get_note_render_theme', {
+        name: 'resume.md',
+      })
+      expect(result).toEqual(theme)
+    })
+
+    it('should get the table of contents of a note', async () => {
+      const toc = [
+        { level: 1, text: 'Intro', slug: 'intro' },
+        { level: 2, text: '???', slug: null },
+      ]
+      mockInvoke.mockResolvedValueOnce(toc)
+
+      const result = await noteService.getToc('outline.md')
+
+      expect(mockInvoke).toHaveBeenCalledWith('get_note_toc', {
+        name: 'outline.md',
+      })
+      expect(result).toEqual(toc)
+    })
+
+    it('should find matches within a single note', async () => {
+      const matches = [{ line: 3, start: 4, end: 9 }]
+      mockInvoke.mockResolvedValueOnce(matches)
+
+      const result = await noteService.findInNote('long.md', 'notes', {
+        caseSensitive: true,
+      })
+
+      expect(mockInvoke).toHaveBeenCalledWith('search_within_note', {
+        name: 'long.md',
+        query: 'notes',
+        wholeWord: false,
+        caseSensitive: true,
+      })
+      expect(result).toEqual(matches)
+    })
+
+    it('should preview a replace across notes', async () => {
+      const results = [{ filename: 'a.md', replacements: 2, preview: [] }]
+      mockInvoke.mockResolvedValueOnce(results)
+
+      const result = await noteService.replaceAcrossNotes('Falcon', 'Osprey', {
+        dryRun: true,
+      })
+
+      expect(mockInvoke).toHaveBeenCalledWith('replace_across_notes', {
+        find: 'Falcon',
+        replace: 'Osprey',
+        regex: false,
+        dryRun: true,
+      })
+      expect(result).toEqual(results)
+    })
+
+    it('should list tags with their note counts', async () => {
+      mockInvoke.mockResolvedValueOnce([
+        ['Rust', 2],
+        ['work', 1],
+      ])
+
+      const tags = await noteService.listAllTags()
+
+      expect(mockInvoke).toHaveBeenCalledWith('list_all_tags')
+      expect(tags).toEqual([
+        ['Rust', 2],
+        ['work', 1],
+      ])
+    })
+
+    it('should stream raw content above the threshold in chunks', async () => {
+      mockInvoke
+        .mockResolvedValueOnce({ content: 'café', next_offset: 5, eof: false })
+        .mockResolvedValueOnce({
+          content: ' au lait',
+          next_offset: 13,
+          eof: true,
+        })
+      const onChunk = vi.fn()
+
+      const result = await noteService.getRawContent('large.md', 4, onChunk)
+
+      expect(mockInvoke).toHaveBeenNthCalledWith(1, 'read_note_chunk', {
+        name: 'large.md',
+        offset: 0,
+        len: 4,
+      })
+      expect(mockInvoke).toHaveBeenNthCalledWith(2, 'read_note_chunk', {
+        name: 'large.md',
+        offset: 5,
+        len: NOTE_CHUNK_BYTES,
+      })
+      expect(result).toBe('café au lait')
+      expect(onChunk.mock.calls).toEqual([['café']])
+    })
+
     it('should save note content', async () => {
       const content = 'Updated content'
       const originalContent = 'Original content'
@@ -203,6 +459,124 @@ describe('noteService', () => {
     })
   })
 
+  describe('autosave', () => {
+    it('should autosave content and return the modified time', async () => {
+      mockInvoke.mockResolvedValueOnce(1700000000)
+
+      const modified = await noteService.autosave('test.md', 'draft')
+
+      expect(modified).toBe(1700000000)
+      expect(mockInvoke).toHaveBeenCalledWith('autosave_note', {
+        name: 'test.md',
+        content: 'draft',
+      })
+    })
+  })
+
+  describe('append', () => {
+    it('should append a line and return the note size', async () => {
+      mockInvoke.mockResolvedValueOnce(42)
+
+      const size = await noteService.append('inbox.md', 'Call back', true)
+
+      expect(size).toBe(42)
+      expect(mockInvoke).toHaveBeenCalledWith('append_to_note', {
+        name: 'inbox.md',
+        text: 'Call back',
+        addTimestamp: true,
+      })
+    })
+  })
+
+  describe('savePastedImage', () => {
+    it('should send the image bytes and return the link', async () => {
+      mockInvoke.mockResolvedValueOnce('![](attachments/a.png)')
+
+      const link = await noteService.savePastedImage(
+        'test.md',
+        new Uint8Array([1, 2, 3])
+      )
+
+      expect(link).toBe('![](attachments/a.png)')
+      expect(mockInvoke).toHaveBeenCalledWith('save_pasted_image', {
+        note: 'test.md',
+        pngBytes: [1, 2, 3],
+      })
+    })
+
+    it('should propagate rejected images', async () => {
+      mockInvoke.mockRejectedValueOnce('Attachment error: too large')
+
+      await expect(
+        noteService.savePastedImage('test.md', new Uint8Array([1]))
+      ).rejects.toBe('Attachment error: too large')
+    })
+  })
+
+  describe('copyAsHtml', () => {
+    it('should copy the note through the backend', async () => {
+      mockInvoke.mockResolvedValueOnce(undefined)
+
+      await noteService.copyAsHtml('test.md')
+
+      expect(mockInvoke).toHaveBeenCalledWith('copy_note_as_html', {
+        name: 'test.md',
+      })
+    })
+
+    it('should propagate clipboard failures', async () => {
+      mockInvoke.mockRejectedValueOnce('Clipboard error: unsupported')
+
+      await expect(noteService.copyAsHtml('test.md')).rejects.toBe(
+        'Clipboard error: unsupported'
+      )
+    })
+  })
+
+  describe('spellcheck', () => {
+    it('should default to the configured language', async () => {
+      const issues = [
+        { word: 'Teh', start: 0, end: 3, suggestions: ['The', 'Tea'] },
+      ]
+      mockInvoke.mockResolvedValueOnce(issues)
+
+      const result = await noteService.spellcheck('Teh fox')
+
+      expect(mockInvoke).toHaveBeenCalledWith('spellcheck_text', {
+        text: 'Teh fox',
+        lang: '',
+      })
+      expect(result).toEqual(issues)
+    })
+  })
+
+  describe('recent notes', () => {
+    it('should record an opened note', async () => {
+      mockInvoke.mockResolvedValueOnce(undefined)
+
+      await noteService.recordOpened('test.md')
+
+      expect(mockInvoke).toHaveBeenCalledWith('record_note_opened', {
+        name: 'test.md',
+      })
+    })
+
+    it('should not throw when recording fails', async () => {
+      mockInvoke.mockRejectedValueOnce('Note not found: test.md')
+
+      await expect(noteService.recordOpened('test.md')).resolves.toBeUndefined()
+    })
+
+    it('should list recent notes', async () => {
+      mockInvoke.mockResolvedValueOnce(['b.md', 'a.md'])
+
+      const recents = await noteService.listRecent(5)
+
+      expect(recents).toEqual(['b.md', 'a.md'])
+      expect(mockInvoke).toHaveBeenCalledWith('list_recent_notes', { limit: 5 })
+    })
+  })
+
   describe('state management', () => {
     it('should track loading state during operations', async () => {
       let loadingDuringOperation = false
@@ -155,6 +155,35 @@ describe('versionService (factory-based - TDD)', () => {
     })
   })
 
+  describe('searchVersions', () => {
+    it('should return matches from current content and versions', async () => {
+      const matches = [
+        {
+          filename: 'plan.md',
+          version_filename: 'plan.rollback.1700000000.md',
+          timestamp: 1700000000,
+          highlights: { match_count: 1, snippets: ['<mark>heron</mark>'] },
+        },
+      ]
+      mockInvoke.mockResolvedValue(matches)
+
+      const result = await versionService.searchVersions('heron')
+
+      expect(result).toEqual(matches)
+      expect(mockInvoke).toHaveBeenCalledWith('search_note_versions', {
+        query: 'heron',
+      })
+    })
+
+    it('should throw error when searching fails', async () => {
+      mockInvoke.mockRejectedValue('Search failed')
+
+      await expect(versionService.searchVersions('heron')).rejects.toEqual(
+        'Search failed'
+      )
+    })
+  })
+
   describe('getDeletedFiles', () => {
     it('should return empty array when no deleted files exist', async () => {
       mockInvoke.mockResolvedValue([])
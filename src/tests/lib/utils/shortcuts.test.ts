@@ -0,0 +1,41 @@
+import { describe, it, expect } from 'vitest'
+import { normalizeShortcut, shortcutFromEvent } from '$lib/utils/shortcuts'
+
+describe('shortcuts', () => {
+  describe('normalizeShortcut', () => {
+    it('should order and deduplicate modifiers', () => {
+      expect(normalizeShortcut('shift+ctrl+n')).toBe('Ctrl+Shift+N')
+      expect(normalizeShortcut(' control + SHIFT + n ')).toBe('Ctrl+Shift+N')
+      expect(normalizeShortcut('cmd+alt+arrowup')).toBe('Alt+Meta+ArrowUp')
+      expect(normalizeShortcut('Ctrl+ctrl+enter')).toBe('Ctrl+Enter')
+    })
+
+    it('should spell letters the way a key press reports them', () => {
+      expect(normalizeShortcut('Ctrl+M')).toBe('Ctrl+m')
+      expect(normalizeShortcut('Ctrl+Shift+m')).toBe('Ctrl+Shift+M')
+      expect(normalizeShortcut('Meta+,')).toBe('Meta+,')
+    })
+
+    it('should resolve CmdOrCtrl to the platform modifier', () => {
+      expect(normalizeShortcut('CmdOrCtrl+m')).toBe('Ctrl+m')
+    })
+  })
+
+  describe('shortcutFromEvent', () => {
+    it('should match the normalized config spelling', () => {
+      const shifted = new KeyboardEvent('keydown', {
+        key: 'N',
+        ctrlKey: true,
+        shiftKey: true,
+      })
+      const plain = new KeyboardEvent('keydown', { key: 'm', ctrlKey: true })
+
+      expect(shortcutFromEvent(shifted)).toBe(
+        normalizeShortcut('Shift+Ctrl+n')
+      )
+      expect(shortcutFromEvent(plain)).toBe(normalizeShortcut('Ctrl+M'))
+      const space = new KeyboardEvent('keydown', { key: ' ' })
+      expect(shortcutFromEvent(space)).toBe('Space')
+    })
+  })
+})